@@ -111,6 +111,28 @@ impl<'a> Iterator for TextIterator<'a> {
     }
 }
 
+/// ANSIエスケープシーケンスを取り除いた文字列を返す
+pub fn strip_ansi(s: &str) -> String {
+    s.ansi_parse()
+        .filter(|text| text.ty == AnsiEscapeSequence::Chars)
+        .map(|text| text.chars)
+        .collect()
+}
+
+#[cfg(test)]
+mod strip_test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn エスケープシーケンスを取り除く() {
+        assert_eq!(
+            strip_ansi("\x1b[92m[\x1b[39mpod \x1b[32mcontainer\x1b[39m\x1b[92m]\x1b[39m log"),
+            "[pod container] log"
+        );
+    }
+}
+
 #[cfg(test)]
 mod parse_test {
     use super::AnsiEscapeSequence::*;
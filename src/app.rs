@@ -9,7 +9,7 @@ use crate::{
     features::{api_resources::kube::ApiConfig, event::kube::EventConfig, pod::kube::PodConfig},
     logger,
     message::Message,
-    workers::{kube::YamlConfig, ApisConfig, KubeWorker, Render, Tick, UserInput},
+    workers::{kube::YamlConfig, ApisConfig, InputRedirect, KubeWorker, Render, Tick, UserInput},
 };
 
 pub struct App;
@@ -17,6 +17,7 @@ pub struct App;
 impl App {
     pub fn run(cmd: Command, config: Config) -> Result<()> {
         let split_direction = cmd.split_direction();
+        let read_only = cmd.read_only;
         let mut kube_worker_config = cmd.kube_worker_config();
 
         let (tx_input, rx_main): (Sender<Message>, Receiver<Message>) = bounded(128);
@@ -26,13 +27,20 @@ impl App {
 
         let (tx_shutdown, rx_shutdown) = bounded::<Result<()>>(1);
 
-        let user_input = UserInput::new(tx_input.clone(), tx_shutdown.clone());
+        let input_redirect = InputRedirect::default();
+
+        let user_input = UserInput::new(
+            tx_input.clone(),
+            tx_shutdown.clone(),
+            input_redirect.clone(),
+        );
 
         kube_worker_config.pod_config = PodConfig::from(config.theme.clone());
         kube_worker_config.event_config = EventConfig::from(config.theme.clone());
         kube_worker_config.api_config = ApiConfig::from(config.theme.clone());
         kube_worker_config.apis_config = ApisConfig::from(config.theme.clone());
         kube_worker_config.yaml_config = YamlConfig::from(config.theme.clone());
+        kube_worker_config.mask_secrets = config.secret.mask;
 
         let kube = KubeWorker::new(
             tx_kube.clone(),
@@ -52,7 +60,9 @@ impl App {
             rx_main.clone(),
             tx_shutdown.clone(),
             split_direction,
-            config.theme.clone(),
+            config,
+            input_redirect,
+            read_only,
         );
 
         logger!(info, "app start");
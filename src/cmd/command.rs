@@ -74,6 +74,10 @@ pub struct Command {
     #[arg(long, display_order = 1000)]
     pub config_file: Option<PathBuf>,
 
+    /// Disable actions that modify resources
+    #[arg(long, display_order = 1000)]
+    pub read_only: bool,
+
     #[command(subcommand)]
     pub subcommand: Option<SubCommand>,
 }
@@ -93,6 +97,7 @@ impl Command {
             context,
             all_namespaces,
             kubeconfig,
+            read_only,
             ..
         } = self.clone();
 
@@ -101,6 +106,7 @@ impl Command {
             target_namespaces: namespaces,
             context,
             all_namespaces: all_namespaces.into(),
+            read_only,
             ..Default::default()
         }
     }
@@ -1,6 +1,9 @@
+pub mod buffer;
+pub mod keybindings;
+pub mod secret;
 pub mod theme;
 
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use anyhow::Result;
 use figment::{
@@ -9,6 +12,9 @@ use figment::{
 };
 use serde::{Deserialize, Serialize};
 
+use buffer::BufferConfig;
+use keybindings::KeyBindingsConfig;
+use secret::SecretConfig;
 use theme::ThemeConfig;
 
 #[derive(Debug, Default)]
@@ -19,9 +25,23 @@ pub enum ConfigLoadOption {
     Path(PathBuf),
 }
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+#[derive(Default, Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub theme: ThemeConfig,
+
+    #[serde(default)]
+    pub buffer: BufferConfig,
+
+    #[serde(default)]
+    pub keybindings: KeyBindingsConfig,
+
+    #[serde(default)]
+    pub secret: SecretConfig,
+
+    /// 名前とログクエリの対応表
+    /// Podタブのダイアログから選択して実行する
+    #[serde(default)]
+    pub saved_queries: BTreeMap<String, String>,
 }
 
 impl Config {
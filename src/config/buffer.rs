@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_LOG_MAX_LINES: usize = 100_000;
+const DEFAULT_EVENT_MAX_LINES: usize = 10_000;
+
+/// ログやイベントを表示するウィジェットが保持する行数の設定
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BufferConfig {
+    #[serde(default = "default_log")]
+    pub log: TextBufferConfig,
+
+    #[serde(default = "default_event")]
+    pub event: TextBufferConfig,
+}
+
+impl Default for BufferConfig {
+    fn default() -> Self {
+        Self {
+            log: default_log(),
+            event: default_event(),
+        }
+    }
+}
+
+fn default_log() -> TextBufferConfig {
+    TextBufferConfig {
+        max_lines: DEFAULT_LOG_MAX_LINES,
+    }
+}
+
+fn default_event() -> TextBufferConfig {
+    TextBufferConfig {
+        max_lines: DEFAULT_EVENT_MAX_LINES,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct TextBufferConfig {
+    /// 保持する行数の上限
+    /// 上限を超えた場合は古い行から削除する
+    /// 0のときは上限なし
+    pub max_lines: usize,
+}
+
+impl TextBufferConfig {
+    pub fn max_lines(&self) -> Option<usize> {
+        (0 < self.max_lines).then_some(self.max_lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn 指定されていない項目はデフォルト値を使う() {
+        let actual: BufferConfig = serde_yaml::from_str(indoc! {
+            r#"
+            log:
+              max_lines: 0
+            "#
+        })
+        .unwrap();
+
+        assert_eq!(actual.log.max_lines(), None);
+        assert_eq!(actual.event.max_lines(), Some(DEFAULT_EVENT_MAX_LINES));
+    }
+}
@@ -0,0 +1,285 @@
+use ratatui::crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+
+use crate::ui::key::{Key, KeyBinding, KeyMap, WidgetKeyBindings};
+
+/// 操作に割り当てるキーの設定
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct KeyBindingsConfig {
+    pub global: GlobalKeyBindings,
+    pub widget: WidgetKeyBindings,
+    pub pod: PodKeyBindings,
+    pub log: LogKeyBindings,
+    pub config: ConfigKeyBindings,
+    pub network: NetworkKeyBindings,
+    pub node: NodeKeyBindings,
+    pub workload: WorkloadKeyBindings,
+    pub helm: HelmKeyBindings,
+    pub port_forward: PortForwardKeyBindings,
+    /// 入力されたキーを別のキーとして扱うための対応表
+    pub key_map: KeyMap,
+}
+
+fn keys<const N: usize>(keys: [Key; N]) -> KeyBinding {
+    KeyBinding::new(keys)
+}
+
+/// どのタブでも使える操作
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct GlobalKeyBindings {
+    pub quit: KeyBinding,
+    pub open_help: KeyBinding,
+    pub select_context: KeyBinding,
+    pub open_namespace_dialog: KeyBinding,
+    pub open_multiple_namespaces_dialog: KeyBinding,
+    pub open_yaml: KeyBinding,
+    pub open_resource_action_menu: KeyBinding,
+    pub open_port_forwards: KeyBinding,
+    pub toggle_split_direction: KeyBinding,
+}
+
+impl Default for GlobalKeyBindings {
+    fn default() -> Self {
+        Self {
+            quit: keys([Key::from('q'), Key::from(KeyCode::Esc)]),
+            open_help: keys([Key::from('h'), Key::from('?')]),
+            select_context: keys([Key::from('c')]),
+            open_namespace_dialog: keys([Key::from('n')]),
+            open_multiple_namespaces_dialog: keys([Key::from('N')]),
+            open_yaml: keys([Key::from('y')]),
+            open_resource_action_menu: keys([Key::from('a')]),
+            open_port_forwards: keys([Key::from('P')]),
+            toggle_split_direction: keys([Key::from('S')]),
+        }
+    }
+}
+
+/// Podタブのテーブルで使える操作
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct PodKeyBindings {
+    pub exec: KeyBinding,
+    pub port_forward: KeyBinding,
+    pub clear_node_filter: KeyBinding,
+}
+
+impl Default for PodKeyBindings {
+    fn default() -> Self {
+        Self {
+            exec: keys([Key::from('e')]),
+            port_forward: keys([Key::from('F')]),
+            clear_node_filter: keys([Key::from('x')]),
+        }
+    }
+}
+
+/// Podタブのログで使える操作
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct LogKeyBindings {
+    pub insert_blank_line: KeyBinding,
+    pub toggle_json: KeyBinding,
+    pub save: KeyBinding,
+    pub toggle_tee: KeyBinding,
+    pub open_saved_queries: KeyBinding,
+}
+
+impl Default for LogKeyBindings {
+    fn default() -> Self {
+        Self {
+            insert_blank_line: keys([Key::from(KeyCode::Enter)]),
+            toggle_json: keys([Key::from('f'), Key::from('p')]),
+            save: keys([Key::from('s')]),
+            toggle_tee: keys([Key::from('t')]),
+            open_saved_queries: keys([Key::from('Q')]),
+        }
+    }
+}
+
+/// Configタブで使える操作
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ConfigKeyBindings {
+    pub toggle_decode: KeyBinding,
+    pub reveal: KeyBinding,
+}
+
+impl Default for ConfigKeyBindings {
+    fn default() -> Self {
+        Self {
+            toggle_decode: keys([Key::from('d')]),
+            reveal: keys([Key::from('r')]),
+        }
+    }
+}
+
+/// Networkタブのテーブルで使える操作
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct NetworkKeyBindings {
+    pub port_forward: KeyBinding,
+    pub reachability: KeyBinding,
+}
+
+impl Default for NetworkKeyBindings {
+    fn default() -> Self {
+        Self {
+            port_forward: keys([Key::from('F')]),
+            reachability: keys([Key::from('r')]),
+        }
+    }
+}
+
+/// Nodeタブのテーブルで使える操作
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct NodeKeyBindings {
+    pub show_pods: KeyBinding,
+}
+
+impl Default for NodeKeyBindings {
+    fn default() -> Self {
+        Self {
+            show_pods: keys([Key::from('p')]),
+        }
+    }
+}
+
+/// Workloadタブのテーブルで使える操作
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct WorkloadKeyBindings {
+    pub show_logs: KeyBinding,
+    pub rollout_history: KeyBinding,
+    pub job_history: KeyBinding,
+}
+
+impl Default for WorkloadKeyBindings {
+    fn default() -> Self {
+        Self {
+            show_logs: keys([Key::from('l')]),
+            rollout_history: keys([Key::from('r')]),
+            job_history: keys([Key::from('H')]),
+        }
+    }
+}
+
+/// Helmタブのテーブルで使える操作
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct HelmKeyBindings {
+    pub select_view: KeyBinding,
+    pub diff: KeyBinding,
+}
+
+impl Default for HelmKeyBindings {
+    fn default() -> Self {
+        Self {
+            select_view: keys([Key::from('v')]),
+            diff: keys([Key::from('d')]),
+        }
+    }
+}
+
+/// ポートフォワードの一覧ダイアログで使える操作
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct PortForwardKeyBindings {
+    pub stop: KeyBinding,
+}
+
+impl Default for PortForwardKeyBindings {
+    fn default() -> Self {
+        Self {
+            stop: keys([Key::from('d'), Key::from(KeyCode::Delete)]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use ratatui::crossterm::event::KeyModifiers;
+
+    #[test]
+    fn 指定していない操作はデフォルトのキーを使う() {
+        let yaml = indoc! {r#"
+            global:
+              open-namespace-dialog: ctrl-n
+              select-context: [c, C]
+            key-map:
+              ctrl-j: down
+        "#};
+
+        let actual: KeyBindingsConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            actual.global.open_namespace_dialog,
+            keys([Key::new(KeyCode::Char('n'), KeyModifiers::CONTROL)])
+        );
+        assert_eq!(
+            actual.global.select_context,
+            keys([Key::from('c'), Key::from('C')])
+        );
+        assert_eq!(actual.global.quit, GlobalKeyBindings::default().quit);
+        assert_eq!(actual.log, LogKeyBindings::default());
+        assert_eq!(
+            actual
+                .key_map
+                .get(&Key::new(KeyCode::Char('j'), KeyModifiers::CONTROL)),
+            Some(&Key::from(KeyCode::Down))
+        );
+        assert_eq!(
+            actual
+                .key_map
+                .get(&Key::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+            Some(&Key::from(KeyCode::Up))
+        );
+    }
+
+    #[test]
+    fn key_mapで変換先にnullを指定したキーはデフォルトの対応を取り除く() {
+        let yaml = indoc! {r#"
+            key-map:
+              ctrl-p: null
+              ctrl-n: up
+        "#};
+
+        let actual: KeyBindingsConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            actual
+                .key_map
+                .get(&Key::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+            None
+        );
+        assert_eq!(
+            actual
+                .key_map
+                .get(&Key::new(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+            Some(&Key::from(KeyCode::Up))
+        );
+        assert_eq!(
+            actual
+                .key_map
+                .get(&Key::new(KeyCode::Char('f'), KeyModifiers::CONTROL)),
+            Some(&Key::from(KeyCode::Right))
+        );
+    }
+
+    #[test]
+    fn デフォルトの設定をシリアライズして読み込める() {
+        let config = KeyBindingsConfig::default();
+
+        let yaml = serde_yaml::to_string(&config).unwrap();
+
+        let actual: KeyBindingsConfig = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(actual, config);
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// Secretの値の表示に関する設定
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SecretConfig {
+    /// trueのとき、Secretの値をマスクして表示する
+    /// キー名は常に表示する
+    #[serde(default = "default_mask")]
+    pub mask: bool,
+}
+
+impl Default for SecretConfig {
+    fn default() -> Self {
+        Self {
+            mask: default_mask(),
+        }
+    }
+}
+
+fn default_mask() -> bool {
+    true
+}
@@ -8,6 +8,7 @@ mod header;
 mod help;
 mod input;
 mod list;
+mod log;
 mod pod;
 mod style;
 mod tab;
@@ -36,6 +37,7 @@ pub use filter::*;
 pub use help::HelpThemeConfig;
 pub use input::*;
 pub use list::*;
+pub use log::LogThemeConfig;
 pub use pod::*;
 pub use style::ThemeStyleConfig;
 pub use table::*;
@@ -63,6 +65,9 @@ pub struct ThemeConfig {
     #[serde(default)]
     pub event: EventThemeConfig,
 
+    #[serde(default)]
+    pub log: LogThemeConfig,
+
     #[serde(default)]
     pub api: ApiThemeConfig,
 
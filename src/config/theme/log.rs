@@ -0,0 +1,125 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::ui::widget::{TextHighlightRule, TextHighlightScope};
+
+use super::ThemeStyleConfig;
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LogThemeConfig {
+    #[serde(default)]
+    pub highlights: Vec<LogHighlightConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LogHighlightConfig {
+    #[serde(with = "serde_regex")]
+    pub regex: Regex,
+
+    /// スタイルを適用する範囲
+    #[serde(default)]
+    pub scope: LogHighlightScope,
+
+    #[serde(flatten)]
+    pub style: ThemeStyleConfig,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogHighlightScope {
+    /// マッチした箇所のみ
+    #[default]
+    Match,
+
+    /// マッチした行全体
+    Line,
+}
+
+mod serde_regex {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(regex: &regex::Regex, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&regex.to_string())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<regex::Regex, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        regex::Regex::new(&s).map_err(de::Error::custom)
+    }
+}
+
+impl PartialEq for LogHighlightConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.regex.as_str() == other.regex.as_str()
+            && self.scope == other.scope
+            && self.style == other.style
+    }
+}
+
+impl From<LogHighlightConfig> for TextHighlightRule {
+    fn from(config: LogHighlightConfig) -> Self {
+        let scope = match config.scope {
+            LogHighlightScope::Match => TextHighlightScope::Match,
+            LogHighlightScope::Line => TextHighlightScope::Line,
+        };
+
+        TextHighlightRule {
+            regex: config.regex,
+            style: config.style.into(),
+            scope,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::{Color, Modifier};
+
+    #[test]
+    fn 範囲を指定しない場合はマッチした箇所のみに適用する() {
+        let actual: LogThemeConfig = serde_yaml::from_str(indoc! {
+            r#"
+            highlights:
+              - regex: ERROR
+                fg_color: red
+                scope: line
+              - regex: trace_id=\w+
+                modifier: underlined
+            "#
+        })
+        .unwrap();
+
+        let expected = LogThemeConfig {
+            highlights: vec![
+                LogHighlightConfig {
+                    regex: Regex::new("ERROR").unwrap(),
+                    scope: LogHighlightScope::Line,
+                    style: ThemeStyleConfig {
+                        fg_color: Some(Color::Red),
+                        ..Default::default()
+                    },
+                },
+                LogHighlightConfig {
+                    regex: Regex::new(r"trace_id=\w+").unwrap(),
+                    scope: LogHighlightScope::Match,
+                    style: ThemeStyleConfig {
+                        modifier: Modifier::UNDERLINED,
+                        ..Default::default()
+                    },
+                },
+            ],
+        };
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -3,12 +3,18 @@ pub mod component_id;
 pub mod config;
 pub mod context;
 pub mod event;
+pub mod exec;
 pub mod get;
+pub mod helm;
 pub mod help;
 pub mod namespace;
 pub mod network;
+pub mod node;
 pub mod pod;
+pub mod port_forward;
+pub mod resource_action;
 mod styled_api_resource;
+pub mod workload;
 pub mod yaml;
 
 pub use styled_api_resource::StyledApiResource;
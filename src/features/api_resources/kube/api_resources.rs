@@ -20,7 +20,7 @@ use crate::{
             metrics::{NodeMetricsList, PodMetricsList},
             v1_table::{Table, TableColumnDefinition, Value},
         },
-        table::insert_ns,
+        table::{insert_ns, KubeTableRow},
         KubeClient, KubeClientRequest as _,
     },
     message::Message,
@@ -86,6 +86,7 @@ impl<T: Into<Vec<ApiResource>>> From<T> for ApiResources {
 pub enum ApiResource {
     Apis {
         name: String,
+        kind: String,
         group: String,
         version: String,
         preferred_version: bool,
@@ -94,6 +95,7 @@ pub enum ApiResource {
     },
     Api {
         name: String,
+        kind: String,
         version: String,
         #[serde(with = "scope_format")]
         scope: Scope,
@@ -163,6 +165,13 @@ impl ApiResource {
         }
     }
 
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::Api { kind, .. } => kind,
+            Self::Apis { kind, .. } => kind,
+        }
+    }
+
     pub fn group_version_url(&self) -> String {
         match self {
             Self::Apis { group, version, .. } => format!("apis/{}/{}", group, version),
@@ -240,6 +249,15 @@ impl Display for ApiResource {
     }
 }
 
+/// APIタブに表示するリソースごとのテーブル
+#[derive(Debug, Clone)]
+pub struct ApiTable {
+    pub resource: ApiResource,
+    pub title: String,
+    pub header: Vec<String>,
+    pub rows: Vec<KubeTableRow>,
+}
+
 #[derive(Clone)]
 pub struct ApiPoller {
     tx: Sender<Message>,
@@ -375,12 +393,14 @@ pub async fn fetch_api_resources(client: &KubeClient) -> Result<ApiResources> {
                             if group.name() == ApiGroup::CORE_GROUP {
                                 Some(ApiResource::Api {
                                     name: ar.plural.to_string(),
+                                    kind: ar.kind.to_string(),
                                     version: ar.version.to_string(),
                                     scope: caps.scope.clone(),
                                 })
                             } else {
                                 Some(ApiResource::Apis {
                                     name: ar.plural.to_string(),
+                                    kind: ar.kind.to_string(),
                                     group: ar.group.to_string(),
                                     version: ar.version.to_string(),
                                     preferred_version: is_preferred_version,
@@ -405,17 +425,22 @@ fn merge_tables(fetch_data: Vec<FetchData>, insert_ns: bool) -> Table {
     let mut base_table = fetch_data[0].table.clone();
     let base_ns = &fetch_data[0].namespace;
 
+    // フィルターは先頭の列に対して行うため、Namespace列はName列の後ろに挿入する
+    let ns_index = base_table.find_index("Name").map_or(0, |i| i + 1);
+
     if insert_ns {
         let column_definitions = TableColumnDefinition {
             name: "Namespace".to_string(),
             ..Default::default()
         };
 
-        base_table.column_definitions.insert(0, column_definitions);
+        base_table
+            .column_definitions
+            .insert(ns_index, column_definitions);
 
         base_table.rows.iter_mut().for_each(|row| {
             row.cells
-                .insert(0, Value(JsonValue::String(base_ns.to_string())))
+                .insert(ns_index, Value(JsonValue::String(base_ns.to_string())))
         });
     }
 
@@ -424,7 +449,7 @@ fn merge_tables(fetch_data: Vec<FetchData>, insert_ns: bool) -> Table {
             let ns = d.namespace.to_string();
             d.table.rows.iter_mut().for_each(|row| {
                 row.cells
-                    .insert(0, Value(JsonValue::String(ns.to_string())));
+                    .insert(ns_index, Value(JsonValue::String(ns.to_string())));
             });
         }
 
@@ -514,7 +539,7 @@ impl<'a> FetchTargetApiResources<'a> {
         }
     }
 
-    async fn fetch_table(&self) -> Result<Vec<String>> {
+    async fn fetch_table(&self) -> Result<Vec<ApiTable>> {
         let mut ret = Vec::new();
         for api_resource in self.target_api_resources {
             let table = if api_resource.is_namespaced() {
@@ -524,15 +549,19 @@ impl<'a> FetchTargetApiResources<'a> {
                 get_table_cluster_resource(self.client, &api_resource.api_url()).await
             }?;
 
-            let data = if table.rows.is_empty() {
-                table_title(api_resource, self.config.resource)
-            } else {
-                let styled_table = StyledTable::new(&table, self.config.header, self.config.rows);
-                table_title(api_resource, self.config.resource) + &styled_table.to_string()
+            let default_namespace = match self.target_namespace.as_slice() {
+                [ns] if api_resource.is_namespaced() => ns.as_str(),
+                _ => "",
             };
 
-            ret.extend(data.lines().map(ToString::to_string).collect::<Vec<_>>());
-            ret.push("".to_string());
+            let styled_table = StyledTable::new(&table, self.config.header, self.config.rows);
+
+            ret.push(ApiTable {
+                resource: api_resource.clone(),
+                title: table_title(api_resource, self.config.resource),
+                header: styled_table.header(),
+                rows: styled_table.rows(default_namespace),
+            });
         }
 
         Ok(ret)
@@ -540,7 +569,7 @@ impl<'a> FetchTargetApiResources<'a> {
 }
 
 fn table_title(api_resource: &ApiResource, style: Style) -> String {
-    format!("{}[ {} ]\x1b[39m\n", style_to_ansi(style), api_resource)
+    format!("{}[ {} ]\x1b[39m", style_to_ansi(style), api_resource)
 }
 
 #[cfg(test)]
@@ -553,9 +582,9 @@ mod tests {
         use rstest::rstest;
 
         #[rstest]
-        #[case(ApiResource::Api { name: "pods".into(), version: "v1".into(), scope: Scope::Namespaced }, "pods")]
-        #[case(ApiResource::Apis { name: "horizontalpodautoscalers".into(), group: "autoscaling".into(), version: "v2".into(), preferred_version: true, scope: Scope::Namespaced }, "horizontalpodautoscalers.autoscaling (*v2)")]
-        #[case(ApiResource::Apis { name: "horizontalpodautoscalers".into(), group: "autoscaling".into(), version: "v1".into(), preferred_version: false, scope: Scope::Namespaced }, "horizontalpodautoscalers.autoscaling (v1)")]
+        #[case(ApiResource::Api { name: "pods".into(), kind: "Pod".into(), version: "v1".into(), scope: Scope::Namespaced }, "pods")]
+        #[case(ApiResource::Apis { name: "horizontalpodautoscalers".into(), kind: "HorizontalPodAutoscaler".into(), group: "autoscaling".into(), version: "v2".into(), preferred_version: true, scope: Scope::Namespaced }, "horizontalpodautoscalers.autoscaling (*v2)")]
+        #[case(ApiResource::Apis { name: "horizontalpodautoscalers".into(), kind: "HorizontalPodAutoscaler".into(), group: "autoscaling".into(), version: "v1".into(), preferred_version: false, scope: Scope::Namespaced }, "horizontalpodautoscalers.autoscaling (v1)")]
         #[test]
         fn to_string(#[case] key: ApiResource, #[case] expected: &str) {
             assert_eq!(key.to_string(), expected)
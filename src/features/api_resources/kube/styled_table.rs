@@ -1,9 +1,14 @@
-use std::fmt::Display;
-
 use ratatui::style::Style;
 
-use crate::{kube::apis::v1_table::Table, ui::widget::ansi_color::style_to_ansi};
+use crate::{
+    kube::{
+        apis::v1_table::{Table, TableRow},
+        table::KubeTableRow,
+    },
+    ui::widget::ansi_color::style_to_ansi,
+};
 
+/// Table APIのレスポンスからpriorityが0の列を抜き出し、ヘッダーと行に色を付ける
 pub struct StyledTable<'a> {
     table: &'a Table,
     header_style: Style,
@@ -18,114 +23,118 @@ impl<'a> StyledTable<'a> {
             rows_style,
         }
     }
-}
 
-const WIDTH_PADDING: &str = "   ";
+    pub fn header(&self) -> Vec<String> {
+        self.column_indexes()
+            .into_iter()
+            .map(|i| {
+                styled(
+                    self.header_style,
+                    &self.table.column_definitions[i].name.to_uppercase(),
+                )
+            })
+            .collect()
+    }
 
-impl Display for StyledTable<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.table.rows.is_empty() || self.table.column_definitions.is_empty() {
-            return Ok(());
-        }
+    /// 各行のnamespaceとnameを取り出し、色を付けたセルと一緒に返す
+    ///
+    /// 行からnamespaceを特定できないときは`default_namespace`を使う
+    pub fn rows(&self, default_namespace: &str) -> Vec<KubeTableRow> {
+        let indexes = self.column_indexes();
 
-        // priorityが0のヘッダーを抜き出す
-        let header: Vec<(usize, &str)> = self
-            .table
-            .column_definitions
-            .iter()
-            .enumerate()
-            .filter(|(_, c)| c.priority == 0)
-            .map(|(i, c)| (i, c.name.as_ref()))
-            .collect::<Vec<_>>();
+        let name_index = self.table.find_index("Name");
+        let namespace_index = self.table.find_index("Namespace");
 
-        // ヘッダーに応じた列を持つ行を抜き出す
-        let rows: Vec<Vec<String>> = self
-            .table
+        self.table
             .rows
             .iter()
             .map(|row| {
-                header
-                    .iter()
-                    .map(|(i, _)| row.cells[*i].to_string())
-                    .collect()
-            })
-            .collect();
+                let name = object_metadata(row, "name")
+                    .or_else(|| cell(row, name_index))
+                    .unwrap_or_default();
 
-        // 各行ごとの文字幅を決定するdigitsを計算
-        // 1. ヘッダーの文字列長を取得
-        let mut digits: Vec<usize> = header.iter().map(|(_, hdr)| hdr.len()).collect();
+                let namespace = object_metadata(row, "namespace")
+                    .or_else(|| cell(row, namespace_index))
+                    .unwrap_or_else(|| default_namespace.to_string());
 
-        // 2. 各ヘッダーの文字列長と列の文字列長を比較して大きいほうで文字列長を更新
-        rows.iter().for_each(|cells| {
-            cells.iter().enumerate().for_each(|(i, cell)| {
-                if digits[i] < cell.len() {
-                    digits[i] = cell.len()
+                let cells = indexes
+                    .iter()
+                    .map(|i| {
+                        let value = row.cells.get(*i).map(ToString::to_string);
+                        styled(self.rows_style, &value.unwrap_or_default())
+                    })
+                    .collect();
+
+                KubeTableRow {
+                    namespace,
+                    name,
+                    row: cells,
+                    ..Default::default()
                 }
-            });
-        });
-
-        // ヘッダーを表示
-        write!(f, "{}", style_to_ansi(self.header_style))?;
+            })
+            .collect()
+    }
 
-        let header_str = header
+    /// priorityが0の列のインデックスを返す
+    fn column_indexes(&self) -> Vec<usize> {
+        self.table
+            .column_definitions
             .iter()
-            .map(|(i, hdr)| format!("{:<digit$}", hdr.to_uppercase(), digit = digits[*i]))
-            .collect::<Vec<String>>()
-            .join(WIDTH_PADDING);
-
-        write!(f, "{}\x1b[39m", header_str)?;
-
-        // 行を表示
-        for row in rows.iter() {
-            write!(f, "\n{}", style_to_ansi(self.rows_style))?;
+            .enumerate()
+            .filter(|(_, c)| c.priority == 0)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
 
-            let rows_str = row
-                .iter()
-                .enumerate()
-                .map(|(i, cell)| format!("{:<digit$}", cell, digit = digits[i]))
-                .collect::<Vec<String>>()
-                .join(WIDTH_PADDING);
+fn styled(style: Style, s: &str) -> String {
+    format!("{}{}\x1b[39m", style_to_ansi(style), s)
+}
 
-            write!(f, "{}\x1b[39m", rows_str)?;
-        }
+fn cell(row: &TableRow, index: Option<usize>) -> Option<String> {
+    index
+        .and_then(|i| row.cells.get(i))
+        .map(ToString::to_string)
+}
 
-        Ok(())
-    }
+fn object_metadata(row: &TableRow, key: &str) -> Option<String> {
+    row.object
+        .as_ref()?
+        .0
+        .get("metadata")?
+        .get(key)?
+        .as_str()
+        .map(ToString::to_string)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::kube::apis::v1_table::{TableColumnDefinition, TableRow};
-    use indoc::indoc;
+    use crate::kube::apis::v1_table::TableColumnDefinition;
+    use k8s_openapi::apimachinery::pkg::runtime::RawExtension;
     use pretty_assertions::assert_eq;
     use ratatui::style::Color;
+    use serde_json::json;
 
     use super::*;
 
-    // テーブルデータが空の場合は何も表示しない
-    #[test]
-    fn when_table_is_empty_then_display_nothing() {
-        let table = Table {
-            column_definitions: vec![],
-            rows: vec![],
-            ..Default::default()
-        };
-
-        let styled_table = StyledTable::new(&table, Style::default(), Style::default());
-
-        assert_eq!(styled_table.to_string(), "");
-    }
-
-    // テーブルデータが存在する場合はヘッダーと行を表示する
-    #[test]
-    fn when_table_has_data_then_display_header_and_rows() {
-        let table = Table {
+    fn table() -> Table {
+        Table {
             column_definitions: vec![
                 TableColumnDefinition {
                     name: "Name".into(),
                     priority: 0,
                     ..Default::default()
                 },
+                TableColumnDefinition {
+                    name: "Namespace".into(),
+                    priority: 0,
+                    ..Default::default()
+                },
+                TableColumnDefinition {
+                    name: "Node".into(),
+                    priority: 1,
+                    ..Default::default()
+                },
                 TableColumnDefinition {
                     name: "Age".into(),
                     priority: 0,
@@ -134,16 +143,33 @@ mod tests {
             ],
             rows: vec![
                 TableRow {
-                    cells: ["Alice", "20"].into_iter().map(|s| s.into()).collect(),
+                    cells: ["alice", "foo", "node-1", "20s"]
+                        .into_iter()
+                        .map(|s| s.into())
+                        .collect(),
+                    object: Some(RawExtension(json!({
+                        "metadata": {
+                            "name": "alice",
+                            "namespace": "foo"
+                        }
+                    }))),
                     ..Default::default()
                 },
                 TableRow {
-                    cells: ["Bob", "30"].into_iter().map(|s| s.into()).collect(),
+                    cells: ["bob", "bar", "node-2", "30s"]
+                        .into_iter()
+                        .map(|s| s.into())
+                        .collect(),
                     ..Default::default()
                 },
             ],
             ..Default::default()
-        };
+        }
+    }
+
+    #[test]
+    fn ヘッダーはpriorityが0の列を大文字にして色を付ける() {
+        let table = table();
 
         let styled_table = StyledTable::new(
             &table,
@@ -151,12 +177,72 @@ mod tests {
             Style::default().fg(Color::White),
         );
 
-        let expected = indoc! {"
-            \x1b[90mNAME    AGE\x1b[39m
-            \x1b[37mAlice   20 \x1b[39m
-            \x1b[37mBob     30 \x1b[39m
-        "};
+        assert_eq!(
+            styled_table.header(),
+            vec![
+                "\x1b[90mNAME\x1b[39m",
+                "\x1b[90mNAMESPACE\x1b[39m",
+                "\x1b[90mAGE\x1b[39m",
+            ]
+        );
+    }
+
+    #[test]
+    fn 行はpriorityが0の列に色を付けnamespaceとnameを持つ() {
+        let table = table();
+
+        let styled_table = StyledTable::new(
+            &table,
+            Style::default().fg(Color::DarkGray),
+            Style::default().fg(Color::White),
+        );
+
+        let expected = vec![
+            KubeTableRow {
+                namespace: "foo".into(),
+                name: "alice".into(),
+                row: vec![
+                    "\x1b[37malice\x1b[39m".into(),
+                    "\x1b[37mfoo\x1b[39m".into(),
+                    "\x1b[37m20s\x1b[39m".into(),
+                ],
+                ..Default::default()
+            },
+            KubeTableRow {
+                namespace: "bar".into(),
+                name: "bob".into(),
+                row: vec![
+                    "\x1b[37mbob\x1b[39m".into(),
+                    "\x1b[37mbar\x1b[39m".into(),
+                    "\x1b[37m30s\x1b[39m".into(),
+                ],
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(styled_table.rows("default"), expected);
+    }
+
+    #[test]
+    fn 行からnamespaceを特定できないときは指定したnamespaceを使う() {
+        let table = Table {
+            column_definitions: vec![TableColumnDefinition {
+                name: "Name".into(),
+                priority: 0,
+                ..Default::default()
+            }],
+            rows: vec![TableRow {
+                cells: vec!["alice".into()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let styled_table = StyledTable::new(&table, Style::default(), Style::default());
+
+        let actual = styled_table.rows("default");
 
-        assert_eq!(styled_table.to_string(), expected.trim_end());
+        assert_eq!(actual[0].namespace, "default");
+        assert_eq!(actual[0].name, "alice");
     }
 }
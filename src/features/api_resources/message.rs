@@ -2,7 +2,7 @@ use anyhow::Result;
 
 use crate::{features::StyledApiResource, message::Message, workers::kube::message::Kube};
 
-use super::kube::ApiResource;
+use super::kube::{ApiResource, ApiTable};
 
 #[derive(Debug)]
 pub enum ApiRequest {
@@ -13,7 +13,7 @@ pub enum ApiRequest {
 #[derive(Debug)]
 pub enum ApiResponse {
     Get(Result<Vec<StyledApiResource>>),
-    Poll(Result<Vec<String>>),
+    Poll(Result<Vec<ApiTable>>),
 }
 
 #[derive(Debug)]
@@ -1,8 +1,8 @@
+use std::{cell::RefCell, rc::Rc};
+
 use crossbeam::channel::Sender;
 use ratatui::prelude::Constraint;
 
-use std::{cell::RefCell, rc::Rc};
-
 use crate::{
     clipboard::Clipboard,
     config::theme::WidgetThemeConfig,
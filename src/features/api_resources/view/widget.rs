@@ -6,15 +6,16 @@ use crate::{
     clipboard::Clipboard,
     config::theme::WidgetThemeConfig,
     features::{
-        api_resources::message::ApiRequest,
-        component_id::{API_DIALOG_ID, API_WIDGET_ID},
+        api_resources::{kube::ApiResource, message::ApiRequest},
+        component_id::{API_DIALOG_ID, API_WIDGET_ID, YAML_DIALOG_ID},
+        get::message::{GetRequest, GetYamlKind},
     },
     message::Message,
     ui::{
         event::EventResult,
         widget::{
-            SearchForm, SearchFormTheme, Text, TextTheme, Widget, WidgetBase, WidgetTheme,
-            WidgetTrait as _,
+            FilterForm, FilterFormTheme, Table, TableItem, TableTheme, Widget, WidgetBase,
+            WidgetTheme, WidgetTrait as _,
         },
         Window,
     },
@@ -25,41 +26,25 @@ pub fn api_widget(
     clipboard: &Option<Rc<RefCell<Clipboard>>>,
     theme: WidgetThemeConfig,
 ) -> Widget<'static> {
-    let tx = tx.clone();
-
-    let open_subwin = move |w: &mut Window| {
-        tx.send(ApiRequest::Get.into())
-            .expect("Failed to send ApiRequest::Get");
-        w.open_dialog(API_DIALOG_ID);
-        EventResult::Nop
-    };
-
     let widget_theme = WidgetTheme::from(theme.clone());
-    let search_theme = SearchFormTheme::from(theme.clone());
-    let text_theme = TextTheme::from(theme);
+    let table_theme = TableTheme::from(theme.clone());
+    let filter_form_theme = FilterFormTheme::from(theme);
 
     let widget_base = WidgetBase::builder()
         .title("API")
         .theme(widget_theme)
         .build();
 
-    let search_form = SearchForm::builder().theme(search_theme).build();
+    let filter_form = FilterForm::builder().theme(filter_form_theme).build();
 
-    let builder = Text::builder()
+    let builder = Table::builder()
         .id(API_WIDGET_ID)
         .widget_base(widget_base)
-        .search_form(search_form)
-        .theme(text_theme)
-        .block_injection(|text: &Text, is_active: bool, is_mouse_over: bool| {
-            let (index, size) = text.state();
-
-            let mut base = text.widget_base().clone();
-
-            *base.append_title_mut() = Some(format!(" [{}/{}]", index, size).into());
-
-            base.render_block(text.can_activate() && is_active, is_mouse_over)
-        })
-        .action('f', open_subwin);
+        .filter_form(filter_form)
+        .theme(table_theme)
+        .block_injection(block_injection())
+        .on_select(on_select(tx.clone()))
+        .action('f', open_subwin(tx.clone()));
 
     if let Some(cb) = clipboard {
         builder.clipboard(cb.clone())
@@ -69,3 +54,69 @@ pub fn api_widget(
     .build()
     .into()
 }
+
+fn block_injection() -> impl Fn(&Table) -> WidgetBase {
+    |table: &Table| {
+        let index = if let Some(index) = table.state().selected() {
+            index + 1
+        } else {
+            0
+        };
+
+        let mut base = table.widget_base().clone();
+
+        *base.append_title_mut() = Some(format!(" [{}/{}]", index, table.items().len()).into());
+
+        base
+    }
+}
+
+fn open_subwin(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        tx.send(ApiRequest::Get.into())
+            .expect("Failed to send ApiRequest::Get");
+        w.open_dialog(API_DIALOG_ID);
+        EventResult::Nop
+    }
+}
+
+/// 選択した行のリソースのyamlを表示する
+///
+/// リソースの見出しやヘッダーの行はmetadataを持たないため何もしない
+fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &TableItem) -> EventResult {
+    move |w: &mut Window, v: &TableItem| {
+        let Some(ref metadata) = v.metadata else {
+            return EventResult::Ignore;
+        };
+
+        let Some(namespace) = metadata.get("namespace") else {
+            return EventResult::Ignore;
+        };
+
+        let Some(name) = metadata.get("name") else {
+            return EventResult::Ignore;
+        };
+
+        let Some(Ok(api_resource)) = metadata
+            .get("key")
+            .map(|key| serde_json::from_str::<ApiResource>(key))
+        else {
+            return EventResult::Ignore;
+        };
+
+        tx.send(
+            GetRequest {
+                name: name.to_string(),
+                namespace: namespace.to_string(),
+                kind: GetYamlKind::ApiResource(api_resource),
+            }
+            .into(),
+        )
+        .expect("Failed to send GetRequest");
+
+        w.widget_clear(YAML_DIALOG_ID);
+        w.open_dialog(YAML_DIALOG_ID);
+
+        EventResult::Nop
+    }
+}
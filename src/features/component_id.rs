@@ -18,6 +18,9 @@ component_id!(
     api_tab,
     network_tab,
     yaml_tab,
+    node_tab,
+    workload_tab,
+    helm_tab,
     // widgets
     pod_widget,
     pod_log_widget,
@@ -29,8 +32,18 @@ component_id!(
     event_widget,
     api_widget,
     yaml_widget,
+    node_widget,
+    node_description_widget,
+    workload_widget,
+    workload_description_widget,
+    helm_widget,
+    helm_content_widget,
     // dialogs
     pod_log_query_help_dialog,
+    pod_log_export_format_dialog,
+    pod_log_export_path_dialog,
+    pod_log_export_result_dialog,
+    pod_log_saved_queries_dialog,
     context_dialog,
     single_namespace_dialog,
     multiple_namespaces_dialog,
@@ -39,5 +52,22 @@ component_id!(
     yaml_name_dialog,
     yaml_not_found_dialog,
     help_dialog,
-    yaml_dialog
+    yaml_dialog,
+    exec_container_dialog,
+    port_forward_target_dialog,
+    port_forward_dialog,
+    network_reachability_dialog,
+    network_reachability_result_dialog,
+    resource_action_dialog,
+    resource_action_confirm_dialog,
+    resource_action_scale_dialog,
+    resource_action_error_dialog,
+    workload_rollout_history_dialog,
+    workload_rollout_menu_dialog,
+    workload_rollout_confirm_dialog,
+    workload_rollout_result_dialog,
+    workload_job_history_dialog,
+    helm_view_dialog,
+    helm_diff_dialog,
+    config_reveal_dialog
 );
@@ -1,38 +1,40 @@
-use std::{collections::BTreeMap, time};
+use std::{collections::BTreeMap, sync::Arc, time};
 
 use crate::{
     features::config::message::ConfigResponse,
     kube::{
-        apis::v1_table::TableRow,
-        table::{get_resource_per_namespace, insert_ns, KubeTable, KubeTableRow},
-        KubeClient,
+        informer::{ChangeDetector, SharedInformers},
+        table::{insert_ns, translate_timestamp_since, KubeTable, KubeTableRow},
     },
     message::Message,
     workers::kube::{SharedTargetNamespaces, Worker, WorkerResult},
 };
 
-use anyhow::Result;
 use async_trait::async_trait;
 use crossbeam::channel::Sender;
-use futures::future::try_join_all;
+use k8s_openapi::{
+    api::core::v1::{ConfigMap, Secret},
+    Resource as _,
+};
+use kube::{core::PartialObjectMeta, ResourceExt};
 
 #[derive(Clone)]
 pub struct ConfigPoller {
     tx: Sender<Message>,
     shared_target_namespaces: SharedTargetNamespaces,
-    kube_client: KubeClient,
+    informers: SharedInformers,
 }
 
 impl ConfigPoller {
     pub fn new(
         tx: Sender<Message>,
         shared_target_namespaces: SharedTargetNamespaces,
-        kube_client: KubeClient,
+        informers: SharedInformers,
     ) -> Self {
         Self {
             tx,
             shared_target_namespaces,
-            kube_client,
+            informers,
         }
     }
 }
@@ -47,15 +49,35 @@ impl Worker for ConfigPoller {
         let Self {
             tx,
             shared_target_namespaces,
-            kube_client,
+            informers,
         } = self;
 
+        let mut configmap_informer = informers.namespaced::<ConfigMap>();
+        let secret_informer = informers.secret_metadata();
+
+        let mut detector = ChangeDetector::default();
+
         loop {
             interval.tick().await;
 
-            let target_namespaces = shared_target_namespaces.read().await;
+            let target_namespaces = shared_target_namespaces.read().await.clone();
+
+            configmap_informer.sync_namespaces(&target_namespaces);
+            secret_informer.sync_namespaces(&target_namespaces);
+
+            let (Some(configmaps), Some(secrets)) =
+                (configmap_informer.state(), secret_informer.state())
+            else {
+                continue;
+            };
 
-            let table = fetch_configs(kube_client, &target_namespaces).await;
+            let table = configmaps.and_then(|configmaps| {
+                secrets.map(|secrets| config_table(&target_namespaces, &configmaps, &secrets))
+            });
+
+            if !detector.is_changed(&table) {
+                continue;
+            }
 
             tx.send(ConfigResponse::Table(table).into())
                 .expect("Failed to send ConfigResponse::Table");
@@ -70,62 +92,53 @@ enum Configs {
 }
 
 impl Configs {
-    fn kind(&self) -> &'static str {
-        match self {
-            Self::ConfigMap => "configmaps",
-            Self::Secret => "secrets",
-        }
-    }
-
     fn resource(&self) -> &'static str {
         match self {
-            Self::ConfigMap => "ConfigMap",
-            Self::Secret => "Secret",
+            Self::ConfigMap => ConfigMap::KIND,
+            Self::Secret => Secret::KIND,
         }
     }
 }
 
-async fn fetch_configs_per_namespace(
-    client: &KubeClient,
-    namespaces: &[String],
+/// dataにはDATA列に表示する値の数を渡す
+fn config_row(
     ty: Configs,
-) -> Result<Vec<KubeTableRow>> {
-    let insert_ns = insert_ns(namespaces);
-    let jobs = try_join_all(namespaces.iter().map(|ns| {
-        get_resource_per_namespace(
-            client,
-            format!("api/v1/namespaces/{}/{}", ns, ty.kind()),
-            &["Name", r#"Data"#, "Age"],
-            move |row: &TableRow, indexes: &[usize]| {
-                let mut row = vec![
-                    ty.resource().to_string(),
-                    row.cells[indexes[0]].to_string(),
-                    row.cells[indexes[1]].to_string(),
-                    row.cells[indexes[2]].to_string(),
-                ];
-
-                let kind = row[0].clone();
-                let name = row[1].clone();
-
-                if insert_ns {
-                    row.insert(0, ns.to_string())
-                }
-
-                KubeTableRow {
-                    namespace: ns.to_string(),
-                    name,
-                    row,
-                    metadata: Some(BTreeMap::from([("kind".to_string(), kind)])),
-                }
-            },
-        )
-    }))
-    .await?;
+    resource: &impl ResourceExt,
+    data: String,
+    insert_ns: bool,
+) -> KubeTableRow {
+    let namespace = resource.namespace().unwrap_or_default();
+    let name = resource.name_any();
+
+    let mut row = vec![
+        ty.resource().to_string(),
+        name.clone(),
+        data,
+        translate_timestamp_since(resource.meta().creation_timestamp.as_ref()),
+    ];
+
+    if insert_ns {
+        row.insert(0, namespace.clone())
+    }
 
-    Ok(jobs.into_iter().flatten().collect())
+    KubeTableRow {
+        namespace,
+        name,
+        row,
+        metadata: Some(BTreeMap::from([(
+            "kind".to_string(),
+            ty.resource().to_string(),
+        )])),
+    }
 }
 
-async fn fetch_configs(client: &KubeClient, namespaces: &[String]) -> Result<KubeTable> {
+fn config_table(
+    namespaces: &[String],
+    configmaps: &[Arc<ConfigMap>],
+    secrets: &[Arc<PartialObjectMeta<Secret>>],
+) -> KubeTable {
+    let insert_ns = insert_ns(namespaces);
+
     let mut table = KubeTable {
         header: if namespaces.len() == 1 {
             ["KIND", "NAME", "DATA", "AGE"]
@@ -141,13 +154,27 @@ async fn fetch_configs(client: &KubeClient, namespaces: &[String]) -> Result<Kub
         ..Default::default()
     };
 
-    let jobs = try_join_all([
-        fetch_configs_per_namespace(client, namespaces, Configs::ConfigMap),
-        fetch_configs_per_namespace(client, namespaces, Configs::Secret),
-    ])
-    .await?;
+    let configmap_rows = configmaps.iter().map(|cm| {
+        let data_len = cm.data.as_ref().map(BTreeMap::len).unwrap_or_default()
+            + cm.binary_data
+                .as_ref()
+                .map(BTreeMap::len)
+                .unwrap_or_default();
+
+        config_row(
+            Configs::ConfigMap,
+            cm.as_ref(),
+            data_len.to_string(),
+            insert_ns,
+        )
+    });
+
+    // Secretはメタデータのみを保持しているため、値の数は表示しない
+    let secret_rows = secrets
+        .iter()
+        .map(|secret| config_row(Configs::Secret, secret.as_ref(), "-".to_string(), insert_ns));
 
-    table.update_rows(jobs.into_iter().flatten().collect());
+    table.update_rows(configmap_rows.chain(secret_rows).collect());
 
-    Ok(table)
+    table
 }
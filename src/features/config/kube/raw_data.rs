@@ -6,12 +6,16 @@ use async_trait::async_trait;
 use crossbeam::channel::Sender;
 
 use crate::{
-    features::config::message::{ConfigData, ConfigRequest, ConfigResponse, RequestData},
+    features::config::message::{
+        ConfigData, ConfigRequest, ConfigResponse, RequestData, SecretKey, SecretReveal,
+    },
     kube::KubeClient,
     message::Message,
     workers::kube::AbortWorker,
 };
 
+pub use self::secret::decode_helm_release;
+
 use self::{configmap::ConfigMapDataWorker, secret::SecretDataWorker};
 
 #[derive(Clone)]
@@ -19,11 +23,31 @@ pub struct ConfigsDataWorker {
     tx: Sender<Message>,
     client: KubeClient,
     req: ConfigRequest,
+    decode: bool,
+    reveal: SecretReveal,
 }
 
 impl ConfigsDataWorker {
     pub fn new(tx: Sender<Message>, client: KubeClient, req: ConfigRequest) -> Self {
-        Self { tx, client, req }
+        Self {
+            tx,
+            client,
+            req,
+            decode: true,
+            reveal: SecretReveal::default(),
+        }
+    }
+
+    /// Secretの値を種類に応じてデコードするか
+    pub fn decode(mut self, decode: bool) -> Self {
+        self.decode = decode;
+        self
+    }
+
+    /// マスクせずに表示するSecretの値
+    pub fn reveal(mut self, reveal: SecretReveal) -> Self {
+        self.reveal = reveal;
+        self
     }
 }
 
@@ -45,9 +69,25 @@ impl AbortWorker for ConfigsDataWorker {
 
 #[async_trait]
 trait Fetch<'a> {
-    fn new(client: &'a KubeClient, namespace: String, name: String) -> Self;
+    /// decodeがtrueの場合は、TLS証明書などの値を種類に応じてデコードして表示する
+    /// revealに含まれないSecretの値はマスクする
+    fn new(
+        client: &'a KubeClient,
+        namespace: String,
+        name: String,
+        decode: bool,
+        reveal: SecretReveal,
+    ) -> Self;
+
+    async fn fetch(&self) -> Result<Fetched>;
+}
 
-    async fn fetch(&self) -> Result<ConfigData>;
+/// 取得した値
+struct Fetched {
+    data: ConfigData,
+    /// Secretの場合はキーの一覧
+    /// 一覧のSecretはメタデータのみを保持しているため、値をマスクするキーの選択に使う
+    secret_keys: Option<Vec<SecretKey>>,
 }
 
 const INTERVAL: u64 = 3;
@@ -61,12 +101,33 @@ impl ConfigsDataWorker {
 
         let RequestData { name, namespace } = self.req.data();
 
-        let worker = Worker::new(&self.client, namespace.to_string(), name.to_string());
+        let worker = Worker::new(
+            &self.client,
+            namespace.to_string(),
+            name.to_string(),
+            self.decode,
+            self.reveal.clone(),
+        );
+
+        let mut last_secret_keys = None;
 
         loop {
             interval.tick().await;
 
-            let fetched_data = worker.fetch().await;
+            let fetched_data = worker.fetch().await.map(|fetched| {
+                if fetched.secret_keys.is_some() && fetched.secret_keys != last_secret_keys {
+                    last_secret_keys.clone_from(&fetched.secret_keys);
+
+                    self.tx
+                        .send(
+                            ConfigResponse::SecretKeys(fetched.secret_keys.unwrap_or_default())
+                                .into(),
+                        )
+                        .expect("Failed to send ConfigResponse::SecretKeys");
+                }
+
+                fetched.data
+            });
 
             self.tx
                 .send(ConfigResponse::Data(fetched_data).into())
@@ -5,9 +5,11 @@ use async_trait::async_trait;
 use k8s_openapi::api::core::v1::ConfigMap;
 use kube::Api;
 
-use crate::{features::config::message::ConfigData, kube::KubeClient, workers::kube::color::Color};
+use crate::{
+    features::config::message::SecretReveal, kube::KubeClient, workers::kube::color::Color,
+};
 
-use super::Fetch;
+use super::{Fetch, Fetched};
 
 pub(super) struct ConfigMapDataWorker<'a> {
     client: &'a KubeClient,
@@ -17,7 +19,13 @@ pub(super) struct ConfigMapDataWorker<'a> {
 
 #[async_trait()]
 impl<'a> Fetch<'a> for ConfigMapDataWorker<'a> {
-    fn new(client: &'a KubeClient, namespace: String, name: String) -> Self {
+    fn new(
+        client: &'a KubeClient,
+        namespace: String,
+        name: String,
+        _decode: bool,
+        _reveal: SecretReveal,
+    ) -> Self {
         Self {
             client,
             namespace,
@@ -25,18 +33,22 @@ impl<'a> Fetch<'a> for ConfigMapDataWorker<'a> {
         }
     }
 
-    async fn fetch(&self) -> Result<ConfigData> {
+    async fn fetch(&self) -> Result<Fetched> {
         let list: Api<ConfigMap> =
             Api::namespaced(self.client.as_client().clone(), &self.namespace);
 
         let target = list.get(&self.name).await?;
 
-        if let Some(data) = target.data {
-            let data = ConfigMapData(data);
-            Ok(data.to_vec_string_with_color())
+        let data = if let Some(data) = target.data {
+            ConfigMapData(data).to_vec_string_with_color()
         } else {
-            Ok(vec!["no data".into()])
-        }
+            vec!["no data".into()]
+        };
+
+        Ok(Fetched {
+            data,
+            secret_keys: None,
+        })
     }
 }
 
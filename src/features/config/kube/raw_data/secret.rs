@@ -1,6 +1,9 @@
 mod any;
+mod docker;
 mod format;
 mod helm;
+mod tls;
+mod token;
 
 use std::collections::BTreeMap;
 
@@ -9,59 +12,146 @@ use async_trait::async_trait;
 use k8s_openapi::{api::core::v1::Secret, ByteString};
 use kube::Api;
 
-use crate::{features::config::message::ConfigData, kube::KubeClient};
+use crate::{
+    features::config::message::{ConfigData, SecretKey, SecretReveal},
+    kube::KubeClient,
+};
 
-use self::{any::Any, helm::Helm};
+pub use self::helm::decode_helm_release;
 
-use super::Fetch;
+use self::{any::Any, docker::DockerConfig, helm::Helm, tls::Tls, token::ServiceAccountToken};
+
+use super::{Fetch, Fetched};
 
 pub(super) struct SecretDataWorker<'a> {
     client: &'a KubeClient,
     namespace: String,
     name: String,
+    decode: bool,
+    reveal: SecretReveal,
 }
 #[async_trait()]
 impl<'a> Fetch<'a> for SecretDataWorker<'a> {
-    fn new(client: &'a KubeClient, namespace: String, name: String) -> Self {
+    fn new(
+        client: &'a KubeClient,
+        namespace: String,
+        name: String,
+        decode: bool,
+        reveal: SecretReveal,
+    ) -> Self {
         Self {
             client,
             namespace,
             name,
+            decode,
+            reveal,
         }
     }
-    async fn fetch(&self) -> Result<ConfigData> {
+    async fn fetch(&self) -> Result<Fetched> {
         let list: Api<Secret> = Api::namespaced(self.client.as_client().clone(), &self.namespace);
         let target = list.get(&self.name).await?;
 
         let type_ = target.type_.as_deref().unwrap_or_default();
 
         let Some(data) = target.data else {
-            return Ok(vec!["no data".into()]);
+            return Ok(Fetched {
+                data: vec!["no data".into()],
+                secret_keys: Some(Vec::new()),
+            });
         };
 
-        let data = SecretData::new(type_, data)?;
-        Ok(data.to_string_key_values())
+        let secret_keys = secret_keys(&data, &self.reveal);
+
+        let data = SecretData::new(type_, data, self.decode)?;
+
+        Ok(Fetched {
+            data: data.to_string_key_values(&self.reveal),
+            secret_keys: Some(secret_keys),
+        })
     }
 }
 
+fn secret_keys(data: &BTreeMap<String, ByteString>, reveal: &SecretReveal) -> Vec<SecretKey> {
+    data.keys()
+        .map(|key| SecretKey {
+            key: key.to_string(),
+            revealed: reveal.is_revealed(key),
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 enum SecretData {
     Helm(Helm),
+    Tls(Tls),
+    DockerConfig(DockerConfig),
+    ServiceAccountToken(ServiceAccountToken),
     Any(Any),
 }
 
 impl SecretData {
-    fn new(type_: &str, data: BTreeMap<String, ByteString>) -> Result<Self> {
+    /// decodeがfalseの場合は種類に関わらずbase64/UTF-8のまま表示する
+    fn new(type_: &str, data: BTreeMap<String, ByteString>, decode: bool) -> Result<Self> {
+        if !decode {
+            return Ok(Self::Any(Any::new(data)));
+        }
+
         match type_ {
             "helm.sh/release.v1" => Ok(Self::Helm(Helm::new(data))),
+            "kubernetes.io/tls" => Ok(Self::Tls(Tls::new(data))),
+            "kubernetes.io/dockerconfigjson" | "kubernetes.io/dockercfg" => {
+                Ok(Self::DockerConfig(DockerConfig::new(data)))
+            }
+            "kubernetes.io/service-account-token" => {
+                Ok(Self::ServiceAccountToken(ServiceAccountToken::new(data)))
+            }
             _ => Ok(Self::Any(Any::new(data))),
         }
     }
 
-    fn to_string_key_values(&self) -> ConfigData {
+    /// revealに含まれないキーの値はマスクする
+    fn to_string_key_values(&self, reveal: &SecretReveal) -> ConfigData {
         match self {
-            Self::Helm(helm) => helm.to_string_key_values(),
-            Self::Any(any) => any.to_string_key_values(),
+            Self::Helm(helm) => helm.to_string_key_values(reveal),
+            Self::Tls(tls) => tls.to_string_key_values(reveal),
+            Self::DockerConfig(docker) => docker.to_string_key_values(reveal),
+            Self::ServiceAccountToken(token) => token.to_string_key_values(reveal),
+            Self::Any(any) => any.to_string_key_values(reveal),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::workers::kube::color::fg::Color;
+
+    #[test]
+    fn 表示するキー以外の値をマスクする() {
+        let data = BTreeMap::from([
+            ("password".to_string(), ByteString("secret".into())),
+            ("username".to_string(), ByteString("admin".into())),
+        ]);
+
+        let mut reveal = SecretReveal::new(true);
+        reveal.toggle("username");
+
+        let actual = SecretData::new("Opaque", data, true)
+            .unwrap()
+            .to_string_key_values(&reveal);
+
+        assert_eq!(
+            actual,
+            vec![
+                format!(
+                    "\x1b[32mpassword:\x1b[39m {}",
+                    Color::DarkGray.wrap("********")
+                ),
+                "\x1b[33musername:\x1b[39m admin".to_string(),
+            ]
+        );
+    }
+}
@@ -1,11 +1,10 @@
 use std::collections::{btree_map, BTreeMap};
 
-use base64::{engine::general_purpose, Engine};
 use k8s_openapi::ByteString;
 
-use crate::workers::kube::color::Color;
+use crate::{features::config::message::SecretReveal, workers::kube::color::Color};
 
-use super::format::{format_error, format_utf8};
+use super::format::{format_masked, format_value};
 
 /// any type secret
 #[derive(Debug, Default)]
@@ -18,8 +17,8 @@ impl Any {
         Self { data }
     }
 
-    pub fn to_string_key_values(&self) -> Vec<String> {
-        self.iter()
+    pub fn to_string_key_values(&self, reveal: &SecretReveal) -> Vec<String> {
+        self.iter(reveal)
             .flat_map(|key_value| {
                 key_value
                     .lines()
@@ -29,10 +28,11 @@ impl Any {
             .collect()
     }
 
-    fn iter(&self) -> Iter {
+    fn iter<'a>(&'a self, reveal: &'a SecretReveal) -> Iter<'a> {
         Iter {
             iter: self.data.iter(),
             color: Color::new(),
+            reveal,
         }
     }
 }
@@ -40,6 +40,7 @@ impl Any {
 struct Iter<'a> {
     iter: btree_map::Iter<'a, String, ByteString>,
     color: Color,
+    reveal: &'a SecretReveal,
 }
 
 impl Iterator for Iter<'_> {
@@ -49,13 +50,10 @@ impl Iterator for Iter<'_> {
 
         let color = self.color.next_color();
 
-        match String::from_utf8(value.to_vec()) {
-            Ok(utf8_data) => Some(format_utf8(key, &utf8_data, color)),
-            Err(err) => {
-                let base64_encoded = general_purpose::STANDARD.encode(value);
-
-                Some(format_error(key, &base64_encoded, &err.to_string(), color))
-            }
+        if !self.reveal.is_revealed(key) {
+            return Some(format_masked(key, color));
         }
+
+        Some(format_value(key, value, color))
     }
 }
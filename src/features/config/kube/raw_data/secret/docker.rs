@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use base64::{engine::general_purpose, Engine};
+use k8s_openapi::ByteString;
+use serde::{Deserialize, Serialize};
+
+use crate::{features::config::message::SecretReveal, workers::kube::color::Color};
+
+use super::format::{format_decoded, format_masked, format_value};
+
+/// kubernetes.io/dockerconfigjson and kubernetes.io/dockercfg type secret
+#[derive(Debug, Default)]
+pub struct DockerConfig {
+    data: BTreeMap<String, ByteString>,
+}
+
+impl DockerConfig {
+    pub fn new(data: BTreeMap<String, ByteString>) -> Self {
+        Self { data }
+    }
+
+    pub fn to_string_key_values(&self, reveal: &SecretReveal) -> Vec<String> {
+        let mut color = Color::new();
+
+        self.data
+            .iter()
+            .flat_map(|(key, ByteString(value))| {
+                let color = color.next_color();
+
+                let formatted = if !reveal.is_revealed(key) {
+                    format_masked(key, color)
+                } else {
+                    match key.as_str() {
+                        ".dockerconfigjson" => {
+                            format_decoded(key, value, describe_docker_config_json(value), color)
+                        }
+                        ".dockercfg" => {
+                            format_decoded(key, value, describe_docker_cfg(value), color)
+                        }
+                        _ => format_value(key, value, color),
+                    }
+                };
+
+                formatted
+                    .lines()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// ~/.docker/config.jsonの形式
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DockerConfigJson {
+    auths: BTreeMap<String, DockerAuth>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DockerAuth {
+    username: Option<String>,
+    /// base64でエンコードした`username:password`
+    auth: Option<String>,
+}
+
+impl DockerAuth {
+    /// usernameがない場合はauthから取り出す
+    fn username(&self) -> Option<String> {
+        if let Some(username) = &self.username {
+            return Some(username.clone());
+        }
+
+        let auth = general_purpose::STANDARD
+            .decode(self.auth.as_deref()?)
+            .ok()?;
+
+        let auth = String::from_utf8(auth).ok()?;
+
+        auth.split_once(':')
+            .map(|(username, _)| username.to_string())
+    }
+}
+
+/// 表示する内容。パスワードは表示しない
+#[derive(Debug, Serialize)]
+struct Registry {
+    registry: String,
+    username: String,
+}
+
+fn describe_registries(auths: BTreeMap<String, DockerAuth>) -> Result<String> {
+    let registries: Vec<Registry> = auths
+        .into_iter()
+        .map(|(registry, auth)| Registry {
+            username: auth.username().unwrap_or_else(|| "<none>".to_string()),
+            registry,
+        })
+        .collect();
+
+    serde_yaml::to_string(&registries).map_err(Into::into)
+}
+
+fn describe_docker_config_json(value: &[u8]) -> Result<String> {
+    let config: DockerConfigJson = serde_json::from_slice(value)?;
+
+    describe_registries(config.auths)
+}
+
+/// 古い形式の.dockercfgは、auths以下の内容がトップレベルにある
+fn describe_docker_cfg(value: &[u8]) -> Result<String> {
+    let auths: BTreeMap<String, DockerAuth> = serde_json::from_slice(value)?;
+
+    describe_registries(auths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn レジストリとユーザー名を表示する() {
+        // authはuser:secretをbase64でエンコードしたもの
+        let value = indoc! {r#"
+            {
+              "auths": {
+                "ghcr.io": {
+                  "username": "kubetui",
+                  "password": "secret"
+                },
+                "registry.example.com": {
+                  "auth": "dXNlcjpzZWNyZXQ="
+                },
+                "unknown.example.com": {}
+              }
+            }
+        "#};
+
+        let actual = describe_docker_config_json(value.as_bytes()).unwrap();
+
+        assert_eq!(
+            actual,
+            indoc! {"
+                - registry: ghcr.io
+                  username: kubetui
+                - registry: registry.example.com
+                  username: user
+                - registry: unknown.example.com
+                  username: <none>
+            "}
+        );
+    }
+}
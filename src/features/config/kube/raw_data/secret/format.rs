@@ -1,4 +1,7 @@
-use crate::workers::kube::color::fg::Color;
+use anyhow::Result;
+use base64::{engine::general_purpose, Engine};
+
+use crate::workers::kube::{color::fg::Color, mask::MASKED_VALUE};
 
 pub(super) fn format_utf8(key: &str, value: &str, color: u8) -> String {
     if value.contains('\n') {
@@ -29,3 +32,37 @@ pub(super) fn format_error(key: &str, value: &str, err: &str, color: u8) -> Stri
         error = err
     )
 }
+
+/// 値をUTF-8の文字列として表示する。UTF-8でない場合はbase64でエンコードして表示する
+pub(super) fn format_value(key: &str, value: &[u8], color: u8) -> String {
+    match String::from_utf8(value.to_vec()) {
+        Ok(utf8_data) => format_utf8(key, &utf8_data, color),
+        Err(err) => {
+            let base64_encoded = general_purpose::STANDARD.encode(value);
+
+            format_error(key, &base64_encoded, &err.to_string(), color)
+        }
+    }
+}
+
+/// マスクした値を表示する
+pub(super) fn format_masked(key: &str, color: u8) -> String {
+    format_utf8(key, &Color::DarkGray.wrap(MASKED_VALUE), color)
+}
+
+/// デコードした内容を表示する。デコードできない場合はエラーと元の値を表示する
+pub(super) fn format_decoded(
+    key: &str,
+    value: &[u8],
+    decoded: Result<String>,
+    color: u8,
+) -> String {
+    match decoded {
+        Ok(decoded) => format_utf8(&format!("{} (decoded)", key), &decoded, color),
+        Err(err) => format!(
+            "{}\n{}",
+            Color::Red.wrap(format!("# Failed to decode the '{}' value: {}", key, err)),
+            format_value(key, value, color)
+        ),
+    }
+}
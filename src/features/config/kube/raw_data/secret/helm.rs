@@ -4,9 +4,12 @@ use anyhow::Result;
 use base64::{engine::general_purpose, Engine};
 use k8s_openapi::ByteString;
 
-use crate::workers::kube::color::{self, Color};
+use crate::{
+    features::config::message::SecretReveal,
+    workers::kube::color::{self, Color},
+};
 
-use super::format::{format_error, format_utf8};
+use super::format::{format_error, format_masked, format_utf8};
 
 #[derive(Debug, Default)]
 pub struct Helm {
@@ -18,13 +21,17 @@ impl Helm {
         Self { data }
     }
 
-    pub fn to_string_key_values(&self) -> Vec<String> {
+    pub fn to_string_key_values(&self, reveal: &SecretReveal) -> Vec<String> {
         let Some(ByteString(value)) = self.data.get("release") else {
             return vec!["no release data".into()];
         };
 
         let mut color = Color::new();
 
+        if !reveal.is_revealed("release") {
+            return vec![format_masked("release", color.next_color())];
+        }
+
         let decoded_release = match decode_release(value) {
             Ok(decoded) => {
                 let color = color.next_color();
@@ -57,7 +64,10 @@ impl Helm {
     }
 }
 
-fn decode_release(data: &[u8]) -> Result<String> {
+/// helm.sh/release.v1のSecretのreleaseの値から、リリースのJSONを取り出す
+///
+/// 値はJSONをgzipで圧縮し、base64でエンコードしたもの
+pub fn decode_helm_release(data: &[u8]) -> Result<String> {
     let gzip = general_purpose::STANDARD.decode(data)?;
 
     // decode gzip
@@ -65,6 +75,12 @@ fn decode_release(data: &[u8]) -> Result<String> {
     let mut decoded = String::new();
     decoder.read_to_string(&mut decoded)?;
 
+    Ok(decoded)
+}
+
+fn decode_release(data: &[u8]) -> Result<String> {
+    let decoded = decode_helm_release(data)?;
+
     let yaml = serde_yaml::from_str::<serde_yaml::Value>(&decoded)?;
 
     serde_yaml::to_string(&yaml).map_err(Into::into)
@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine};
+use chrono::{DateTime, Utc};
+use k8s_openapi::ByteString;
+use serde_json::Value;
+
+use crate::{features::config::message::SecretReveal, workers::kube::color::Color};
+
+use super::{
+    format::{format_decoded, format_masked, format_value},
+    tls::describe_certificates,
+};
+
+/// 時刻を表すJWTのクレーム
+const TIME_CLAIMS: [&str; 3] = ["exp", "iat", "nbf"];
+
+/// kubernetes.io/service-account-token type secret
+#[derive(Debug, Default)]
+pub struct ServiceAccountToken {
+    data: BTreeMap<String, ByteString>,
+}
+
+impl ServiceAccountToken {
+    pub fn new(data: BTreeMap<String, ByteString>) -> Self {
+        Self { data }
+    }
+
+    pub fn to_string_key_values(&self, reveal: &SecretReveal) -> Vec<String> {
+        let mut color = Color::new();
+
+        self.data
+            .iter()
+            .flat_map(|(key, ByteString(value))| {
+                let color = color.next_color();
+
+                let formatted = if !reveal.is_revealed(key) {
+                    format_masked(key, color)
+                } else {
+                    match key.as_str() {
+                        "token" => format_decoded(key, value, decode_jwt_claims(value), color),
+                        "ca.crt" => format_decoded(
+                            key,
+                            value,
+                            describe_certificates(value, Utc::now()),
+                            color,
+                        ),
+                        _ => format_value(key, value, color),
+                    }
+                };
+
+                formatted
+                    .lines()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// JWTのペイロードをデコードし、クレームをYAML形式で返す
+///
+/// 署名は検証しない。時刻のクレームにはRFC3339形式の時刻を併記する
+fn decode_jwt_claims(value: &[u8]) -> Result<String> {
+    let token = std::str::from_utf8(value)?.trim();
+
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| anyhow!("invalid JWT: no payload"))?;
+
+    let payload = general_purpose::URL_SAFE_NO_PAD.decode(payload.trim_end_matches('='))?;
+
+    let mut claims: Value = serde_json::from_slice(&payload)?;
+
+    if let Some(claims) = claims.as_object_mut() {
+        for key in TIME_CLAIMS {
+            let Some(time) = claims.get(key).and_then(Value::as_i64) else {
+                continue;
+            };
+
+            if let Some(datetime) = DateTime::<Utc>::from_timestamp(time, 0) {
+                claims.insert(
+                    key.to_string(),
+                    Value::String(format!(
+                        "{} ({})",
+                        time,
+                        datetime.format("%Y-%m-%dT%H:%M:%SZ")
+                    )),
+                );
+            }
+        }
+    }
+
+    serde_yaml::to_string(&claims).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn jwtのクレームをデコードする() {
+        let payload = json!({
+            "iss": "kubernetes/serviceaccount",
+            "kubernetes.io/serviceaccount/namespace": "default",
+            "kubernetes.io/serviceaccount/service-account.name": "builder",
+            "sub": "system:serviceaccount:default:builder",
+            "iat": 1700000000
+        });
+
+        let token = format!(
+            "{}.{}.signature",
+            general_purpose::URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256"}"#),
+            general_purpose::URL_SAFE_NO_PAD.encode(payload.to_string())
+        );
+
+        let actual = decode_jwt_claims(token.as_bytes()).unwrap();
+
+        assert_eq!(
+            actual,
+            indoc! {"
+                iat: 1700000000 (2023-11-14T22:13:20Z)
+                iss: kubernetes/serviceaccount
+                kubernetes.io/serviceaccount/namespace: default
+                kubernetes.io/serviceaccount/service-account.name: builder
+                sub: system:serviceaccount:default:builder
+            "}
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use anyhow::Result;
 
 use crate::{kube::table::KubeTable, message::Message, workers::kube::message::Kube};
@@ -14,6 +16,10 @@ pub struct RequestData {
 pub enum ConfigMessage {
     Request(ConfigRequest),
     Response(ConfigResponse),
+    /// Secretの表示を、種類に応じてデコードした内容とbase64/UTF-8のままの内容で切り替える
+    ToggleSecretDecode,
+    /// Secretの値のうち、マスクせずに表示するものを変更する
+    RevealSecret(SecretReveal),
 }
 
 #[derive(Debug, Clone)]
@@ -26,6 +32,52 @@ pub enum ConfigRequest {
 pub enum ConfigResponse {
     Table(Result<KubeTable>),
     Data(Result<ConfigData>),
+    /// 表示しているSecretのキーの一覧
+    SecretKeys(Vec<SecretKey>),
+}
+
+/// Secretのキーと、値をマスクせずに表示しているか
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretKey {
+    pub key: String,
+    pub revealed: bool,
+}
+
+/// Secretの値のうち、マスクせずに表示するもの
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecretReveal {
+    /// trueのときはkeysに含まれないキーの値を、falseのときはkeysに含まれるキーの値を表示する
+    all: bool,
+    keys: BTreeSet<String>,
+}
+
+impl SecretReveal {
+    /// maskがtrueのときはすべての値をマスクし、falseのときはすべての値を表示する
+    pub fn new(mask: bool) -> Self {
+        Self {
+            all: !mask,
+            keys: BTreeSet::new(),
+        }
+    }
+
+    pub fn is_revealed(&self, key: &str) -> bool {
+        self.all != self.keys.contains(key)
+    }
+
+    /// 指定したキーの値の表示とマスクを切り替える
+    pub fn toggle(&mut self, key: &str) {
+        if !self.keys.remove(key) {
+            self.keys.insert(key.to_string());
+        }
+    }
+
+    pub fn reveal_all(&mut self) {
+        *self = Self::new(false);
+    }
+
+    pub fn mask_all(&mut self) {
+        *self = Self::new(true);
+    }
 }
 
 impl ConfigRequest {
@@ -1,4 +1,6 @@
+mod reveal;
 mod tab;
 mod widgets;
 
+pub use reveal::*;
 pub use tab::*;
@@ -0,0 +1,221 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crossbeam::channel::Sender;
+use k8s_openapi::{api::core::v1::Secret, Resource as _};
+
+use crate::{
+    config::theme::WidgetThemeConfig,
+    features::{
+        component_id::{CONFIG_RAW_DATA_WIDGET_ID, CONFIG_REVEAL_DIALOG_ID, CONFIG_WIDGET_ID},
+        config::message::{ConfigMessage, SecretKey, SecretReveal},
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            single_select::{
+                FilterForm, FilterFormTheme, SelectForm, SelectFormTheme, SingleSelectTheme,
+            },
+            LiteralItem, SelectedItem, SingleSelect, Widget, WidgetBase, WidgetTheme,
+            WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+const REVEAL_ALL: &str = "Reveal all";
+const MASK_ALL: &str = "Mask all";
+
+/// 選択しているSecretのうち、マスクせずに表示する値
+///
+/// ダイアログとConfigのテーブルで共有し、別のリソースを選択したときに初期状態に戻す
+#[derive(Clone)]
+pub struct SharedSecretReveal {
+    reveal: Rc<RefCell<SecretReveal>>,
+    mask: bool,
+}
+
+impl SharedSecretReveal {
+    /// maskがtrueのときは、すべての値をマスクした状態を初期状態とする
+    pub fn new(mask: bool) -> Self {
+        Self {
+            reveal: Rc::new(RefCell::new(SecretReveal::new(mask))),
+            mask,
+        }
+    }
+
+    /// 初期状態に戻す
+    pub fn reset(&self) {
+        *self.reveal.borrow_mut() = SecretReveal::new(self.mask);
+    }
+}
+
+/// Secretの値を一時的に表示するダイアログ
+///
+/// キーの一覧は、選択しているSecretの値を取得したときに更新する
+pub struct RevealDialog {
+    pub widget: Widget<'static>,
+}
+
+impl RevealDialog {
+    pub fn new(tx: &Sender<Message>, reveal: SharedSecretReveal, theme: WidgetThemeConfig) -> Self {
+        Self {
+            widget: reveal_widget(tx.clone(), reveal, theme),
+        }
+    }
+
+    /// Configテーブルで選択しているSecretのキーの一覧を開く
+    pub fn open(&self) -> impl Fn(&mut Window) -> EventResult {
+        move |w: &mut Window| {
+            let Some(SelectedItem::TableRow { metadata, .. }) =
+                w.find_widget(CONFIG_WIDGET_ID).widget_item()
+            else {
+                return EventResult::Ignore;
+            };
+
+            let Some(ref metadata) = metadata else {
+                return EventResult::Ignore;
+            };
+
+            let (Some(name), Some(Secret::KIND)) = (
+                metadata.get("name"),
+                metadata.get("kind").map(String::as_str),
+            ) else {
+                return EventResult::Ignore;
+            };
+
+            let dialog = w.find_widget_mut(CONFIG_REVEAL_DIALOG_ID);
+
+            *dialog.widget_base_mut().append_title_mut() =
+                Some(format!(" : secret/{}", name).into());
+
+            w.open_dialog(CONFIG_REVEAL_DIALOG_ID);
+
+            EventResult::Nop
+        }
+    }
+}
+
+/// すべての値の表示・マスクと、キーごとの表示の切り替えを候補にする
+pub fn reveal_items(keys: &[SecretKey]) -> Vec<LiteralItem> {
+    let all = [REVEAL_ALL, MASK_ALL].into_iter().map(|action| {
+        LiteralItem::new(
+            action,
+            Some(BTreeMap::from([("action".to_string(), action.to_string())])),
+        )
+    });
+
+    let keys = keys.iter().map(|SecretKey { key, revealed }| {
+        let state = if *revealed { "revealed" } else { "masked" };
+
+        LiteralItem::new(
+            format!("{} ({})", key, state),
+            Some(BTreeMap::from([("key".to_string(), key.to_string())])),
+        )
+    });
+
+    all.chain(keys).collect()
+}
+
+fn reveal_widget(
+    tx: Sender<Message>,
+    reveal: SharedSecretReveal,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    let single_select_theme = SingleSelectTheme::default().status_style(theme.list.status);
+
+    let filter_form = FilterForm::builder()
+        .theme(FilterFormTheme::from(theme.clone()))
+        .build();
+
+    let select_form = SelectForm::builder()
+        .theme(SelectFormTheme::from(theme.clone()))
+        .on_select(on_select(tx, reveal))
+        .build();
+
+    let widget_base = WidgetBase::builder()
+        .title("Reveal")
+        .theme(WidgetTheme::from(theme))
+        .build();
+
+    SingleSelect::builder()
+        .id(CONFIG_REVEAL_DIALOG_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .select_form(select_form)
+        .theme(single_select_theme)
+        .build()
+        .into()
+}
+
+fn on_select(
+    tx: Sender<Message>,
+    reveal: SharedSecretReveal,
+) -> impl Fn(&mut Window, &LiteralItem) -> EventResult {
+    move |w: &mut Window, v: &LiteralItem| {
+        let Some(ref metadata) = v.metadata else {
+            return EventResult::Ignore;
+        };
+
+        let updated = {
+            let mut reveal = reveal.reveal.borrow_mut();
+
+            match (
+                metadata.get("action").map(String::as_str),
+                metadata.get("key"),
+            ) {
+                (Some(REVEAL_ALL), _) => reveal.reveal_all(),
+                (Some(MASK_ALL), _) => reveal.mask_all(),
+                (_, Some(key)) => reveal.toggle(key),
+                _ => return EventResult::Ignore,
+            }
+
+            reveal.clone()
+        };
+
+        w.close_dialog();
+
+        w.widget_clear(CONFIG_RAW_DATA_WIDGET_ID);
+
+        tx.send(ConfigMessage::RevealSecret(updated).into())
+            .expect("Failed to send ConfigMessage::RevealSecret");
+
+        EventResult::Nop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn キーごとに表示しているかマスクしているかを示す() {
+        let keys = [
+            SecretKey {
+                key: "password".to_string(),
+                revealed: true,
+            },
+            SecretKey {
+                key: "username".to_string(),
+                revealed: false,
+            },
+        ];
+
+        let actual: Vec<String> = reveal_items(&keys)
+            .into_iter()
+            .map(|item| item.item)
+            .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                "Reveal all",
+                "Mask all",
+                "password (revealed)",
+                "username (masked)"
+            ]
+        );
+    }
+}
@@ -5,7 +5,7 @@ use ratatui::layout::{Constraint, Direction};
 
 use crate::{
     clipboard::Clipboard,
-    config::theme::WidgetThemeConfig,
+    config::{keybindings::ConfigKeyBindings, secret::SecretConfig, theme::WidgetThemeConfig},
     features::component_id::CONFIG_TAB_ID,
     message::Message,
     ui::{
@@ -14,10 +14,14 @@ use crate::{
     },
 };
 
-use super::widgets::{config_widget, raw_data_widget};
+use super::{
+    widgets::{config_widget, raw_data_widget},
+    RevealDialog, SharedSecretReveal,
+};
 
 pub struct ConfigTab {
     pub tab: Tab<'static>,
+    pub reveal_dialog: RevealDialog,
 }
 
 impl ConfigTab {
@@ -26,10 +30,16 @@ impl ConfigTab {
         tx: &Sender<Message>,
         clipboard: &Option<Rc<RefCell<Clipboard>>>,
         split_direction: Direction,
+        keybindings: ConfigKeyBindings,
+        secret: SecretConfig,
         theme: WidgetThemeConfig,
     ) -> Self {
-        let config_widget = config_widget(tx, theme.clone());
-        let raw_data_widget = raw_data_widget(clipboard, theme);
+        let reveal = SharedSecretReveal::new(secret.mask);
+
+        let reveal_dialog = RevealDialog::new(tx, reveal.clone(), theme.clone());
+
+        let config_widget = config_widget(tx, &keybindings, reveal, &reveal_dialog, theme.clone());
+        let raw_data_widget = raw_data_widget(tx, clipboard, &keybindings, &reveal_dialog, theme);
 
         let layout = TabLayout::new(layout, split_direction);
 
@@ -40,6 +50,7 @@ impl ConfigTab {
                 [config_widget, raw_data_widget],
                 layout,
             ),
+            reveal_dialog,
         }
     }
 }
@@ -1,10 +1,13 @@
 use crossbeam::channel::Sender;
 
 use crate::{
-    config::theme::WidgetThemeConfig,
+    config::{keybindings::ConfigKeyBindings, theme::WidgetThemeConfig},
     features::{
-        component_id::{CONFIG_RAW_DATA_WIDGET_ID, CONFIG_WIDGET_ID},
-        config::message::{ConfigRequest, RequestData},
+        component_id::{CONFIG_RAW_DATA_WIDGET_ID, CONFIG_REVEAL_DIALOG_ID, CONFIG_WIDGET_ID},
+        config::{
+            message::{ConfigRequest, RequestData},
+            view::{RevealDialog, SharedSecretReveal},
+        },
     },
     message::Message,
     ui::{
@@ -17,7 +20,15 @@ use crate::{
     },
 };
 
-pub fn config_widget(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Widget<'static> {
+use super::toggle_decode;
+
+pub fn config_widget(
+    tx: &Sender<Message>,
+    keybindings: &ConfigKeyBindings,
+    reveal: SharedSecretReveal,
+    reveal_dialog: &RevealDialog,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
     let tx = tx.clone();
 
     let widget_theme = WidgetTheme::from(theme.clone());
@@ -38,7 +49,9 @@ pub fn config_widget(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Widget<'
         .theme(table_theme)
         .filtered_key("NAME")
         .block_injection(block_injection())
-        .on_select(on_select(tx))
+        .on_select(on_select(tx.clone(), reveal))
+        .key_action(&keybindings.toggle_decode, toggle_decode(tx))
+        .key_action(&keybindings.reveal, reveal_dialog.open())
         .build()
         .into()
 }
@@ -59,9 +72,16 @@ fn block_injection() -> impl Fn(&Table) -> WidgetBase {
     }
 }
 
-fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &TableItem) -> EventResult {
+fn on_select(
+    tx: Sender<Message>,
+    reveal: SharedSecretReveal,
+) -> impl Fn(&mut Window, &TableItem) -> EventResult {
     move |w, v| {
         w.widget_clear(CONFIG_RAW_DATA_WIDGET_ID);
+        w.widget_clear(CONFIG_REVEAL_DIALOG_ID);
+
+        // 表示した値は、別のリソースを選択したときにマスクし直す
+        reveal.reset();
 
         let Some(metadata) = v.metadata.as_ref() else {
             return EventResult::Ignore;
@@ -1,19 +1,31 @@
 use std::{cell::RefCell, rc::Rc};
 
+use crossbeam::channel::Sender;
 use ratatui::widgets::Block;
 
 use crate::{
     clipboard::Clipboard,
-    config::theme::WidgetThemeConfig,
-    features::component_id::CONFIG_RAW_DATA_WIDGET_ID,
-    ui::widget::{
-        SearchForm, SearchFormTheme, Text, TextTheme, Widget, WidgetBase, WidgetTheme,
-        WidgetTrait as _,
+    config::{keybindings::ConfigKeyBindings, theme::WidgetThemeConfig},
+    features::{
+        component_id::CONFIG_RAW_DATA_WIDGET_ID,
+        config::{message::ConfigMessage, view::RevealDialog},
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            SearchForm, SearchFormTheme, Text, TextTheme, Widget, WidgetBase, WidgetTheme,
+            WidgetTrait as _,
+        },
+        Window,
     },
 };
 
 pub fn raw_data_widget(
+    tx: &Sender<Message>,
     clipboard: &Option<Rc<RefCell<Clipboard>>>,
+    keybindings: &ConfigKeyBindings,
+    reveal_dialog: &RevealDialog,
     theme: WidgetThemeConfig,
 ) -> Widget<'static> {
     let widget_theme = WidgetTheme::from(theme.clone());
@@ -33,7 +45,9 @@ pub fn raw_data_widget(
         .search_form(search_form)
         .theme(text_theme)
         .wrap()
-        .block_injection(block_injection());
+        .block_injection(block_injection())
+        .key_action(&keybindings.toggle_decode, toggle_decode(tx.clone()))
+        .key_action(&keybindings.reveal, reveal_dialog.open());
 
     if let Some(cb) = clipboard {
         builder.clipboard(cb.clone())
@@ -55,3 +69,15 @@ fn block_injection() -> impl Fn(&Text, bool, bool) -> Block<'static> {
         base.render_block(text.can_activate() && is_active, is_mouse_over)
     }
 }
+
+/// Secretの値をデコードして表示するかを切り替える
+pub fn toggle_decode(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        w.widget_clear(CONFIG_RAW_DATA_WIDGET_ID);
+
+        tx.send(ConfigMessage::ToggleSecretDecode.into())
+            .expect("Failed to send ConfigMessage::ToggleSecretDecode");
+
+        EventResult::Nop
+    }
+}
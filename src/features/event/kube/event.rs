@@ -1,16 +1,16 @@
-use std::time;
+use std::{sync::Arc, time};
 
-use anyhow::Result;
 use async_trait::async_trait;
 use crossbeam::channel::Sender;
-use futures::future::try_join_all;
+use k8s_openapi::{api::core::v1::Event as V1Event, apimachinery::pkg::apis::meta::v1::MicroTime};
+use kube::ResourceExt as _;
 use ratatui::style::{Color, Style};
 
 use crate::{
     kube::{
-        apis::v1_table::{TableRow, ToTime as _},
-        table::{get_resource_per_namespace, insert_ns, KubeTableRow},
-        KubeClient,
+        apis::v1_table::ToTime as _,
+        informer::{ChangeDetector, SharedInformers},
+        table::{insert_ns, translate_datetime_since, translate_timestamp_since},
     },
     message::Message,
     ui::widget::ansi_color::style_to_ansi,
@@ -43,7 +43,7 @@ pub struct EventHighlightRule {
 pub struct EventPoller {
     tx: Sender<Message>,
     shared_target_namespaces: SharedTargetNamespaces,
-    kube_client: KubeClient,
+    informers: SharedInformers,
     config: EventConfig,
 }
 
@@ -51,13 +51,13 @@ impl EventPoller {
     pub fn new(
         tx: Sender<Message>,
         shared_target_namespaces: SharedTargetNamespaces,
-        kube_client: KubeClient,
+        informers: SharedInformers,
         config: EventConfig,
     ) -> Self {
         Self {
             tx,
             shared_target_namespaces,
-            kube_client,
+            informers,
             config,
         }
     }
@@ -70,17 +70,33 @@ impl Worker for EventPoller {
         let Self {
             tx,
             shared_target_namespaces,
-            kube_client,
+            informers,
             config,
         } = self;
 
         let mut interval = tokio::time::interval(time::Duration::from_millis(1000));
 
+        let mut informer = informers.namespaced::<V1Event>();
+
+        let mut detector = ChangeDetector::default();
+
         loop {
             interval.tick().await;
-            let target_namespaces = shared_target_namespaces.read().await;
 
-            let event_list = get_event_table(config, kube_client, &target_namespaces).await;
+            let target_namespaces = shared_target_namespaces.read().await.clone();
+
+            informer.sync_namespaces(&target_namespaces);
+
+            let Some(events) = informer.state() else {
+                continue;
+            };
+
+            let event_list =
+                events.map(|events| get_event_table(config, &target_namespaces, &events));
+
+            if !detector.is_changed(&event_list) {
+                continue;
+            }
 
             tx.send(Message::Kube(Kube::Event(event_list)))
                 .expect("Failed to send Kube::Event");
@@ -97,68 +113,73 @@ struct Event {
     namespace: Option<String>,
 }
 
-const TARGET_LEN: usize = 5;
-const TARGET: [&str; TARGET_LEN] = ["Last Seen", "Type", "Object", "Reason", "Message"];
-
-async fn get_event_per_namespace(
-    client: &KubeClient,
-    namespace: &str,
-    insert_ns: bool,
-) -> Result<Vec<Event>> {
-    let tables = get_resource_per_namespace(
-        client,
-        format!("api/v1/namespaces/{}/{}", namespace, "events"),
-        &TARGET,
-        move |row: &TableRow, indexes: &[usize]| {
-            let row: Vec<String> = indexes.iter().map(|i| row.cells[*i].to_string()).collect();
-
-            KubeTableRow {
-                namespace: namespace.to_string(),
-                row,
-                ..Default::default()
-            }
-        },
-    )
-    .await?;
-
-    let ret = tables
-        .into_iter()
-        .map(|table| Event {
-            last_seen: table.row[0].clone(),
-            ty: table.row[1].clone(),
-            object: table.row[2].clone(),
-            reason: table.row[3].clone(),
-            message: table.row[4].clone(),
+impl Event {
+    /// kubectl get eventsの各列と同じ内容を生成する
+    fn new(event: &V1Event, insert_ns: bool) -> Self {
+        let first_seen = match &event.first_timestamp {
+            Some(ts) => translate_timestamp_since(Some(ts)),
+            None => event
+                .event_time
+                .as_ref()
+                .map(|MicroTime(ts)| translate_datetime_since(ts))
+                .unwrap_or_else(|| translate_timestamp_since(None)),
+        };
+
+        let last_seen = if let Some(MicroTime(ts)) = event
+            .series
+            .as_ref()
+            .and_then(|series| series.last_observed_time.as_ref())
+        {
+            translate_datetime_since(ts)
+        } else if let Some(ts) = &event.last_timestamp {
+            translate_timestamp_since(Some(ts))
+        } else {
+            first_seen
+        };
+
+        let object = format!(
+            "{}/{}",
+            event
+                .involved_object
+                .kind
+                .as_deref()
+                .unwrap_or_default()
+                .to_lowercase(),
+            event.involved_object.name.as_deref().unwrap_or_default()
+        );
+
+        Self {
+            last_seen,
+            ty: event.type_.clone().unwrap_or_default(),
+            object,
+            reason: event.reason.clone().unwrap_or_default(),
+            message: event
+                .message
+                .as_deref()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
             namespace: if insert_ns {
-                Some(namespace.to_string())
+                Some(event.namespace().unwrap_or_default())
             } else {
                 None
             },
-        })
-        .collect();
-
-    Ok(ret)
+        }
+    }
 }
 
-async fn get_event_table(
+fn get_event_table(
     config: &EventConfig,
-    client: &KubeClient,
     namespaces: &[String],
-) -> Result<Vec<String>> {
+    events: &[Arc<V1Event>],
+) -> Vec<String> {
     let insert_ns = insert_ns(namespaces);
 
-    let jobs = try_join_all(
-        namespaces
-            .iter()
-            .map(|ns| get_event_per_namespace(client, ns, insert_ns)),
-    )
-    .await?;
-
-    let mut ok_only: Vec<Event> = jobs.into_iter().flatten().collect();
+    let mut ok_only: Vec<Event> = events.iter().map(|ev| Event::new(ev, insert_ns)).collect();
 
     ok_only.sort_by_key(|ev| ev.last_seen.to_time());
 
-    Ok(ok_only
+    ok_only
         .iter()
         .flat_map(|ev| {
             let (summary_style, message_style) = config.get_style(&ev.ty);
@@ -186,5 +207,5 @@ async fn get_event_table(
                 .chain(message.into_iter())
                 .collect::<Vec<_>>()
         })
-        .collect())
+        .collect()
 }
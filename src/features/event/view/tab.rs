@@ -4,7 +4,7 @@ use ratatui::prelude::Constraint;
 
 use crate::{
     clipboard::Clipboard,
-    config::theme::WidgetThemeConfig,
+    config::{buffer::TextBufferConfig, theme::WidgetThemeConfig},
     features::component_id::EVENT_TAB_ID,
     ui::{
         tab::{LayoutElement, NestedLayoutElement, NestedWidgetLayout, TabLayout},
@@ -22,9 +22,10 @@ impl EventTab {
     pub fn new(
         title: &str,
         clipboard: &Option<Rc<RefCell<Clipboard>>>,
+        buffer: TextBufferConfig,
         theme: WidgetThemeConfig,
     ) -> Self {
-        let event_widget = event_widget(clipboard, theme);
+        let event_widget = event_widget(clipboard, buffer, theme);
 
         let layout = TabLayout::new(
             |_| {
@@ -4,7 +4,7 @@ use ratatui::widgets::Block;
 
 use crate::{
     clipboard::Clipboard,
-    config::theme::WidgetThemeConfig,
+    config::{buffer::TextBufferConfig, theme::WidgetThemeConfig},
     features::component_id::EVENT_WIDGET_ID,
     ui::widget::{
         SearchForm, SearchFormTheme, Text, TextTheme, Widget, WidgetBase, WidgetTheme,
@@ -14,6 +14,7 @@ use crate::{
 
 pub fn event_widget(
     clipboard: &Option<Rc<RefCell<Clipboard>>>,
+    buffer: TextBufferConfig,
     theme: WidgetThemeConfig,
 ) -> Widget<'static> {
     let widget_theme = WidgetTheme::from(theme.clone());
@@ -34,6 +35,7 @@ pub fn event_widget(
         .theme(text_theme)
         .wrap()
         .follow()
+        .max_lines(buffer.max_lines())
         .block_injection(block_injection());
 
     if let Some(cb) = clipboard {
@@ -0,0 +1,314 @@
+use anyhow::{anyhow, Result};
+use crossbeam::channel::Sender;
+use futures::{
+    future::{self, Either},
+    SinkExt as _,
+};
+use k8s_openapi::api::core::v1::Pod;
+use kube::{
+    api::{AttachParams, TerminalSize},
+    Api,
+};
+use ratatui::crossterm::{
+    event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+};
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    sync::mpsc::UnboundedReceiver,
+    task::AbortHandle,
+};
+
+use crate::{
+    features::exec::message::{ExecResponse, ExecTarget},
+    kube::KubeClient,
+    logger,
+    message::Message,
+};
+
+/// コンテナ内で実行するコマンド
+/// bashがあればbashを、なければshを起動する
+const SHELL_COMMAND: [&str; 3] = [
+    "/bin/sh",
+    "-c",
+    "command -v bash > /dev/null 2>&1 && exec bash || exec sh",
+];
+
+/// Podに定義されているコンテナの一覧を取得する
+pub async fn fetch_exec_targets(
+    client: &KubeClient,
+    namespace: &str,
+    pod: &str,
+) -> Result<Vec<ExecTarget>> {
+    let api: Api<Pod> = Api::namespaced(client.to_client(), namespace);
+
+    let pod = api.get(pod).await?;
+
+    let containers = pod
+        .spec
+        .as_ref()
+        .map(|spec| {
+            spec.containers
+                .iter()
+                .map(|c| c.name.to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    Ok(containers
+        .into_iter()
+        .map(|container| ExecTarget {
+            namespace: namespace.to_string(),
+            pod: pod.metadata.name.clone().unwrap_or_default(),
+            container,
+        })
+        .collect())
+}
+
+/// pods/execサブリソースを介してコンテナ内のシェルと端末を接続する
+///
+/// 標準出力はそのまま端末に書き込み、入力は端末のイベントをバイト列に変換して送信する。
+/// シェルが終了したらExecResponse::Finishedを送信する。
+pub struct ExecWorker {
+    tx: Sender<Message>,
+    client: KubeClient,
+    target: ExecTarget,
+    input: UnboundedReceiver<Event>,
+}
+
+impl ExecWorker {
+    pub fn new(
+        tx: Sender<Message>,
+        client: KubeClient,
+        target: ExecTarget,
+        input: UnboundedReceiver<Event>,
+    ) -> Self {
+        Self {
+            tx,
+            client,
+            target,
+            input,
+        }
+    }
+
+    pub fn spawn(self) -> AbortHandle {
+        tokio::spawn(async move {
+            let Self {
+                tx,
+                client,
+                target,
+                input,
+            } = self;
+
+            let result = exec(&client, &target, input).await;
+
+            if let Err(e) = &result {
+                logger!(error, "Failed to exec {:?}: {}", target, e);
+            }
+
+            tx.send(ExecResponse::Finished(result).into())
+                .expect("Failed to send ExecResponse::Finished");
+        })
+        .abort_handle()
+    }
+}
+
+async fn exec(
+    client: &KubeClient,
+    target: &ExecTarget,
+    mut input: UnboundedReceiver<Event>,
+) -> Result<()> {
+    let api: Api<Pod> = Api::namespaced(client.to_client(), &target.namespace);
+
+    let params = AttachParams::interactive_tty().container(&target.container);
+
+    let mut process = api.exec(&target.pod, SHELL_COMMAND, &params).await?;
+
+    let mut stdin = process
+        .stdin()
+        .ok_or_else(|| anyhow!("Failed to attach stdin"))?;
+
+    let mut stdout = process
+        .stdout()
+        .ok_or_else(|| anyhow!("Failed to attach stdout"))?;
+
+    let mut terminal_size = process
+        .terminal_size()
+        .ok_or_else(|| anyhow!("Failed to attach terminal size"))?;
+
+    let status = process
+        .take_status()
+        .ok_or_else(|| anyhow!("Failed to attach status"))?;
+
+    if let Ok((width, height)) = terminal::size() {
+        terminal_size.send(TerminalSize { width, height }).await?;
+    }
+
+    let output = async move {
+        let mut out = tokio::io::stdout();
+        let mut buf = [0u8; 4096];
+
+        loop {
+            let n = stdout.read(&mut buf).await?;
+
+            if n == 0 {
+                break;
+            }
+
+            out.write_all(&buf[..n]).await?;
+            out.flush().await?;
+        }
+
+        anyhow::Ok(())
+    };
+
+    let input = async move {
+        while let Some(ev) = input.recv().await {
+            match ev {
+                Event::Key(key) => {
+                    let bytes = key_event_to_bytes(key);
+
+                    if !bytes.is_empty() {
+                        stdin.write_all(&bytes).await?;
+                    }
+                }
+                Event::Paste(text) => {
+                    stdin.write_all(text.as_bytes()).await?;
+                }
+                Event::Resize(width, height) => {
+                    terminal_size.send(TerminalSize { width, height }).await?;
+                }
+                _ => {}
+            }
+        }
+
+        anyhow::Ok(())
+    };
+
+    // 出力が閉じられた時点でシェルが終了したとみなす
+    match future::select(Box::pin(output), Box::pin(input)).await {
+        Either::Left((result, _)) => result?,
+        Either::Right((result, output)) => {
+            result?;
+            output.await?;
+        }
+    }
+
+    if let Some(status) = status.await {
+        if status.status.as_deref() == Some("Failure")
+            && status.reason.as_deref() != Some("NonZeroExitCode")
+        {
+            return Err(anyhow!(status.message.unwrap_or_default()));
+        }
+    }
+
+    process.abort();
+
+    Ok(())
+}
+
+/// 端末のキーイベントを、コンテナの端末に送るバイト列に変換する
+///
+/// キーを離したイベントも受け取る環境で同じ入力を2回送らないように、押したときのみ変換する
+fn key_event_to_bytes(ev: KeyEvent) -> Vec<u8> {
+    let KeyEvent {
+        code,
+        modifiers,
+        kind,
+        ..
+    } = ev;
+
+    if kind == KeyEventKind::Release {
+        return vec![];
+    }
+
+    let mut bytes: Vec<u8> = match code {
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+            match c.to_ascii_lowercase() {
+                c @ 'a'..='z' => vec![c as u8 & 0x1f],
+                '@' | ' ' | '2' => vec![0x00],
+                '[' | '3' => vec![0x1b],
+                '\\' | '4' => vec![0x1c],
+                ']' | '5' => vec![0x1d],
+                '^' | '6' => vec![0x1e],
+                '_' | '7' => vec![0x1f],
+                '?' | '8' => vec![0x7f],
+                c => c.to_string().into_bytes(),
+            }
+        }
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::F(n) => match n {
+            1 => b"\x1bOP".to_vec(),
+            2 => b"\x1bOQ".to_vec(),
+            3 => b"\x1bOR".to_vec(),
+            4 => b"\x1bOS".to_vec(),
+            5 => b"\x1b[15~".to_vec(),
+            6 => b"\x1b[17~".to_vec(),
+            7 => b"\x1b[18~".to_vec(),
+            8 => b"\x1b[19~".to_vec(),
+            9 => b"\x1b[20~".to_vec(),
+            10 => b"\x1b[21~".to_vec(),
+            11 => b"\x1b[23~".to_vec(),
+            12 => b"\x1b[24~".to_vec(),
+            _ => vec![],
+        },
+        _ => vec![],
+    };
+
+    if modifiers.contains(KeyModifiers::ALT) && !bytes.is_empty() {
+        bytes.insert(0, 0x1b);
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(KeyEvent::from(KeyCode::Char('a')), b"a".to_vec())]
+    #[case(KeyEvent::from(KeyCode::Char('あ')), "あ".as_bytes().to_vec())]
+    #[case(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT), b"A".to_vec())]
+    #[case(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL), vec![0x03])]
+    #[case(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL), vec![0x04])]
+    #[case(KeyEvent::new(KeyCode::Char('['), KeyModifiers::CONTROL), vec![0x1b])]
+    #[case(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::ALT), b"\x1bb".to_vec())]
+    #[case(KeyEvent::from(KeyCode::Enter), b"\r".to_vec())]
+    #[case(KeyEvent::from(KeyCode::Backspace), vec![0x7f])]
+    #[case(KeyEvent::from(KeyCode::Up), b"\x1b[A".to_vec())]
+    #[case(KeyEvent::from(KeyCode::F(5)), b"\x1b[15~".to_vec())]
+    #[case(KeyEvent::from(KeyCode::CapsLock), vec![])]
+    #[case(
+        KeyEvent::new_with_kind(KeyCode::Char('a'), KeyModifiers::NONE, KeyEventKind::Repeat),
+        b"a".to_vec()
+    )]
+    #[case(
+        KeyEvent::new_with_kind(KeyCode::Char('a'), KeyModifiers::NONE, KeyEventKind::Release),
+        vec![]
+    )]
+    fn キーイベントをバイト列に変換する(
+        #[case] ev: KeyEvent,
+        #[case] expected: Vec<u8>,
+    ) {
+        assert_eq!(key_event_to_bytes(ev), expected);
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use ratatui::crossterm::event::Event;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::{message::Message, workers::kube::message::Kube};
+
+#[derive(Debug)]
+pub enum ExecMessage {
+    Request(ExecRequest),
+    Response(ExecResponse),
+}
+
+/// execの対象となるコンテナ
+#[derive(Debug, Clone)]
+pub struct ExecTarget {
+    pub namespace: String,
+    pub pod: String,
+    pub container: String,
+}
+
+#[derive(Debug)]
+pub enum ExecRequest {
+    /// Podに含まれるコンテナ一覧を取得する
+    Containers { namespace: String, pod: String },
+    /// コンテナ内でシェルを起動する
+    /// 端末の入力イベントはinputから受け取る
+    Start {
+        target: ExecTarget,
+        input: UnboundedReceiver<Event>,
+    },
+}
+
+#[derive(Debug)]
+pub enum ExecResponse {
+    Containers(Result<Vec<ExecTarget>>),
+    /// シェルが終了した
+    Finished(Result<()>),
+}
+
+impl From<ExecRequest> for Message {
+    fn from(req: ExecRequest) -> Self {
+        Message::Kube(Kube::Exec(ExecMessage::Request(req)))
+    }
+}
+
+impl From<ExecResponse> for Message {
+    fn from(res: ExecResponse) -> Self {
+        Message::Kube(Kube::Exec(ExecMessage::Response(res)))
+    }
+}
@@ -0,0 +1,3 @@
+mod dialog;
+
+pub use dialog::*;
@@ -0,0 +1,81 @@
+use crate::{
+    config::theme::ThemeConfig,
+    features::{component_id::EXEC_CONTAINER_DIALOG_ID, exec::message::ExecTarget},
+    ui::{
+        event::EventResult,
+        widget::{
+            single_select::{
+                FilterForm, FilterFormTheme, SelectForm, SelectFormTheme, SingleSelectTheme,
+            },
+            LiteralItem, SingleSelect, Widget, WidgetBase, WidgetTheme,
+        },
+        Window, WindowAction,
+    },
+};
+
+/// exec対象のコンテナを選択するダイアログ
+pub struct ExecContainerDialog {
+    pub widget: Widget<'static>,
+}
+
+impl ExecContainerDialog {
+    pub fn new(theme: ThemeConfig) -> Self {
+        Self {
+            widget: widget(theme),
+        }
+    }
+}
+
+fn widget(theme: ThemeConfig) -> Widget<'static> {
+    let widget_theme = WidgetTheme::from(theme.component.clone());
+    let filter_theme = FilterFormTheme::from(theme.component.clone());
+    let select_theme = SelectFormTheme::from(theme.component.clone());
+    let single_select_theme =
+        SingleSelectTheme::default().status_style(theme.component.list.status);
+
+    let filter_form = FilterForm::builder().theme(filter_theme).build();
+    let select_form = SelectForm::builder()
+        .theme(select_theme)
+        .on_select(on_select())
+        .build();
+
+    let widget_base = WidgetBase::builder()
+        .title("Exec")
+        .theme(widget_theme)
+        .build();
+
+    SingleSelect::builder()
+        .id(EXEC_CONTAINER_DIALOG_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .select_form(select_form)
+        .theme(single_select_theme)
+        .build()
+        .into()
+}
+
+fn on_select() -> impl Fn(&mut Window, &LiteralItem) -> EventResult {
+    move |w, v| {
+        let Some(metadata) = &v.metadata else {
+            return EventResult::Ignore;
+        };
+
+        let (Some(namespace), Some(pod), Some(container)) = (
+            metadata.get("namespace"),
+            metadata.get("pod"),
+            metadata.get("container"),
+        ) else {
+            return EventResult::Ignore;
+        };
+
+        let target = ExecTarget {
+            namespace: namespace.to_string(),
+            pod: pod.to_string(),
+            container: container.to_string(),
+        };
+
+        w.close_dialog();
+
+        EventResult::WindowAction(WindowAction::Exec(target))
+    }
+}
@@ -9,11 +9,14 @@ use k8s_openapi::{
 };
 use kube::Api;
 use serde::{de::DeserializeOwned, Serialize};
+use serde_yaml::Value;
 
 use crate::{
     features::{
+        api_resources::kube::ApiResource,
         get::message::{GetRequest, GetResponse},
         network::message::{GatewayVersion, HTTPRouteVersion},
+        yaml::kube::fetch_api_resource_value,
     },
     kube::{
         apis::networking::gateway::{v1, v1beta1},
@@ -21,7 +24,7 @@ use crate::{
     },
     logger,
     message::Message,
-    workers::kube::AbortWorker,
+    workers::kube::{mask::mask_secret_values, AbortWorker},
 };
 
 #[derive(Debug, Clone)]
@@ -34,6 +37,8 @@ pub enum GetYamlKind {
     NetworkPolicy,
     Gateway(GatewayVersion),
     HTTPRoute(HTTPRouteVersion),
+    /// APIタブで選択したリソース
+    ApiResource(ApiResource),
 }
 
 impl std::fmt::Display for GetYamlKind {
@@ -53,6 +58,7 @@ impl std::fmt::Display for GetYamlKind {
                 HTTPRouteVersion::V1 => write!(f, "{}", v1::HTTPRoute::URL_PATH_SEGMENT),
                 HTTPRouteVersion::V1Beta1 => write!(f, "{}", v1beta1::HTTPRoute::URL_PATH_SEGMENT),
             },
+            Self::ApiResource(api_resource) => write!(f, "{}", api_resource.name()),
         }
     }
 }
@@ -62,11 +68,23 @@ pub struct GetYamlWorker {
     tx: Sender<Message>,
     client: KubeClient,
     req: GetRequest,
+    mask_secrets: bool,
 }
 
 impl GetYamlWorker {
     pub fn new(tx: Sender<Message>, client: KubeClient, req: GetRequest) -> Self {
-        Self { tx, client, req }
+        Self {
+            tx,
+            client,
+            req,
+            mask_secrets: false,
+        }
+    }
+
+    /// Secretの値をマスクするか
+    pub fn mask_secrets(mut self, mask_secrets: bool) -> Self {
+        self.mask_secrets = mask_secrets;
+        self
     }
 }
 
@@ -85,41 +103,51 @@ impl AbortWorker for GetYamlWorker {
             interval.tick().await;
 
             let yaml = match kind {
-                GetYamlKind::Pod => fetch_resource_yaml::<Pod>(&self.client, name, namespace).await,
+                GetYamlKind::Pod => fetch_resource::<Pod>(&self.client, name, namespace).await,
                 GetYamlKind::ConfigMap => {
-                    fetch_resource_yaml::<ConfigMap>(&self.client, name, namespace).await
+                    fetch_resource::<ConfigMap>(&self.client, name, namespace).await
                 }
                 GetYamlKind::Secret => {
-                    fetch_resource_yaml::<Secret>(&self.client, name, namespace).await
+                    fetch_resource::<Secret>(&self.client, name, namespace).await
                 }
                 GetYamlKind::Ingress => {
-                    fetch_resource_yaml::<Ingress>(&self.client, name, namespace).await
+                    fetch_resource::<Ingress>(&self.client, name, namespace).await
                 }
                 GetYamlKind::Service => {
-                    fetch_resource_yaml::<Service>(&self.client, name, namespace).await
+                    fetch_resource::<Service>(&self.client, name, namespace).await
                 }
                 GetYamlKind::NetworkPolicy => {
-                    fetch_resource_yaml::<NetworkPolicy>(&self.client, name, namespace).await
+                    fetch_resource::<NetworkPolicy>(&self.client, name, namespace).await
                 }
                 GetYamlKind::Gateway(version) => match version {
                     GatewayVersion::V1 => {
-                        fetch_resource_yaml::<v1::Gateway>(&self.client, name, namespace).await
+                        fetch_resource::<v1::Gateway>(&self.client, name, namespace).await
                     }
                     GatewayVersion::V1Beta1 => {
-                        fetch_resource_yaml::<v1beta1::Gateway>(&self.client, name, namespace).await
+                        fetch_resource::<v1beta1::Gateway>(&self.client, name, namespace).await
                     }
                 },
                 GetYamlKind::HTTPRoute(version) => match version {
                     HTTPRouteVersion::V1 => {
-                        fetch_resource_yaml::<v1::HTTPRoute>(&self.client, name, namespace).await
+                        fetch_resource::<v1::HTTPRoute>(&self.client, name, namespace).await
                     }
                     HTTPRouteVersion::V1Beta1 => {
-                        fetch_resource_yaml::<v1beta1::HTTPRoute>(&self.client, name, namespace)
-                            .await
+                        fetch_resource::<v1beta1::HTTPRoute>(&self.client, name, namespace).await
                     }
                 },
+                GetYamlKind::ApiResource(api_resource) => {
+                    fetch_api_resource_value(&self.client, api_resource, name, namespace).await
+                }
             };
 
+            let yaml = yaml.and_then(|mut yaml| {
+                if self.mask_secrets {
+                    mask_secret_values(&mut yaml);
+                }
+
+                to_lines(&yaml)
+            });
+
             self.tx
                 .send(
                     GetResponse {
@@ -134,8 +162,8 @@ impl AbortWorker for GetYamlWorker {
     }
 }
 
-/// 選択されているリソースのyamlを取得する
-async fn fetch_resource_yaml<K>(client: &KubeClient, name: &str, ns: &str) -> Result<Vec<String>>
+/// 選択されているリソースを取得し、managedFieldsを除いた値を返す
+async fn fetch_resource<K>(client: &KubeClient, name: &str, ns: &str) -> Result<Value>
 where
     K: kube::Resource<Scope = NamespaceResourceScope>,
     <K as kube::Resource>::DynamicType: Default,
@@ -157,7 +185,11 @@ where
     let metadata = data.meta_mut();
     metadata.managed_fields = None;
 
-    let yaml_string = serde_yaml::to_string(&data)?
+    serde_yaml::to_value(&data).map_err(Into::into)
+}
+
+fn to_lines(yaml: &Value) -> Result<Vec<String>> {
+    let yaml_string = serde_yaml::to_string(yaml)?
         .lines()
         .map(ToString::to_string)
         .collect();
@@ -0,0 +1,6 @@
+mod content;
+mod helm;
+mod release;
+
+pub use content::*;
+pub use helm::*;
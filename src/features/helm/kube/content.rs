@@ -0,0 +1,192 @@
+use anyhow::Result;
+use k8s_openapi::api::core::v1::Secret;
+
+use crate::{
+    features::helm::message::{HelmView, ReleaseTarget},
+    kube::KubeClientRequest,
+    workers::kube::{color::fg::Color, diff::diff_lines},
+};
+
+use super::release::Release;
+
+/// 指定したリビジョンのリリースを取得する
+///
+/// mask_secretsがtrueの場合は、valuesとマニフェストに含まれるSecretの値をマスクする
+async fn fetch_release<C: KubeClientRequest>(
+    client: &C,
+    target: &ReleaseTarget,
+    revision: i64,
+    mask_secrets: bool,
+) -> Result<Release> {
+    let secret: Secret = client
+        .request(&format!(
+            "/api/v1/namespaces/{}/secrets/sh.helm.release.v1.{}.v{}",
+            target.namespace, target.name, revision
+        ))
+        .await?;
+
+    let mut release = Release::from_secret(&secret)?;
+
+    if mask_secrets {
+        release.mask_secrets();
+    }
+
+    Ok(release)
+}
+
+/// 指定したリビジョンのvaluesやマニフェストなどを行ごとに返す
+pub async fn fetch_release_content<C: KubeClientRequest>(
+    client: &C,
+    target: &ReleaseTarget,
+    revision: i64,
+    view: HelmView,
+    mask_secrets: bool,
+) -> Result<Vec<String>> {
+    let release = fetch_release(client, target, revision, mask_secrets).await?;
+
+    Ok(release
+        .view(view)?
+        .lines()
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// fromのリビジョンからtoのリビジョンへの、valuesやマニフェストなどの差分を返す
+pub async fn fetch_release_diff<C: KubeClientRequest>(
+    client: &C,
+    target: &ReleaseTarget,
+    view: HelmView,
+    from: i64,
+    to: i64,
+    mask_secrets: bool,
+) -> Result<Vec<String>> {
+    let old = fetch_release(client, target, from, mask_secrets)
+        .await?
+        .view(view)?;
+    let new = fetch_release(client, target, to, mask_secrets)
+        .await?
+        .view(view)?;
+
+    let mut lines = vec![
+        Color::Red.wrap(format!("--- revision {}", from)),
+        Color::Green.wrap(format!("+++ revision {}", to)),
+    ];
+
+    if old == new {
+        lines.push(format!(
+            "No differences in {}",
+            view.to_string().to_lowercase()
+        ));
+    } else {
+        lines.extend(diff_lines(
+            &old.lines().collect::<Vec<_>>(),
+            &new.lines().collect::<Vec<_>>(),
+        ));
+    }
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::{
+        features::helm::kube::release::tests::release_secret, kube::mock::MockTestKubeClient,
+        mock_expect,
+    };
+
+    fn release(tag: &str) -> Secret {
+        release_secret(
+            "app",
+            "web",
+            if tag == "1.0" { 1 } else { 2 },
+            json!({
+                "config": {
+                    "replicaCount": 2,
+                    "image": {
+                        "tag": tag
+                    }
+                },
+                "manifest": "kind: Deployment\n"
+            }),
+        )
+    }
+
+    fn target() -> ReleaseTarget {
+        ReleaseTarget {
+            namespace: "app".to_string(),
+            name: "web".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn リビジョン間のvaluesの差分を返す() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            [
+                (
+                    Secret,
+                    eq("/api/v1/namespaces/app/secrets/sh.helm.release.v1.web.v1"),
+                    Ok(release("1.0"))
+                ),
+                (
+                    Secret,
+                    eq("/api/v1/namespaces/app/secrets/sh.helm.release.v1.web.v2"),
+                    Ok(release("1.1"))
+                )
+            ]
+        );
+
+        let actual = fetch_release_diff(&client, &target(), HelmView::Values, 1, 2, false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual,
+            vec![
+                Color::Red.wrap("--- revision 1"),
+                Color::Green.wrap("+++ revision 2"),
+                "  image:".to_string(),
+                Color::Red.wrap("-   tag: '1.0'"),
+                Color::Green.wrap("+   tag: '1.1'"),
+                "  replicaCount: 2".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn 差分がない場合はその旨を返す() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            [
+                (
+                    Secret,
+                    eq("/api/v1/namespaces/app/secrets/sh.helm.release.v1.web.v1"),
+                    Ok(release("1.0"))
+                ),
+                (
+                    Secret,
+                    eq("/api/v1/namespaces/app/secrets/sh.helm.release.v1.web.v2"),
+                    Ok(release("1.1"))
+                )
+            ]
+        );
+
+        let actual = fetch_release_diff(&client, &target(), HelmView::Manifest, 1, 2, false)
+            .await
+            .unwrap();
+
+        assert_eq!(actual[2], "No differences in manifest");
+    }
+}
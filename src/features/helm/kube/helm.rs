@@ -0,0 +1,494 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crossbeam::channel::Sender;
+use k8s_openapi::api::core::v1::Secret;
+use kube::{core::PartialObjectMeta, ResourceExt};
+
+use crate::{
+    features::helm::message::HelmResponse,
+    kube::{
+        informer::{ChangeDetector, SharedInformers},
+        table::{insert_ns, translate_datetime_since, KubeTable, KubeTableRow},
+        KubeClient, KubeClientRequest,
+    },
+    logger,
+    message::Message,
+    workers::kube::{SharedTargetNamespaces, Worker, WorkerResult},
+};
+
+use super::release::Release;
+
+/// helmがリリースを保存するSecretに付与するownerラベルの値
+const HELM_RELEASE_OWNER: &str = "helm";
+
+/// メタデータのみを取得したSecret
+type SecretMetadata = PartialObjectMeta<Secret>;
+
+#[derive(Clone)]
+pub struct HelmPoller {
+    tx: Sender<Message>,
+    shared_target_namespaces: SharedTargetNamespaces,
+    kube_client: KubeClient,
+    informers: SharedInformers,
+}
+
+impl HelmPoller {
+    pub fn new(
+        tx: Sender<Message>,
+        shared_target_namespaces: SharedTargetNamespaces,
+        kube_client: KubeClient,
+        informers: SharedInformers,
+    ) -> Self {
+        Self {
+            tx,
+            shared_target_namespaces,
+            kube_client,
+            informers,
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for HelmPoller {
+    type Output = WorkerResult;
+
+    async fn run(&self) -> Self::Output {
+        let mut interval = tokio::time::interval(time::Duration::from_secs(1));
+
+        let Self {
+            tx,
+            shared_target_namespaces,
+            kube_client,
+            informers,
+        } = self;
+
+        // ConfigPollerと共有するため、helmのSecretのみに絞り込まずに監視する
+        let informer = informers.secret_metadata();
+
+        let mut cache = ReleaseCache::default();
+
+        let mut detector = ChangeDetector::default();
+
+        loop {
+            interval.tick().await;
+
+            let target_namespaces = shared_target_namespaces.read().await.clone();
+
+            informer.sync_namespaces(&target_namespaces);
+
+            let Some(secrets) = informer.state() else {
+                continue;
+            };
+
+            let table = match secrets {
+                Ok(secrets) => {
+                    let releases = group_releases(&target_namespaces, &secrets);
+
+                    cache.refresh(kube_client, &releases).await;
+
+                    Ok(release_table(&target_namespaces, &releases, &cache))
+                }
+                Err(e) => Err(e),
+            };
+
+            if !detector.is_changed(&table) {
+                continue;
+            }
+
+            tx.send(HelmResponse::List(table).into())
+                .expect("Failed to send HelmResponse::List");
+        }
+    }
+}
+
+/// 一覧に表示するリリースの概要
+#[derive(Debug, Clone)]
+struct ReleaseSummary {
+    status: String,
+    chart: String,
+    app_version: String,
+    updated: Option<DateTime<Utc>>,
+}
+
+impl ReleaseSummary {
+    fn new(secret: &Secret) -> Self {
+        match Release::from_secret(secret) {
+            Ok(release) => Self {
+                status: release.info.status.clone().unwrap_or_default(),
+                chart: release.chart(),
+                app_version: release
+                    .chart
+                    .metadata
+                    .app_version
+                    .clone()
+                    .unwrap_or_default(),
+                updated: release
+                    .info
+                    .last_deployed
+                    .as_deref()
+                    .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                    .map(|time| time.with_timezone(&Utc)),
+            },
+            // デコードできない場合もリリースの存在は分かるように、ラベルの情報のみ表示する
+            Err(e) => {
+                logger!(
+                    error,
+                    "Failed to decode helm release: namespace={} name={} {}",
+                    secret.namespace().unwrap_or_default(),
+                    secret.name_any(),
+                    e
+                );
+
+                Self::from_labels(secret)
+            }
+        }
+    }
+
+    /// リリースを取得していない場合は、ラベルの情報のみ表示する
+    fn from_labels(secret: &impl ResourceExt) -> Self {
+        Self {
+            status: secret.labels().get("status").cloned().unwrap_or_default(),
+            chart: "-".to_string(),
+            app_version: "-".to_string(),
+            updated: None,
+        }
+    }
+}
+
+/// namespaceとリリース名でまとめた、リビジョンごとのSecret
+struct ReleaseRevisions<'a> {
+    name: String,
+    /// リビジョンの降順
+    revisions: Vec<(i64, &'a SecretMetadata)>,
+}
+
+impl ReleaseRevisions<'_> {
+    fn latest(&self) -> Option<(i64, &SecretMetadata)> {
+        self.revisions.first().copied()
+    }
+}
+
+/// helmがリリースを保存するSecretのみを、namespaceとリリース名でまとめる
+///
+/// Secretはリビジョンごとに作成されるため、リリースごとにリビジョンの降順に並べる
+fn group_releases<'a>(
+    namespaces: &[String],
+    secrets: &'a [Arc<SecretMetadata>],
+) -> Vec<ReleaseRevisions<'a>> {
+    // key: (namespaceの指定順, リリース名), value: (リビジョン, Secret)の一覧
+    let mut releases: BTreeMap<(usize, String), Vec<(i64, &SecretMetadata)>> = BTreeMap::new();
+
+    for secret in secrets {
+        let labels = secret.labels();
+
+        if labels.get("owner").map(String::as_str) != Some(HELM_RELEASE_OWNER) {
+            continue;
+        }
+
+        let (Some(name), Some(revision)) = (
+            labels.get("name"),
+            labels.get("version").and_then(|v| v.parse::<i64>().ok()),
+        ) else {
+            continue;
+        };
+
+        let namespace = secret.namespace().unwrap_or_default();
+
+        let order = namespaces
+            .iter()
+            .position(|ns| *ns == namespace)
+            .unwrap_or(namespaces.len());
+
+        releases
+            .entry((order, name.clone()))
+            .or_default()
+            .push((revision, secret.as_ref()));
+    }
+
+    releases
+        .into_iter()
+        .map(|((_, name), mut revisions)| {
+            revisions.sort_by_key(|(revision, _)| std::cmp::Reverse(*revision));
+
+            ReleaseRevisions { name, revisions }
+        })
+        .collect()
+}
+
+/// デコードしたリリースの概要をSecretごとに保持する
+///
+/// 一覧のSecretはメタデータのみを保持しているため、最新のリビジョンのSecretが
+/// 更新されたときのみSecretを取得してデコードする
+#[derive(Debug, Default)]
+struct ReleaseCache {
+    /// key: (namespace, Secretの名前), value: (resourceVersion, 概要)
+    summaries: HashMap<(String, String), (Option<String>, ReleaseSummary)>,
+}
+
+impl ReleaseCache {
+    /// 各リリースの最新のリビジョンの概要を更新し、一覧に含まれなくなったSecretの概要を破棄する
+    async fn refresh<C: KubeClientRequest>(
+        &mut self,
+        client: &C,
+        releases: &[ReleaseRevisions<'_>],
+    ) {
+        let latest_secrets: Vec<&SecretMetadata> = releases
+            .iter()
+            .filter_map(|release| release.latest().map(|(_, secret)| secret))
+            .collect();
+
+        self.summaries.retain(|(namespace, name), _| {
+            latest_secrets.iter().any(|secret| {
+                secret.namespace().as_ref() == Some(namespace) && &secret.name_any() == name
+            })
+        });
+
+        for secret in latest_secrets {
+            let key = (secret.namespace().unwrap_or_default(), secret.name_any());
+
+            if matches!(self.summaries.get(&key), Some((version, _)) if *version == secret.resource_version())
+            {
+                continue;
+            }
+
+            let path = format!("/api/v1/namespaces/{}/secrets/{}", key.0, key.1);
+
+            // 取得に失敗した場合は、次回の更新時に再度取得する
+            let summary = match client.request::<Secret>(&path).await {
+                Ok(secret) => ReleaseSummary::new(&secret),
+                Err(e) => {
+                    logger!(
+                        error,
+                        "Failed to fetch helm release: namespace={} name={} {}",
+                        key.0,
+                        key.1,
+                        e
+                    );
+
+                    continue;
+                }
+            };
+
+            self.summaries
+                .insert(key, (secret.resource_version(), summary));
+        }
+    }
+
+    /// 取得していない場合はラベルの情報のみを返す
+    fn get(&self, secret: &SecretMetadata) -> ReleaseSummary {
+        let key = (secret.namespace().unwrap_or_default(), secret.name_any());
+
+        self.summaries
+            .get(&key)
+            .map(|(_, summary)| summary.clone())
+            .unwrap_or_else(|| ReleaseSummary::from_labels(secret))
+    }
+}
+
+/// リリースの一覧からテーブルを生成する
+///
+/// 各リリースの最新のリビジョンを表示する
+fn release_table(
+    namespaces: &[String],
+    releases: &[ReleaseRevisions<'_>],
+    cache: &ReleaseCache,
+) -> KubeTable {
+    let insert_ns = insert_ns(namespaces);
+
+    let rows = releases
+        .iter()
+        .filter_map(|release| {
+            let (revision, secret) = release.latest()?;
+
+            let namespace = secret.namespace().unwrap_or_default();
+
+            let summary = cache.get(secret);
+
+            let mut row = vec![
+                release.name.clone(),
+                revision.to_string(),
+                summary.status,
+                summary.chart,
+                summary.app_version,
+                summary
+                    .updated
+                    .as_ref()
+                    .map_or_else(|| "<unknown>".to_string(), translate_datetime_since),
+            ];
+
+            if insert_ns {
+                row.insert(0, namespace.clone());
+            }
+
+            let revisions = release
+                .revisions
+                .iter()
+                .map(|(revision, _)| revision.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+
+            Some(KubeTableRow {
+                namespace,
+                name: release.name.clone(),
+                metadata: Some(BTreeMap::from([
+                    ("revision".to_string(), revision.to_string()),
+                    ("revisions".to_string(), revisions),
+                ])),
+                row,
+            })
+        })
+        .collect();
+
+    let mut header = vec![
+        "NAME",
+        "REVISION",
+        "STATUS",
+        "CHART",
+        "APP VERSION",
+        "UPDATED",
+    ];
+
+    if insert_ns {
+        header.insert(0, "NAMESPACE");
+    }
+
+    let mut table = KubeTable {
+        header: header.into_iter().map(ToString::to_string).collect(),
+        ..Default::default()
+    };
+
+    table.update_rows(rows);
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use kube::core::PartialObjectMetaExt as _;
+    use mockall::predicate::eq;
+
+    use crate::{
+        features::helm::kube::release::tests::release_secret, kube::mock::MockTestKubeClient,
+        mock_expect,
+    };
+
+    fn release(status: &str, version: &str) -> serde_json::Value {
+        json!({
+            "info": {
+                "status": status,
+                "last_deployed": "2024-01-01T00:00:00Z"
+            },
+            "chart": {
+                "metadata": {
+                    "name": "web",
+                    "version": version,
+                    "appVersion": "2.0.0"
+                }
+            }
+        })
+    }
+
+    fn metadata(secret: Secret) -> Arc<SecretMetadata> {
+        Arc::new(secret.metadata.into_response_partial())
+    }
+
+    #[tokio::test]
+    async fn リビジョンごとのsecretをまとめて最新のリビジョンのみ取得して表示する() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            [
+                (
+                    Secret,
+                    eq("/api/v1/namespaces/app/secrets/sh.helm.release.v1.db.v1"),
+                    Ok(release_secret("app", "db", 1, release("failed", "0.1.0")))
+                ),
+                (
+                    Secret,
+                    eq("/api/v1/namespaces/app/secrets/sh.helm.release.v1.web.v10"),
+                    Ok(release_secret(
+                        "app",
+                        "web",
+                        10,
+                        release("deployed", "1.1.0")
+                    ))
+                )
+            ]
+        );
+
+        let mut token = release_secret("app", "token", 1, json!({}));
+        token.metadata.labels = None;
+
+        let secrets = vec![
+            metadata(release_secret(
+                "app",
+                "web",
+                1,
+                release("superseded", "1.0.0"),
+            )),
+            metadata(release_secret(
+                "app",
+                "web",
+                10,
+                release("deployed", "1.1.0"),
+            )),
+            metadata(release_secret(
+                "app",
+                "web",
+                2,
+                release("superseded", "1.0.1"),
+            )),
+            metadata(release_secret("app", "db", 1, release("failed", "0.1.0"))),
+            metadata(token),
+        ];
+
+        let namespaces = ["app".to_string()];
+
+        let releases = group_releases(&namespaces, &secrets);
+
+        let mut cache = ReleaseCache::default();
+
+        cache.refresh(&client, &releases).await;
+
+        let actual = release_table(&namespaces, &releases, &cache);
+
+        let rows: Vec<(&[String], _)> = actual
+            .rows
+            .iter()
+            .map(|row| (&row.row[..5], row.metadata.clone().unwrap()))
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    &["db", "1", "failed", "web-0.1.0", "2.0.0"].map(String::from)[..],
+                    BTreeMap::from([
+                        ("revision".to_string(), "1".to_string()),
+                        ("revisions".to_string(), "1".to_string()),
+                    ])
+                ),
+                (
+                    &["web", "10", "deployed", "web-1.1.0", "2.0.0"].map(String::from)[..],
+                    BTreeMap::from([
+                        ("revision".to_string(), "10".to_string()),
+                        ("revisions".to_string(), "10,2,1".to_string()),
+                    ])
+                ),
+            ]
+        );
+    }
+}
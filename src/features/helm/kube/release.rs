@@ -0,0 +1,294 @@
+use anyhow::{anyhow, Result};
+use k8s_openapi::{api::core::v1::Secret, ByteString};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    features::{config::kube::decode_helm_release, helm::message::HelmView},
+    workers::kube::mask::{mask_json_values, mask_manifest_secrets},
+};
+
+/// helm.sh/release.v1のSecretに保存されているリリース
+///
+/// 表示に使うフィールドのみを扱う
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Release {
+    pub info: ReleaseInfo,
+    pub chart: Chart,
+    /// ユーザーが指定したvalues
+    pub config: Option<Value>,
+    pub manifest: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ReleaseInfo {
+    pub status: Option<String>,
+    pub last_deployed: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Chart {
+    pub metadata: ChartMetadata,
+    /// チャートのvalues.yamlの値
+    pub values: Option<Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ChartMetadata {
+    pub name: String,
+    pub version: String,
+    pub app_version: Option<String>,
+}
+
+impl Release {
+    pub fn from_secret(secret: &Secret) -> Result<Self> {
+        let Some(ByteString(data)) = secret.data.as_ref().and_then(|data| data.get("release"))
+        else {
+            return Err(anyhow!("no release data"));
+        };
+
+        let json = decode_helm_release(data)?;
+
+        serde_json::from_str(&json).map_err(Into::into)
+    }
+
+    /// valuesとマニフェストに含まれるSecretの値をマスクする
+    ///
+    /// valuesにはパスワードなどを指定することがあるため、チャートのデフォルト値を含めてすべての値をマスクする
+    pub fn mask_secrets(&mut self) {
+        if let Some(config) = self.config.as_mut() {
+            mask_json_values(config);
+        }
+
+        if let Some(values) = self.chart.values.as_mut() {
+            mask_json_values(values);
+        }
+
+        if let Some(manifest) = self.manifest.as_mut() {
+            *manifest = mask_manifest_secrets(manifest);
+        }
+    }
+
+    /// helm listのCHART列と同じく、チャート名とバージョンをつなげたもの
+    pub fn chart(&self) -> String {
+        format!(
+            "{}-{}",
+            self.chart.metadata.name, self.chart.metadata.version
+        )
+    }
+
+    /// helm get values --allと同じく、チャートのデフォルト値にユーザーが指定したvaluesをマージする
+    pub fn computed_values(&self) -> Value {
+        let empty = Value::Object(Default::default());
+
+        coalesce(
+            self.chart.values.as_ref().unwrap_or(&empty),
+            self.config.as_ref().unwrap_or(&empty),
+        )
+    }
+
+    /// 表示する内容をテキストで返す
+    pub fn view(&self, view: HelmView) -> Result<String> {
+        let empty = Value::Object(Default::default());
+
+        match view {
+            HelmView::Values => {
+                serde_yaml::to_string(self.config.as_ref().unwrap_or(&empty)).map_err(Into::into)
+            }
+            HelmView::ComputedValues => {
+                serde_yaml::to_string(&self.computed_values()).map_err(Into::into)
+            }
+            HelmView::Manifest => Ok(self.manifest.clone().unwrap_or_default()),
+            HelmView::Notes => Ok(self.info.notes.clone().unwrap_or_default()),
+        }
+    }
+}
+
+/// baseにoverridesを再帰的にマージする
+///
+/// overridesでnullを指定したキーはbaseから削除する
+fn coalesce(base: &Value, overrides: &Value) -> Value {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            let mut merged = base.clone();
+
+            for (key, value) in overrides {
+                if value.is_null() {
+                    merged.remove(key);
+                    continue;
+                }
+
+                let value = match base.get(key) {
+                    Some(base) => coalesce(base, value),
+                    None => value.clone(),
+                };
+
+                merged.insert(key.clone(), value);
+            }
+
+            Value::Object(merged)
+        }
+        _ => overrides.clone(),
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    use std::{collections::BTreeMap, io::Write as _};
+
+    use base64::{engine::general_purpose, Engine as _};
+    use indoc::indoc;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    /// helmと同じ形式でリリースを保存したSecretを生成する
+    pub fn release_secret(namespace: &str, name: &str, revision: i64, release: Value) -> Secret {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+
+        encoder.write_all(release.to_string().as_bytes()).unwrap();
+
+        let gzip = encoder.finish().unwrap();
+
+        Secret {
+            metadata: ObjectMeta {
+                name: Some(format!("sh.helm.release.v1.{}.v{}", name, revision)),
+                namespace: Some(namespace.to_string()),
+                labels: Some(BTreeMap::from([
+                    ("name".to_string(), name.to_string()),
+                    ("owner".to_string(), "helm".to_string()),
+                    ("version".to_string(), revision.to_string()),
+                ])),
+                ..Default::default()
+            },
+            data: Some(BTreeMap::from([(
+                "release".to_string(),
+                ByteString(general_purpose::STANDARD.encode(gzip).into_bytes()),
+            )])),
+            type_: Some("helm.sh/release.v1".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn release() -> Value {
+        json!({
+            "name": "web",
+            "info": {
+                "status": "deployed",
+                "last_deployed": "2024-01-01T00:00:00Z",
+                "notes": "Thank you for installing web."
+            },
+            "chart": {
+                "metadata": {
+                    "name": "web",
+                    "version": "1.2.3",
+                    "appVersion": "2.0.0"
+                },
+                "values": {
+                    "replicaCount": 1,
+                    "image": {
+                        "repository": "web",
+                        "tag": "latest"
+                    },
+                    "ingress": {
+                        "enabled": false
+                    }
+                }
+            },
+            "config": {
+                "image": {
+                    "tag": "2.0.0"
+                },
+                "ingress": null
+            },
+            "manifest": "---\nkind: Deployment\n",
+            "version": 1
+        })
+    }
+
+    #[test]
+    fn secretからリリースをデコードする() {
+        let actual = Release::from_secret(&release_secret("app", "web", 1, release())).unwrap();
+
+        assert_eq!(actual.chart(), "web-1.2.3");
+        assert_eq!(actual.chart.metadata.app_version.as_deref(), Some("2.0.0"));
+        assert_eq!(actual.info.status.as_deref(), Some("deployed"));
+        assert_eq!(
+            actual.view(HelmView::Notes).unwrap(),
+            "Thank you for installing web."
+        );
+    }
+
+    #[test]
+    fn チャートのデフォルト値にユーザーが指定したvaluesをマージする() {
+        let actual = Release::from_secret(&release_secret("app", "web", 1, release())).unwrap();
+
+        assert_eq!(
+            actual.view(HelmView::Values).unwrap(),
+            indoc! {"
+                image:
+                  tag: 2.0.0
+                ingress: null
+            "}
+        );
+
+        assert_eq!(
+            actual.view(HelmView::ComputedValues).unwrap(),
+            indoc! {"
+                image:
+                  repository: web
+                  tag: 2.0.0
+                replicaCount: 1
+            "}
+        );
+    }
+
+    #[test]
+    fn valuesとマニフェストのsecretの値をマスクする() {
+        let mut release = release();
+
+        release["manifest"] =
+            json!("---\nkind: Secret\napiVersion: v1\ndata:\n  password: c2VjcmV0\n");
+
+        let mut actual = Release::from_secret(&release_secret("app", "web", 1, release)).unwrap();
+
+        actual.mask_secrets();
+
+        assert_eq!(
+            actual.view(HelmView::Values).unwrap(),
+            indoc! {"
+                image:
+                  tag: '********'
+                ingress: null
+            "}
+        );
+
+        assert_eq!(
+            actual.view(HelmView::ComputedValues).unwrap(),
+            indoc! {"
+                image:
+                  repository: '********'
+                  tag: '********'
+                replicaCount: '********'
+            "}
+        );
+
+        assert_eq!(
+            actual.view(HelmView::Manifest).unwrap(),
+            indoc! {"
+                ---
+                kind: Secret
+                apiVersion: v1
+                data:
+                  password: '********'
+            "}
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+
+use crate::{kube::table::KubeTable, message::Message, workers::kube::message::Kube};
+
+/// リリースの内容のうち、表示するもの
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HelmView {
+    /// ユーザーが指定したvalues
+    #[default]
+    Values,
+    /// チャートのデフォルト値とユーザーが指定したvaluesをマージしたもの
+    ComputedValues,
+    Manifest,
+    Notes,
+}
+
+impl HelmView {
+    pub const ALL: [Self; 4] = [
+        Self::Values,
+        Self::ComputedValues,
+        Self::Manifest,
+        Self::Notes,
+    ];
+}
+
+impl Display for HelmView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Values => write!(f, "Values"),
+            Self::ComputedValues => write!(f, "Computed Values"),
+            Self::Manifest => write!(f, "Manifest"),
+            Self::Notes => write!(f, "Notes"),
+        }
+    }
+}
+
+/// 対象のリリース
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseTarget {
+    pub namespace: String,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub enum HelmRequest {
+    /// 指定したリビジョンの内容
+    Show {
+        target: ReleaseTarget,
+        revision: i64,
+        view: HelmView,
+    },
+    /// fromのリビジョンからtoのリビジョンへの差分
+    Diff {
+        target: ReleaseTarget,
+        view: HelmView,
+        from: i64,
+        to: i64,
+    },
+}
+
+#[derive(Debug)]
+pub enum HelmResponse {
+    List(Result<KubeTable>),
+    Content(Result<Vec<String>>),
+}
+
+#[derive(Debug)]
+pub enum HelmMessage {
+    Request(HelmRequest),
+    Response(HelmResponse),
+}
+
+impl From<HelmMessage> for Kube {
+    fn from(m: HelmMessage) -> Self {
+        Self::Helm(m)
+    }
+}
+
+impl From<HelmMessage> for Message {
+    fn from(m: HelmMessage) -> Self {
+        Self::Kube(m.into())
+    }
+}
+
+impl From<HelmRequest> for Message {
+    fn from(req: HelmRequest) -> Self {
+        HelmMessage::Request(req).into()
+    }
+}
+
+impl From<HelmResponse> for Message {
+    fn from(res: HelmResponse) -> Self {
+        HelmMessage::Response(res).into()
+    }
+}
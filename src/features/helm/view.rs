@@ -0,0 +1,6 @@
+mod dialogs;
+mod tab;
+mod widgets;
+
+pub use dialogs::*;
+pub use tab::*;
@@ -0,0 +1,302 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crossbeam::channel::Sender;
+
+use crate::{
+    config::theme::WidgetThemeConfig,
+    features::{
+        component_id::{
+            HELM_CONTENT_WIDGET_ID, HELM_DIFF_DIALOG_ID, HELM_VIEW_DIALOG_ID, HELM_WIDGET_ID,
+        },
+        helm::message::{HelmRequest, HelmView, ReleaseTarget},
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            single_select::{
+                FilterForm, FilterFormTheme, SelectForm, SelectFormTheme, SingleSelectTheme,
+            },
+            Item, LiteralItem, SelectedItem, SingleSelect, Widget, WidgetBase, WidgetTheme,
+            WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+/// 差分を表示できる内容
+const DIFF_VIEWS: [HelmView; 2] = [HelmView::Values, HelmView::Manifest];
+
+/// リリースを選択したときに表示する内容
+///
+/// 表示する内容を切り替えるダイアログとリリースのテーブルで共有する
+pub type SharedHelmView = Rc<RefCell<HelmView>>;
+
+/// リリースの表示内容の切り替えや、リビジョン間の差分の表示を行うダイアログ群
+pub struct HelmDialogs {
+    pub view_dialog: Widget<'static>,
+    pub diff_dialog: Widget<'static>,
+    view: SharedHelmView,
+}
+
+impl HelmDialogs {
+    pub fn new(tx: &Sender<Message>, view: SharedHelmView, theme: WidgetThemeConfig) -> Self {
+        Self {
+            view_dialog: view_widget(tx.clone(), view.clone(), theme.clone()),
+            diff_dialog: diff_widget(tx.clone(), theme),
+            view,
+        }
+    }
+
+    /// 表示する内容を選択するダイアログを開く
+    pub fn open_view(&self) -> impl Fn(&mut Window) -> EventResult {
+        let view = self.view.clone();
+
+        move |w: &mut Window| {
+            let current = *view.borrow();
+
+            let items = HelmView::ALL
+                .iter()
+                .map(|view| {
+                    if *view == current {
+                        format!("{} (current)", view)
+                    } else {
+                        view.to_string()
+                    }
+                })
+                .map(|item| LiteralItem::new(item, None))
+                .collect();
+
+            w.find_widget_mut(HELM_VIEW_DIALOG_ID)
+                .update_widget_item(Item::Array(items));
+
+            w.open_dialog(HELM_VIEW_DIALOG_ID);
+
+            EventResult::Nop
+        }
+    }
+
+    /// 選択しているリリースのリビジョンの組み合わせを、差分の候補として表示するダイアログを開く
+    pub fn open_diff(&self) -> impl Fn(&mut Window) -> EventResult {
+        move |w: &mut Window| {
+            let Some(metadata) = selected_release(w) else {
+                return EventResult::Ignore;
+            };
+
+            let (Some(target), Some(revisions)) =
+                (release_target(&metadata), metadata.get("revisions"))
+            else {
+                return EventResult::Ignore;
+            };
+
+            // リビジョンの降順に並んでいる
+            let revisions: Vec<i64> = revisions
+                .split(',')
+                .filter_map(|revision| revision.parse().ok())
+                .collect();
+
+            if revisions.len() < 2 {
+                return EventResult::Ignore;
+            }
+
+            let (target, revisions) = (&target, &revisions);
+
+            let items = DIFF_VIEWS
+                .iter()
+                .flat_map(|view| {
+                    revisions.iter().enumerate().flat_map(move |(i, to)| {
+                        revisions[i + 1..].iter().map(move |from| {
+                            LiteralItem::new(
+                                format!("{}: revision {} -> {}", view, from, to),
+                                Some(BTreeMap::from([
+                                    ("namespace".to_string(), target.namespace.clone()),
+                                    ("name".to_string(), target.name.clone()),
+                                    ("view".to_string(), view.to_string()),
+                                    ("from".to_string(), from.to_string()),
+                                    ("to".to_string(), to.to_string()),
+                                ])),
+                            )
+                        })
+                    })
+                })
+                .collect();
+
+            let dialog = w.find_widget_mut(HELM_DIFF_DIALOG_ID);
+
+            *dialog.widget_base_mut().append_title_mut() =
+                Some(format!(" : {}", target.name).into());
+
+            dialog.update_widget_item(Item::Array(items));
+
+            w.open_dialog(HELM_DIFF_DIALOG_ID);
+
+            EventResult::Nop
+        }
+    }
+}
+
+/// リリースのテーブルで選択している行のメタデータ
+fn selected_release(w: &Window) -> Option<BTreeMap<String, String>> {
+    let Some(SelectedItem::TableRow { metadata, .. }) = w.find_widget(HELM_WIDGET_ID).widget_item()
+    else {
+        return None;
+    };
+
+    metadata
+}
+
+fn release_target(metadata: &BTreeMap<String, String>) -> Option<ReleaseTarget> {
+    Some(ReleaseTarget {
+        namespace: metadata.get("namespace")?.to_string(),
+        name: metadata.get("name")?.to_string(),
+    })
+}
+
+/// リリースの最新のリビジョンの内容を表示する
+pub(super) fn show_release(
+    w: &mut Window,
+    tx: &Sender<Message>,
+    metadata: &BTreeMap<String, String>,
+    view: HelmView,
+) -> EventResult {
+    w.widget_clear(HELM_CONTENT_WIDGET_ID);
+
+    let (Some(target), Some(revision)) = (
+        release_target(metadata),
+        metadata
+            .get("revision")
+            .and_then(|revision| revision.parse().ok()),
+    ) else {
+        return EventResult::Ignore;
+    };
+
+    *w.find_widget_mut(HELM_CONTENT_WIDGET_ID)
+        .widget_base_mut()
+        .append_title_mut() =
+        Some(format!(" : {} revision {} ({})", target.name, revision, view).into());
+
+    tx.send(
+        HelmRequest::Show {
+            target,
+            revision,
+            view,
+        }
+        .into(),
+    )
+    .expect("Failed to send HelmRequest::Show");
+
+    EventResult::Nop
+}
+
+fn single_select_widget(
+    id: &str,
+    title: &str,
+    theme: WidgetThemeConfig,
+    on_select: impl Fn(&mut Window, &LiteralItem) -> EventResult + 'static,
+) -> Widget<'static> {
+    let single_select_theme = SingleSelectTheme::default().status_style(theme.list.status);
+
+    let filter_form = FilterForm::builder()
+        .theme(FilterFormTheme::from(theme.clone()))
+        .build();
+
+    let select_form = SelectForm::builder()
+        .theme(SelectFormTheme::from(theme.clone()))
+        .on_select(on_select)
+        .build();
+
+    let widget_base = WidgetBase::builder()
+        .title(title)
+        .theme(WidgetTheme::from(theme))
+        .build();
+
+    SingleSelect::builder()
+        .id(id)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .select_form(select_form)
+        .theme(single_select_theme)
+        .build()
+        .into()
+}
+
+fn view_widget(
+    tx: Sender<Message>,
+    view: SharedHelmView,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    single_select_widget(
+        HELM_VIEW_DIALOG_ID,
+        "View",
+        theme,
+        move |w: &mut Window, v: &LiteralItem| {
+            let Some(selected) = HelmView::ALL
+                .into_iter()
+                .find(|view| v.item.trim_end_matches(" (current)") == view.to_string())
+            else {
+                return EventResult::Ignore;
+            };
+
+            *view.borrow_mut() = selected;
+
+            w.close_dialog();
+
+            let Some(metadata) = selected_release(w) else {
+                return EventResult::Nop;
+            };
+
+            show_release(w, &tx, &metadata, selected)
+        },
+    )
+}
+
+fn diff_widget(tx: Sender<Message>, theme: WidgetThemeConfig) -> Widget<'static> {
+    single_select_widget(
+        HELM_DIFF_DIALOG_ID,
+        "Diff",
+        theme,
+        move |w: &mut Window, v: &LiteralItem| {
+            let Some(ref metadata) = v.metadata else {
+                return EventResult::Ignore;
+            };
+
+            let (Some(target), Some(view), Some(from), Some(to)) = (
+                release_target(metadata),
+                metadata
+                    .get("view")
+                    .and_then(|view| HelmView::ALL.into_iter().find(|v| v.to_string() == *view)),
+                metadata.get("from").and_then(|from| from.parse().ok()),
+                metadata.get("to").and_then(|to| to.parse().ok()),
+            ) else {
+                return EventResult::Ignore;
+            };
+
+            w.close_dialog();
+
+            w.widget_clear(HELM_CONTENT_WIDGET_ID);
+
+            *w.find_widget_mut(HELM_CONTENT_WIDGET_ID)
+                .widget_base_mut()
+                .append_title_mut() = Some(
+                format!(
+                    " : {} revision {} -> {} ({} diff)",
+                    target.name, from, to, view
+                )
+                .into(),
+            );
+
+            tx.send(
+                HelmRequest::Diff {
+                    target,
+                    view,
+                    from,
+                    to,
+                }
+                .into(),
+            )
+            .expect("Failed to send HelmRequest::Diff");
+
+            EventResult::Nop
+        },
+    )
+}
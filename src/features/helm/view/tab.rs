@@ -0,0 +1,60 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossbeam::channel::Sender;
+use ratatui::layout::{Constraint, Direction};
+
+use crate::{
+    clipboard::Clipboard,
+    config::{keybindings::HelmKeyBindings, theme::WidgetThemeConfig},
+    features::{
+        component_id::HELM_TAB_ID,
+        helm::view::{
+            widgets::{content_widget, release_widget},
+            HelmDialogs, SharedHelmView,
+        },
+    },
+    message::Message,
+    ui::{
+        tab::{LayoutElement, NestedLayoutElement, NestedWidgetLayout, TabLayout},
+        Tab,
+    },
+};
+
+pub struct HelmTab {
+    pub tab: Tab<'static>,
+    pub dialogs: HelmDialogs,
+}
+
+impl HelmTab {
+    pub fn new(
+        title: &'static str,
+        tx: &Sender<Message>,
+        clipboard: &Option<Rc<RefCell<Clipboard>>>,
+        split_direction: Direction,
+        keybindings: HelmKeyBindings,
+        theme: WidgetThemeConfig,
+    ) -> Self {
+        let view = SharedHelmView::default();
+
+        let dialogs = HelmDialogs::new(tx, view.clone(), theme.clone());
+
+        let release_widget = release_widget(tx, keybindings, view, &dialogs, theme.clone());
+        let content_widget = content_widget(clipboard, theme);
+
+        let layout = TabLayout::new(layout, split_direction);
+
+        HelmTab {
+            tab: Tab::new(HELM_TAB_ID, title, [release_widget, content_widget], layout),
+            dialogs,
+        }
+    }
+}
+
+fn layout(split_direction: Direction) -> NestedWidgetLayout {
+    NestedWidgetLayout::default()
+        .direction(split_direction)
+        .nested_widget_layout([
+            NestedLayoutElement(Constraint::Percentage(50), LayoutElement::WidgetIndex(0)),
+            NestedLayoutElement(Constraint::Percentage(50), LayoutElement::WidgetIndex(1)),
+        ])
+}
@@ -0,0 +1,5 @@
+mod content;
+mod release;
+
+pub(super) use content::*;
+pub(super) use release::*;
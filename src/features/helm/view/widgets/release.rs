@@ -0,0 +1,83 @@
+use crossbeam::channel::Sender;
+
+use crate::{
+    config::{keybindings::HelmKeyBindings, theme::WidgetThemeConfig},
+    features::{
+        component_id::HELM_WIDGET_ID,
+        helm::view::{dialogs::show_release, HelmDialogs, SharedHelmView},
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            FilterForm, FilterFormTheme, Table, TableItem, TableTheme, Widget, WidgetBase,
+            WidgetTheme, WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+pub fn release_widget(
+    tx: &Sender<Message>,
+    keybindings: HelmKeyBindings,
+    view: SharedHelmView,
+    dialogs: &HelmDialogs,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    let tx = tx.clone();
+
+    let widget_theme = WidgetTheme::from(theme.clone());
+    let filter_theme = FilterFormTheme::from(theme.clone());
+    let table_theme = TableTheme::from(theme);
+
+    let widget_base = WidgetBase::builder()
+        .title("Helm")
+        .theme(widget_theme)
+        .build();
+
+    let filter_form = FilterForm::builder().theme(filter_theme).build();
+
+    Table::builder()
+        .id(HELM_WIDGET_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .theme(table_theme)
+        .filtered_key("NAME")
+        .block_injection(block_injection())
+        .on_select(on_select(tx, view))
+        .key_action(&keybindings.select_view, dialogs.open_view())
+        .key_action(&keybindings.diff, dialogs.open_diff())
+        .build()
+        .into()
+}
+
+fn block_injection() -> impl Fn(&Table) -> WidgetBase {
+    |table: &Table| {
+        let index = if let Some(index) = table.state().selected() {
+            index + 1
+        } else {
+            0
+        };
+
+        let mut base = table.widget_base().clone();
+
+        *base.append_title_mut() = Some(format!(" [{}/{}]", index, table.items().len()).into());
+
+        base
+    }
+}
+
+fn on_select(
+    tx: Sender<Message>,
+    view: SharedHelmView,
+) -> impl Fn(&mut Window, &TableItem) -> EventResult {
+    move |w: &mut Window, v: &TableItem| {
+        let Some(ref metadata) = v.metadata else {
+            return EventResult::Ignore;
+        };
+
+        let view = *view.borrow();
+
+        show_release(w, &tx, metadata, view)
+    }
+}
@@ -1,230 +1,263 @@
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::{
+    crossterm::event::KeyCode,
+    style::{Color, Modifier, Style},
+};
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
     ansi::{AnsiEscapeSequence, TextParser},
-    config::theme::ThemeConfig,
+    config::{keybindings::KeyBindingsConfig, theme::ThemeConfig},
     features::component_id::HELP_DIALOG_ID,
-    ui::widget::{
-        ansi_color::style_to_ansi, SearchForm, SearchFormTheme, Text, TextTheme, Widget,
-        WidgetBase, WidgetTheme,
+    ui::{
+        key::{Key, KeyBinding, KeyMap},
+        widget::{
+            ansi_color::style_to_ansi, SearchForm, SearchFormTheme, Text, TextTheme, Widget,
+            WidgetBase, WidgetTheme,
+        },
     },
 };
 
-const LEFT_HELP_TEXT: &[HelpBlock] = &[
-    HelpBlock {
-        title: "General",
-        bindings: &[
-            KeyBindings {
-                keys: &["0~6"],
-                desc: "switch tab",
-            },
-            KeyBindings {
-                keys: &["Enter"],
-                desc: "select",
-            },
-            KeyBindings {
-                keys: &["c"],
-                desc: "change context",
-            },
-            KeyBindings {
-                keys: &["n"],
-                desc: "select namespace",
-            },
-            KeyBindings {
-                keys: &["N"],
-                desc: "select namespaces",
-            },
-            KeyBindings {
-                keys: &["Tab"],
-                desc: "change focus",
-            },
-            KeyBindings {
-                keys: &["y"],
-                desc: "open yaml dialog",
-            },
-            KeyBindings {
-                keys: &["q", "Esc"],
-                desc: "quit",
-            },
-            KeyBindings {
-                keys: &["q", "Esc"],
-                desc: "close dialog",
-            },
-            KeyBindings {
-                keys: &["h", "?"],
-                desc: "Show this help",
-            },
-        ],
-    },
-    HelpBlock {
-        title: "View Control",
-        bindings: &[
-            KeyBindings {
-                keys: &["j", "k", "Down", "Up"],
-                desc: "goto next/previous line",
-            },
-            KeyBindings {
-                keys: &["PgDn", "PgUp"],
-                desc: "scroll upward/downward",
-            },
-            KeyBindings {
-                keys: &["Left", "Right"],
-                desc: "scroll horizontal",
-            },
-            KeyBindings {
-                keys: &["g"],
-                desc: "goto first line",
-            },
-            KeyBindings {
-                keys: &["G"],
-                desc: "goto last line",
-            },
-        ],
-    },
-    HelpBlock {
-        title: "Remap Keys",
-        bindings: &[
-            KeyBindings {
-                keys: &["Ctrl-p"],
-                desc: "↑",
-            },
-            KeyBindings {
-                keys: &["Ctrl-n"],
-                desc: "↓",
-            },
-            KeyBindings {
-                keys: &["Ctrl-f"],
-                desc: "→",
-            },
-            KeyBindings {
-                keys: &["Ctrl-b"],
-                desc: "←",
-            },
-            KeyBindings {
-                keys: &["Ctrl-u"],
-                desc: "PgUp",
-            },
-            KeyBindings {
-                keys: &["Ctrl-d"],
-                desc: "PgDn",
-            },
-            KeyBindings {
-                keys: &["Ctrl-h", "BS"],
-                desc: "Del",
-            },
-            KeyBindings {
-                keys: &["Ctrl-a"],
-                desc: "Home",
-            },
-            KeyBindings {
-                keys: &["Ctrl-e"],
-                desc: "End",
-            },
-            KeyBindings {
-                keys: &["Ctrl-["],
-                desc: "Esc",
-            },
-        ],
-    },
-];
-
-const RIGHT_HELP_TEXT: &[HelpBlock] = &[
-    HelpBlock {
-        title: "Input Form",
-        bindings: &[
-            KeyBindings {
-                keys: &["Ctrl-a", "Home"],
-                desc: "move the cursor to the first",
-            },
-            KeyBindings {
-                keys: &["Ctrl-e", "End"],
-                desc: "move the cursor to the end",
-            },
-            KeyBindings {
-                keys: &["Ctrl-f", "Right"],
-                desc: "move the cursor to the right",
-            },
-            KeyBindings {
-                keys: &["Ctrl-b", "Left"],
-                desc: "move the cursor to the left",
-            },
-            KeyBindings {
-                keys: &["Ctrl-w"],
-                desc: "delete the text from the cursor position to the first",
-            },
-            KeyBindings {
-                keys: &["Ctrl-k"],
-                desc: "delete the text from the cursor position to the end",
-            },
-        ],
-    },
-    HelpBlock {
-        title: "API / Yaml Tab",
-        bindings: &[KeyBindings {
-            keys: &["f"],
-            desc: "open select dialog",
-        }],
-    },
-    HelpBlock {
-        title: "Search (Only text view)",
-        bindings: &[
-            KeyBindings {
-                keys: &["/"],
-                desc: "enable search mode",
-            },
-            KeyBindings {
-                keys: &["q", "Esc"],
-                desc: "disable search mode",
-            },
-            KeyBindings {
-                keys: &["Enter"],
-                desc: "confirm search word",
-            },
-            KeyBindings {
-                keys: &["n", "N"],
-                desc: "goto next/prev word",
-            },
-        ],
-    },
-    HelpBlock {
-        title: "Filter (Only table view)",
-        bindings: &[
-            KeyBindings {
-                keys: &["/"],
-                desc: "open filter form",
-            },
-            KeyBindings {
-                keys: &["q", "Esc"],
-                desc: "clear filter form",
-            },
-            KeyBindings {
-                keys: &["Enter"],
-                desc: "confirm filter word",
-            },
-        ],
-    },
-    HelpBlock {
-        title: "Log",
-        bindings: &[
-            KeyBindings {
-                keys: &["Enter"],
-                desc: "insert blank line",
-            },
-            KeyBindings {
-                keys: &["f", "p"],
-                desc: "toggle json pretty print",
-            },
-        ],
-    },
-];
+fn left_help_text(keys: &KeyBindingsConfig) -> Vec<HelpBlock> {
+    let global = &keys.global;
+    let widget = &keys.widget;
+
+    vec![
+        HelpBlock {
+            title: "General",
+            bindings: vec![
+                KeyBindings::literal(&["1~7"], "switch tab"),
+                KeyBindings::literal(&["Enter"], "select"),
+                KeyBindings::new(&global.select_context, "change context"),
+                KeyBindings::new(&global.open_namespace_dialog, "select namespace"),
+                KeyBindings::new(&global.open_multiple_namespaces_dialog, "select namespaces"),
+                KeyBindings::new(&widget.next_widget, "change focus"),
+                KeyBindings::new(&global.open_yaml, "open yaml dialog"),
+                KeyBindings::new(&global.open_port_forwards, "open port-forwards dialog"),
+                KeyBindings::new(
+                    &global.open_resource_action_menu,
+                    "open action menu (delete / restart / scale / cordon)",
+                ),
+                KeyBindings::new(&global.toggle_split_direction, "toggle split direction"),
+                KeyBindings::new(&global.quit, "quit"),
+                KeyBindings::new(&global.quit, "close dialog"),
+                KeyBindings::new(&global.open_help, "Show this help"),
+            ],
+        },
+        HelpBlock {
+            title: "View Control",
+            bindings: vec![
+                KeyBindings {
+                    keys: [&widget.down, &widget.up]
+                        .iter()
+                        .flat_map(|binding| binding.keys().iter().map(ToString::to_string))
+                        .chain(["Down".to_string(), "Up".to_string()])
+                        .collect(),
+                    desc: "goto next/previous line".to_string(),
+                },
+                KeyBindings::literal(&["PgDn", "PgUp"], "scroll upward/downward"),
+                KeyBindings::literal(&["Left", "Right"], "scroll horizontal"),
+                KeyBindings::new(&widget.first, "goto first line"),
+                KeyBindings::new(&widget.last, "goto last line"),
+            ],
+        },
+        HelpBlock {
+            title: "Remap Keys",
+            bindings: remap_key_bindings(&keys.key_map),
+        },
+    ]
+}
+
+/// 変換先のキーが同じものは1行にまとめる
+fn remap_key_bindings(key_map: &KeyMap) -> Vec<KeyBindings> {
+    let mut bindings: Vec<(Key, Vec<String>)> = Vec::new();
+
+    for (from, to) in key_map.entries() {
+        match bindings.iter_mut().find(|(key, _)| *key == to) {
+            Some((_, keys)) => keys.push(from.to_string()),
+            None => bindings.push((to, vec![from.to_string()])),
+        }
+    }
+
+    bindings
+        .into_iter()
+        .map(|(to, keys)| KeyBindings {
+            keys,
+            desc: remap_key_desc(to),
+        })
+        .collect()
+}
+
+fn remap_key_desc(key: Key) -> String {
+    match key.code() {
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        _ => key.to_string(),
+    }
+}
+
+fn right_help_text(keys: &KeyBindingsConfig) -> Vec<HelpBlock> {
+    let widget = &keys.widget;
+
+    vec![
+        HelpBlock {
+            title: "Input Form",
+            bindings: vec![
+                KeyBindings::literal(&["Ctrl-a", "Home"], "move the cursor to the first"),
+                KeyBindings::literal(&["Ctrl-e", "End"], "move the cursor to the end"),
+                KeyBindings::literal(&["Ctrl-f", "Right"], "move the cursor to the right"),
+                KeyBindings::literal(&["Ctrl-b", "Left"], "move the cursor to the left"),
+                KeyBindings::literal(
+                    &["Ctrl-w"],
+                    "delete the text from the cursor position to the first",
+                ),
+                KeyBindings::literal(
+                    &["Ctrl-k"],
+                    "delete the text from the cursor position to the end",
+                ),
+            ],
+        },
+        HelpBlock {
+            title: "API / Yaml Tab",
+            bindings: vec![
+                KeyBindings::literal(&["f"], "open select dialog"),
+                KeyBindings::literal(&["Enter"], "show yaml of the selected row (API)"),
+            ],
+        },
+        HelpBlock {
+            title: "Search (Only text view)",
+            bindings: vec![
+                KeyBindings::new(&widget.search, "enable search mode"),
+                KeyBindings::with_esc(&widget.cancel, "disable search mode"),
+                KeyBindings::literal(&["Enter"], "confirm search word"),
+                KeyBindings {
+                    keys: [&widget.next_match, &widget.prev_match]
+                        .iter()
+                        .flat_map(|binding| binding.keys().iter().map(ToString::to_string))
+                        .collect(),
+                    desc: "goto next/prev word".to_string(),
+                },
+            ],
+        },
+        HelpBlock {
+            title: "Filter (Only table view)",
+            bindings: vec![
+                KeyBindings::new(&widget.search, "open filter form"),
+                KeyBindings::with_esc(&widget.cancel, "clear filter form"),
+                KeyBindings::literal(&["Enter"], "confirm filter word"),
+            ],
+        },
+        HelpBlock {
+            title: "Pod",
+            bindings: vec![
+                KeyBindings::new(&keys.pod.exec, "exec into container"),
+                KeyBindings::new(&keys.pod.port_forward, "start port-forward"),
+                KeyBindings::new(&keys.pod.clear_node_filter, "clear node filter"),
+            ],
+        },
+        HelpBlock {
+            title: "Config",
+            bindings: vec![
+                KeyBindings::new(
+                    &keys.config.toggle_decode,
+                    "toggle decoded / raw secret data",
+                ),
+                KeyBindings::new(&keys.config.reveal, "reveal / mask secret values"),
+            ],
+        },
+        HelpBlock {
+            title: "Network (Pod / Service)",
+            bindings: vec![
+                KeyBindings::new(&keys.network.port_forward, "start port-forward"),
+                KeyBindings::new(
+                    &keys.network.reachability,
+                    "check reachability from pod (NetworkPolicy)",
+                ),
+            ],
+        },
+        HelpBlock {
+            title: "Node",
+            bindings: vec![KeyBindings::new(
+                &keys.node.show_pods,
+                "show pods on the node",
+            )],
+        },
+        HelpBlock {
+            title: "Workload",
+            bindings: vec![
+                KeyBindings::new(
+                    &keys.workload.show_logs,
+                    "show logs of the workload in pod tab",
+                ),
+                KeyBindings::new(
+                    &keys.workload.rollout_history,
+                    "open rollout history (Deployment)",
+                ),
+                KeyBindings::new(&keys.workload.job_history, "open job history (CronJob)"),
+            ],
+        },
+        HelpBlock {
+            title: "Helm",
+            bindings: vec![
+                KeyBindings::new(
+                    &keys.helm.select_view,
+                    "select values, computed values, manifest or notes",
+                ),
+                KeyBindings::new(&keys.helm.diff, "diff values or manifest between revisions"),
+            ],
+        },
+        HelpBlock {
+            title: "Port Forwards",
+            bindings: vec![KeyBindings::new(
+                &keys.port_forward.stop,
+                "stop port-forward",
+            )],
+        },
+        HelpBlock {
+            title: "Log",
+            bindings: vec![
+                KeyBindings::new(&keys.log.insert_blank_line, "insert blank line"),
+                KeyBindings::new(&keys.log.toggle_json, "toggle json pretty print"),
+                KeyBindings::new(&keys.log.save, "save log to file"),
+                KeyBindings::new(&keys.log.toggle_tee, "start/stop writing log to file (tee)"),
+                KeyBindings::new(&keys.log.open_saved_queries, "select saved log query"),
+            ],
+        },
+    ]
+}
 
 struct KeyBindings {
-    keys: &'static [&'static str],
-    desc: &'static str,
+    keys: Vec<String>,
+    desc: String,
 }
 
 impl KeyBindings {
+    fn new(binding: &KeyBinding, desc: &str) -> Self {
+        Self {
+            keys: binding.keys().iter().map(ToString::to_string).collect(),
+            desc: desc.to_string(),
+        }
+    }
+
+    /// 設定で変更できないキー
+    fn literal(keys: &[&str], desc: &str) -> Self {
+        Self {
+            keys: keys.iter().map(ToString::to_string).collect(),
+            desc: desc.to_string(),
+        }
+    }
+
+    /// Escキーでも同じ操作ができるもの
+    fn with_esc(binding: &KeyBinding, desc: &str) -> Self {
+        let mut bindings = Self::new(binding, desc);
+
+        bindings.keys.push("Esc".to_string());
+
+        bindings
+    }
+
     fn keys(&self) -> String {
         self.keys.join(" ")
     }
@@ -234,10 +267,9 @@ impl KeyBindings {
     }
 }
 
-#[derive(Clone)]
 struct HelpBlock {
     title: &'static str,
-    bindings: &'static [KeyBindings],
+    bindings: Vec<KeyBindings>,
 }
 
 fn print_help_block(block: &HelpBlock, theme: &HelpItemTheme) -> Vec<String> {
@@ -249,15 +281,20 @@ fn print_help_block(block: &HelpBlock, theme: &HelpItemTheme) -> Vec<String> {
         block.title
     ));
 
-    let max_key_len = block
+    // キーが割り当てられていない操作は表示しない
+    let bindings: Vec<&KeyBindings> = block
         .bindings
+        .iter()
+        .filter(|b| !b.keys.is_empty())
+        .collect();
+
+    let max_key_len = bindings
         .iter()
         .map(|b| b.keys().width())
         .max()
-        .expect("no bindings");
+        .unwrap_or_default();
 
-    let lines: Vec<String> = block
-        .bindings
+    let lines: Vec<String> = bindings
         .iter()
         .map(|b| {
             format!(
@@ -287,10 +324,10 @@ fn print_help_blocks(blocks: &[HelpBlock], theme: &HelpItemTheme) -> Vec<String>
         .collect()
 }
 
-fn generate(theme: HelpItemTheme) -> Vec<String> {
-    let mut left = print_help_blocks(LEFT_HELP_TEXT, &theme);
+fn generate(keys: &KeyBindingsConfig, theme: HelpItemTheme) -> Vec<String> {
+    let mut left = print_help_blocks(&left_help_text(keys), &theme);
 
-    let mut right = print_help_blocks(RIGHT_HELP_TEXT, &theme);
+    let mut right = print_help_blocks(&right_help_text(keys), &theme);
 
     let len = left.len().max(right.len());
 
@@ -348,7 +385,7 @@ pub struct HelpDialog {
 }
 
 impl HelpDialog {
-    pub fn new(theme: ThemeConfig) -> Self {
+    pub fn new(keys: &KeyBindingsConfig, theme: ThemeConfig) -> Self {
         let widget_theme = WidgetTheme::from(theme.component.clone());
         let text_theme = TextTheme::from(theme.component.clone());
         let search_theme = SearchFormTheme::from(theme.component.clone());
@@ -368,7 +405,7 @@ impl HelpDialog {
                 .widget_base(widget_base)
                 .search_form(search_form)
                 .theme(text_theme)
-                .items(generate(item_theme))
+                .items(generate(keys, item_theme))
                 .build()
                 .into(),
         }
@@ -5,8 +5,10 @@ use crate::{
     features::{
         component_id::{
             API_WIDGET_ID, CONFIG_RAW_DATA_WIDGET_ID, CONFIG_WIDGET_ID, EVENT_WIDGET_ID,
-            MULTIPLE_NAMESPACES_DIALOG_ID, NETWORK_DESCRIPTION_WIDGET_ID, NETWORK_WIDGET_ID,
-            POD_LOG_QUERY_WIDGET_ID, POD_LOG_WIDGET_ID, POD_WIDGET_ID, YAML_WIDGET_ID,
+            HELM_CONTENT_WIDGET_ID, HELM_WIDGET_ID, MULTIPLE_NAMESPACES_DIALOG_ID,
+            NETWORK_DESCRIPTION_WIDGET_ID, NETWORK_WIDGET_ID, POD_LOG_QUERY_WIDGET_ID,
+            POD_LOG_WIDGET_ID, POD_WIDGET_ID, WORKLOAD_DESCRIPTION_WIDGET_ID, WORKLOAD_WIDGET_ID,
+            YAML_WIDGET_ID,
         },
         namespace::message::NamespaceRequest,
     },
@@ -93,6 +95,10 @@ fn on_select(
         w.widget_clear(CONFIG_RAW_DATA_WIDGET_ID);
         w.widget_clear(NETWORK_WIDGET_ID);
         w.widget_clear(NETWORK_DESCRIPTION_WIDGET_ID);
+        w.widget_clear(WORKLOAD_WIDGET_ID);
+        w.widget_clear(WORKLOAD_DESCRIPTION_WIDGET_ID);
+        w.widget_clear(HELM_WIDGET_ID);
+        w.widget_clear(HELM_CONTENT_WIDGET_ID);
         w.widget_clear(EVENT_WIDGET_ID);
         w.widget_clear(API_WIDGET_ID);
         w.widget_clear(YAML_WIDGET_ID);
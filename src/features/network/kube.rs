@@ -1,5 +1,7 @@
 mod description;
 mod network;
+mod reachability;
 
 pub use description::*;
 pub use network::*;
+pub use reachability::*;
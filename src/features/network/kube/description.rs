@@ -3,11 +3,13 @@ mod httproute;
 mod ingress;
 mod network_policy;
 mod pod;
+mod reference_grant;
+mod route;
 mod service;
 mod utils;
 
 #[allow(dead_code)]
-mod related_resources;
+pub(super) mod related_resources;
 
 use crate::{
     features::{
@@ -21,9 +23,17 @@ use crate::{
 };
 
 use self::{
-    gateway::GatewayDescriptionWorker, httproute::HTTPRouteDescriptionWorker,
-    ingress::IngressDescriptionWorker, network_policy::NetworkPolicyDescriptionWorker,
-    pod::PodDescriptionWorker, service::ServiceDescriptionWorker,
+    gateway::GatewayDescriptionWorker,
+    httproute::HTTPRouteDescriptionWorker,
+    ingress::IngressDescriptionWorker,
+    network_policy::NetworkPolicyDescriptionWorker,
+    pod::PodDescriptionWorker,
+    reference_grant::ReferenceGrantDescriptionWorker,
+    route::{
+        GRPCRouteDescriptionWorker, TCPRouteDescriptionWorker, TLSRouteDescriptionWorker,
+        UDPRouteDescriptionWorker,
+    },
+    service::ServiceDescriptionWorker,
 };
 
 use anyhow::Result;
@@ -104,6 +114,26 @@ where
                 self.fetch_description::<HTTPRouteDescriptionWorker<C>>()
                     .await
             }
+            NetworkRequest::GRPCRoute(_) => {
+                self.fetch_description::<GRPCRouteDescriptionWorker<C>>()
+                    .await
+            }
+            NetworkRequest::TCPRoute(_) => {
+                self.fetch_description::<TCPRouteDescriptionWorker<C>>()
+                    .await
+            }
+            NetworkRequest::TLSRoute(_) => {
+                self.fetch_description::<TLSRouteDescriptionWorker<C>>()
+                    .await
+            }
+            NetworkRequest::UDPRoute(_) => {
+                self.fetch_description::<UDPRouteDescriptionWorker<C>>()
+                    .await
+            }
+            NetworkRequest::ReferenceGrant(_) => {
+                self.fetch_description::<ReferenceGrantDescriptionWorker<C>>()
+                    .await
+            }
         };
 
         if let Err(e) = ret {
@@ -0,0 +1,334 @@
+use anyhow::{Context as _, Result};
+use kube::{Api, Client, ResourceExt as _};
+use serde::Serialize;
+
+use crate::{
+    features::{
+        api_resources::kube::SharedApiResources,
+        network::message::{NetworkRequestTargetParams, ReferenceGrantVersion},
+    },
+    kube::{
+        apis::networking::gateway::{v1alpha2, v1beta1},
+        KubeClientRequest,
+    },
+};
+
+use super::{Fetch, FetchedData};
+
+/// バージョンによらずReferenceGrantを扱うための型
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct ReferenceGrant {
+    #[serde(skip)]
+    pub name: String,
+    pub from: Vec<ReferenceGrantFrom>,
+    pub to: Vec<ReferenceGrantTo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct ReferenceGrantFrom {
+    pub group: String,
+    pub kind: String,
+    pub namespace: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct ReferenceGrantTo {
+    pub group: String,
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl From<v1beta1::ReferenceGrant> for ReferenceGrant {
+    fn from(value: v1beta1::ReferenceGrant) -> Self {
+        Self {
+            name: value.name_any(),
+            from: value
+                .spec
+                .from
+                .into_iter()
+                .map(|f| ReferenceGrantFrom {
+                    group: f.group,
+                    kind: f.kind,
+                    namespace: f.namespace,
+                })
+                .collect(),
+            to: value
+                .spec
+                .to
+                .into_iter()
+                .map(|t| ReferenceGrantTo {
+                    group: t.group,
+                    kind: t.kind,
+                    name: t.name,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<v1alpha2::ReferenceGrant> for ReferenceGrant {
+    fn from(value: v1alpha2::ReferenceGrant) -> Self {
+        Self {
+            name: value.name_any(),
+            from: value
+                .spec
+                .from
+                .into_iter()
+                .map(|f| ReferenceGrantFrom {
+                    group: f.group,
+                    kind: f.kind,
+                    namespace: f.namespace,
+                })
+                .collect(),
+            to: value
+                .spec
+                .to
+                .into_iter()
+                .map(|t| ReferenceGrantTo {
+                    group: t.group,
+                    kind: t.kind,
+                    name: t.name,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 別のnamespaceにあるリソースへの参照
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Reference<'a> {
+    pub from_group: &'a str,
+    pub from_kind: &'a str,
+    pub from_namespace: &'a str,
+    pub to_group: &'a str,
+    pub to_kind: &'a str,
+    pub to_name: &'a str,
+}
+
+/// 参照先のnamespaceにあるReferenceGrantから、参照を許可しているものの名前を返す
+pub(super) fn find_permitting_grant<'a>(
+    grants: &'a [ReferenceGrant],
+    reference: &Reference,
+) -> Option<&'a str> {
+    grants
+        .iter()
+        .find(|grant| {
+            let from = grant.from.iter().any(|from| {
+                from.group == reference.from_group
+                    && from.kind == reference.from_kind
+                    && from.namespace == reference.from_namespace
+            });
+
+            let to = grant.to.iter().any(|to| {
+                to.group == reference.to_group
+                    && to.kind == reference.to_kind
+                    && to
+                        .name
+                        .as_ref()
+                        .is_none_or(|name| name == reference.to_name)
+            });
+
+            from && to
+        })
+        .map(|grant| grant.name.as_str())
+}
+
+/// namespaceにあるReferenceGrantを取得する
+pub(super) async fn fetch_reference_grants(
+    client: Client,
+    namespace: &str,
+    version: ReferenceGrantVersion,
+) -> Result<Vec<ReferenceGrant>> {
+    let grants = match version {
+        ReferenceGrantVersion::V1Beta1 => {
+            Api::<v1beta1::ReferenceGrant>::namespaced(client, namespace)
+                .list(&Default::default())
+                .await?
+                .into_iter()
+                .map(ReferenceGrant::from)
+                .collect()
+        }
+        ReferenceGrantVersion::V1Alpha2 => {
+            Api::<v1alpha2::ReferenceGrant>::namespaced(client, namespace)
+                .list(&Default::default())
+                .await?
+                .into_iter()
+                .map(ReferenceGrant::from)
+                .collect()
+        }
+    };
+
+    Ok(grants)
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Description {
+    referencegrant: DescriptionReferenceGrant,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DescriptionReferenceGrant {
+    metadata: MetadataName,
+    spec: ReferenceGrant,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct MetadataName {
+    name: String,
+}
+
+pub(super) struct ReferenceGrantDescriptionWorker<'a, C>
+where
+    C: KubeClientRequest,
+{
+    client: &'a C,
+    namespace: String,
+    name: String,
+    version: String,
+}
+
+#[async_trait::async_trait]
+impl<'a, C> Fetch<'a, C> for ReferenceGrantDescriptionWorker<'a, C>
+where
+    C: KubeClientRequest,
+{
+    fn new(client: &'a C, params: NetworkRequestTargetParams, _: SharedApiResources) -> Self {
+        let NetworkRequestTargetParams {
+            namespace,
+            name,
+            version,
+        } = params;
+
+        Self {
+            client,
+            namespace,
+            name,
+            version,
+        }
+    }
+
+    async fn fetch(&self) -> Result<FetchedData> {
+        let client = self.client.client().clone();
+
+        let context = || {
+            format!(
+                "Failed to fetch ReferenceGrant: namespace={}, name={}",
+                self.namespace, self.name
+            )
+        };
+
+        let grant: ReferenceGrant = match self.version.parse() {
+            Ok(ReferenceGrantVersion::V1Beta1) => {
+                Api::<v1beta1::ReferenceGrant>::namespaced(client, &self.namespace)
+                    .get(&self.name)
+                    .await
+                    .with_context(context)?
+                    .into()
+            }
+            Ok(ReferenceGrantVersion::V1Alpha2) => {
+                Api::<v1alpha2::ReferenceGrant>::namespaced(client, &self.namespace)
+                    .get(&self.name)
+                    .await
+                    .with_context(context)?
+                    .into()
+            }
+            Err(_) => {
+                unreachable!()
+            }
+        };
+
+        let description = Description {
+            referencegrant: DescriptionReferenceGrant {
+                metadata: MetadataName {
+                    name: grant.name.clone(),
+                },
+                spec: grant,
+            },
+        };
+
+        let yaml = serde_yaml::to_string(&description)?
+            .lines()
+            .map(ToString::to_string)
+            .collect();
+
+        Ok(yaml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn grant(to_name: Option<&str>) -> ReferenceGrant {
+        ReferenceGrant {
+            name: "allow-tcproute".into(),
+            from: vec![ReferenceGrantFrom {
+                group: "gateway.networking.k8s.io".into(),
+                kind: "TCPRoute".into(),
+                namespace: "app".into(),
+            }],
+            to: vec![ReferenceGrantTo {
+                group: "".into(),
+                kind: "Service".into(),
+                name: to_name.map(ToString::to_string),
+            }],
+        }
+    }
+
+    fn reference<'a>(
+        from_kind: &'a str,
+        from_namespace: &'a str,
+        to_name: &'a str,
+    ) -> Reference<'a> {
+        Reference {
+            from_group: "gateway.networking.k8s.io",
+            from_kind,
+            from_namespace,
+            to_group: "",
+            to_kind: "Service",
+            to_name,
+        }
+    }
+
+    #[rstest]
+    #[case::名前を指定していないとき全てのserviceを許可する(
+        None,
+        reference("TCPRoute", "app", "backend"),
+        Some("allow-tcproute")
+    )]
+    #[case::名前を指定しているとき一致するserviceを許可する(
+        Some("backend"),
+        reference("TCPRoute", "app", "backend"),
+        Some("allow-tcproute")
+    )]
+    #[case::名前が一致しないとき許可しない(
+        Some("other"),
+        reference("TCPRoute", "app", "backend"),
+        None
+    )]
+    #[case::参照元のkindが一致しないとき許可しない(
+        None,
+        reference("UDPRoute", "app", "backend"),
+        None
+    )]
+    #[case::参照元のnamespaceが一致しないとき許可しない(
+        None,
+        reference("TCPRoute", "other", "backend"),
+        None
+    )]
+    fn 参照を許可するreference_grantを返す(
+        #[case] to_name: Option<&str>,
+        #[case] reference: Reference,
+        #[case] expected: Option<&str>,
+    ) {
+        let grants = vec![grant(to_name)];
+
+        let actual = find_permitting_grant(&grants, &reference);
+
+        assert_eq!(actual, expected);
+    }
+}
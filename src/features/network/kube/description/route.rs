@@ -0,0 +1,358 @@
+mod related_resource;
+
+use std::marker::PhantomData;
+
+use anyhow::{Context as _, Result};
+use kube::{Api, ResourceExt as _};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    features::{
+        api_resources::kube::SharedApiResources,
+        network::{kube::network::reference_grant_version, message::NetworkRequestTargetParams},
+    },
+    kube::{
+        apis::networking::gateway::v1alpha2::{GRPCRoute, TCPRoute, TLSRoute, UDPRoute},
+        informer::InformerResource,
+        KubeClientRequest,
+    },
+};
+
+use self::related_resource::discover_related_resources;
+
+use super::{Fetch, FetchedData};
+
+pub(super) type GRPCRouteDescriptionWorker<'a, C> = RouteDescriptionWorker<'a, C, GRPCRoute>;
+pub(super) type TCPRouteDescriptionWorker<'a, C> = RouteDescriptionWorker<'a, C, TCPRoute>;
+pub(super) type TLSRouteDescriptionWorker<'a, C> = RouteDescriptionWorker<'a, C, TLSRoute>;
+pub(super) type UDPRouteDescriptionWorker<'a, C> = RouteDescriptionWorker<'a, C, UDPRoute>;
+
+/// parentRefやbackendRefで参照しているリソース
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct RouteRef {
+    pub group: Option<String>,
+    pub kind: Option<String>,
+    pub name: String,
+    pub namespace: Option<String>,
+}
+
+impl RouteRef {
+    fn new(
+        group: &Option<String>,
+        kind: &Option<String>,
+        name: &str,
+        namespace: &Option<String>,
+    ) -> Self {
+        Self {
+            group: group.clone(),
+            kind: kind.clone(),
+            name: name.to_string(),
+            namespace: namespace.clone(),
+        }
+    }
+}
+
+/// v1alpha2のRoute（GRPCRoute・TCPRoute・TLSRoute・UDPRoute）を共通して扱うためのトレイト
+pub(super) trait Route: InformerResource + Serialize {
+    fn parent_refs(&self) -> Vec<RouteRef>;
+
+    fn backend_refs(&self) -> Vec<RouteRef>;
+}
+
+impl Route for GRPCRoute {
+    fn parent_refs(&self) -> Vec<RouteRef> {
+        self.spec
+            .parent_refs
+            .iter()
+            .flatten()
+            .map(|r| RouteRef::new(&r.group, &r.kind, &r.name, &r.namespace))
+            .collect()
+    }
+
+    fn backend_refs(&self) -> Vec<RouteRef> {
+        self.spec
+            .rules
+            .iter()
+            .flatten()
+            .flat_map(|rule| rule.backend_refs.iter().flatten())
+            .map(|r| RouteRef::new(&r.group, &r.kind, &r.name, &r.namespace))
+            .collect()
+    }
+}
+
+impl Route for TCPRoute {
+    fn parent_refs(&self) -> Vec<RouteRef> {
+        self.spec
+            .parent_refs
+            .iter()
+            .flatten()
+            .map(|r| RouteRef::new(&r.group, &r.kind, &r.name, &r.namespace))
+            .collect()
+    }
+
+    fn backend_refs(&self) -> Vec<RouteRef> {
+        self.spec
+            .rules
+            .iter()
+            .flat_map(|rule| rule.backend_refs.iter().flatten())
+            .map(|r| RouteRef::new(&r.group, &r.kind, &r.name, &r.namespace))
+            .collect()
+    }
+}
+
+impl Route for TLSRoute {
+    fn parent_refs(&self) -> Vec<RouteRef> {
+        self.spec
+            .parent_refs
+            .iter()
+            .flatten()
+            .map(|r| RouteRef::new(&r.group, &r.kind, &r.name, &r.namespace))
+            .collect()
+    }
+
+    fn backend_refs(&self) -> Vec<RouteRef> {
+        self.spec
+            .rules
+            .iter()
+            .flat_map(|rule| rule.backend_refs.iter().flatten())
+            .map(|r| RouteRef::new(&r.group, &r.kind, &r.name, &r.namespace))
+            .collect()
+    }
+}
+
+impl Route for UDPRoute {
+    fn parent_refs(&self) -> Vec<RouteRef> {
+        self.spec
+            .parent_refs
+            .iter()
+            .flatten()
+            .map(|r| RouteRef::new(&r.group, &r.kind, &r.name, &r.namespace))
+            .collect()
+    }
+
+    fn backend_refs(&self) -> Vec<RouteRef> {
+        self.spec
+            .rules
+            .iter()
+            .flat_map(|rule| rule.backend_refs.iter().flatten())
+            .map(|r| RouteRef::new(&r.group, &r.kind, &r.name, &r.namespace))
+            .collect()
+    }
+}
+
+/// Routeの名前・spec・statusを表示用に変換する
+/// HTTPRouteと同様にstatus.parentsのconditionsは表示しない
+fn description<R: Route>(route: &R) -> Result<Value> {
+    let mut value = serde_json::to_value(route)?;
+
+    let spec = value.get("spec").cloned().unwrap_or_default();
+
+    let mut route_description = json!({
+        "metadata": {
+            "name": route.name_any(),
+        },
+        "spec": spec,
+    });
+
+    if let Some(Value::Array(parents)) = value.pointer_mut("/status/parents") {
+        parents.iter_mut().for_each(|parent| {
+            if let Value::Object(parent) = parent {
+                parent.remove("conditions");
+            }
+        });
+
+        if !parents.is_empty() {
+            route_description["status"] = json!({ "parents": parents });
+        }
+    }
+
+    let mut ret = Map::new();
+    ret.insert(R::kind(&()).to_lowercase(), route_description);
+
+    Ok(Value::Object(ret))
+}
+
+pub(super) struct RouteDescriptionWorker<'a, C, R>
+where
+    C: KubeClientRequest,
+{
+    client: &'a C,
+    namespace: String,
+    name: String,
+    api_resources: SharedApiResources,
+    _route: PhantomData<fn() -> R>,
+}
+
+#[async_trait::async_trait]
+impl<'a, C, R> Fetch<'a, C> for RouteDescriptionWorker<'a, C, R>
+where
+    C: KubeClientRequest,
+    R: Route,
+{
+    fn new(
+        client: &'a C,
+        params: NetworkRequestTargetParams,
+        api_resources: SharedApiResources,
+    ) -> Self {
+        let NetworkRequestTargetParams {
+            namespace, name, ..
+        } = params;
+
+        Self {
+            client,
+            namespace,
+            name,
+            api_resources,
+            _route: PhantomData,
+        }
+    }
+
+    async fn fetch(&self) -> Result<FetchedData> {
+        let client = self.client.client().clone();
+
+        let api = Api::<R>::namespaced(client.clone(), &self.namespace);
+
+        let route = api.get(&self.name).await.with_context(|| {
+            format!(
+                "Failed to fetch {}: namespace={}, name={}",
+                R::kind(&()),
+                self.namespace,
+                self.name
+            )
+        })?;
+
+        let reference_grant_version = {
+            let api_resources = self.api_resources.read().await;
+            reference_grant_version(&api_resources)
+        };
+
+        let related_resources =
+            discover_related_resources(client, &self.namespace, &route, reference_grant_version)
+                .await?;
+
+        let mut yaml = serde_yaml::to_string(&description(&route)?)?
+            .lines()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+
+        let mut related_resources_yaml = serde_yaml::to_string(&related_resources)?
+            .lines()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+
+        if !related_resources_yaml.is_empty() {
+            yaml.push("".into());
+
+            yaml.append(&mut related_resources_yaml);
+        }
+
+        Ok(yaml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn tcproute() -> TCPRoute {
+        let yaml = indoc! {"
+            apiVersion: gateway.networking.k8s.io/v1alpha2
+            kind: TCPRoute
+            metadata:
+              name: tcp
+              namespace: app
+            spec:
+              parentRefs:
+                - name: gateway
+                  sectionName: tcp
+              rules:
+                - backendRefs:
+                    - name: backend
+                      port: 8080
+                    - name: other
+                      namespace: db
+                      port: 5432
+            status:
+              parents:
+                - controllerName: example.com/gateway-controller
+                  parentRef:
+                    name: gateway
+                  conditions:
+                    - type: Accepted
+                      status: \"True\"
+                      reason: Accepted
+                      message: \"\"
+                      lastTransitionTime: \"2024-01-01T00:00:00Z\"
+        "};
+
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn parent_refsとbackend_refsを取り出す() {
+        let route = tcproute();
+
+        assert_eq!(
+            route.parent_refs(),
+            vec![RouteRef {
+                group: None,
+                kind: None,
+                name: "gateway".into(),
+                namespace: None,
+            }]
+        );
+
+        assert_eq!(
+            route.backend_refs(),
+            vec![
+                RouteRef {
+                    group: None,
+                    kind: None,
+                    name: "backend".into(),
+                    namespace: None,
+                },
+                RouteRef {
+                    group: None,
+                    kind: None,
+                    name: "other".into(),
+                    namespace: Some("db".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn 名前とspecとconditionsを除いたstatusを表示する() {
+        let route = tcproute();
+
+        let actual = serde_yaml::to_string(&description(&route).unwrap()).unwrap();
+
+        let expected = indoc! {"
+            tcproute:
+              metadata:
+                name: tcp
+              spec:
+                parentRefs:
+                - name: gateway
+                  sectionName: tcp
+                rules:
+                - backendRefs:
+                  - name: backend
+                    port: 8080
+                  - name: other
+                    namespace: db
+                    port: 5432
+              status:
+                parents:
+                - controllerName: example.com/gateway-controller
+                  parentRef:
+                    name: gateway
+        "};
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,407 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use futures::future::join_all;
+use k8s_openapi::{
+    api::core::v1::{Pod, Service},
+    Resource as _,
+};
+use kube::{api::ListParams, Api, Client, ResourceExt as _};
+use serde::Serialize;
+
+use crate::{
+    features::network::{
+        kube::description::reference_grant::{
+            fetch_reference_grants, find_permitting_grant, Reference, ReferenceGrant,
+        },
+        message::ReferenceGrantVersion,
+    },
+    kube::apis::networking::gateway::v1::Gateway,
+    logger,
+};
+
+use super::{Route, RouteRef};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteRelatedResources {
+    related_resources: RouteRelatedResourceItems,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RouteRelatedResourceItems {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gateways: Option<Vec<RelatedResource>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    services: Option<Vec<RelatedService>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pods: Option<Vec<RelatedPod>>,
+    /// ReferenceGrantで許可されていない別namespaceのbackendRef
+    #[serde(skip_serializing_if = "Option::is_none")]
+    not_permitted_services: Option<Vec<RelatedResource>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct RelatedResource {
+    name: String,
+    namespace: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RelatedService {
+    name: String,
+    namespace: String,
+    /// 別namespaceのServiceへの参照を許可しているReferenceGrant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reference_grant: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct RelatedPod {
+    name: String,
+    namespace: String,
+    service: String,
+}
+
+/// parentRefのうちGatewayを参照しているものを返す
+fn related_gateways(route_namespace: &str, parent_refs: &[RouteRef]) -> Vec<RelatedResource> {
+    let mut gateways: Vec<_> = parent_refs
+        .iter()
+        .filter_map(|r| {
+            let group = r.group.as_deref().unwrap_or(Gateway::GROUP);
+            let kind = r.kind.as_deref().unwrap_or(Gateway::KIND);
+
+            if group != Gateway::GROUP || kind != Gateway::KIND {
+                logger!(
+                    warn,
+                    "ParentRef is not a Gateway, skipping. Group: {group}, Kind: {kind} name: {}",
+                    r.name
+                );
+
+                return None;
+            }
+
+            Some(RelatedResource {
+                name: r.name.clone(),
+                namespace: r
+                    .namespace
+                    .clone()
+                    .unwrap_or_else(|| route_namespace.to_string()),
+            })
+        })
+        .collect();
+
+    gateways.sort();
+    gateways.dedup();
+
+    gateways
+}
+
+/// backendRefのうちServiceを参照しているものを返す
+fn backend_services(route_namespace: &str, backend_refs: &[RouteRef]) -> Vec<RelatedResource> {
+    let mut services: Vec<_> = backend_refs
+        .iter()
+        .filter(|r| {
+            r.group.as_deref().unwrap_or_default().is_empty()
+                && r.kind.as_deref().unwrap_or(Service::KIND) == Service::KIND
+        })
+        .map(|r| RelatedResource {
+            name: r.name.clone(),
+            namespace: r
+                .namespace
+                .clone()
+                .unwrap_or_else(|| route_namespace.to_string()),
+        })
+        .collect();
+
+    services.sort();
+    services.dedup();
+
+    services
+}
+
+/// Serviceへの参照が許可されているかを判定する
+///
+/// - 同じnamespaceのServiceは常に許可される
+/// - 別namespaceのServiceは、参照先のnamespaceにあるReferenceGrantで許可されている必要がある
+fn check_permission(
+    route_kind: &str,
+    route_namespace: &str,
+    service: &RelatedResource,
+    grants: &[ReferenceGrant],
+) -> Result<Option<String>, ()> {
+    if service.namespace == route_namespace {
+        return Ok(None);
+    }
+
+    let reference = Reference {
+        from_group: Gateway::GROUP,
+        from_kind: route_kind,
+        from_namespace: route_namespace,
+        to_group: "",
+        to_kind: Service::KIND,
+        to_name: &service.name,
+    };
+
+    find_permitting_grant(grants, &reference)
+        .map(|name| Some(name.to_string()))
+        .ok_or(())
+}
+
+pub async fn discover_related_resources<R: Route>(
+    client: Client,
+    route_namespace: &str,
+    route: &R,
+    reference_grant_version: Option<ReferenceGrantVersion>,
+) -> Result<RouteRelatedResources> {
+    let route_kind = R::kind(&());
+
+    let gateways = related_gateways(route_namespace, &route.parent_refs());
+
+    let backend_services = backend_services(route_namespace, &route.backend_refs());
+
+    // 参照先のnamespaceごとにReferenceGrantを取得する
+    let mut grants: BTreeMap<String, Vec<ReferenceGrant>> = BTreeMap::new();
+
+    if let Some(version) = reference_grant_version {
+        for service in &backend_services {
+            if service.namespace == route_namespace || grants.contains_key(&service.namespace) {
+                continue;
+            }
+
+            let fetched = fetch_reference_grants(client.clone(), &service.namespace, version)
+                .await
+                .unwrap_or_else(|err| {
+                    logger!(
+                        error,
+                        "failed to list referencegrants in {}: {}",
+                        service.namespace,
+                        err
+                    );
+                    Vec::new()
+                });
+
+            grants.insert(service.namespace.clone(), fetched);
+        }
+    }
+
+    let mut permitted = Vec::new();
+    let mut not_permitted = Vec::new();
+
+    for service in backend_services {
+        let namespace_grants = grants
+            .get(&service.namespace)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        match check_permission(&route_kind, route_namespace, &service, namespace_grants) {
+            Ok(reference_grant) => permitted.push((service, reference_grant)),
+            Err(()) => not_permitted.push(service),
+        }
+    }
+
+    let fetched_services = join_all(permitted.into_iter().map(|(service, reference_grant)| {
+        let client = client.clone();
+        async move {
+            let api = Api::<Service>::namespaced(client, &service.namespace);
+
+            match api.get(&service.name).await {
+                Ok(resource) => Some((
+                    RelatedService {
+                        name: resource.name_any(),
+                        namespace: service.namespace,
+                        reference_grant,
+                    },
+                    resource,
+                )),
+                Err(err) => {
+                    logger!(
+                        error,
+                        "failed to get service {}/{}: {}",
+                        service.namespace,
+                        service.name,
+                        err
+                    );
+                    None
+                }
+            }
+        }
+    }))
+    .await
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    let mut pods = join_all(
+        fetched_services
+            .iter()
+            .map(|(service, resource)| fetch_pods(client.clone(), service, resource)),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    pods.sort();
+
+    let mut services: Vec<_> = fetched_services
+        .into_iter()
+        .map(|(service, _)| service)
+        .collect();
+
+    services.sort();
+
+    Ok(RouteRelatedResources {
+        related_resources: RouteRelatedResourceItems {
+            gateways: non_empty(gateways),
+            services: non_empty(services),
+            pods: non_empty(pods),
+            not_permitted_services: non_empty(not_permitted),
+        },
+    })
+}
+
+async fn fetch_pods(
+    client: Client,
+    service: &RelatedService,
+    resource: &Service,
+) -> Vec<RelatedPod> {
+    let Some(selector) = resource
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.selector.as_ref())
+    else {
+        return Vec::new();
+    };
+
+    let label_selector = selector
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let api = Api::<Pod>::namespaced(client, &service.namespace);
+
+    match api
+        .list(&ListParams::default().labels(&label_selector))
+        .await
+    {
+        Ok(pods) => pods
+            .into_iter()
+            .map(|pod| RelatedPod {
+                name: pod.name_any(),
+                namespace: service.namespace.clone(),
+                service: service.name.clone(),
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn non_empty<T>(v: Vec<T>) -> Option<Vec<T>> {
+    if v.is_empty() {
+        None
+    } else {
+        Some(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::features::network::kube::description::reference_grant::{
+        ReferenceGrantFrom, ReferenceGrantTo,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn route_ref(
+        group: Option<&str>,
+        kind: Option<&str>,
+        name: &str,
+        namespace: Option<&str>,
+    ) -> RouteRef {
+        RouteRef {
+            group: group.map(ToString::to_string),
+            kind: kind.map(ToString::to_string),
+            name: name.to_string(),
+            namespace: namespace.map(ToString::to_string),
+        }
+    }
+
+    fn resource(name: &str, namespace: &str) -> RelatedResource {
+        RelatedResource {
+            name: name.to_string(),
+            namespace: namespace.to_string(),
+        }
+    }
+
+    #[test]
+    fn parent_refsからgatewayのみを取り出す() {
+        let parent_refs = vec![
+            route_ref(None, None, "gateway", None),
+            route_ref(None, None, "shared", Some("infra")),
+            route_ref(Some("example.com"), Some("Mesh"), "mesh", None),
+        ];
+
+        let actual = related_gateways("app", &parent_refs);
+
+        assert_eq!(
+            actual,
+            vec![resource("gateway", "app"), resource("shared", "infra")]
+        );
+    }
+
+    #[test]
+    fn backend_refsからserviceのみを取り出す() {
+        let backend_refs = vec![
+            route_ref(None, None, "backend", None),
+            route_ref(Some(""), Some("Service"), "backend", None),
+            route_ref(None, None, "db", Some("data")),
+            route_ref(Some("example.com"), Some("Bucket"), "bucket", None),
+        ];
+
+        let actual = backend_services("app", &backend_refs);
+
+        assert_eq!(
+            actual,
+            vec![resource("backend", "app"), resource("db", "data")]
+        );
+    }
+
+    #[test]
+    fn 別namespaceのserviceはreference_grantで許可されているときのみ参照できる() {
+        let grants = vec![ReferenceGrant {
+            name: "allow-app".into(),
+            from: vec![ReferenceGrantFrom {
+                group: Gateway::GROUP.into(),
+                kind: "TCPRoute".into(),
+                namespace: "app".into(),
+            }],
+            to: vec![ReferenceGrantTo {
+                group: "".into(),
+                kind: Service::KIND.into(),
+                name: Some("db".into()),
+            }],
+        }];
+
+        assert_eq!(
+            check_permission("TCPRoute", "app", &resource("backend", "app"), &[]),
+            Ok(None)
+        );
+        assert_eq!(
+            check_permission("TCPRoute", "app", &resource("db", "data"), &grants),
+            Ok(Some("allow-app".into()))
+        );
+        assert_eq!(
+            check_permission("TCPRoute", "app", &resource("cache", "data"), &grants),
+            Err(())
+        );
+        assert_eq!(
+            check_permission("UDPRoute", "app", &resource("db", "data"), &grants),
+            Err(())
+        );
+    }
+}
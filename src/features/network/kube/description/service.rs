@@ -1,7 +1,10 @@
+mod endpoints;
+
 use anyhow::Result;
 use k8s_openapi::{
     api::{
         core::v1::{Pod, Service, ServiceSpec},
+        discovery::v1::EndpointSlice,
         networking::v1::Ingress,
     },
     List,
@@ -14,9 +17,10 @@ use crate::{
         api_resources::kube::SharedApiResources, network::message::NetworkRequestTargetParams,
     },
     kube::KubeClientRequest,
+    logger,
 };
 
-use self::to_value::ToValue;
+use self::{endpoints::Endpoints, to_value::ToValue};
 
 use super::{
     related_resources::{to_list_value::ToListValue, RelatedClient},
@@ -78,6 +82,20 @@ where
             None
         };
 
+        let endpoint_slices_url = EndpointSlice::url_path(&(), Some(&self.namespace));
+
+        // EndpointSliceを参照できない場合でも、Serviceの情報は表示する
+        let endpoint_slices: Option<List<EndpointSlice>> =
+            match self.client.request(&endpoint_slices_url).await {
+                Ok(slices) => Some(slices),
+                Err(err) => {
+                    logger!(error, "failed to list endpointslices: {}", err);
+                    None
+                }
+            };
+
+        let matched_pods = related_pods.as_ref().map_or(0, |pods| pods.items.len());
+
         let mut related_resources = Mapping::new();
 
         if let Some(ingresses) = related_ingresses {
@@ -99,6 +117,12 @@ where
 
         let mut value = service;
 
+        if let Some(slices) = endpoint_slices {
+            value.push(Default::default());
+
+            value.extend(Endpoints::new(&self.name, &slices).to_lines(matched_pods)?);
+        }
+
         if !related_resources.is_empty() {
             let mut root = Mapping::new();
 
@@ -123,7 +147,7 @@ mod tests {
     use anyhow::bail;
     use indoc::indoc;
     use k8s_openapi::{
-        api::{core::v1::Pod, networking::v1::Ingress},
+        api::{core::v1::Pod, discovery::v1::EndpointSlice, networking::v1::Ingress},
         List,
     };
     use mockall::predicate::eq;
@@ -218,6 +242,40 @@ mod tests {
         .unwrap()
     }
 
+    fn endpoint_slices() -> List<EndpointSlice> {
+        serde_yaml::from_str(indoc! {
+            "
+            items:
+            - metadata:
+                name: service-abcde
+                labels:
+                  kubernetes.io/service-name: service
+              addressType: IPv4
+              endpoints:
+              - addresses:
+                - 10.244.0.1
+                conditions:
+                  ready: true
+                  serving: true
+                  terminating: false
+                targetRef:
+                  kind: Pod
+                  name: pod-1
+                zone: zone-a
+              ports:
+              - port: 80
+                protocol: TCP
+            - metadata:
+                name: service-2-abcde
+                labels:
+                  kubernetes.io/service-name: service-2
+              addressType: IPv4
+              endpoints: []
+            "
+        })
+        .unwrap()
+    }
+
     #[tokio::test]
     async fn yamlデータを返す() {
         let mut client = MockTestKubeClient::new();
@@ -239,6 +297,11 @@ mod tests {
                     List<Pod>,
                     eq("/api/v1/namespaces/default/pods"),
                     Ok(pods())
+                ),
+                (
+                    List<EndpointSlice>,
+                    eq("/apis/discovery.k8s.io/v1/namespaces/default/endpointslices"),
+                    Ok(endpoint_slices())
                 )
             ]
         );
@@ -274,6 +337,25 @@ mod tests {
                 sessionAffinity: None
                 type: ClusterIP
 
+            endpoints:
+              ready: 1
+              serving: 1
+              terminating: 0
+              slices:
+              - name: service-abcde
+                addressType: IPv4
+                ports:
+                - port: 80
+                  protocol: TCP
+                endpoints:
+                - addresses:
+                  - 10.244.0.1
+                  ready: true
+                  serving: true
+                  terminating: false
+                  pod: pod-1
+                  zone: zone-a
+
             relatedResources:
               ingresses:
               - ingress-1
@@ -310,8 +392,12 @@ mod tests {
                     List<Pod>,
                     eq("/api/v1/namespaces/default/pods"),
                     bail!("error")
+                ),
+                (
+                    List<EndpointSlice>,
+                    eq("/apis/discovery.k8s.io/v1/namespaces/default/endpointslices"),
+                    bail!("error")
                 )
-
             ]
         );
 
@@ -0,0 +1,281 @@
+use k8s_openapi::{
+    api::discovery::v1::{EndpointConditions, EndpointSlice},
+    List,
+};
+use kube::ResourceExt as _;
+use serde::Serialize;
+
+use crate::workers::kube::color::fg::Color;
+
+/// EndpointSliceとServiceを関連付けるラベル
+const SERVICE_NAME_LABEL: &str = "kubernetes.io/service-name";
+
+/// Serviceの転送先となっているEndpointSliceの要約
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Endpoints {
+    ready: usize,
+    serving: usize,
+    terminating: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    slices: Vec<Slice>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Slice {
+    name: String,
+    address_type: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ports: Vec<Port>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    endpoints: Vec<Endpoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Port {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Endpoint {
+    addresses: Vec<String>,
+    ready: bool,
+    serving: bool,
+    terminating: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pod: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    node_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    zone: Option<String>,
+}
+
+/// readyとservingは未設定のときtrue、terminatingは未設定のときfalseとして扱う
+fn conditions(conditions: Option<&EndpointConditions>) -> (bool, bool, bool) {
+    let ready = conditions.and_then(|c| c.ready).unwrap_or(true);
+    let serving = conditions.and_then(|c| c.serving).unwrap_or(ready);
+    let terminating = conditions.and_then(|c| c.terminating).unwrap_or(false);
+
+    (ready, serving, terminating)
+}
+
+impl Endpoints {
+    /// namespaceのEndpointSliceのうち、Serviceに関連付けられているものを要約する
+    pub fn new(service_name: &str, slices: &List<EndpointSlice>) -> Self {
+        let mut slices: Vec<Slice> = slices
+            .items
+            .iter()
+            .filter(|slice| {
+                slice.labels().get(SERVICE_NAME_LABEL).map(String::as_str) == Some(service_name)
+            })
+            .map(|slice| Slice {
+                name: slice.name_any(),
+                address_type: slice.address_type.clone(),
+                ports: slice
+                    .ports
+                    .iter()
+                    .flatten()
+                    .map(|port| Port {
+                        name: port.name.clone(),
+                        port: port.port,
+                        protocol: port.protocol.clone(),
+                    })
+                    .collect(),
+                endpoints: slice
+                    .endpoints
+                    .iter()
+                    .map(|endpoint| {
+                        let (ready, serving, terminating) =
+                            conditions(endpoint.conditions.as_ref());
+
+                        Endpoint {
+                            addresses: endpoint.addresses.clone(),
+                            ready,
+                            serving,
+                            terminating,
+                            pod: endpoint
+                                .target_ref
+                                .as_ref()
+                                .filter(|target| target.kind.as_deref() == Some("Pod"))
+                                .and_then(|target| target.name.clone()),
+                            node_name: endpoint.node_name.clone(),
+                            zone: endpoint.zone.clone(),
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        slices.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let count = |f: fn(&Endpoint) -> bool| {
+            slices
+                .iter()
+                .flat_map(|slice| &slice.endpoints)
+                .filter(|endpoint| f(endpoint))
+                .count()
+        };
+
+        Self {
+            ready: count(|e| e.ready),
+            serving: count(|e| e.serving),
+            terminating: count(|e| e.terminating),
+            slices,
+        }
+    }
+
+    /// 表示用の行に変換する
+    ///
+    /// セレクタに一致するPodがあるのにreadyなエンドポイントがない場合は、先頭に警告を表示する
+    pub fn to_lines(&self, matched_pods: usize) -> anyhow::Result<Vec<String>> {
+        let mut root = serde_yaml::Mapping::new();
+
+        root.insert("endpoints".into(), serde_yaml::to_value(self)?);
+
+        let mut lines = Vec::new();
+
+        if self.ready == 0 && 0 < matched_pods {
+            lines.push(Color::Red.wrap(format!(
+                "# WARNING: no ready endpoints ({} pods match the selector)",
+                matched_pods
+            )));
+        }
+
+        lines.extend(
+            serde_yaml::to_string(&root)?
+                .lines()
+                .map(ToString::to_string),
+        );
+
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn slices() -> List<EndpointSlice> {
+        serde_yaml::from_str(indoc! {
+            "
+            items:
+            - metadata:
+                name: service-b
+                labels:
+                  kubernetes.io/service-name: service
+              addressType: IPv4
+              endpoints:
+              - addresses:
+                - 10.0.0.2
+                conditions:
+                  ready: false
+                  serving: true
+                  terminating: true
+                targetRef:
+                  kind: Pod
+                  name: pod-2
+                zone: zone-b
+              ports:
+              - name: http
+                port: 8080
+                protocol: TCP
+            - metadata:
+                name: service-a
+                labels:
+                  kubernetes.io/service-name: service
+              addressType: IPv4
+              endpoints:
+              - addresses:
+                - 10.0.0.1
+                conditions: {}
+                nodeName: node-1
+                targetRef:
+                  kind: Pod
+                  name: pod-1
+                zone: zone-a
+            - metadata:
+                name: other-a
+                labels:
+                  kubernetes.io/service-name: other
+              addressType: IPv4
+              endpoints:
+              - addresses:
+                - 10.0.0.3
+            "
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn serviceに関連付けられたendpointsliceを要約する() {
+        let actual = Endpoints::new("service", &slices()).to_lines(2).unwrap();
+
+        let expected: Vec<String> = indoc! {
+            "
+            endpoints:
+              ready: 1
+              serving: 2
+              terminating: 1
+              slices:
+              - name: service-a
+                addressType: IPv4
+                endpoints:
+                - addresses:
+                  - 10.0.0.1
+                  ready: true
+                  serving: true
+                  terminating: false
+                  pod: pod-1
+                  nodeName: node-1
+                  zone: zone-a
+              - name: service-b
+                addressType: IPv4
+                ports:
+                - name: http
+                  port: 8080
+                  protocol: TCP
+                endpoints:
+                - addresses:
+                  - 10.0.0.2
+                  ready: false
+                  serving: true
+                  terminating: true
+                  pod: pod-2
+                  zone: zone-b
+            "
+        }
+        .lines()
+        .map(ToString::to_string)
+        .collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn セレクタに一致するpodがあるのにreadyなエンドポイントがないとき警告を表示する() {
+        let endpoints = Endpoints::new("nothing", &slices());
+
+        let actual = endpoints.to_lines(3).unwrap();
+
+        let expected = vec![
+            "\x1b[31m# WARNING: no ready endpoints (3 pods match the selector)\x1b[39m".to_string(),
+            "endpoints:".to_string(),
+            "  ready: 0".to_string(),
+            "  serving: 0".to_string(),
+            "  terminating: 0".to_string(),
+        ];
+
+        assert_eq!(actual, expected);
+
+        assert_eq!(endpoints.to_lines(0).unwrap(), expected[1..].to_vec());
+    }
+}
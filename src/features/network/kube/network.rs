@@ -1,9 +1,8 @@
-use std::{collections::BTreeMap, time};
+use std::{collections::BTreeMap, sync::Arc, time};
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use async_trait::async_trait;
 use crossbeam::channel::Sender;
-use futures::future::{join_all, try_join_all};
 use k8s_openapi::{
     api::{
         core::v1::{Pod, Service},
@@ -11,19 +10,21 @@ use k8s_openapi::{
     },
     Resource,
 };
+use kube::ResourceExt as _;
 
 use crate::{
     features::{
         api_resources::kube::{ApiResource, ApiResources, SharedApiResources},
-        network::message::{GatewayVersion, HTTPRouteVersion, NetworkResponse},
+        network::message::{
+            GatewayVersion, HTTPRouteVersion, NetworkResponse, ReferenceGrantVersion,
+        },
     },
     kube::{
-        apis::{
-            networking::gateway::{v1, v1beta1},
-            v1_table::Table,
+        apis::networking::gateway::{v1, v1alpha2, v1beta1},
+        informer::{
+            ChangeDetector, InformerResource, NamespacedInformer, SharedInformer, SharedInformers,
         },
-        table::{insert_ns, KubeTable, KubeTableRow},
-        KubeClient, KubeClientRequest,
+        table::{insert_ns, translate_timestamp_since, KubeTable, KubeTableRow},
     },
     logger,
     message::Message,
@@ -108,7 +109,7 @@ impl NetworkTable {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 enum TargetResource {
     Ingress,
     Service,
@@ -116,6 +117,11 @@ enum TargetResource {
     NetworkPolicy,
     Gateway(GatewayVersion),
     HTTPRoute(HTTPRouteVersion),
+    GRPCRoute,
+    TCPRoute,
+    TLSRoute,
+    UDPRoute,
+    ReferenceGrant(ReferenceGrantVersion),
 }
 
 impl TargetResource {
@@ -133,6 +139,14 @@ impl TargetResource {
                 HTTPRouteVersion::V1 => v1::HTTPRoute::KIND,
                 HTTPRouteVersion::V1Beta1 => v1beta1::HTTPRoute::KIND,
             },
+            Self::GRPCRoute => v1alpha2::GRPCRoute::KIND,
+            Self::TCPRoute => v1alpha2::TCPRoute::KIND,
+            Self::TLSRoute => v1alpha2::TLSRoute::KIND,
+            Self::UDPRoute => v1alpha2::UDPRoute::KIND,
+            Self::ReferenceGrant(version) => match version {
+                ReferenceGrantVersion::V1Beta1 => v1beta1::ReferenceGrant::KIND,
+                ReferenceGrantVersion::V1Alpha2 => v1alpha2::ReferenceGrant::KIND,
+            },
         }
     }
 
@@ -146,37 +160,98 @@ impl TargetResource {
             Self::Gateway(GatewayVersion::V1Beta1) => v1beta1::Gateway::VERSION,
             Self::HTTPRoute(HTTPRouteVersion::V1) => v1::HTTPRoute::VERSION,
             Self::HTTPRoute(HTTPRouteVersion::V1Beta1) => v1beta1::HTTPRoute::VERSION,
+            Self::GRPCRoute => v1alpha2::GRPCRoute::VERSION,
+            Self::TCPRoute => v1alpha2::TCPRoute::VERSION,
+            Self::TLSRoute => v1alpha2::TLSRoute::VERSION,
+            Self::UDPRoute => v1alpha2::UDPRoute::VERSION,
+            Self::ReferenceGrant(ReferenceGrantVersion::V1Beta1) => {
+                v1beta1::ReferenceGrant::VERSION
+            }
+            Self::ReferenceGrant(ReferenceGrantVersion::V1Alpha2) => {
+                v1alpha2::ReferenceGrant::VERSION
+            }
         }
     }
 
-    async fn fetch_table(&self, client: &KubeClient, ns: &str) -> Result<Table> {
+    fn informer(&self, informers: &SharedInformers) -> Box<dyn NetworkInformer> {
         match self {
-            Self::Ingress => client.table_namespaced::<Ingress>(ns).await,
-            Self::Service => client.table_namespaced::<Service>(ns).await,
-            Self::Pod => client.table_namespaced::<Pod>(ns).await,
-            Self::NetworkPolicy => client.table_namespaced::<NetworkPolicy>(ns).await,
-            Self::Gateway(GatewayVersion::V1) => client.table_namespaced::<v1::Gateway>(ns).await,
+            Self::Ingress => Box::new(informers.namespaced::<Ingress>()),
+            Self::Service => Box::new(informers.namespaced::<Service>()),
+            Self::Pod => Box::new(informers.pod()),
+            Self::NetworkPolicy => Box::new(informers.namespaced::<NetworkPolicy>()),
+            Self::Gateway(GatewayVersion::V1) => Box::new(informers.namespaced::<v1::Gateway>()),
             Self::Gateway(GatewayVersion::V1Beta1) => {
-                client.table_namespaced::<v1beta1::Gateway>(ns).await
+                Box::new(informers.namespaced::<v1beta1::Gateway>())
             }
             Self::HTTPRoute(HTTPRouteVersion::V1) => {
-                client.table_namespaced::<v1::HTTPRoute>(ns).await
+                Box::new(informers.namespaced::<v1::HTTPRoute>())
             }
             Self::HTTPRoute(HTTPRouteVersion::V1Beta1) => {
-                client.table_namespaced::<v1beta1::HTTPRoute>(ns).await
+                Box::new(informers.namespaced::<v1beta1::HTTPRoute>())
+            }
+            Self::GRPCRoute => Box::new(informers.namespaced::<v1alpha2::GRPCRoute>()),
+            Self::TCPRoute => Box::new(informers.namespaced::<v1alpha2::TCPRoute>()),
+            Self::TLSRoute => Box::new(informers.namespaced::<v1alpha2::TLSRoute>()),
+            Self::UDPRoute => Box::new(informers.namespaced::<v1alpha2::UDPRoute>()),
+            Self::ReferenceGrant(ReferenceGrantVersion::V1Beta1) => {
+                Box::new(informers.namespaced::<v1beta1::ReferenceGrant>())
+            }
+            Self::ReferenceGrant(ReferenceGrantVersion::V1Alpha2) => {
+                Box::new(informers.namespaced::<v1alpha2::ReferenceGrant>())
             }
         }
-        .with_context(|| {
-            format!(
-                "Failed to fetch table: kind={} ({}) namespace={}",
-                self.as_str(),
-                self.version(),
-                ns
-            )
-        })
     }
 }
 
+/// 種類の異なるリソースのInformerをまとめて扱うためのトレイト
+trait NetworkInformer: Send + Sync {
+    fn sync_namespaces(&mut self, namespaces: &[String]);
+
+    fn rows(&self, kind: &TargetResource) -> Option<Result<Vec<NetworkTableRow>>>;
+}
+
+impl<K: InformerResource> NetworkInformer for NamespacedInformer<K> {
+    fn sync_namespaces(&mut self, namespaces: &[String]) {
+        NamespacedInformer::sync_namespaces(self, namespaces);
+    }
+
+    fn rows(&self, kind: &TargetResource) -> Option<Result<Vec<NetworkTableRow>>> {
+        Some(
+            self.state()?
+                .map(|resources| network_rows(kind, &resources)),
+        )
+    }
+}
+
+impl<K: InformerResource> NetworkInformer for SharedInformer<K> {
+    fn sync_namespaces(&mut self, namespaces: &[String]) {
+        SharedInformer::sync_namespaces(self, namespaces);
+    }
+
+    fn rows(&self, kind: &TargetResource) -> Option<Result<Vec<NetworkTableRow>>> {
+        Some(
+            self.state()?
+                .map(|resources| network_rows(kind, &resources)),
+        )
+    }
+}
+
+fn network_rows<K: InformerResource>(
+    kind: &TargetResource,
+    resources: &[Arc<K>],
+) -> Vec<NetworkTableRow> {
+    resources
+        .iter()
+        .map(|resource| NetworkTableRow {
+            namespace: resource.namespace().unwrap_or_default(),
+            kind: kind.to_string(),
+            version: kind.version().to_string(),
+            name: resource.name_any(),
+            age: translate_timestamp_since(resource.meta().creation_timestamp.as_ref()),
+        })
+        .collect()
+}
+
 impl std::fmt::Display for TargetResource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
@@ -187,7 +262,7 @@ impl std::fmt::Display for TargetResource {
 pub struct NetworkPoller {
     tx: Sender<Message>,
     shared_target_namespaces: SharedTargetNamespaces,
-    kube_client: KubeClient,
+    informers: SharedInformers,
     api_resources: SharedApiResources,
 }
 
@@ -195,13 +270,13 @@ impl NetworkPoller {
     pub fn new(
         tx: Sender<Message>,
         shared_target_namespaces: SharedTargetNamespaces,
-        kube_client: KubeClient,
+        informers: SharedInformers,
         api_resources: SharedApiResources,
     ) -> Self {
         Self {
             tx,
             shared_target_namespaces,
-            kube_client,
+            informers,
             api_resources,
         }
     }
@@ -257,9 +332,64 @@ fn target_resources(api_resources: &ApiResources) -> Vec<TargetResource> {
         }
     }
 
+    // v1alpha2のRouteは、APIサーバーがv1alpha2を提供している場合のみ対象にする
+    let routes = [
+        (
+            v1alpha2::GRPCRoute::URL_PATH_SEGMENT,
+            TargetResource::GRPCRoute,
+        ),
+        (
+            v1alpha2::TCPRoute::URL_PATH_SEGMENT,
+            TargetResource::TCPRoute,
+        ),
+        (
+            v1alpha2::TLSRoute::URL_PATH_SEGMENT,
+            TargetResource::TLSRoute,
+        ),
+        (
+            v1alpha2::UDPRoute::URL_PATH_SEGMENT,
+            TargetResource::UDPRoute,
+        ),
+    ];
+
+    targets.extend(routes.into_iter().filter_map(|(plural, target)| {
+        is_served_version(
+            api_resources,
+            v1alpha2::GRPCRoute::GROUP,
+            plural,
+            v1alpha2::GRPCRoute::VERSION,
+        )
+        .then_some(target)
+    }));
+
+    if let Some(version) = reference_grant_version(api_resources) {
+        targets.push(TargetResource::ReferenceGrant(version));
+    }
+
     targets
 }
 
+/// APIサーバーが提供しているReferenceGrantのバージョンを返す
+pub(super) fn reference_grant_version(
+    api_resources: &ApiResources,
+) -> Option<ReferenceGrantVersion> {
+    match find_api_resource(
+        api_resources,
+        v1beta1::ReferenceGrant::GROUP,
+        v1beta1::ReferenceGrant::URL_PATH_SEGMENT,
+    )
+    .map(|api| api.version())
+    {
+        Some("v1beta1") => Some(ReferenceGrantVersion::V1Beta1),
+        Some("v1alpha2") => Some(ReferenceGrantVersion::V1Alpha2),
+        Some(v) => {
+            logger!(warn, "ReferenceGrant is not support: {}", v);
+            None
+        }
+        None => None,
+    }
+}
+
 #[async_trait()]
 impl Worker for NetworkPoller {
     type Output = WorkerResult;
@@ -269,6 +399,10 @@ impl Worker for NetworkPoller {
 
         let tx = &self.tx;
 
+        let mut informers: Vec<(TargetResource, Box<dyn NetworkInformer>)> = Vec::new();
+
+        let mut detector = ChangeDetector::default();
+
         loop {
             interval.tick().await;
 
@@ -277,7 +411,17 @@ impl Worker for NetworkPoller {
                 target_resources(&apis)
             };
 
-            let table = self.polling(&target_resources).await;
+            let target_namespaces = self.shared_target_namespaces.read().await.clone();
+
+            self.sync_informers(&mut informers, &target_resources, &target_namespaces);
+
+            let Some(table) = Self::table(&informers, &target_namespaces) else {
+                continue;
+            };
+
+            if !detector.is_changed(&table) {
+                continue;
+            }
 
             tx.send(NetworkResponse::List(table).into())
                 .expect("Failed to send NetworkResponse::List");
@@ -285,76 +429,53 @@ impl Worker for NetworkPoller {
     }
 }
 
-const TARGET_COLUMNS: [&str; 2] = ["Name", "Age"];
-
 impl NetworkPoller {
-    async fn polling(&self, target_resources: &[TargetResource]) -> Result<KubeTable> {
-        let target_namespaces = self.shared_target_namespaces.read().await;
-
-        let rows: Vec<_> = join_all(
-            target_resources
-                .iter()
-                .map(|kind| self.fetch_resource(kind, &target_namespaces)),
-        )
-        .await
-        .into_iter()
-        .inspect(|res| {
-            if let Err(e) = res {
-                logger!(error, "Failed to fetch resource: {:?}", e);
-            }
-        })
-        .filter_map(|res| res.ok())
-        .collect();
-
-        let table = NetworkTable::new(
-            insert_ns(&target_namespaces),
-            rows.into_iter().flatten().collect(),
-        );
-
-        Ok(table.to_kube_table())
-    }
-
-    async fn fetch_resource(
+    /// 対象リソースの変更に合わせてInformerを追加・削除し、監視対象のnamespaceを更新する
+    fn sync_informers(
         &self,
-        kind: &TargetResource,
+        informers: &mut Vec<(TargetResource, Box<dyn NetworkInformer>)>,
+        target_resources: &[TargetResource],
         namespaces: &[String],
-    ) -> Result<Vec<NetworkTableRow>> {
-        let client = &self.kube_client;
+    ) {
+        let mut current = std::mem::take(informers);
 
-        let jobs = try_join_all(
-            namespaces
-                .iter()
-                .map(|ns| fetch_resource_per_namespace(client, kind, ns, &TARGET_COLUMNS)),
-        )
-        .await?;
-
-        Ok(jobs.into_iter().flatten().collect())
+        *informers = target_resources
+            .iter()
+            .map(|kind| {
+                if let Some(index) = current.iter().position(|(k, _)| k == kind) {
+                    current.remove(index)
+                } else {
+                    (*kind, kind.informer(&self.informers))
+                }
+            })
+            .collect();
+
+        for (_, informer) in informers.iter_mut() {
+            informer.sync_namespaces(namespaces);
+        }
     }
-}
 
-async fn fetch_resource_per_namespace(
-    client: &KubeClient,
-    kind: &TargetResource,
-    ns: &str,
-    target_columns: &[&str],
-) -> Result<Vec<NetworkTableRow>> {
-    let table = kind.fetch_table(client, ns).await?;
+    /// Informerのキャッシュからテーブルを生成する
+    /// 初回の取得が完了していないリソースがある場合はNoneを返す
+    fn table(
+        informers: &[(TargetResource, Box<dyn NetworkInformer>)],
+        namespaces: &[String],
+    ) -> Option<Result<KubeTable>> {
+        let mut rows = Vec::new();
+
+        for (kind, informer) in informers {
+            match informer.rows(kind)? {
+                Ok(r) => rows.extend(r),
+                Err(e) => {
+                    logger!(error, "Failed to fetch resource: {:?}", e);
+                }
+            }
+        }
 
-    let indexes = table.find_indexes(target_columns);
+        let table = NetworkTable::new(insert_ns(namespaces), rows);
 
-    let rows = table
-        .rows
-        .iter()
-        .map(|row| NetworkTableRow {
-            namespace: ns.to_string(),
-            kind: kind.to_string(),
-            version: kind.version().to_string(),
-            name: row.cells[indexes[0]].to_string(),
-            age: row.cells[indexes[1]].to_string(),
-        })
-        .collect();
-
-    Ok(rows)
+        Some(Ok(table.to_kube_table()))
+    }
 }
 
 /// groupとkindが一致するAPIリソースを取得する
@@ -379,6 +500,13 @@ fn find_api_resource<'a>(
     }
 }
 
+/// groupとkindが一致するAPIリソースのうち、指定したバージョンが提供されているかを返す
+fn is_served_version(api_resources: &ApiResources, group: &str, kind: &str, version: &str) -> bool {
+    api_resources
+        .iter()
+        .any(|api| api.group() == group && api.name() == kind && api.version() == version)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -395,6 +523,7 @@ mod tests {
                 ApiResource::Apis {
                     group: "group1".to_string(),
                     name: "kind1".to_string(),
+                    kind: "Kind1".to_string(),
                     version: "v1".to_string(),
                     preferred_version: false,
                     scope: Scope::Namespaced,
@@ -402,6 +531,7 @@ mod tests {
                 ApiResource::Apis {
                     group: "group1".to_string(),
                     name: "kind1".to_string(),
+                    kind: "Kind1".to_string(),
                     version: "v2".to_string(),
                     preferred_version: true,
                     scope: Scope::Namespaced,
@@ -413,6 +543,7 @@ mod tests {
             let expected = ApiResource::Apis {
                 group: "group1".to_string(),
                 name: "kind1".to_string(),
+                kind: "Kind1".to_string(),
                 version: "v2".to_string(),
                 preferred_version: true,
                 scope: Scope::Namespaced,
@@ -427,6 +558,7 @@ mod tests {
                 ApiResource::Apis {
                     group: "group1".to_string(),
                     name: "kind1".to_string(),
+                    kind: "Kind1".to_string(),
                     version: "v2".to_string(),
                     preferred_version: false,
                     scope: Scope::Namespaced,
@@ -434,6 +566,7 @@ mod tests {
                 ApiResource::Apis {
                     group: "group1".to_string(),
                     name: "kind1".to_string(),
+                    kind: "Kind1".to_string(),
                     version: "v1".to_string(),
                     preferred_version: false,
                     scope: Scope::Namespaced,
@@ -445,6 +578,7 @@ mod tests {
             let expected = ApiResource::Apis {
                 group: "group1".to_string(),
                 name: "kind1".to_string(),
+                kind: "Kind1".to_string(),
                 version: "v2".to_string(),
                 preferred_version: false,
                 scope: Scope::Namespaced,
@@ -462,4 +596,75 @@ mod tests {
             assert_eq!(actual, None);
         }
     }
+
+    mod target_resources {
+        use super::*;
+
+        use kube::discovery::Scope;
+        use pretty_assertions::assert_eq;
+
+        fn gateway_api(name: &str, version: &str, preferred_version: bool) -> ApiResource {
+            ApiResource::Apis {
+                group: "gateway.networking.k8s.io".to_string(),
+                name: name.to_string(),
+                kind: String::default(),
+                version: version.to_string(),
+                preferred_version,
+                scope: Scope::Namespaced,
+            }
+        }
+
+        #[test]
+        fn v1alpha2のrouteとreference_grantを提供しているとき対象に含める() {
+            let api_resources = ApiResources::from([
+                gateway_api("gateways", "v1", true),
+                gateway_api("httproutes", "v1", true),
+                gateway_api("grpcroutes", "v1", true),
+                gateway_api("grpcroutes", "v1alpha2", false),
+                gateway_api("tcproutes", "v1alpha2", true),
+                gateway_api("tlsroutes", "v1alpha2", true),
+                gateway_api("udproutes", "v1alpha2", true),
+                gateway_api("referencegrants", "v1beta1", true),
+                gateway_api("referencegrants", "v1alpha2", false),
+            ]);
+
+            let actual = target_resources(&api_resources);
+
+            let expected = vec![
+                TargetResource::Ingress,
+                TargetResource::Service,
+                TargetResource::Pod,
+                TargetResource::NetworkPolicy,
+                TargetResource::Gateway(GatewayVersion::V1),
+                TargetResource::HTTPRoute(HTTPRouteVersion::V1),
+                TargetResource::GRPCRoute,
+                TargetResource::TCPRoute,
+                TargetResource::TLSRoute,
+                TargetResource::UDPRoute,
+                TargetResource::ReferenceGrant(ReferenceGrantVersion::V1Beta1),
+            ];
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn v1alpha2を提供していないrouteは対象に含めない() {
+            let api_resources = ApiResources::from([
+                gateway_api("grpcroutes", "v1", true),
+                gateway_api("referencegrants", "v1alpha2", true),
+            ]);
+
+            let actual = target_resources(&api_resources);
+
+            let expected = vec![
+                TargetResource::Ingress,
+                TargetResource::Service,
+                TargetResource::Pod,
+                TargetResource::NetworkPolicy,
+                TargetResource::ReferenceGrant(ReferenceGrantVersion::V1Alpha2),
+            ];
+
+            assert_eq!(actual, expected);
+        }
+    }
 }
@@ -0,0 +1,407 @@
+mod cidr;
+mod policy;
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use k8s_openapi::api::{
+    core::v1::{Namespace, Pod},
+    networking::v1::NetworkPolicy,
+};
+use kube::{Api, ResourceExt as _};
+
+use crate::{
+    features::network::message::{ReachabilityDestination, ReachabilityQuery},
+    kube::KubeClient,
+    logger,
+};
+
+use self::{
+    cidr::IpCidr,
+    policy::{evaluate, Decision, Direction, Endpoint, Port},
+};
+
+const PROTOCOLS: [&str; 3] = ["TCP", "UDP", "SCTP"];
+
+/// 疎通確認ダイアログの入力から確認する内容を生成する
+///
+/// 入力の形式は `<destination> <port>[/<protocol>]`
+/// - destinationは `<pod>`、`<namespace>/<pod>`、IPアドレスまたはCIDR
+/// - namespaceを省略した場合は送信元のPodと同じnamespaceとする
+/// - protocolを省略した場合はTCPとする
+pub fn parse_reachability_query(
+    namespace: &str,
+    pod: &str,
+    input: &str,
+) -> Result<ReachabilityQuery> {
+    let mut tokens = input.split_whitespace();
+
+    let (Some(destination), Some(port), None) = (tokens.next(), tokens.next(), tokens.next())
+    else {
+        return Err(anyhow!(
+            "Invalid input: expected \"<pod|namespace/pod|cidr> <port>[/<protocol>]\""
+        ));
+    };
+
+    let destination = if destination.parse::<IpCidr>().is_ok() {
+        ReachabilityDestination::Cidr(destination.to_string())
+    } else {
+        match destination.split_once('/') {
+            Some((ns, name)) if !ns.is_empty() && !name.is_empty() => {
+                ReachabilityDestination::Pod {
+                    namespace: ns.to_string(),
+                    name: name.to_string(),
+                }
+            }
+            Some(_) => return Err(anyhow!("Invalid destination: {}", destination)),
+            None => ReachabilityDestination::Pod {
+                namespace: namespace.to_string(),
+                name: destination.to_string(),
+            },
+        }
+    };
+
+    let (port, protocol) = match port.split_once('/') {
+        Some((port, protocol)) => (port, protocol.to_uppercase()),
+        None => (port, "TCP".to_string()),
+    };
+
+    let port = port
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port != 0)
+        .ok_or_else(|| anyhow!("Invalid port: {}", port))?;
+
+    if !PROTOCOLS.contains(&protocol.as_str()) {
+        return Err(anyhow!(
+            "Invalid protocol: {} (expected one of {})",
+            protocol,
+            PROTOCOLS.join(", ")
+        ));
+    }
+
+    Ok(ReachabilityQuery {
+        namespace: namespace.to_string(),
+        pod: pod.to_string(),
+        destination,
+        port,
+        protocol,
+    })
+}
+
+/// 送信元のPodのegressと宛先のPodのingressのNetworkPolicyを評価し、結果を表示用の行で返す
+pub async fn check_reachability(
+    client: &KubeClient,
+    query: &ReachabilityQuery,
+) -> Result<Vec<String>> {
+    let source = fetch_pod_endpoint(client, &query.namespace, &query.pod).await?;
+
+    let destination = match &query.destination {
+        ReachabilityDestination::Pod { namespace, name } => {
+            fetch_pod_endpoint(client, namespace, name).await?
+        }
+        ReachabilityDestination::Cidr(cidr) => Endpoint {
+            ip: Some(cidr.parse()?),
+            ..Default::default()
+        },
+    };
+
+    let port = Port {
+        number: query.port,
+        protocol: query.protocol.clone(),
+    };
+
+    let source_policies = list_network_policies(client, &query.namespace).await?;
+
+    let egress = evaluate(
+        Direction::Egress,
+        &source_policies,
+        &source,
+        &destination,
+        &port,
+    );
+
+    let ingress = match &query.destination {
+        ReachabilityDestination::Pod { namespace, .. } => {
+            let policies = if namespace == &query.namespace {
+                source_policies
+            } else {
+                list_network_policies(client, namespace).await?
+            };
+
+            Some(evaluate(
+                Direction::Ingress,
+                &policies,
+                &source,
+                &destination,
+                &port,
+            ))
+        }
+        ReachabilityDestination::Cidr(_) => None,
+    };
+
+    Ok(format_result(
+        query,
+        &source,
+        &destination,
+        &egress,
+        ingress.as_ref(),
+    ))
+}
+
+async fn list_network_policies(client: &KubeClient, namespace: &str) -> Result<Vec<NetworkPolicy>> {
+    let api: Api<NetworkPolicy> = Api::namespaced(client.to_client(), namespace);
+
+    Ok(api.list(&Default::default()).await?.items)
+}
+
+async fn fetch_pod_endpoint(client: &KubeClient, namespace: &str, name: &str) -> Result<Endpoint> {
+    let api: Api<Pod> = Api::namespaced(client.to_client(), namespace);
+
+    let pod = api.get(name).await?;
+
+    let namespace_labels = fetch_namespace_labels(client, namespace).await;
+
+    let ip = pod
+        .status
+        .as_ref()
+        .and_then(|status| status.pod_ip.as_deref())
+        .and_then(|ip| ip.parse().ok());
+
+    let ports = pod
+        .spec
+        .iter()
+        .flat_map(|spec| &spec.containers)
+        .flat_map(|container| container.ports.iter().flatten())
+        .filter_map(|port| {
+            port.name.as_ref().map(|name| {
+                (
+                    name.to_string(),
+                    port.container_port,
+                    port.protocol.clone().unwrap_or_else(|| "TCP".to_string()),
+                )
+            })
+        })
+        .collect();
+
+    Ok(Endpoint {
+        namespace: Some(namespace.to_string()),
+        labels: pod.labels().clone(),
+        namespace_labels,
+        ip,
+        ports,
+    })
+}
+
+/// namespaceSelectorの評価に使うnamespaceのラベルを取得する
+///
+/// 取得できない場合は、APIサーバーが自動で付与する `kubernetes.io/metadata.name` のみを使う
+async fn fetch_namespace_labels(client: &KubeClient, namespace: &str) -> BTreeMap<String, String> {
+    let api: Api<Namespace> = Api::all(client.to_client());
+
+    match api.get(namespace).await {
+        Ok(ns) => ns.labels().clone(),
+        Err(err) => {
+            logger!(
+                warn,
+                "Failed to get namespace {}, using the default label. {}",
+                namespace,
+                err
+            );
+
+            BTreeMap::from([(
+                "kubernetes.io/metadata.name".to_string(),
+                namespace.to_string(),
+            )])
+        }
+    }
+}
+
+fn endpoint_name(name: &str, endpoint: &Endpoint) -> String {
+    match &endpoint.ip {
+        Some(ip) => format!("{} ({})", name, ip),
+        None => name.to_string(),
+    }
+}
+
+fn format_decision(direction: Direction, decision: &Decision) -> Vec<String> {
+    match decision {
+        Decision::NotIsolated => {
+            vec![format!(
+                "{}: allowed (no NetworkPolicy selects the pod for {})",
+                direction, direction
+            )]
+        }
+        Decision::Allowed { policy, rule } => {
+            vec![format!(
+                "{}: allowed by {} ({}[{}])",
+                direction, policy, direction, rule
+            )]
+        }
+        Decision::Denied { policies } => {
+            vec![
+                format!("{}: denied", direction),
+                format!("  selected by {}", policies.join(", ")),
+                format!("  no {} rule matches", direction),
+            ]
+        }
+    }
+}
+
+fn format_result(
+    query: &ReachabilityQuery,
+    source: &Endpoint,
+    destination: &Endpoint,
+    egress: &Decision,
+    ingress: Option<&Decision>,
+) -> Vec<String> {
+    let destination_name = match &query.destination {
+        ReachabilityDestination::Pod { namespace, name } => {
+            endpoint_name(&format!("{}/{}", namespace, name), destination)
+        }
+        ReachabilityDestination::Cidr(cidr) => cidr.to_string(),
+    };
+
+    let mut lines = vec![
+        format!(
+            "source:      {}",
+            endpoint_name(&format!("{}/{}", query.namespace, query.pod), source)
+        ),
+        format!("destination: {}", destination_name),
+        format!("port:        {}/{}", query.port, query.protocol),
+        String::new(),
+    ];
+
+    lines.extend(format_decision(Direction::Egress, egress));
+
+    match ingress {
+        Some(ingress) => lines.extend(format_decision(Direction::Ingress, ingress)),
+        None => lines.push("ingress: not evaluated (destination is outside the cluster)".into()),
+    }
+
+    let allowed = egress.is_allowed() && ingress.is_none_or(Decision::is_allowed);
+
+    lines.push(String::new());
+    lines.push(format!(
+        "result: {}",
+        if allowed { "ALLOWED" } else { "DENIED" }
+    ));
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn query(destination: ReachabilityDestination, port: u16, protocol: &str) -> ReachabilityQuery {
+        ReachabilityQuery {
+            namespace: "app".into(),
+            pod: "frontend".into(),
+            destination,
+            port,
+            protocol: protocol.into(),
+        }
+    }
+
+    fn pod(namespace: &str, name: &str) -> ReachabilityDestination {
+        ReachabilityDestination::Pod {
+            namespace: namespace.into(),
+            name: name.into(),
+        }
+    }
+
+    #[rstest]
+    #[case("backend 8080", query(pod("app", "backend"), 8080, "TCP"))]
+    #[case("db/postgres 5432/tcp", query(pod("db", "postgres"), 5432, "TCP"))]
+    #[case("10.0.0.0/8 53/UDP", query(ReachabilityDestination::Cidr("10.0.0.0/8".into()), 53, "UDP"))]
+    #[case("8.8.8.8 443", query(ReachabilityDestination::Cidr("8.8.8.8".into()), 443, "TCP"))]
+    fn 入力から確認する内容を生成する(
+        #[case] input: &str,
+        #[case] expected: ReachabilityQuery,
+    ) {
+        let actual = parse_reachability_query("app", "frontend", input).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("backend")]
+    #[case("backend 8080 extra")]
+    #[case("backend http")]
+    #[case("backend 0")]
+    #[case("backend 8080/ICMP")]
+    #[case("/backend 8080")]
+    fn 不正な入力はエラーを返す(#[case] input: &str) {
+        assert!(parse_reachability_query("app", "frontend", input).is_err());
+    }
+
+    #[test]
+    fn 許可と拒否の理由を表示する() {
+        let source = Endpoint {
+            ip: Some("10.0.0.1".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let destination = Endpoint {
+            ip: Some("10.0.0.2".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let actual = format_result(
+            &query(pod("app", "backend"), 8080, "TCP"),
+            &source,
+            &destination,
+            &Decision::Allowed {
+                policy: "app/allow-egress".into(),
+                rule: 0,
+            },
+            Some(&Decision::Denied {
+                policies: vec!["app/default-deny".into()],
+            }),
+        );
+
+        let expected = indoc! {"
+            source:      app/frontend (10.0.0.1)
+            destination: app/backend (10.0.0.2)
+            port:        8080/TCP
+
+            egress: allowed by app/allow-egress (egress[0])
+            ingress: denied
+              selected by app/default-deny
+              no ingress rule matches
+
+            result: DENIED"
+        };
+
+        assert_eq!(actual.join("\n"), expected);
+    }
+
+    #[test]
+    fn 宛先がクラスタ外のときはingressを評価しない() {
+        let actual = format_result(
+            &query(ReachabilityDestination::Cidr("8.8.8.8".into()), 53, "UDP"),
+            &Endpoint::default(),
+            &Endpoint::default(),
+            &Decision::NotIsolated,
+            None,
+        );
+
+        let expected = indoc! {"
+            source:      app/frontend
+            destination: 8.8.8.8
+            port:        53/UDP
+
+            egress: allowed (no NetworkPolicy selects the pod for egress)
+            ingress: not evaluated (destination is outside the cluster)
+
+            result: ALLOWED"
+        };
+
+        assert_eq!(actual.join("\n"), expected);
+    }
+}
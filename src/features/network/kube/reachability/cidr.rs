@@ -0,0 +1,146 @@
+use std::{fmt::Display, net::IpAddr, str::FromStr};
+
+use anyhow::{anyhow, Result};
+
+/// IPアドレスの範囲
+///
+/// プレフィックス長を省略した場合は単一のアドレス（/32, /128）として扱う
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpCidr {
+    fn max_prefix(addr: &IpAddr) -> u8 {
+        match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    fn bits(addr: &IpAddr) -> u128 {
+        match addr {
+            IpAddr::V4(v4) => u32::from(*v4) as u128,
+            IpAddr::V6(v6) => u128::from(*v6),
+        }
+    }
+
+    /// プレフィックス長の分だけ上位ビットを残したアドレス
+    fn network(&self, addr: &IpAddr) -> u128 {
+        let max = Self::max_prefix(addr);
+
+        let host_bits = u32::from(max - self.prefix);
+
+        Self::bits(addr).checked_shr(host_bits).unwrap_or_default()
+    }
+
+    /// otherの範囲がすべてこの範囲に含まれるかを返す
+    pub fn contains(&self, other: &IpCidr) -> bool {
+        let same_family = matches!(
+            (self.addr, other.addr),
+            (IpAddr::V4(_), IpAddr::V4(_)) | (IpAddr::V6(_), IpAddr::V6(_))
+        );
+
+        same_family
+            && self.prefix <= other.prefix
+            && self.network(&self.addr) == self.network(&other.addr)
+    }
+
+    /// 範囲が重なっているかを返す
+    pub fn overlaps(&self, other: &IpCidr) -> bool {
+        self.contains(other) || other.contains(self)
+    }
+}
+
+impl From<IpAddr> for IpCidr {
+    fn from(addr: IpAddr) -> Self {
+        Self {
+            addr,
+            prefix: Self::max_prefix(&addr),
+        }
+    }
+}
+
+impl FromStr for IpCidr {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|_| anyhow!("Invalid IP address: {}", s))?;
+
+        let max = Self::max_prefix(&addr);
+
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max)
+                .ok_or_else(|| anyhow!("Invalid prefix length: {}", s))?,
+            None => max,
+        };
+
+        Ok(Self { addr, prefix })
+    }
+}
+
+impl Display for IpCidr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.prefix == Self::max_prefix(&self.addr) {
+            write!(f, "{}", self.addr)
+        } else {
+            write!(f, "{}/{}", self.addr, self.prefix)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn cidr(s: &str) -> IpCidr {
+        s.parse().unwrap()
+    }
+
+    #[rstest]
+    #[case("10.0.0.0/8", "10.1.2.3", true)]
+    #[case("10.0.0.0/8", "10.1.0.0/16", true)]
+    #[case("10.0.0.0/16", "10.0.0.0/8", false)]
+    #[case("10.0.0.0/8", "192.168.0.1", false)]
+    #[case("0.0.0.0/0", "192.168.0.1", true)]
+    #[case("192.168.0.1", "192.168.0.1", true)]
+    #[case("fd00::/8", "fd00::1", true)]
+    #[case("fd00::/8", "10.0.0.1", false)]
+    fn 範囲に含まれるかを返す(
+        #[case] outer: &str,
+        #[case] inner: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(cidr(outer).contains(&cidr(inner)), expected);
+    }
+
+    #[rstest]
+    #[case("10.0.0.1")]
+    #[case("10.0.0.0/24")]
+    #[case("fd00::/64")]
+    fn 文字列に変換すると元の表記に戻る(#[case] s: &str) {
+        assert_eq!(cidr(s).to_string(), s);
+    }
+
+    #[rstest]
+    #[case("10.0.0")]
+    #[case("10.0.0.0/33")]
+    #[case("pod")]
+    fn 不正な値はエラーを返す(#[case] s: &str) {
+        assert!(s.parse::<IpCidr>().is_err());
+    }
+}
@@ -0,0 +1,464 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use k8s_openapi::{
+    api::networking::v1::{NetworkPolicy, NetworkPolicyPeer, NetworkPolicyPort},
+    apimachinery::pkg::util::intstr::IntOrString,
+};
+use kube::ResourceExt as _;
+
+use crate::features::network::kube::description::related_resources::label_selector::{
+    LabelSelectorExpression as _, LabelSelectorWrapper,
+};
+
+use super::cidr::IpCidr;
+
+/// 通信の送信元・宛先
+///
+/// クラスタ外のアドレスの場合はnamespaceとlabelsを持たない
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Endpoint {
+    pub namespace: Option<String>,
+    pub labels: BTreeMap<String, String>,
+    pub namespace_labels: BTreeMap<String, String>,
+    pub ip: Option<IpCidr>,
+    /// 名前付きポートを解決するためのコンテナポート（名前, ポート番号, プロトコル）
+    pub ports: Vec<(String, i32, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Ingress,
+    Egress,
+}
+
+impl Direction {
+    fn policy_type(&self) -> &'static str {
+        match self {
+            Self::Ingress => "Ingress",
+            Self::Egress => "Egress",
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ingress => write!(f, "ingress"),
+            Self::Egress => write!(f, "egress"),
+        }
+    }
+}
+
+/// 疎通の判定結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// 対象のPodを選択しているNetworkPolicyがないため、すべての通信が許可される
+    NotIsolated,
+    /// ルールによって許可された
+    Allowed {
+        policy: String,
+        /// ingress・egressの何番目のルールか
+        rule: usize,
+    },
+    /// 対象のPodを選択しているNetworkPolicyのいずれのルールにも一致しない
+    Denied { policies: Vec<String> },
+}
+
+impl Decision {
+    pub fn is_allowed(&self) -> bool {
+        !matches!(self, Self::Denied { .. })
+    }
+}
+
+/// 通信の宛先のポート
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Port {
+    pub number: u16,
+    pub protocol: String,
+}
+
+/// ingressのfromまたはegressのtoと、portsの組
+type Rule<'a> = (
+    Option<&'a Vec<NetworkPolicyPeer>>,
+    Option<&'a Vec<NetworkPolicyPort>>,
+);
+
+/// NetworkPolicyのingressまたはegressのルールで、sourceからdestinationへの通信が許可されるかを判定する
+///
+/// - ingressはdestinationを、egressはsourceを選択しているNetworkPolicyを評価する
+/// - 名前付きポートはdestinationのコンテナポートから解決する
+pub fn evaluate(
+    direction: Direction,
+    policies: &[NetworkPolicy],
+    source: &Endpoint,
+    destination: &Endpoint,
+    port: &Port,
+) -> Decision {
+    let (subject, peer) = match direction {
+        Direction::Ingress => (destination, source),
+        Direction::Egress => (source, destination),
+    };
+
+    let Some(subject_namespace) = subject.namespace.as_deref() else {
+        return Decision::NotIsolated;
+    };
+
+    let selecting: Vec<&NetworkPolicy> = policies
+        .iter()
+        .filter(|policy| policy.namespace().as_deref() == Some(subject_namespace))
+        .filter(|policy| selects(policy, &subject.labels, direction))
+        .collect();
+
+    if selecting.is_empty() {
+        return Decision::NotIsolated;
+    }
+
+    for policy in &selecting {
+        let Some(spec) = policy.spec.as_ref() else {
+            continue;
+        };
+
+        let rules: Vec<Rule> = match direction {
+            Direction::Ingress => spec
+                .ingress
+                .iter()
+                .flatten()
+                .map(|rule| (rule.from.as_ref(), rule.ports.as_ref()))
+                .collect(),
+            Direction::Egress => spec
+                .egress
+                .iter()
+                .flatten()
+                .map(|rule| (rule.to.as_ref(), rule.ports.as_ref()))
+                .collect(),
+        };
+
+        let matched = rules.iter().position(|(peers, ports)| {
+            let peer_matched = match peers {
+                Some(peers) if !peers.is_empty() => peers
+                    .iter()
+                    .any(|p| matches_peer(p, subject_namespace, peer)),
+                _ => true,
+            };
+
+            let port_matched = match ports {
+                Some(ports) if !ports.is_empty() => {
+                    ports.iter().any(|p| matches_port(p, destination, port))
+                }
+                _ => true,
+            };
+
+            peer_matched && port_matched
+        });
+
+        if let Some(rule) = matched {
+            return Decision::Allowed {
+                policy: policy_name(policy),
+                rule,
+            };
+        }
+    }
+
+    Decision::Denied {
+        policies: selecting.iter().map(|policy| policy_name(policy)).collect(),
+    }
+}
+
+fn policy_name(policy: &NetworkPolicy) -> String {
+    format!(
+        "{}/{}",
+        policy.namespace().unwrap_or_default(),
+        policy.name_any()
+    )
+}
+
+/// NetworkPolicyがPodを選択していて、指定した方向の通信を対象にしているかを返す
+///
+/// policyTypesを省略した場合は、Ingressと、egressルールがあればEgressを対象にする
+fn selects(
+    policy: &NetworkPolicy,
+    labels: &BTreeMap<String, String>,
+    direction: Direction,
+) -> bool {
+    let Some(spec) = policy.spec.as_ref() else {
+        return false;
+    };
+
+    let has_type = match &spec.policy_types {
+        Some(types) => types.iter().any(|t| t == direction.policy_type()),
+        None => match direction {
+            Direction::Ingress => true,
+            Direction::Egress => spec.egress.is_some(),
+        },
+    };
+
+    has_type && LabelSelectorWrapper::from(spec.pod_selector.clone()).expression(labels)
+}
+
+fn matches_peer(peer: &NetworkPolicyPeer, policy_namespace: &str, endpoint: &Endpoint) -> bool {
+    if let Some(ip_block) = &peer.ip_block {
+        let (Some(ip), Ok(cidr)) = (&endpoint.ip, ip_block.cidr.parse::<IpCidr>()) else {
+            return false;
+        };
+
+        let excepted = ip_block
+            .except
+            .iter()
+            .flatten()
+            .filter_map(|except| except.parse::<IpCidr>().ok())
+            .any(|except| except.overlaps(ip));
+
+        return cidr.contains(ip) && !excepted;
+    }
+
+    // podSelectorとnamespaceSelectorはクラスタ内のPodにのみ一致する
+    let Some(namespace) = endpoint.namespace.as_deref() else {
+        return false;
+    };
+
+    let namespace_matched = match &peer.namespace_selector {
+        Some(selector) => {
+            LabelSelectorWrapper::from(selector.clone()).expression(&endpoint.namespace_labels)
+        }
+        None => namespace == policy_namespace,
+    };
+
+    let pod_matched = match &peer.pod_selector {
+        Some(selector) => LabelSelectorWrapper::from(selector.clone()).expression(&endpoint.labels),
+        None => true,
+    };
+
+    namespace_matched && pod_matched
+}
+
+fn matches_port(policy_port: &NetworkPolicyPort, destination: &Endpoint, port: &Port) -> bool {
+    let protocol = policy_port.protocol.as_deref().unwrap_or("TCP");
+
+    if !protocol.eq_ignore_ascii_case(&port.protocol) {
+        return false;
+    }
+
+    let number = i32::from(port.number);
+
+    match &policy_port.port {
+        None => true,
+        Some(IntOrString::Int(start)) => {
+            let end = policy_port.end_port.unwrap_or(*start);
+
+            (*start..=end).contains(&number)
+        }
+        Some(IntOrString::String(name)) => {
+            destination
+                .ports
+                .iter()
+                .any(|(port_name, port_number, port_protocol)| {
+                    port_name == name
+                        && *port_number == number
+                        && port_protocol.eq_ignore_ascii_case(protocol)
+                })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn policies() -> Vec<NetworkPolicy> {
+        serde_yaml::from_str(indoc! {r#"
+            - metadata:
+                name: default-deny
+                namespace: app
+              spec:
+                podSelector: {}
+                policyTypes:
+                  - Ingress
+                  - Egress
+            - metadata:
+                name: allow-frontend
+                namespace: app
+              spec:
+                podSelector:
+                  matchLabels:
+                    app: backend
+                ingress:
+                  - from:
+                      - podSelector:
+                          matchLabels:
+                            app: other
+                  - from:
+                      - podSelector:
+                          matchLabels:
+                            app: frontend
+                    ports:
+                      - port: http
+            - metadata:
+                name: allow-egress
+                namespace: app
+              spec:
+                podSelector:
+                  matchLabels:
+                    app: frontend
+                egress:
+                  - to:
+                      - namespaceSelector:
+                          matchLabels:
+                            kubernetes.io/metadata.name: app
+                    ports:
+                      - port: 8000
+                        endPort: 9000
+                  - to:
+                      - ipBlock:
+                          cidr: 10.0.0.0/8
+                          except:
+                            - 10.1.0.0/16
+                policyTypes:
+                  - Egress
+        "#})
+        .unwrap()
+    }
+
+    fn pod(app: &str, ip: &str) -> Endpoint {
+        Endpoint {
+            namespace: Some("app".into()),
+            labels: BTreeMap::from([("app".into(), app.into())]),
+            namespace_labels: BTreeMap::from([(
+                "kubernetes.io/metadata.name".into(),
+                "app".into(),
+            )]),
+            ip: Some(ip.parse().unwrap()),
+            ports: vec![("http".into(), 8080, "TCP".into())],
+        }
+    }
+
+    fn external(cidr: &str) -> Endpoint {
+        Endpoint {
+            ip: Some(cidr.parse().unwrap()),
+            ..Default::default()
+        }
+    }
+
+    fn tcp(number: u16) -> Port {
+        Port {
+            number,
+            protocol: "TCP".into(),
+        }
+    }
+
+    #[test]
+    fn 名前付きポートに一致するingressルールで許可する() {
+        let actual = evaluate(
+            Direction::Ingress,
+            &policies(),
+            &pod("frontend", "10.0.0.1"),
+            &pod("backend", "10.0.0.2"),
+            &tcp(8080),
+        );
+
+        assert_eq!(
+            actual,
+            Decision::Allowed {
+                policy: "app/allow-frontend".into(),
+                rule: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn いずれのルールにも一致しないとき選択しているnetworkpolicyを返す() {
+        let actual = evaluate(
+            Direction::Ingress,
+            &policies(),
+            &pod("frontend", "10.0.0.1"),
+            &pod("backend", "10.0.0.2"),
+            &tcp(9090),
+        );
+
+        assert_eq!(
+            actual,
+            Decision::Denied {
+                policies: vec!["app/default-deny".into(), "app/allow-frontend".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn end_portまでのポート範囲に一致するegressルールで許可する() {
+        let actual = evaluate(
+            Direction::Egress,
+            &policies(),
+            &pod("frontend", "10.0.0.1"),
+            &pod("backend", "10.0.0.2"),
+            &tcp(8080),
+        );
+
+        assert_eq!(
+            actual,
+            Decision::Allowed {
+                policy: "app/allow-egress".into(),
+                rule: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn ip_blockのexceptに含まれる宛先は許可しない() {
+        let policies = policies();
+        let source = pod("frontend", "10.0.0.1");
+
+        assert_eq!(
+            evaluate(
+                Direction::Egress,
+                &policies,
+                &source,
+                &external("10.2.0.0/24"),
+                &tcp(443)
+            ),
+            Decision::Allowed {
+                policy: "app/allow-egress".into(),
+                rule: 1,
+            }
+        );
+
+        assert!(!evaluate(
+            Direction::Egress,
+            &policies,
+            &source,
+            &external("10.1.2.3"),
+            &tcp(443)
+        )
+        .is_allowed());
+    }
+
+    #[test]
+    fn 選択しているnetworkpolicyがないときは隔離されていない() {
+        let actual = evaluate(
+            Direction::Ingress,
+            &policies(),
+            &pod("frontend", "10.0.0.1"),
+            &external("192.168.0.1"),
+            &tcp(443),
+        );
+
+        assert_eq!(actual, Decision::NotIsolated);
+    }
+
+    #[test]
+    fn プロトコルが一致しないポートは許可しない() {
+        let actual = evaluate(
+            Direction::Egress,
+            &policies(),
+            &pod("frontend", "10.0.0.1"),
+            &pod("backend", "172.16.0.2"),
+            &Port {
+                number: 8080,
+                protocol: "UDP".into(),
+            },
+        );
+
+        assert!(!actual.is_allowed());
+    }
+}
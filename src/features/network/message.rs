@@ -3,7 +3,7 @@ use strum::EnumString;
 
 use crate::{kube::table::KubeTable, message::Message, workers::kube::message::Kube};
 
-#[derive(Copy, Clone, Default, Debug, EnumString)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, EnumString)]
 #[strum(serialize_all = "lowercase")]
 pub enum GatewayVersion {
     #[default]
@@ -11,7 +11,7 @@ pub enum GatewayVersion {
     V1Beta1,
 }
 
-#[derive(Copy, Clone, Default, Debug, EnumString)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, EnumString)]
 #[strum(serialize_all = "lowercase")]
 pub enum HTTPRouteVersion {
     #[default]
@@ -19,6 +19,14 @@ pub enum HTTPRouteVersion {
     V1Beta1,
 }
 
+#[derive(Copy, Clone, Default, Debug, PartialEq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum ReferenceGrantVersion {
+    #[default]
+    V1Beta1,
+    V1Alpha2,
+}
+
 #[derive(Debug, Clone)]
 pub struct NetworkRequestTargetParams {
     pub name: String,
@@ -34,18 +42,46 @@ pub enum NetworkRequest {
     NetworkPolicy(NetworkRequestTargetParams),
     Gateway(NetworkRequestTargetParams),
     HTTPRoute(NetworkRequestTargetParams),
+    GRPCRoute(NetworkRequestTargetParams),
+    TCPRoute(NetworkRequestTargetParams),
+    TLSRoute(NetworkRequestTargetParams),
+    UDPRoute(NetworkRequestTargetParams),
+    ReferenceGrant(NetworkRequestTargetParams),
+}
+
+/// NetworkPolicyによる疎通の確認で指定する宛先
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReachabilityDestination {
+    Pod {
+        namespace: String,
+        name: String,
+    },
+    /// クラスタ外のIPアドレスまたはCIDR
+    Cidr(String),
+}
+
+/// 送信元のPodから宛先への通信がNetworkPolicyで許可されるかを確認する
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReachabilityQuery {
+    pub namespace: String,
+    pub pod: String,
+    pub destination: ReachabilityDestination,
+    pub port: u16,
+    pub protocol: String,
 }
 
 #[derive(Debug)]
 pub enum NetworkResponse {
     List(Result<KubeTable>),
     Yaml(Result<Vec<String>>),
+    Reachability(Result<Vec<String>>),
 }
 
 #[derive(Debug)]
 pub enum NetworkMessage {
     Request(NetworkRequest),
     Response(NetworkResponse),
+    Reachability(ReachabilityQuery),
 }
 
 impl NetworkRequest {
@@ -57,6 +93,11 @@ impl NetworkRequest {
             Self::NetworkPolicy(data) => data,
             Self::Gateway(data) => data,
             Self::HTTPRoute(data) => data,
+            Self::GRPCRoute(data) => data,
+            Self::TCPRoute(data) => data,
+            Self::TLSRoute(data) => data,
+            Self::UDPRoute(data) => data,
+            Self::ReferenceGrant(data) => data,
         }
     }
 }
@@ -84,3 +125,9 @@ impl From<NetworkResponse> for Message {
         NetworkMessage::Response(res).into()
     }
 }
+
+impl From<ReachabilityQuery> for Message {
+    fn from(query: ReachabilityQuery) -> Self {
+        NetworkMessage::Reachability(query).into()
+    }
+}
@@ -1,4 +1,6 @@
+mod reachability;
 mod tab;
 mod widgets;
 
+pub use reachability::*;
 pub use tab::*;
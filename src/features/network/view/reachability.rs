@@ -0,0 +1,153 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossbeam::channel::Sender;
+use k8s_openapi::{api::core::v1::Pod, Resource as _};
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    config::theme::WidgetThemeConfig,
+    features::{
+        component_id::{
+            NETWORK_REACHABILITY_DIALOG_ID, NETWORK_REACHABILITY_RESULT_DIALOG_ID,
+            NETWORK_WIDGET_ID,
+        },
+        network::kube::parse_reachability_query,
+    },
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{
+            InputForm, InputFormTheme, Item, LiteralItem, SelectedItem, Text, TextTheme, Widget,
+            WidgetBase, WidgetTheme, WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+const TITLE: &str = "Reachability";
+
+/// 送信元のPod（namespace, 名前）
+///
+/// Networkテーブルで選択したPodを、宛先の入力ダイアログと共有する
+type SharedReachabilitySource = Rc<RefCell<(String, String)>>;
+
+/// NetworkPolicyによる疎通を確認するためのダイアログ群
+pub struct ReachabilityDialogs {
+    pub input_dialog: Widget<'static>,
+    pub result_dialog: Widget<'static>,
+    source: SharedReachabilitySource,
+}
+
+impl ReachabilityDialogs {
+    pub fn new(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Self {
+        let source = SharedReachabilitySource::default();
+
+        Self {
+            input_dialog: input_widget(tx.clone(), source.clone(), theme.clone()),
+            result_dialog: result_widget(theme),
+            source,
+        }
+    }
+
+    /// Networkテーブルで選択しているPodを送信元として、宛先の入力ダイアログを開く
+    pub fn open(&self) -> impl Fn(&mut Window) -> EventResult {
+        let source = self.source.clone();
+
+        move |w: &mut Window| {
+            let widget = w.find_widget(NETWORK_WIDGET_ID);
+
+            let Some(SelectedItem::TableRow { metadata, .. }) = widget.widget_item() else {
+                return EventResult::Ignore;
+            };
+
+            let Some(ref metadata) = metadata else {
+                return EventResult::Ignore;
+            };
+
+            let (Some(namespace), Some(name), Some(Pod::KIND)) = (
+                metadata.get("namespace"),
+                metadata.get("name"),
+                metadata.get("kind").map(String::as_str),
+            ) else {
+                return EventResult::Ignore;
+            };
+
+            *source.borrow_mut() = (namespace.to_string(), name.to_string());
+
+            w.widget_clear(NETWORK_REACHABILITY_DIALOG_ID);
+
+            *w.find_widget_mut(NETWORK_REACHABILITY_DIALOG_ID)
+                .widget_base_mut()
+                .append_title_mut() = Some(format!(" : from {}/{}", namespace, name).into());
+
+            w.open_dialog(NETWORK_REACHABILITY_DIALOG_ID);
+
+            EventResult::Nop
+        }
+    }
+}
+
+fn input_widget(
+    tx: Sender<Message>,
+    source: SharedReachabilitySource,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    let widget_base = WidgetBase::builder()
+        .title(TITLE)
+        .theme(WidgetTheme::from(theme.clone()))
+        .build();
+
+    InputForm::builder()
+        .id(NETWORK_REACHABILITY_DIALOG_ID)
+        .widget_base(widget_base)
+        .theme(InputFormTheme::from(theme))
+        .prefix("to: ")
+        .actions(UserEvent::from(KeyCode::Enter), move |w: &mut Window| {
+            let widget = w.find_widget(NETWORK_REACHABILITY_DIALOG_ID);
+
+            let Some(SelectedItem::Literal { item, .. }) = widget.widget_item() else {
+                return EventResult::Ignore;
+            };
+
+            let (namespace, pod) = source.borrow().clone();
+
+            w.widget_clear(NETWORK_REACHABILITY_RESULT_DIALOG_ID);
+
+            let result = w.find_widget_mut(NETWORK_REACHABILITY_RESULT_DIALOG_ID);
+
+            *result.widget_base_mut().append_title_mut() =
+                Some(format!(" : {}/{} -> {}", namespace, pod, item.trim()).into());
+
+            match parse_reachability_query(&namespace, &pod, &item) {
+                Ok(query) => {
+                    tx.send(query.into())
+                        .expect("Failed to send NetworkMessage::Reachability");
+                }
+                Err(err) => {
+                    result
+                        .update_widget_item(Item::Array(vec![LiteralItem::from(err.to_string())]));
+                }
+            }
+
+            w.open_dialog(NETWORK_REACHABILITY_RESULT_DIALOG_ID);
+
+            EventResult::Nop
+        })
+        .build()
+        .into()
+}
+
+fn result_widget(theme: WidgetThemeConfig) -> Widget<'static> {
+    let widget_base = WidgetBase::builder()
+        .title(TITLE)
+        .theme(WidgetTheme::from(theme.clone()))
+        .build();
+
+    Text::builder()
+        .id(NETWORK_REACHABILITY_RESULT_DIALOG_ID)
+        .widget_base(widget_base)
+        .theme(TextTheme::from(theme))
+        .wrap()
+        .build()
+        .into()
+}
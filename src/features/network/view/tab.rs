@@ -5,10 +5,13 @@ use ratatui::layout::{Constraint, Direction};
 
 use crate::{
     clipboard::Clipboard,
-    config::theme::WidgetThemeConfig,
+    config::{keybindings::NetworkKeyBindings, theme::WidgetThemeConfig},
     features::{
         component_id::NETWORK_TAB_ID,
-        network::view::widgets::{description_widget, network_widget},
+        network::view::{
+            widgets::{description_widget, network_widget},
+            ReachabilityDialogs,
+        },
     },
     message::Message,
     ui::{
@@ -19,6 +22,7 @@ use crate::{
 
 pub struct NetworkTab {
     pub tab: Tab<'static>,
+    pub reachability_dialogs: ReachabilityDialogs,
 }
 
 impl NetworkTab {
@@ -27,9 +31,12 @@ impl NetworkTab {
         tx: &Sender<Message>,
         clipboard: &Option<Rc<RefCell<Clipboard>>>,
         split_direction: Direction,
+        keybindings: NetworkKeyBindings,
         theme: WidgetThemeConfig,
     ) -> Self {
-        let network_widget = network_widget(tx, theme.clone());
+        let reachability_dialogs = ReachabilityDialogs::new(tx, theme.clone());
+
+        let network_widget = network_widget(tx, keybindings, &reachability_dialogs, theme.clone());
         let description_widget = description_widget(clipboard, theme);
 
         let layout = TabLayout::new(layout, split_direction);
@@ -41,6 +48,7 @@ impl NetworkTab {
                 [network_widget, description_widget],
                 layout,
             ),
+            reachability_dialogs,
         }
     }
 }
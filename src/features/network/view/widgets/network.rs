@@ -8,24 +8,38 @@ use k8s_openapi::{
 };
 
 use crate::{
-    config::theme::WidgetThemeConfig,
+    config::{keybindings::NetworkKeyBindings, theme::WidgetThemeConfig},
     features::{
-        component_id::{NETWORK_DESCRIPTION_WIDGET_ID, NETWORK_WIDGET_ID},
-        network::message::{NetworkRequest, NetworkRequestTargetParams},
+        component_id::{
+            NETWORK_DESCRIPTION_WIDGET_ID, NETWORK_WIDGET_ID, PORT_FORWARD_TARGET_DIALOG_ID,
+        },
+        network::{
+            message::{NetworkRequest, NetworkRequestTargetParams},
+            view::ReachabilityDialogs,
+        },
+        port_forward::message::{PortForwardKind, PortForwardRequest},
+    },
+    kube::apis::networking::gateway::{
+        v1::{Gateway, HTTPRoute},
+        v1alpha2::{GRPCRoute, ReferenceGrant, TCPRoute, TLSRoute, UDPRoute},
     },
-    kube::apis::networking::gateway::v1::{Gateway, HTTPRoute},
     message::Message,
     ui::{
         event::EventResult,
         widget::{
-            FilterForm, FilterFormTheme, Table, TableItem, TableTheme, Widget, WidgetBase,
-            WidgetTheme, WidgetTrait as _,
+            FilterForm, FilterFormTheme, SelectedItem, Table, TableItem, TableTheme, Widget,
+            WidgetBase, WidgetTheme, WidgetTrait as _,
         },
         Window, WindowAction,
     },
 };
 
-pub fn network_widget(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Widget<'static> {
+pub fn network_widget(
+    tx: &Sender<Message>,
+    keybindings: NetworkKeyBindings,
+    reachability: &ReachabilityDialogs,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
     let tx = tx.clone();
 
     let widget_theme = WidgetTheme::from(theme.clone());
@@ -46,7 +60,9 @@ pub fn network_widget(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Widget<
         .theme(table_theme)
         .filtered_key("NAME")
         .block_injection(block_injection())
-        .on_select(on_select(tx))
+        .on_select(on_select(tx.clone()))
+        .key_action(&keybindings.port_forward, open_port_forward_dialog(tx))
+        .key_action(&keybindings.reachability, reachability.open())
         .build()
         .into()
 }
@@ -126,6 +142,26 @@ fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &TableItem) -> EventRe
                 tx.send(NetworkRequest::HTTPRoute(request_data).into())
                     .expect("Failed to send NetworkRequest::HTTPRoute");
             }
+            GRPCRoute::KIND => {
+                tx.send(NetworkRequest::GRPCRoute(request_data).into())
+                    .expect("Failed to send NetworkRequest::GRPCRoute");
+            }
+            TCPRoute::KIND => {
+                tx.send(NetworkRequest::TCPRoute(request_data).into())
+                    .expect("Failed to send NetworkRequest::TCPRoute");
+            }
+            TLSRoute::KIND => {
+                tx.send(NetworkRequest::TLSRoute(request_data).into())
+                    .expect("Failed to send NetworkRequest::TLSRoute");
+            }
+            UDPRoute::KIND => {
+                tx.send(NetworkRequest::UDPRoute(request_data).into())
+                    .expect("Failed to send NetworkRequest::UDPRoute");
+            }
+            ReferenceGrant::KIND => {
+                tx.send(NetworkRequest::ReferenceGrant(request_data).into())
+                    .expect("Failed to send NetworkRequest::ReferenceGrant");
+            }
             _ => {
                 unreachable!()
             }
@@ -134,3 +170,46 @@ fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &TableItem) -> EventRe
         EventResult::WindowAction(WindowAction::Continue)
     }
 }
+
+fn open_port_forward_dialog(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let widget = w.find_widget(NETWORK_WIDGET_ID);
+
+        let Some(SelectedItem::TableRow { metadata, .. }) = widget.widget_item() else {
+            return EventResult::Ignore;
+        };
+
+        let Some(ref metadata) = metadata else {
+            return EventResult::Ignore;
+        };
+
+        let (Some(namespace), Some(name), Some(kind)) = (
+            metadata.get("namespace"),
+            metadata.get("name"),
+            metadata.get("kind"),
+        ) else {
+            return EventResult::Ignore;
+        };
+
+        let kind = match kind.as_str() {
+            Pod::KIND => PortForwardKind::Pod,
+            Service::KIND => PortForwardKind::Service,
+            _ => return EventResult::Ignore,
+        };
+
+        tx.send(
+            PortForwardRequest::Targets {
+                kind,
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+            }
+            .into(),
+        )
+        .expect("Failed to send PortForwardRequest::Targets");
+
+        w.widget_clear(PORT_FORWARD_TARGET_DIALOG_ID);
+        w.open_dialog(PORT_FORWARD_TARGET_DIALOG_ID);
+
+        EventResult::Nop
+    }
+}
@@ -0,0 +1,6 @@
+mod description;
+mod node;
+mod resources;
+
+pub use description::*;
+pub use node::*;
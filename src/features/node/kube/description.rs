@@ -0,0 +1,348 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossbeam::channel::Sender;
+use k8s_openapi::{
+    api::core::v1::{Node, Pod},
+    List,
+};
+use kube::{Resource as _, ResourceExt as _};
+use serde_yaml::{Mapping, Value};
+
+use crate::{
+    features::node::message::{NodeRequest, NodeResponse},
+    kube::{
+        quantity::{format_cpu_millis, format_memory_bytes},
+        KubeClientRequest,
+    },
+    logger,
+    message::Message,
+    workers::kube::AbortWorker,
+};
+
+use super::{
+    node::format_percentage,
+    resources::{
+        is_terminated, node_roles, node_taints, pod_node_name, NodeAllocated, ResourceAmount,
+    },
+};
+
+const INTERVAL: u64 = 3;
+
+#[derive(Clone)]
+pub struct NodeDescriptionWorker<C>
+where
+    C: KubeClientRequest,
+{
+    tx: Sender<Message>,
+    client: C,
+    req: NodeRequest,
+}
+
+impl<C> NodeDescriptionWorker<C>
+where
+    C: KubeClientRequest,
+{
+    pub fn new(tx: Sender<Message>, client: C, req: NodeRequest) -> Self {
+        Self { tx, client, req }
+    }
+}
+
+#[async_trait]
+impl<C> AbortWorker for NodeDescriptionWorker<C>
+where
+    C: KubeClientRequest,
+{
+    async fn run(&self) {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(INTERVAL));
+
+        loop {
+            interval.tick().await;
+
+            let description = fetch_node_description(&self.client, &self.req.name).await;
+
+            if let Err(e) = &description {
+                logger!(error, "Failed to fetch node description: {:?}", e);
+            }
+
+            self.tx
+                .send(NodeResponse::Description(description).into())
+                .expect("Failed to send NodeResponse::Description");
+        }
+    }
+}
+
+/// Nodeの状態と、Nodeで実行されているPodの一覧をYAML形式で返す
+async fn fetch_node_description<C: KubeClientRequest>(
+    client: &C,
+    name: &str,
+) -> Result<Vec<String>> {
+    let node: Node = client
+        .request(&format!("{}/{}", Node::url_path(&(), None), name))
+        .await?;
+
+    let pods: List<Pod> = client.request(&Pod::url_path(&(), None)).await?;
+
+    let pods: Vec<&Pod> = pods
+        .items
+        .iter()
+        .filter(|pod| pod_node_name(pod) == Some(name) && !is_terminated(pod))
+        .collect();
+
+    let mut root = Mapping::new();
+
+    root.insert("node".into(), node_value(&node, &pods)?);
+
+    let pods: Vec<Value> = pods
+        .iter()
+        .map(|pod| format!("{}/{}", pod.namespace().unwrap_or_default(), pod.name_any()).into())
+        .collect();
+
+    root.insert("pods".into(), pods.into());
+
+    Ok(serde_yaml::to_string(&root)?
+        .lines()
+        .map(ToString::to_string)
+        .collect())
+}
+
+fn node_value(node: &Node, pods: &[&Pod]) -> Result<Value> {
+    let mut value = Mapping::new();
+
+    value.insert("name".into(), node.name_any().into());
+
+    value.insert("roles".into(), node_roles(node).into());
+
+    if let Some(conditions) = node
+        .status
+        .as_ref()
+        .and_then(|status| status.conditions.as_ref())
+    {
+        let conditions: Vec<Value> = conditions
+            .iter()
+            .map(|condition| {
+                let mut value = Mapping::new();
+
+                value.insert("type".into(), condition.type_.clone().into());
+                value.insert("status".into(), condition.status.clone().into());
+
+                if let Some(reason) = &condition.reason {
+                    value.insert("reason".into(), reason.clone().into());
+                }
+
+                if let Some(message) = &condition.message {
+                    value.insert("message".into(), message.clone().into());
+                }
+
+                if let Some(time) = &condition.last_transition_time {
+                    value.insert("lastTransitionTime".into(), time.0.to_rfc3339().into());
+                }
+
+                value.into()
+            })
+            .collect();
+
+        value.insert("conditions".into(), conditions.into());
+    }
+
+    let taints = node_taints(node);
+
+    if !taints.is_empty() {
+        value.insert("taints".into(), serde_yaml::to_value(taints)?);
+    }
+
+    if let Some(labels) = &node.metadata.labels {
+        value.insert("labels".into(), serde_yaml::to_value(labels)?);
+    }
+
+    if let Some(status) = &node.status {
+        if let Some(capacity) = &status.capacity {
+            value.insert("capacity".into(), serde_yaml::to_value(capacity)?);
+        }
+
+        if let Some(allocatable) = &status.allocatable {
+            value.insert("allocatable".into(), serde_yaml::to_value(allocatable)?);
+        }
+    }
+
+    value.insert("allocated".into(), allocated_value(node, pods));
+
+    if let Some(info) = node.status.as_ref().and_then(|s| s.node_info.as_ref()) {
+        let mut value_info = Mapping::new();
+
+        value_info.insert("kubeletVersion".into(), info.kubelet_version.clone().into());
+        value_info.insert(
+            "containerRuntimeVersion".into(),
+            info.container_runtime_version.clone().into(),
+        );
+        value_info.insert("osImage".into(), info.os_image.clone().into());
+        value_info.insert("kernelVersion".into(), info.kernel_version.clone().into());
+        value_info.insert("architecture".into(), info.architecture.clone().into());
+
+        value.insert("nodeInfo".into(), value_info.into());
+    }
+
+    Ok(value.into())
+}
+
+/// kubectl describe nodeのAllocated resourcesと同じく、割り当て可能な量に対する割合を併記する
+fn allocated_value(node: &Node, pods: &[&Pod]) -> Value {
+    let allocatable = ResourceAmount::allocatable(node);
+
+    let mut allocated = NodeAllocated::default();
+
+    for pod in pods {
+        allocated.add_pod(pod);
+    }
+
+    let cpu = |millis: u64| {
+        format!(
+            "{} ({})",
+            format_cpu_millis(millis),
+            format_percentage(millis, allocatable.cpu_millis)
+        )
+    };
+
+    let memory = |bytes: u64| {
+        format!(
+            "{} ({})",
+            format_memory_bytes(bytes),
+            format_percentage(bytes, allocatable.memory_bytes)
+        )
+    };
+
+    let mut requests = Mapping::new();
+    requests.insert("cpu".into(), cpu(allocated.requests.cpu_millis).into());
+    requests.insert(
+        "memory".into(),
+        memory(allocated.requests.memory_bytes).into(),
+    );
+
+    let mut limits = Mapping::new();
+    limits.insert("cpu".into(), cpu(allocated.limits.cpu_millis).into());
+    limits.insert(
+        "memory".into(),
+        memory(allocated.limits.memory_bytes).into(),
+    );
+
+    let mut value = Mapping::new();
+    value.insert("requests".into(), requests.into());
+    value.insert("limits".into(), limits.into());
+
+    value.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+
+    use crate::{kube::mock::MockTestKubeClient, mock_expect};
+
+    fn node() -> Node {
+        serde_yaml::from_str(indoc! {
+            r#"
+            metadata:
+              name: worker
+              labels:
+                kubernetes.io/hostname: worker
+            spec:
+              taints:
+                - key: dedicated
+                  value: gpu
+                  effect: NoSchedule
+            status:
+              allocatable:
+                cpu: "2"
+                memory: 4Gi
+              conditions:
+                - type: Ready
+                  status: "True"
+                  reason: KubeletReady
+            "#
+        })
+        .unwrap()
+    }
+
+    fn pods() -> List<Pod> {
+        serde_yaml::from_str(indoc! {
+            r#"
+            items:
+              - metadata:
+                  name: app
+                  namespace: default
+                spec:
+                  nodeName: worker
+                  containers:
+                    - name: a
+                      resources:
+                        requests:
+                          cpu: 500m
+                          memory: 1Gi
+                status:
+                  phase: Running
+              - metadata:
+                  name: other
+                  namespace: default
+                spec:
+                  nodeName: other
+                  containers:
+                    - name: a
+                status:
+                  phase: Running
+            "#
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn nodeの状態とnodeで実行されているpodを返す() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            [
+                (Node, eq("/api/v1/nodes/worker"), Ok(node())),
+                (List<Pod>, eq("/api/v1/pods"), Ok(pods()))
+            ]
+        );
+
+        let actual = fetch_node_description(&client, "worker").await.unwrap();
+
+        let expected: Vec<String> = indoc! {
+            r#"
+            node:
+              name: worker
+              roles: <none>
+              conditions:
+              - type: Ready
+                status: 'True'
+                reason: KubeletReady
+              taints:
+              - dedicated=gpu:NoSchedule
+              labels:
+                kubernetes.io/hostname: worker
+              allocatable:
+                cpu: '2'
+                memory: 4Gi
+              allocated:
+                requests:
+                  cpu: 500m (25%)
+                  memory: 1024Mi (25%)
+                limits:
+                  cpu: 0m (0%)
+                  memory: 0Mi (0%)
+            pods:
+            - default/app
+            "#
+        }
+        .lines()
+        .map(ToString::to_string)
+        .collect();
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,437 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{self, Instant},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use crossbeam::channel::Sender;
+use k8s_openapi::{
+    api::core::v1::{Node, Pod},
+    Resource as _,
+};
+use kube::ResourceExt as _;
+
+use crate::{
+    features::node::message::NodeResponse,
+    kube::{
+        apis::metrics::NodeMetricsList,
+        informer::{ChangeDetector, SharedInformers},
+        quantity::{format_cpu_millis, format_memory_bytes, percentage},
+        table::{translate_timestamp_since, KubeTable, KubeTableRow},
+        KubeClient, KubeClientRequest,
+    },
+    logger,
+    message::Message,
+    workers::kube::{Worker, WorkerResult},
+};
+
+use super::resources::{
+    is_terminated, node_roles, node_status, pod_node_name, NodeAllocated, ResourceAmount,
+};
+
+/// metrics-serverから使用量を取得する間隔
+const METRICS_INTERVAL: time::Duration = time::Duration::from_secs(5);
+
+/// Nodeタブを開いたか
+///
+/// Nodeタブを開くまでは、NodeとすべてのnamespaceのPodのwatchを開始しない
+pub type SharedNodeTabOpened = Arc<AtomicBool>;
+
+#[derive(Clone)]
+pub struct NodePoller {
+    tx: Sender<Message>,
+    kube_client: KubeClient,
+    informers: SharedInformers,
+    tab_opened: SharedNodeTabOpened,
+}
+
+impl NodePoller {
+    pub fn new(
+        tx: Sender<Message>,
+        kube_client: KubeClient,
+        informers: SharedInformers,
+        tab_opened: SharedNodeTabOpened,
+    ) -> Self {
+        Self {
+            tx,
+            kube_client,
+            informers,
+            tab_opened,
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for NodePoller {
+    type Output = WorkerResult;
+
+    async fn run(&self) -> Self::Output {
+        let mut interval = tokio::time::interval(time::Duration::from_secs(1));
+
+        while !self.tab_opened.load(Ordering::Relaxed) {
+            interval.tick().await;
+        }
+
+        let node_informer = self.informers.cluster::<Node>();
+
+        // 割り当て量の集計に使うため、すべてのnamespaceのPodを監視する
+        let pod_informer = self.informers.cluster_pod();
+
+        let mut metrics = NodeMetricsCache::default();
+
+        let mut detector = ChangeDetector::default();
+
+        loop {
+            interval.tick().await;
+
+            let (Some(nodes), Some(pods)) = (node_informer.state(), pod_informer.state()) else {
+                continue;
+            };
+
+            metrics.refresh(&self.kube_client).await;
+
+            let table = nodes
+                .and_then(|nodes| pods.map(|pods| node_table(&nodes, &pods, metrics.usages())));
+
+            if !detector.is_changed(&table) {
+                continue;
+            }
+
+            self.tx
+                .send(NodeResponse::List(table).into())
+                .expect("Failed to send NodeResponse::List");
+        }
+    }
+}
+
+/// Nodeの使用量を一定間隔で取得し、次の取得まで保持する
+#[derive(Debug, Default)]
+struct NodeMetricsCache {
+    /// 取得できなかった場合はNone
+    usages: Option<HashMap<String, ResourceAmount>>,
+    last_fetched: Option<Instant>,
+}
+
+impl NodeMetricsCache {
+    async fn refresh<C: KubeClientRequest>(&mut self, client: &C) {
+        if self
+            .last_fetched
+            .is_some_and(|fetched| fetched.elapsed() < METRICS_INTERVAL)
+        {
+            return;
+        }
+
+        self.usages = fetch_node_usages(client)
+            .await
+            .inspect_err(|e| logger!(debug, "Node metrics are not available: {}", e))
+            .ok();
+
+        self.last_fetched = Some(Instant::now());
+    }
+
+    fn usages(&self) -> Option<&HashMap<String, ResourceAmount>> {
+        self.usages.as_ref()
+    }
+}
+
+async fn fetch_node_usages<C: KubeClientRequest>(
+    client: &C,
+) -> Result<HashMap<String, ResourceAmount>> {
+    let metrics = client
+        .request::<NodeMetricsList>("/apis/metrics.k8s.io/v1beta1/nodes")
+        .await?;
+
+    Ok(metrics
+        .items
+        .iter()
+        .filter_map(|m| {
+            let name = m.metadata.as_ref()?.name.clone()?;
+
+            Some((name, ResourceAmount::usage(m)))
+        })
+        .collect())
+}
+
+/// Nodeの一覧と、Podから集計したリソースの割り当て量、metrics-serverから取得した使用量からテーブルを生成する
+/// metrics-serverから使用量を取得できない場合は、CPU列とMEM列を表示しない
+fn node_table(
+    nodes: &[Arc<Node>],
+    pods: &[Arc<Pod>],
+    usages: Option<&HashMap<String, ResourceAmount>>,
+) -> KubeTable {
+    let mut allocated: HashMap<&str, NodeAllocated> = HashMap::new();
+
+    for pod in pods.iter().filter(|pod| !is_terminated(pod)) {
+        if let Some(node) = pod_node_name(pod) {
+            allocated.entry(node).or_default().add_pod(pod);
+        }
+    }
+
+    let mut header = vec!["NAME", "STATUS", "ROLES", "VERSION"];
+
+    if usages.is_some() {
+        header.extend(["CPU", "MEM"]);
+    }
+
+    header.extend(["CPU(R/L)", "MEM(R/L)", "AGE"]);
+
+    let mut table = KubeTable {
+        header: header.into_iter().map(ToString::to_string).collect(),
+        ..Default::default()
+    };
+
+    table.update_rows(
+        nodes
+            .iter()
+            .map(|node| {
+                let name = node.name_any();
+
+                let allocated = allocated.get(name.as_str()).copied().unwrap_or_default();
+
+                let usage = usages.map(|usages| usages.get(&name));
+
+                node_row(node, &allocated, usage)
+            })
+            .collect(),
+    );
+
+    table
+}
+
+fn node_row(
+    node: &Node,
+    allocated: &NodeAllocated,
+    usage: Option<Option<&ResourceAmount>>,
+) -> KubeTableRow {
+    let name = node.name_any();
+
+    let allocatable = ResourceAmount::allocatable(node);
+
+    let version = node
+        .status
+        .as_ref()
+        .and_then(|status| status.node_info.as_ref())
+        .map(|info| info.kubelet_version.to_string())
+        .unwrap_or_default();
+
+    let mut row = vec![name.clone(), node_status(node), node_roles(node), version];
+
+    if let Some(usage) = usage {
+        match usage {
+            Some(usage) => row.extend([
+                format!(
+                    "{} ({})",
+                    format_cpu_millis(usage.cpu_millis),
+                    format_percentage(usage.cpu_millis, allocatable.cpu_millis)
+                ),
+                format!(
+                    "{} ({})",
+                    format_memory_bytes(usage.memory_bytes),
+                    format_percentage(usage.memory_bytes, allocatable.memory_bytes)
+                ),
+            ]),
+            None => row.extend(["-".to_string(), "-".to_string()]),
+        }
+    }
+
+    row.extend([
+        format!(
+            "{}/{}",
+            format_percentage(allocated.requests.cpu_millis, allocatable.cpu_millis),
+            format_percentage(allocated.limits.cpu_millis, allocatable.cpu_millis)
+        ),
+        format!(
+            "{}/{}",
+            format_percentage(allocated.requests.memory_bytes, allocatable.memory_bytes),
+            format_percentage(allocated.limits.memory_bytes, allocatable.memory_bytes)
+        ),
+        translate_timestamp_since(node.metadata.creation_timestamp.as_ref()),
+    ]);
+
+    KubeTableRow {
+        // クラスタスコープのリソースのためnamespaceは空にする
+        namespace: String::new(),
+        name,
+        metadata: Some(BTreeMap::from([(
+            "kind".to_string(),
+            Node::KIND.to_string(),
+        )])),
+        row,
+    }
+}
+
+pub(super) fn format_percentage(usage: u64, total: u64) -> String {
+    percentage(usage, total)
+        .map(|p| format!("{}%", p))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::bail;
+    use indoc::indoc;
+    use k8s_openapi::List;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+
+    use crate::{kube::mock::MockTestKubeClient, mock_expect};
+
+    fn nodes() -> Vec<Arc<Node>> {
+        serde_yaml::from_str::<List<Node>>(indoc! {
+            r#"
+            items:
+              - metadata:
+                  name: worker
+                status:
+                  allocatable:
+                    cpu: "2"
+                    memory: 4Gi
+                  conditions:
+                    - type: Ready
+                      status: "True"
+                  nodeInfo:
+                    architecture: amd64
+                    bootID: ""
+                    containerRuntimeVersion: containerd://1.7.0
+                    kernelVersion: 6.0.0
+                    kubeProxyVersion: v1.30.0
+                    kubeletVersion: v1.30.0
+                    machineID: ""
+                    operatingSystem: linux
+                    osImage: Ubuntu
+                    systemUUID: ""
+            "#
+        })
+        .unwrap()
+        .items
+        .into_iter()
+        .map(Arc::new)
+        .collect()
+    }
+
+    fn pods() -> Vec<Arc<Pod>> {
+        serde_yaml::from_str::<List<Pod>>(indoc! {
+            r#"
+            items:
+              - metadata:
+                  name: running
+                  namespace: default
+                spec:
+                  nodeName: worker
+                  containers:
+                    - name: a
+                      resources:
+                        requests:
+                          cpu: 500m
+                          memory: 1Gi
+                        limits:
+                          cpu: "1"
+                          memory: 2Gi
+                status:
+                  phase: Running
+              - metadata:
+                  name: completed
+                  namespace: default
+                spec:
+                  nodeName: worker
+                  containers:
+                    - name: a
+                      resources:
+                        requests:
+                          cpu: "1"
+                status:
+                  phase: Succeeded
+            "#
+        })
+        .unwrap()
+        .items
+        .into_iter()
+        .map(Arc::new)
+        .collect()
+    }
+
+    fn node_metrics() -> NodeMetricsList {
+        serde_yaml::from_str(indoc! {
+            r#"
+            items:
+              - metadata:
+                  name: worker
+                timestamp: "2024-01-01T00:00:00Z"
+                window: 15s
+                usage:
+                  cpu: 200m
+                  memory: 1Gi
+            "#
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn nodeごとに使用量と割り当て量を表示する() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            NodeMetricsList,
+            eq("/apis/metrics.k8s.io/v1beta1/nodes"),
+            Ok(node_metrics())
+        );
+
+        let mut metrics = NodeMetricsCache::default();
+
+        metrics.refresh(&client).await;
+
+        let actual = node_table(&nodes(), &pods(), metrics.usages());
+
+        assert_eq!(
+            actual.header,
+            vec!["NAME", "STATUS", "ROLES", "VERSION", "CPU", "MEM", "CPU(R/L)", "MEM(R/L)", "AGE"]
+        );
+
+        assert_eq!(
+            actual.rows[0].row[..8],
+            [
+                "worker",
+                "Ready",
+                "<none>",
+                "v1.30.0",
+                "200m (10%)",
+                "1024Mi (25%)",
+                "25%/50%",
+                "25%/50%"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn metrics_serverがないとき使用量の列を表示しない() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            NodeMetricsList,
+            eq("/apis/metrics.k8s.io/v1beta1/nodes"),
+            bail!("the server could not find the requested resource")
+        );
+
+        let mut metrics = NodeMetricsCache::default();
+
+        metrics.refresh(&client).await;
+
+        let actual = node_table(&nodes(), &pods(), metrics.usages());
+
+        assert_eq!(
+            actual.header,
+            vec!["NAME", "STATUS", "ROLES", "VERSION", "CPU(R/L)", "MEM(R/L)", "AGE"]
+        );
+    }
+}
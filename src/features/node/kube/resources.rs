@@ -0,0 +1,330 @@
+use std::collections::BTreeMap;
+
+use k8s_openapi::{
+    api::core::v1::{Node, Pod},
+    apimachinery::pkg::api::resource::Quantity,
+};
+
+use crate::kube::{
+    apis::metrics::NodeMetrics,
+    quantity::{parse_cpu_millis, parse_memory_bytes},
+};
+
+const ROLE_LABEL_PREFIX: &str = "node-role.kubernetes.io/";
+const ROLE_LABEL: &str = "kubernetes.io/role";
+
+/// kubectl get nodesのSTATUS列に相当する値
+pub(super) fn node_status(node: &Node) -> String {
+    let ready = node
+        .status
+        .as_ref()
+        .and_then(|status| status.conditions.as_ref())
+        .and_then(|conditions| conditions.iter().find(|c| c.type_ == "Ready"));
+
+    let mut status = match ready {
+        Some(condition) if condition.status == "True" => "Ready".to_string(),
+        Some(_) => "NotReady".to_string(),
+        None => "Unknown".to_string(),
+    };
+
+    if node
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.unschedulable)
+        .unwrap_or_default()
+    {
+        status.push_str(",SchedulingDisabled");
+    }
+
+    status
+}
+
+/// kubectl get nodesのROLES列に相当する値
+pub(super) fn node_roles(node: &Node) -> String {
+    let mut roles: Vec<&str> = node
+        .metadata
+        .labels
+        .iter()
+        .flatten()
+        .filter_map(|(key, value)| {
+            if let Some(role) = key.strip_prefix(ROLE_LABEL_PREFIX) {
+                Some(role)
+            } else if key == ROLE_LABEL {
+                Some(value.as_str())
+            } else {
+                None
+            }
+        })
+        .filter(|role| !role.is_empty())
+        .collect();
+
+    if roles.is_empty() {
+        return "<none>".to_string();
+    }
+
+    roles.sort();
+    roles.dedup();
+
+    roles.join(",")
+}
+
+/// Nodeのtaintをkubectlと同じ key=value:effect の形式で返す
+pub(super) fn node_taints(node: &Node) -> Vec<String> {
+    node.spec
+        .as_ref()
+        .and_then(|spec| spec.taints.as_ref())
+        .iter()
+        .flat_map(|taints| taints.iter())
+        .map(|taint| match &taint.value {
+            Some(value) if !value.is_empty() => {
+                format!("{}={}:{}", taint.key, value, taint.effect)
+            }
+            _ => format!("{}:{}", taint.key, taint.effect),
+        })
+        .collect()
+}
+
+/// 終了したPodはNodeのリソースを占有しない
+pub(super) fn is_terminated(pod: &Pod) -> bool {
+    matches!(
+        pod.status.as_ref().and_then(|s| s.phase.as_deref()),
+        Some("Succeeded" | "Failed")
+    )
+}
+
+/// CPUとメモリの量
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ResourceAmount {
+    pub cpu_millis: u64,
+    pub memory_bytes: u64,
+}
+
+impl ResourceAmount {
+    fn from_quantities(quantities: Option<&BTreeMap<String, Quantity>>) -> Self {
+        let cpu_millis = quantities
+            .and_then(|q| q.get("cpu"))
+            .and_then(|q| parse_cpu_millis(&q.0))
+            .unwrap_or_default();
+
+        let memory_bytes = quantities
+            .and_then(|q| q.get("memory"))
+            .and_then(|q| parse_memory_bytes(&q.0))
+            .unwrap_or_default();
+
+        Self {
+            cpu_millis,
+            memory_bytes,
+        }
+    }
+
+    /// Nodeに割り当て可能なリソース量
+    pub fn allocatable(node: &Node) -> Self {
+        Self::from_quantities(
+            node.status
+                .as_ref()
+                .and_then(|status| status.allocatable.as_ref()),
+        )
+    }
+
+    /// metrics-serverから取得したNodeの使用量
+    pub fn usage(metrics: &NodeMetrics) -> Self {
+        let cpu_millis = metrics
+            .usage
+            .get("cpu")
+            .and_then(|q| parse_cpu_millis(q))
+            .unwrap_or_default();
+
+        let memory_bytes = metrics
+            .usage
+            .get("memory")
+            .and_then(|q| parse_memory_bytes(q))
+            .unwrap_or_default();
+
+        Self {
+            cpu_millis,
+            memory_bytes,
+        }
+    }
+}
+
+impl std::ops::AddAssign for ResourceAmount {
+    fn add_assign(&mut self, rhs: Self) {
+        self.cpu_millis += rhs.cpu_millis;
+        self.memory_bytes += rhs.memory_bytes;
+    }
+}
+
+/// Nodeにスケジュールされたコンテナのrequestsとlimitsの合計
+///
+/// kubectl describe nodeのAllocated resourcesと同じく、設定されている値のみを合計する
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) struct NodeAllocated {
+    pub requests: ResourceAmount,
+    pub limits: ResourceAmount,
+}
+
+impl NodeAllocated {
+    pub fn add_pod(&mut self, pod: &Pod) {
+        let containers = pod
+            .spec
+            .as_ref()
+            .map(|spec| spec.containers.as_slice())
+            .unwrap_or_default();
+
+        for container in containers {
+            let resources = container.resources.as_ref();
+
+            self.requests +=
+                ResourceAmount::from_quantities(resources.and_then(|r| r.requests.as_ref()));
+
+            self.limits +=
+                ResourceAmount::from_quantities(resources.and_then(|r| r.limits.as_ref()));
+        }
+    }
+}
+
+/// Podが実行されているNode名
+pub(super) fn pod_node_name(pod: &Pod) -> Option<&str> {
+    pod.spec.as_ref().and_then(|spec| spec.node_name.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn node(yaml: &str) -> Node {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn readyかつスケジュール不可のときscheduling_disabledを付与する() {
+        let node = node(indoc! {
+            r#"
+            metadata:
+              name: node
+            spec:
+              unschedulable: true
+            status:
+              conditions:
+                - type: Ready
+                  status: "True"
+            "#
+        });
+
+        assert_eq!(node_status(&node), "Ready,SchedulingDisabled");
+    }
+
+    #[test]
+    fn readyでないときnot_readyを返す() {
+        let node = node(indoc! {
+            r#"
+            metadata:
+              name: node
+            status:
+              conditions:
+                - type: Ready
+                  status: "False"
+            "#
+        });
+
+        assert_eq!(node_status(&node), "NotReady");
+    }
+
+    #[test]
+    fn ロールのラベルからロールを返す() {
+        let node = node(indoc! {
+            r#"
+            metadata:
+              name: node
+              labels:
+                node-role.kubernetes.io/control-plane: ""
+                node-role.kubernetes.io/master: ""
+                kubernetes.io/hostname: node
+            "#
+        });
+
+        assert_eq!(node_roles(&node), "control-plane,master");
+    }
+
+    #[test]
+    fn ロールのラベルがないときnoneを返す() {
+        let node = node(indoc! {
+            r#"
+            metadata:
+              name: node
+            "#
+        });
+
+        assert_eq!(node_roles(&node), "<none>");
+    }
+
+    #[test]
+    fn taintをkey_value_effectの形式で返す() {
+        let node = node(indoc! {
+            r#"
+            metadata:
+              name: node
+            spec:
+              taints:
+                - key: dedicated
+                  value: gpu
+                  effect: NoSchedule
+                - key: node-role.kubernetes.io/control-plane
+                  effect: NoSchedule
+            "#
+        });
+
+        assert_eq!(
+            node_taints(&node),
+            vec![
+                "dedicated=gpu:NoSchedule".to_string(),
+                "node-role.kubernetes.io/control-plane:NoSchedule".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn コンテナのrequestsとlimitsを合計する() {
+        let pod: Pod = serde_yaml::from_str(indoc! {
+            r#"
+            metadata:
+              name: pod
+            spec:
+              containers:
+                - name: a
+                  resources:
+                    requests:
+                      cpu: 100m
+                      memory: 64Mi
+                    limits:
+                      cpu: 500m
+                - name: b
+                  resources:
+                    requests:
+                      cpu: 250m
+            "#
+        })
+        .unwrap();
+
+        let mut allocated = NodeAllocated::default();
+
+        allocated.add_pod(&pod);
+
+        assert_eq!(
+            allocated,
+            NodeAllocated {
+                requests: ResourceAmount {
+                    cpu_millis: 350,
+                    memory_bytes: 64 * 1024 * 1024,
+                },
+                limits: ResourceAmount {
+                    cpu_millis: 500,
+                    memory_bytes: 0,
+                },
+            }
+        );
+    }
+}
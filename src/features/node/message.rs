@@ -0,0 +1,46 @@
+use anyhow::Result;
+
+use crate::{kube::table::KubeTable, message::Message, workers::kube::message::Kube};
+
+#[derive(Debug, Clone)]
+pub struct NodeRequest {
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub enum NodeResponse {
+    List(Result<KubeTable>),
+    Description(Result<Vec<String>>),
+}
+
+#[derive(Debug)]
+pub enum NodeMessage {
+    Request(NodeRequest),
+    Response(NodeResponse),
+    /// Nodeタブを開いた
+    Opened,
+}
+
+impl From<NodeMessage> for Kube {
+    fn from(m: NodeMessage) -> Self {
+        Self::Node(m)
+    }
+}
+
+impl From<NodeMessage> for Message {
+    fn from(m: NodeMessage) -> Self {
+        Self::Kube(m.into())
+    }
+}
+
+impl From<NodeRequest> for Message {
+    fn from(req: NodeRequest) -> Self {
+        NodeMessage::Request(req).into()
+    }
+}
+
+impl From<NodeResponse> for Message {
+    fn from(res: NodeResponse) -> Self {
+        NodeMessage::Response(res).into()
+    }
+}
@@ -0,0 +1,56 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossbeam::channel::Sender;
+use ratatui::layout::{Constraint, Direction};
+
+use crate::{
+    clipboard::Clipboard,
+    config::{keybindings::NodeKeyBindings, theme::WidgetThemeConfig},
+    features::{
+        component_id::NODE_TAB_ID,
+        node::view::widgets::{description_widget, node_widget},
+    },
+    message::Message,
+    ui::{
+        tab::{LayoutElement, NestedLayoutElement, NestedWidgetLayout, TabLayout},
+        Tab,
+    },
+};
+
+pub struct NodeTab {
+    pub tab: Tab<'static>,
+}
+
+impl NodeTab {
+    pub fn new(
+        title: &'static str,
+        tx: &Sender<Message>,
+        clipboard: &Option<Rc<RefCell<Clipboard>>>,
+        split_direction: Direction,
+        keybindings: NodeKeyBindings,
+        theme: WidgetThemeConfig,
+    ) -> Self {
+        let node_widget = node_widget(tx, keybindings, theme.clone());
+        let description_widget = description_widget(clipboard, theme);
+
+        let layout = TabLayout::new(layout, split_direction);
+
+        NodeTab {
+            tab: Tab::new(
+                NODE_TAB_ID,
+                title,
+                [node_widget, description_widget],
+                layout,
+            ),
+        }
+    }
+}
+
+fn layout(split_direction: Direction) -> NestedWidgetLayout {
+    NestedWidgetLayout::default()
+        .direction(split_direction)
+        .nested_widget_layout([
+            NestedLayoutElement(Constraint::Percentage(50), LayoutElement::WidgetIndex(0)),
+            NestedLayoutElement(Constraint::Percentage(50), LayoutElement::WidgetIndex(1)),
+        ])
+}
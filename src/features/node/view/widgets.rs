@@ -0,0 +1,5 @@
+mod description;
+mod node;
+
+pub(super) use description::*;
+pub(super) use node::*;
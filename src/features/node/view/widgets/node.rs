@@ -0,0 +1,115 @@
+use crossbeam::channel::Sender;
+
+use crate::{
+    config::{keybindings::NodeKeyBindings, theme::WidgetThemeConfig},
+    features::{
+        component_id::{NODE_DESCRIPTION_WIDGET_ID, NODE_WIDGET_ID, POD_TAB_ID},
+        node::message::NodeRequest,
+        pod::view::filter_pods_by_node,
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            FilterForm, FilterFormTheme, SelectedItem, Table, TableItem, TableTheme, Widget,
+            WidgetBase, WidgetTheme, WidgetTrait as _,
+        },
+        Window, WindowAction,
+    },
+};
+
+pub fn node_widget(
+    tx: &Sender<Message>,
+    keybindings: NodeKeyBindings,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    let tx = tx.clone();
+
+    let widget_theme = WidgetTheme::from(theme.clone());
+    let filter_theme = FilterFormTheme::from(theme.clone());
+    let table_theme = TableTheme::from(theme.clone());
+
+    let widget_base = WidgetBase::builder()
+        .title("Node")
+        .theme(widget_theme)
+        .build();
+
+    let filter_form = FilterForm::builder().theme(filter_theme).build();
+
+    Table::builder()
+        .id(NODE_WIDGET_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .theme(table_theme)
+        .filtered_key("NAME")
+        .block_injection(block_injection())
+        .on_select(on_select(tx.clone()))
+        .key_action(&keybindings.show_pods, show_pods(tx))
+        .build()
+        .into()
+}
+
+fn block_injection() -> impl Fn(&Table) -> WidgetBase {
+    |table: &Table| {
+        let index = if let Some(index) = table.state().selected() {
+            index + 1
+        } else {
+            0
+        };
+
+        let mut base = table.widget_base().clone();
+
+        *base.append_title_mut() = Some(format!(" [{}/{}]", index, table.items().len()).into());
+
+        base
+    }
+}
+
+fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &TableItem) -> EventResult {
+    move |w: &mut Window, v: &TableItem| {
+        w.widget_clear(NODE_DESCRIPTION_WIDGET_ID);
+
+        let Some(name) = v
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("name"))
+        else {
+            return EventResult::Ignore;
+        };
+
+        *(w.find_widget_mut(NODE_DESCRIPTION_WIDGET_ID)
+            .widget_base_mut()
+            .append_title_mut()) = Some((format!(" : {}", name)).into());
+
+        tx.send(
+            NodeRequest {
+                name: name.to_string(),
+            }
+            .into(),
+        )
+        .expect("Failed to send NodeRequest");
+
+        EventResult::WindowAction(WindowAction::Continue)
+    }
+}
+
+/// 選択しているNodeで実行されているPodをPodタブに表示する
+fn show_pods(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let widget = w.find_widget(NODE_WIDGET_ID);
+
+        let Some(SelectedItem::TableRow { metadata, .. }) = widget.widget_item() else {
+            return EventResult::Ignore;
+        };
+
+        let Some(name) = metadata.as_ref().and_then(|metadata| metadata.get("name")) else {
+            return EventResult::Ignore;
+        };
+
+        filter_pods_by_node(&tx, w, Some(name.to_string()));
+
+        w.activate_tab_by_id(POD_TAB_ID);
+
+        EventResult::Nop
+    }
+}
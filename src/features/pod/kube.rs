@@ -1,5 +1,6 @@
 mod filter;
 mod log;
+mod metrics;
 mod pod;
 
 pub use log::*;
@@ -1,10 +1,14 @@
+mod json;
 mod parser;
 
 use std::borrow::Cow;
 
 use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
 use regex::Regex;
 
+pub use self::json::{JsonCondition, JsonPath};
+
 use self::parser::parse_attributes;
 
 #[derive(Debug, thiserror::Error)]
@@ -25,6 +29,18 @@ pub struct Filter {
     pub label_selector: Option<LabelSelector>,
     pub include_log: Option<Vec<Regex>>,
     pub exclude_log: Option<Vec<Regex>>,
+    /// JSONのログのうち、すべての条件を満たすものを表示する
+    pub include_json: Option<Vec<JsonCondition>>,
+    /// JSONのログのうち、いずれかの条件を満たすものを除く
+    pub exclude_json: Option<Vec<JsonCondition>>,
+    /// JSONのログのうち、指定したキーの値のみを列として表示する
+    pub json_fields: Option<Vec<JsonPath>>,
+    pub since: Option<LogSince>,
+    pub tail_lines: Option<i64>,
+    pub previous: bool,
+    pub timestamps: LogTimestamps,
+    /// 複数のコンテナのログを時刻順に並べる
+    pub ordered: bool,
 }
 
 impl Filter {
@@ -137,6 +153,55 @@ impl Filter {
                         filter.exclude_log = Some(vec![regex]);
                     }
                 }
+
+                FilterAttribute::IncludeJson(condition) => {
+                    let condition = condition.parse()?;
+
+                    if let Some(include) = &mut filter.include_json {
+                        include.push(condition);
+                    } else {
+                        filter.include_json = Some(vec![condition]);
+                    }
+                }
+
+                FilterAttribute::ExcludeJson(condition) => {
+                    let condition = condition.parse()?;
+
+                    if let Some(exclude) = &mut filter.exclude_json {
+                        exclude.push(condition);
+                    } else {
+                        filter.exclude_json = Some(vec![condition]);
+                    }
+                }
+
+                FilterAttribute::JsonFields(fields) => {
+                    filter.json_fields = Some(
+                        fields
+                            .split(',')
+                            .map(str::parse)
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+
+                FilterAttribute::Since(value) => {
+                    filter.since = Some(value.parse()?);
+                }
+
+                FilterAttribute::Tail(lines) => {
+                    filter.tail_lines = Some(lines.into());
+                }
+
+                FilterAttribute::Previous(previous) => {
+                    filter.previous = previous;
+                }
+
+                FilterAttribute::Timestamps(timestamps) => {
+                    filter.timestamps = timestamps;
+                }
+
+                FilterAttribute::Ordered(ordered) => {
+                    filter.ordered = ordered;
+                }
             }
         }
 
@@ -205,10 +270,147 @@ impl std::fmt::Display for Filter {
             }
         }
 
+        if let Some(include) = &self.include_json {
+            for i in include {
+                buf.push(format!("json={}", i));
+            }
+        }
+
+        if let Some(exclude) = &self.exclude_json {
+            for e in exclude {
+                buf.push(format!("exclude_json={}", e));
+            }
+        }
+
+        if let Some(fields) = &self.json_fields {
+            buf.push(format!(
+                "json_fields={}",
+                fields
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ));
+        }
+
+        if let Some(since) = &self.since {
+            buf.push(format!("since={}", since));
+        }
+
+        if let Some(tail_lines) = self.tail_lines {
+            buf.push(format!("tail={}", tail_lines));
+        }
+
+        if self.previous {
+            buf.push("previous=true".into());
+        }
+
+        if self.timestamps != LogTimestamps::None {
+            buf.push(format!("timestamps={}", self.timestamps));
+        }
+
+        if self.ordered {
+            buf.push("ordered=true".into());
+        }
+
         write!(f, "{}", buf.join(" "))
     }
 }
 
+/// ログに付けるタイムスタンプ
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum LogTimestamps {
+    #[default]
+    None,
+    /// コンテナランタイムが付けたRFC3339形式のタイムスタンプを、ログの先頭にそのまま表示する
+    Raw,
+    /// UTCのタイムスタンプを列として表示する
+    Utc,
+    /// ローカル時刻のタイムスタンプを列として表示する
+    Local,
+}
+
+impl std::fmt::Display for LogTimestamps {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "false"),
+            Self::Raw => write!(f, "true"),
+            Self::Utc => write!(f, "utc"),
+            Self::Local => write!(f, "local"),
+        }
+    }
+}
+
+/// ログを取得し始める時点
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LogSince {
+    /// 現在からの相対秒数
+    Seconds(i64),
+    /// RFC3339形式の絶対時刻
+    Time(DateTime<Utc>),
+}
+
+impl std::str::FromStr for LogSince {
+    type Err = FilterError;
+
+    /// `10m`、`1h30m`のような期間か、`2024-01-01T00:00:00Z`のようなRFC3339形式の時刻をパースする
+    /// 期間の単位は`s`、`m`、`h`、`d`に対応する
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+            return Ok(Self::Time(time.into()));
+        }
+
+        let invalid = || {
+            FilterError::Syntax(format!(
+                "Invalid since value: {}. Specify a duration such as 10m or 1h30m, or an RFC3339 time.",
+                s
+            ))
+        };
+
+        let mut seconds: i64 = 0;
+        let mut number = String::new();
+
+        for c in s.chars() {
+            if c.is_ascii_digit() {
+                number.push(c);
+                continue;
+            }
+
+            let unit = match c {
+                's' => 1,
+                'm' => 60,
+                'h' => 60 * 60,
+                'd' => 24 * 60 * 60,
+                _ => return Err(invalid()),
+            };
+
+            let value: i64 = number.parse().map_err(|_| invalid())?;
+
+            seconds = value
+                .checked_mul(unit)
+                .and_then(|value| seconds.checked_add(value))
+                .ok_or_else(invalid)?;
+
+            number.clear();
+        }
+
+        if !number.is_empty() || seconds == 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self::Seconds(seconds))
+    }
+}
+
+impl std::fmt::Display for LogSince {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogSince::Seconds(seconds) => write!(f, "{}s", seconds),
+            LogSince::Time(time) => write!(f, "{}", time.to_rfc3339()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum LabelSelector {
     Resource(RetrievableResource),
@@ -281,6 +483,14 @@ pub enum FilterAttribute<'a> {
     FieldSelector(Cow<'a, str>),
     IncludeLog(Cow<'a, str>),
     ExcludeLog(Cow<'a, str>),
+    IncludeJson(Cow<'a, str>),
+    ExcludeJson(Cow<'a, str>),
+    JsonFields(Cow<'a, str>),
+    Since(Cow<'a, str>),
+    Tail(u32),
+    Previous(bool),
+    Timestamps(LogTimestamps),
+    Ordered(bool),
 }
 
 struct FilterAttributes;
@@ -303,3 +513,73 @@ impl<'a> From<SpecifiedResource<'a>> for FilterAttribute<'a> {
         Self::Resource(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("30s", LogSince::Seconds(30))]
+    #[case("10m", LogSince::Seconds(600))]
+    #[case("1h30m", LogSince::Seconds(5400))]
+    #[case("2d", LogSince::Seconds(172800))]
+    #[case(
+        "2024-01-01T00:00:00Z",
+        LogSince::Time("2024-01-01T00:00:00Z".parse().unwrap())
+    )]
+    fn sinceの値をパースする(#[case] value: &str, #[case] expected: LogSince) {
+        assert_eq!(value.parse::<LogSince>().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("10")]
+    #[case("m")]
+    #[case("0s")]
+    #[case("10y")]
+    fn 不正なsinceの値はエラーを返す(#[case] value: &str) {
+        assert!(value.parse::<LogSince>().is_err());
+    }
+
+    #[test]
+    fn ログの取得オプションをパースする() {
+        let filter =
+            Filter::parse("pod:app since:10m tail:500 previous:true timestamps:true").unwrap();
+
+        assert_eq!(filter.since, Some(LogSince::Seconds(600)));
+        assert_eq!(filter.tail_lines, Some(500));
+        assert!(filter.previous);
+        assert_eq!(filter.timestamps, LogTimestamps::Raw);
+        assert_eq!(
+            filter.to_string(),
+            "pod=app since=600s tail=500 previous=true timestamps=true"
+        );
+    }
+
+    #[test]
+    fn jsonの条件と表示するキーをパースする() {
+        let filter = Filter::parse(
+            "json:level=error json:.http.status>=500 !json:path=/healthz fields:ts,level,msg field:status.phase=Running",
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.to_string(),
+            "field_selector=status.phase=Running json=level=error json=.http.status>=500 exclude_json=path=/healthz json_fields=ts,level,msg"
+        );
+    }
+
+    #[test]
+    fn 時刻順に並べてタイムスタンプを列として表示するオプションをパースする() {
+        let filter = Filter::parse("deploy/api timestamps:local ordered:true").unwrap();
+
+        assert_eq!(filter.timestamps, LogTimestamps::Local);
+        assert!(filter.ordered);
+        assert_eq!(
+            filter.to_string(),
+            "label_selector_from=deployment/api timestamps=local ordered=true"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+use super::FilterError;
+
+/// JSONのキーのパス
+///
+/// `.http.status`や`http.status`のように、ドット区切りでネストしたキーを指定する。
+/// 配列の要素はインデックスで指定する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    path: String,
+    keys: Vec<String>,
+}
+
+impl JsonPath {
+    pub fn get<'a>(&self, json: &'a Value) -> Option<&'a Value> {
+        self.keys.iter().try_fold(json, |value, key| match value {
+            Value::Object(map) => map.get(key),
+            Value::Array(array) => array.get(key.parse::<usize>().ok()?),
+            _ => None,
+        })
+    }
+}
+
+impl std::str::FromStr for JsonPath {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys: Vec<String> = s
+            .strip_prefix('.')
+            .unwrap_or(s)
+            .split('.')
+            .map(ToString::to_string)
+            .collect();
+
+        if keys.iter().any(String::is_empty) {
+            return Err(FilterError::Syntax(format!("Invalid JSON key: {:?}", s)));
+        }
+
+        Ok(Self {
+            path: s.to_string(),
+            keys,
+        })
+    }
+}
+
+impl std::fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonOperator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl JsonOperator {
+    /// 2文字の演算子を先に判定する
+    const ALL: [(&'static str, Self); 6] = [
+        ("!=", Self::Ne),
+        (">=", Self::Ge),
+        ("<=", Self::Le),
+        ("=", Self::Eq),
+        (">", Self::Gt),
+        ("<", Self::Lt),
+    ];
+
+    fn as_str(&self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(_, op)| op == self)
+            .map(|(s, _)| *s)
+            .unwrap_or_default()
+    }
+}
+
+/// JSONのログのキーの値に対する条件
+///
+/// 数値同士は数値として比較し、それ以外は文字列として比較する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonCondition {
+    path: JsonPath,
+    operator: JsonOperator,
+    value: String,
+}
+
+impl JsonCondition {
+    /// キーが存在しない場合は`!=`のみ条件を満たす
+    ///
+    /// `level!=debug`のような条件で、キーを持たないログまで除かれないようにする
+    pub fn matches(&self, json: &Value) -> bool {
+        let Some(actual) = self.path.get(json) else {
+            return self.operator == JsonOperator::Ne;
+        };
+
+        match self.operator {
+            JsonOperator::Eq => self.equals(actual),
+            JsonOperator::Ne => !self.equals(actual),
+            JsonOperator::Gt => self.compare(actual) == Some(Ordering::Greater),
+            JsonOperator::Ge => self.compare(actual).is_some_and(Ordering::is_ge),
+            JsonOperator::Lt => self.compare(actual) == Some(Ordering::Less),
+            JsonOperator::Le => self.compare(actual).is_some_and(Ordering::is_le),
+        }
+    }
+
+    fn equals(&self, actual: &Value) -> bool {
+        match actual {
+            Value::String(s) => *s == self.value,
+            Value::Number(n) => n.as_f64() == self.value.parse::<f64>().ok(),
+            Value::Bool(b) => b.to_string() == self.value,
+            Value::Null => self.value == "null",
+            Value::Array(_) | Value::Object(_) => false,
+        }
+    }
+
+    fn compare(&self, actual: &Value) -> Option<Ordering> {
+        match actual {
+            Value::Number(n) => n.as_f64()?.partial_cmp(&self.value.parse::<f64>().ok()?),
+            Value::String(s) => Some(s.as_str().cmp(&self.value)),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for JsonCondition {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 最初に現れる演算子でキーと値に分ける
+        let (index, op, operator) = JsonOperator::ALL
+            .iter()
+            .filter_map(|(op, operator)| s.find(op).map(|index| (index, *op, *operator)))
+            .min_by_key(|(index, op, _)| (*index, std::cmp::Reverse(op.len())))
+            .ok_or_else(|| {
+                FilterError::Syntax(format!(
+                    "Invalid JSON condition: {:?}. Use <key><operator><value> (operators: =, !=, >, >=, <, <=)",
+                    s
+                ))
+            })?;
+
+        Ok(Self {
+            path: s[..index].parse()?,
+            operator,
+            value: s[index + op.len()..].to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for JsonCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.path, self.operator.as_str(), self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    #[case("level=error", true)]
+    #[case("level!=error", false)]
+    #[case(".http.status>=500", true)]
+    #[case("http.status>502", false)]
+    #[case("http.status<=502", true)]
+    #[case("http.status=502", true)]
+    #[case("tags.1=api", true)]
+    #[case("path=/healthz", false)]
+    #[case("retry=false", true)]
+    #[case("unknown!=error", true)]
+    #[case("unknown=error", false)]
+    #[case("unknown>=0", false)]
+    fn jsonの値を条件と比較する(#[case] condition: &str, #[case] expected: bool) {
+        let log = json!({
+            "level": "error",
+            "path": "/users",
+            "retry": false,
+            "tags": ["web", "api"],
+            "http": {
+                "status": 502
+            }
+        });
+
+        let condition: JsonCondition = condition.parse().unwrap();
+
+        assert_eq!(condition.matches(&log), expected);
+    }
+
+    #[rstest]
+    #[case("level")]
+    #[case("=error")]
+    #[case("http..status=500")]
+    fn 不正なjsonの条件はエラーを返す(#[case] condition: &str) {
+        assert!(condition.parse::<JsonCondition>().is_err());
+    }
+}
@@ -3,15 +3,15 @@ use std::borrow::Cow;
 use nom::{
     branch::alt,
     bytes::complete::{is_not, tag},
-    character::complete::{alphanumeric1, anychar, char, multispace0, multispace1},
-    combinator::{all_consuming, map, recognize, value, verify},
+    character::complete::{alphanumeric1, anychar, char, multispace0, multispace1, u32},
+    combinator::{all_consuming, eof, map, peek, recognize, value, verify},
     error::{ContextError, ParseError},
     multi::{fold_many0, many1_count, separated_list1},
-    sequence::{delimited, preceded, separated_pair},
+    sequence::{delimited, preceded, separated_pair, terminated},
     IResult, Parser,
 };
 
-use super::{FilterAttribute, SpecifiedResource};
+use super::{FilterAttribute, LogTimestamps, SpecifiedResource};
 
 /// 空白文字を含まない文字列をパースする
 fn non_space<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
@@ -202,6 +202,96 @@ fn field_selector<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     Ok((remaining, FilterAttribute::FieldSelector(value)))
 }
 
+fn include_json<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute<'a>, E> {
+    let (remaining, (_, value)) = separated_pair(tag("json"), char(':'), regex).parse(s)?;
+    Ok((remaining, FilterAttribute::IncludeJson(value)))
+}
+
+fn exclude_json<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute<'a>, E> {
+    let (remaining, (_, value)) = separated_pair(tag("!json"), char(':'), regex).parse(s)?;
+    Ok((remaining, FilterAttribute::ExcludeJson(value)))
+}
+
+/// JSONのキーの一覧をパースする
+///
+/// `fields:`はフィールドセレクタの別名でもあるため、`=`を含まないカンマ区切りのキーの一覧のみ受け付ける
+fn json_fields<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute<'a>, E> {
+    let json_key = recognize(many1_count(alt((
+        alphanumeric1,
+        tag("_"),
+        tag("-"),
+        tag("."),
+    ))));
+
+    let (remaining, (_, value)) = separated_pair(
+        tag("fields"),
+        char(':'),
+        terminated(
+            recognize(separated_list1(char(','), json_key)),
+            peek(alt((multispace1, eof))),
+        ),
+    )
+    .parse(s)?;
+    Ok((remaining, FilterAttribute::JsonFields(Cow::Borrowed(value))))
+}
+
+fn since<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute<'a>, E> {
+    let (remaining, (_, value)) = separated_pair(tag("since"), char(':'), unquoted).parse(s)?;
+    Ok((remaining, FilterAttribute::Since(value)))
+}
+
+fn tail<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute<'a>, E> {
+    let (remaining, (_, value)) = separated_pair(tag("tail"), char(':'), u32).parse(s)?;
+    Ok((remaining, FilterAttribute::Tail(value)))
+}
+
+fn boolean<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, bool, E> {
+    alt((value(true, tag("true")), value(false, tag("false")))).parse(s)
+}
+
+fn previous<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute<'a>, E> {
+    let (remaining, (_, value)) = separated_pair(tag("previous"), char(':'), boolean).parse(s)?;
+    Ok((remaining, FilterAttribute::Previous(value)))
+}
+
+fn timestamps<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute<'a>, E> {
+    let (remaining, (_, value)) = separated_pair(
+        tag("timestamps"),
+        char(':'),
+        alt((
+            value(LogTimestamps::Raw, tag("true")),
+            value(LogTimestamps::None, tag("false")),
+            value(LogTimestamps::Utc, tag("utc")),
+            value(LogTimestamps::Local, tag("local")),
+        )),
+    )
+    .parse(s)?;
+    Ok((remaining, FilterAttribute::Timestamps(value)))
+}
+
+fn ordered<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute<'a>, E> {
+    let (remaining, (_, value)) = separated_pair(tag("ordered"), char(':'), boolean).parse(s)?;
+    Ok((remaining, FilterAttribute::Ordered(value)))
+}
+
 fn specified_daemonset<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     s: &'a str,
 ) -> IResult<&'a str, FilterAttribute<'a>, E> {
@@ -307,13 +397,16 @@ fn attribute<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     s: &'a str,
 ) -> IResult<&'a str, FilterAttribute<'a>, E> {
     let (remaining, value) = alt((
-        specified_pod,
-        specified_daemonset,
-        specified_deployment,
-        specified_job,
-        specified_replicaset,
-        specified_service,
-        specified_statefulset,
+        alt((
+            specified_pod,
+            specified_daemonset,
+            specified_deployment,
+            specified_job,
+            specified_replicaset,
+            specified_service,
+            specified_statefulset,
+        )),
+        json_fields,
         field_selector,
         label_selector,
         pod,
@@ -322,6 +415,13 @@ fn attribute<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
         exclude_container,
         include_log,
         exclude_log,
+        include_json,
+        exclude_json,
+        since,
+        tail,
+        previous,
+        timestamps,
+        ordered,
     ))
     .parse(s)?;
 
@@ -458,6 +558,71 @@ mod tests {
         assert_eq!(remaining, "");
     }
 
+    /// Since
+    #[rstest]
+    #[case("since:10m", "10m")]
+    #[case("since:1h30m", "1h30m")]
+    #[case("since:2024-01-01T00:00:00Z", "2024-01-01T00:00:00Z")]
+    fn since(#[case] query: &str, #[case] expected: &str) {
+        let (remaining, actual) = super::since::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, FilterAttribute::Since(expected.into()));
+        assert_eq!(remaining, "");
+    }
+
+    /// Tail
+    #[rstest]
+    #[case("tail:0", 0)]
+    #[case("tail:500", 500)]
+    fn tail(#[case] query: &str, #[case] expected: u32) {
+        let (remaining, actual) = super::tail::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, FilterAttribute::Tail(expected));
+        assert_eq!(remaining, "");
+    }
+
+    #[rstest]
+    #[case("tail:-1")]
+    #[case("tail:abc")]
+    fn tail_error(#[case] query: &str) {
+        assert!(super::tail::<Error<_>>(query).is_err());
+    }
+
+    /// Previous
+    #[rstest]
+    #[case("previous:true", true)]
+    #[case("previous:false", false)]
+    fn previous(#[case] query: &str, #[case] expected: bool) {
+        let (remaining, actual) = super::previous::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, FilterAttribute::Previous(expected));
+        assert_eq!(remaining, "");
+    }
+
+    /// Timestamps
+    #[rstest]
+    #[case("timestamps:true", LogTimestamps::Raw)]
+    #[case("timestamps:false", LogTimestamps::None)]
+    #[case("timestamps:utc", LogTimestamps::Utc)]
+    #[case("timestamps:local", LogTimestamps::Local)]
+    fn timestamps(#[case] query: &str, #[case] expected: LogTimestamps) {
+        let (remaining, actual) = super::timestamps::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, FilterAttribute::Timestamps(expected));
+        assert_eq!(remaining, "");
+    }
+
+    /// Ordered
+    #[rstest]
+    #[case("ordered:true", true)]
+    #[case("ordered:false", false)]
+    fn ordered(#[case] query: &str, #[case] expected: bool) {
+        let (remaining, actual) = super::ordered::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, FilterAttribute::Ordered(expected));
+        assert_eq!(remaining, "");
+    }
+
     // Specified resoruces
 
     /// DaemonSet
@@ -673,6 +838,16 @@ mod tests {
     #[case("replicaset/app", FilterAttribute::Resource(SpecifiedResource::ReplicaSet("app")))]
     #[case("service/app", FilterAttribute::Resource(SpecifiedResource::Service("app")))]
     #[case("statefulset/app", FilterAttribute::Resource(SpecifiedResource::StatefulSet("app")))]
+    #[case("since:10m", FilterAttribute::Since("10m".into()))]
+    #[case("tail:500", FilterAttribute::Tail(500))]
+    #[case("previous:true", FilterAttribute::Previous(true))]
+    #[case("json:level=error", FilterAttribute::IncludeJson("level=error".into()))]
+    #[case("!json:'msg=a b'", FilterAttribute::ExcludeJson("msg=a b".into()))]
+    #[case("fields:ts,level,msg", FilterAttribute::JsonFields("ts,level,msg".into()))]
+    #[case("fields:.http.status", FilterAttribute::JsonFields(".http.status".into()))]
+    #[case("fields:status.phase=Running", FilterAttribute::FieldSelector("status.phase=Running".into()))]
+    #[case("timestamps:true", FilterAttribute::Timestamps(LogTimestamps::Raw))]
+    #[case("ordered:true", FilterAttribute::Ordered(true))]
     fn attribute(#[case] query: &str, #[case] expected: FilterAttribute) {
         let (remaining, actual) = super::attribute::<Error<_>>(query).unwrap();
 
@@ -699,6 +874,11 @@ mod tests {
             "replicaset/app",
             "service/app",
             "statefulset/app",
+            "since:10m",
+            "tail:500",
+            "previous:true",
+            "timestamps:true",
+            "ordered:true",
             "     ",
         ]
         .join("  ");
@@ -721,6 +901,11 @@ mod tests {
             FilterAttribute::Resource(SpecifiedResource::ReplicaSet("app")),
             FilterAttribute::Resource(SpecifiedResource::Service("app")),
             FilterAttribute::Resource(SpecifiedResource::StatefulSet("app")),
+            FilterAttribute::Since("10m".into()),
+            FilterAttribute::Tail(500),
+            FilterAttribute::Previous(true),
+            FilterAttribute::Timestamps(LogTimestamps::Raw),
+            FilterAttribute::Ordered(true),
         ];
 
         assert_eq!(actual, expected);
@@ -1,5 +1,8 @@
 mod log_collector;
 mod log_content;
+mod log_export;
+mod log_order;
+mod log_projection;
 mod log_streamer;
 mod pod_watcher;
 
@@ -24,8 +27,11 @@ use crate::{
     workers::kube::{AbortWorker, Worker},
 };
 
+pub use self::log_export::{expand_path, export_log, LogExportFormat, LogTee, SharedLogTee};
 pub use self::log_streamer::LogPrefixType;
-pub use super::filter::{Filter, LabelSelector, RetrievableResource};
+pub use super::filter::{
+    Filter, JsonCondition, JsonPath, LabelSelector, LogSince, LogTimestamps, RetrievableResource,
+};
 
 use self::{
     log_collector::{LogBuffer, LogCollector},
@@ -71,12 +77,23 @@ impl LogConfig {
 pub struct LogWorker {
     tx: Sender<Message>,
     client: KubeClient,
+    tee: SharedLogTee,
     pub config: LogConfig,
 }
 
 impl LogWorker {
-    pub fn new(tx: Sender<Message>, client: KubeClient, config: LogConfig) -> Self {
-        Self { tx, client, config }
+    pub fn new(
+        tx: Sender<Message>,
+        client: KubeClient,
+        tee: SharedLogTee,
+        config: LogConfig,
+    ) -> Self {
+        Self {
+            tx,
+            client,
+            tee,
+            config,
+        }
     }
 
     async fn spawn_tasks(&self, filter: Filter) -> Result<LogHandle> {
@@ -120,6 +137,12 @@ impl LogWorker {
                 prefix_type: self.config.prefix_type,
                 include_log: filter.include_log.clone(),
                 exclude_log: filter.exclude_log.clone(),
+                include_json: filter.include_json.clone(),
+                exclude_json: filter.exclude_json.clone(),
+                since: filter.since,
+                tail_lines: filter.tail_lines,
+                previous: filter.previous,
+                timestamps: filter.timestamps,
             });
 
             pod_watchers.push(pod_watcher);
@@ -132,7 +155,10 @@ impl LogWorker {
             self.tx.clone(),
             log_buffer.clone(),
             self.config.json_pretty_print,
+            self.tee.clone(),
         )
+        .ordered(filter.ordered)
+        .json_fields(filter.json_fields.clone())
         .spawn();
 
         handles.push(collector_handle);
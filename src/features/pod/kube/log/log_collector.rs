@@ -2,11 +2,20 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use crossbeam::channel::Sender;
-use tokio::{sync::Mutex, time};
+use tokio::{
+    sync::Mutex,
+    time::{self, Instant},
+};
 
-use crate::{message::Message, send_response, workers::kube::Worker};
+use crate::{
+    features::pod::message::LogMessage, logger, message::Message, send_response,
+    workers::kube::Worker,
+};
 
-use super::log_content::LogContent;
+use super::{
+    log_content::LogContent, log_export::SharedLogTee, log_order::LogReorder,
+    log_projection::JsonProjection, JsonPath,
+};
 
 pub type LogBuffer = Arc<Mutex<Vec<LogContent>>>;
 
@@ -15,14 +24,57 @@ pub struct LogCollector {
     tx: Sender<Message>,
     buffer: LogBuffer,
     json_pretty_print: bool,
+    tee: SharedLogTee,
+    ordered: bool,
+    json_fields: Option<Vec<JsonPath>>,
 }
 
 impl LogCollector {
-    pub fn new(tx: Sender<Message>, buffer: LogBuffer, json_pretty_print: bool) -> Self {
+    pub fn new(
+        tx: Sender<Message>,
+        buffer: LogBuffer,
+        json_pretty_print: bool,
+        tee: SharedLogTee,
+    ) -> Self {
         Self {
             tx,
             buffer,
             json_pretty_print,
+            tee,
+            ordered: false,
+            json_fields: None,
+        }
+    }
+
+    /// 複数のストリームのログを時刻順に並べて出力する
+    pub fn ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
+    /// JSONのログは指定したキーの値のみを列として表示する
+    pub fn json_fields(mut self, json_fields: Option<Vec<JsonPath>>) -> Self {
+        self.json_fields = json_fields;
+        self
+    }
+
+    /// teeモードのときは受け取ったログをファイルに追記する
+    /// 書き込みに失敗した場合はteeモードを終了する
+    async fn tee(&self, logs: &[String]) {
+        let mut tee = self.tee.lock().await;
+
+        let Some(inner) = tee.as_mut() else {
+            return;
+        };
+
+        if let Err(err) = inner.write(logs) {
+            logger!(error, "{:?}", err);
+
+            *tee = None;
+
+            self.tx
+                .send(LogMessage::TeeResponse(Err(err)).into())
+                .expect("Failed to send LogMessage::TeeResponse");
         }
     }
 }
@@ -34,18 +86,35 @@ impl Worker for LogCollector {
     async fn run(&self) -> Self::Output {
         let mut interval = tokio::time::interval(time::Duration::from_millis(200));
 
+        let mut reorder = self.ordered.then(LogReorder::default);
+
+        let mut projection = self.json_fields.clone().map(JsonProjection::new);
+
         loop {
             interval.tick().await;
 
-            let mut buf = self.buffer.lock().await;
+            let contents = std::mem::take(&mut *self.buffer.lock().await);
 
-            let contents = std::mem::take(&mut *buf);
+            let contents = if let Some(reorder) = reorder.as_mut() {
+                let now = Instant::now();
+
+                reorder.push(contents, now);
+                reorder.pop(now)
+            } else {
+                contents
+            };
 
             if contents.is_empty() {
                 continue;
             }
 
-            let logs = if self.json_pretty_print {
+            // 列の表示は整形表示より優先する
+            let logs: Vec<String> = if let Some(projection) = projection.as_mut() {
+                contents
+                    .iter()
+                    .map(|content| projection.project(content))
+                    .collect()
+            } else if self.json_pretty_print {
                 contents
                     .into_iter()
                     .flat_map(|content| content.try_json_pritty_print())
@@ -57,6 +126,8 @@ impl Worker for LogCollector {
                     .collect()
             };
 
+            self.tee(&logs).await;
+
             send_response!(self.tx, Ok(logs));
         }
     }
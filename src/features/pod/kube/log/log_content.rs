@@ -1,4 +1,8 @@
+use chrono::{DateTime, Utc};
+
 pub struct LogContent {
+    /// ログに付いていた時刻。時刻順に並べるときに使う
+    pub timestamp: Option<DateTime<Utc>>,
     pub prefix: String,
     pub content: String,
 }
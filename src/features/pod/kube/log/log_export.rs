@@ -0,0 +1,220 @@
+use std::{
+    fmt::Display,
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write as _},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+use anyhow::{bail, Context as _, Result};
+use tokio::sync::Mutex;
+
+use crate::ansi::strip_ansi;
+
+/// ログをファイルに書き出すときの形式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LogExportFormat {
+    /// 表示している文字列をそのまま書き出す
+    Raw,
+    /// ANSIエスケープシーケンスを取り除く
+    #[default]
+    Plain,
+    /// ANSIエスケープシーケンスと[pod container]のプレフィックスを取り除く
+    PlainWithoutPrefix,
+}
+
+impl LogExportFormat {
+    pub const ALL: [Self; 3] = [Self::Plain, Self::PlainWithoutPrefix, Self::Raw];
+
+    /// 表示している1行を書き出す形式に変換する
+    pub fn format_line(&self, line: &str) -> String {
+        match self {
+            Self::Raw => line.to_string(),
+            Self::Plain => strip_ansi(line),
+            Self::PlainWithoutPrefix => {
+                let line = strip_ansi(line);
+
+                if !line.starts_with('[') {
+                    return line;
+                }
+
+                match line.split_once("] ") {
+                    Some((_, content)) => content.to_string(),
+                    None => line,
+                }
+            }
+        }
+    }
+}
+
+impl Display for LogExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Raw => write!(f, "raw (with ANSI codes)"),
+            Self::Plain => write!(f, "plain text"),
+            Self::PlainWithoutPrefix => write!(f, "plain text without prefix"),
+        }
+    }
+}
+
+impl FromStr for LogExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.to_string() == s)
+            .with_context(|| format!("Unknown log export format: {}", s))
+    }
+}
+
+/// 入力されたパスの先頭の~をホームディレクトリに展開する
+pub fn expand_path(path: &str) -> Result<PathBuf> {
+    let path = path.trim();
+
+    if path.is_empty() {
+        bail!("File path is empty");
+    }
+
+    if let Some(rest) = path.strip_prefix("~/") {
+        let home = dirs::home_dir().context("Failed to get the home directory")?;
+
+        return Ok(home.join(rest));
+    }
+
+    Ok(PathBuf::from(path))
+}
+
+/// ログをファイルに書き出し、書き出した行数を返す
+/// 既にファイルが存在する場合は上書きする
+pub fn export_log<'a>(
+    path: &Path,
+    lines: impl Iterator<Item = &'a str>,
+    format: LogExportFormat,
+) -> Result<usize> {
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+
+    let mut writer = BufWriter::new(file);
+
+    let mut count = 0;
+
+    for line in lines {
+        writeln!(writer, "{}", format.format_line(line))?;
+
+        count += 1;
+    }
+
+    writer.flush()?;
+
+    Ok(count)
+}
+
+/// LogCollectorが受け取ったログを追記するファイル
+pub struct LogTee {
+    path: PathBuf,
+    file: File,
+    format: LogExportFormat,
+}
+
+pub type SharedLogTee = Arc<Mutex<Option<LogTee>>>;
+
+impl LogTee {
+    pub fn open(path: PathBuf, format: LogExportFormat) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        Ok(Self { path, file, format })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write(&mut self, lines: &[String]) -> Result<()> {
+        let mut writer = BufWriter::new(&self.file);
+
+        for line in lines {
+            writeln!(writer, "{}", self.format.format_line(line))?;
+        }
+
+        writer
+            .flush()
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const LINE: &str =
+        "\x1b[92m[\x1b[39mpod \x1b[32mcontainer\x1b[39m\x1b[92m]\x1b[39m {\"level\": \"info\"}";
+
+    #[rstest]
+    #[case(LogExportFormat::Raw, LINE)]
+    #[case(LogExportFormat::Plain, "[pod container] {\"level\": \"info\"}")]
+    #[case(LogExportFormat::PlainWithoutPrefix, "{\"level\": \"info\"}")]
+    fn 形式に合わせて1行を変換する(
+        #[case] format: LogExportFormat,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format.format_line(LINE), expected);
+    }
+
+    #[test]
+    fn プレフィックスがない行はそのまま返す() {
+        assert_eq!(
+            LogExportFormat::PlainWithoutPrefix.format_line("\x1b[92m+\x1b[39m pod container"),
+            "+ pod container"
+        );
+    }
+
+    #[test]
+    fn 表示名から形式を取得する() {
+        for format in LogExportFormat::ALL {
+            assert_eq!(
+                format.to_string().parse::<LogExportFormat>().unwrap(),
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn 空のパスはエラーを返す() {
+        assert!(expand_path("  ").is_err());
+    }
+
+    #[test]
+    fn ログを書き出しteeで追記する() {
+        let dir = std::env::temp_dir().join(format!("kubetui-log-export-{}", std::process::id()));
+
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("log.txt");
+
+        let count = export_log(&path, [LINE, ""].into_iter(), LogExportFormat::Plain).unwrap();
+
+        assert_eq!(count, 2);
+
+        let mut tee = LogTee::open(path.clone(), LogExportFormat::PlainWithoutPrefix).unwrap();
+
+        tee.write(&[LINE.to_string()]).unwrap();
+
+        let actual = std::fs::read_to_string(&path).unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            actual,
+            "[pod container] {\"level\": \"info\"}\n\n{\"level\": \"info\"}\n"
+        );
+    }
+}
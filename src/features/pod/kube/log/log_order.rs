@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use tokio::time::{Duration, Instant};
+
+use super::log_content::LogContent;
+
+/// 他のストリームのログを待つ時間
+pub const REORDER_WINDOW: Duration = Duration::from_secs(1);
+
+struct PendingLog {
+    received: Instant,
+    /// 並べ替えに使う時刻
+    timestamp: Option<DateTime<Utc>>,
+    content: LogContent,
+}
+
+/// 複数のストリームのログを時刻順に並べる
+///
+/// 受け取ったログをwindowの間保持し、その間に受け取った他のストリームのログと時刻順に並べて出力する。
+/// windowより遅れて届いたログは、既に出力したログより前の時刻でもそのまま出力する。
+pub struct LogReorder {
+    window: Duration,
+    pending: Vec<PendingLog>,
+    /// 受け取ったログの最新の時刻。時刻のないログはこの時刻のログとして扱う
+    latest: Option<DateTime<Utc>>,
+}
+
+impl Default for LogReorder {
+    fn default() -> Self {
+        Self::new(REORDER_WINDOW)
+    }
+}
+
+impl LogReorder {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: Vec::new(),
+            latest: None,
+        }
+    }
+
+    pub fn push(&mut self, contents: Vec<LogContent>, now: Instant) {
+        for content in contents {
+            if content.timestamp > self.latest {
+                self.latest = content.timestamp;
+            }
+
+            self.pending.push(PendingLog {
+                received: now,
+                timestamp: content.timestamp.or(self.latest),
+                content,
+            });
+        }
+    }
+
+    /// windowを過ぎたログのうち最も新しい時刻までのログを、時刻順に返す
+    pub fn pop(&mut self, now: Instant) -> Vec<LogContent> {
+        let Some(watermark) = self
+            .pending
+            .iter()
+            .filter(|log| self.window <= now.duration_since(log.received))
+            .map(|log| log.timestamp)
+            .max()
+        else {
+            return Vec::new();
+        };
+
+        // 安定ソートのため、同じ時刻のログは受け取った順になる
+        self.pending.sort_by_key(|log| log.timestamp);
+
+        let index = self
+            .pending
+            .partition_point(|log| log.timestamp <= watermark);
+
+        self.pending.drain(..index).map(|log| log.content).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn log(timestamp: Option<&str>, content: &str) -> LogContent {
+        LogContent {
+            timestamp: timestamp.map(|t| DateTime::parse_from_rfc3339(t).unwrap().into()),
+            prefix: String::new(),
+            content: content.to_string(),
+        }
+    }
+
+    fn contents(logs: Vec<LogContent>) -> Vec<String> {
+        logs.into_iter().map(|log| log.content).collect()
+    }
+
+    #[test]
+    fn windowの間に受け取ったログを時刻順に並べる() {
+        let mut reorder = LogReorder::new(Duration::from_secs(1));
+
+        let start = Instant::now();
+
+        reorder.push(
+            vec![
+                log(Some("2024-01-01T00:00:00.300Z"), "a3"),
+                log(None, "a-marker"),
+            ],
+            start,
+        );
+
+        reorder.push(
+            vec![
+                log(Some("2024-01-01T00:00:00.100Z"), "b1"),
+                log(Some("2024-01-01T00:00:00.500Z"), "b5"),
+            ],
+            start + Duration::from_millis(500),
+        );
+
+        assert_eq!(reorder.pop(start + Duration::from_millis(900)).len(), 0);
+
+        assert_eq!(
+            contents(reorder.pop(start + Duration::from_secs(1))),
+            vec!["b1", "a3", "a-marker"]
+        );
+
+        assert_eq!(
+            contents(reorder.pop(start + Duration::from_millis(1500))),
+            vec!["b5"]
+        );
+    }
+}
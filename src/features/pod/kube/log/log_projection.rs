@@ -0,0 +1,108 @@
+use serde_json::Value;
+use unicode_width::UnicodeWidthStr as _;
+
+use super::{log_content::LogContent, JsonPath};
+
+const MISSING_VALUE: &str = "-";
+
+const COLUMN_SEPARATOR: &str = "  ";
+
+/// JSONのログから指定したキーの値のみを取り出し、列を揃えて表示する
+///
+/// 列の幅はこれまでに表示した値の最大幅に合わせる
+pub struct JsonProjection {
+    fields: Vec<JsonPath>,
+    widths: Vec<usize>,
+}
+
+impl JsonProjection {
+    pub fn new(fields: Vec<JsonPath>) -> Self {
+        Self {
+            widths: vec![0; fields.len()],
+            fields,
+        }
+    }
+
+    /// JSONではないログはそのまま返す
+    pub fn project(&mut self, content: &LogContent) -> String {
+        let Ok(json) = serde_json::from_str::<Value>(&content.content) else {
+            return content.print();
+        };
+
+        let values: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| match field.get(&json) {
+                Some(Value::String(s)) => s.clone(),
+                Some(value) => value.to_string(),
+                None => MISSING_VALUE.to_string(),
+            })
+            .collect();
+
+        let last = values.len().saturating_sub(1);
+
+        let columns: Vec<String> = values
+            .into_iter()
+            .zip(self.widths.iter_mut())
+            .enumerate()
+            .map(|(i, (value, width))| {
+                let value_width = value.width();
+
+                *width = (*width).max(value_width);
+
+                // 最後の列は末尾に空白を付けない
+                if i == last {
+                    value
+                } else {
+                    format!("{}{}", value, " ".repeat(*width - value_width))
+                }
+            })
+            .collect();
+
+        format!("{} {}", content.prefix, columns.join(COLUMN_SEPARATOR))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn log(content: &str) -> LogContent {
+        LogContent {
+            timestamp: None,
+            prefix: "[app]".to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn 指定したキーの値を列を揃えて表示する() {
+        let mut projection = JsonProjection::new(vec![
+            "level".parse().unwrap(),
+            ".http.status".parse().unwrap(),
+            "msg".parse().unwrap(),
+        ]);
+
+        let actual = [
+            log(r#"{"level":"info","http":{"status":200},"msg":"ok"}"#),
+            log(r#"{"level":"error","msg":"failed"}"#),
+            log("plain text"),
+            log(r#"{"level":"warn","http":{"status":404},"msg":"not found"}"#),
+        ]
+        .iter()
+        .map(|content| projection.project(content))
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                "[app] info  200  ok",
+                "[app] error  -    failed",
+                "[app] plain text",
+                "[app] warn   404  not found",
+            ]
+        );
+    }
+}
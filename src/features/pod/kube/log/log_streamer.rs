@@ -9,7 +9,7 @@ use std::{
 
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, SecondsFormat, TimeZone, Utc};
 use futures::{AsyncBufReadExt, TryStreamExt};
 use k8s_openapi::api::core::v1::Pod;
 use kube::{api::LogParams, Api};
@@ -22,7 +22,9 @@ use crate::{
     workers::kube::{color::fg::Color, AbortWorker},
 };
 
-use super::{log_collector::LogBuffer, log_content::LogContent};
+use super::{
+    log_collector::LogBuffer, log_content::LogContent, JsonCondition, LogSince, LogTimestamps,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum LogPrefixType {
@@ -75,6 +77,14 @@ pub struct LogStreamerOptions {
     pub prefix_type: LogPrefixType,
     pub include_log: Option<Vec<Regex>>,
     pub exclude_log: Option<Vec<Regex>>,
+    pub include_json: Option<Vec<JsonCondition>>,
+    pub exclude_json: Option<Vec<JsonCondition>>,
+    pub since: Option<LogSince>,
+    pub tail_lines: Option<i64>,
+    /// 前回終了したコンテナのログを取得する
+    pub previous: bool,
+    /// ログに付けるタイムスタンプ
+    pub timestamps: LogTimestamps,
 }
 
 #[derive(Clone)]
@@ -93,31 +103,177 @@ pub struct LogStreamer {
     options: LogStreamerOptions,
 }
 
+/// タイムスタンプの列の形式。ミリ秒まで表示する
+fn format_timestamp<Tz: TimeZone>(timestamp: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// 再接続までの待ち時間の初期値
+const INITIAL_BACKOFF: time::Duration = time::Duration::from_secs(1);
+
+/// 再接続までの待ち時間の上限
+const MAX_BACKOFF: time::Duration = time::Duration::from_secs(30);
+
+/// ストリームが正常に終了したときの再接続までの待ち時間
+///
+/// 終了直後の再接続が繰り返されても、APIサーバーへのリクエストが集中しないように少しだけ待つ
+const RECONNECT_DELAY: time::Duration = time::Duration::from_millis(500);
+
+/// 再接続までの待ち時間
+///
+/// エラーで接続が切れるたびに倍にし、新しいログを受け取るかストリームが正常に終了したら初期値に戻す
+#[derive(Debug)]
+struct Backoff {
+    current: time::Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            current: INITIAL_BACKOFF,
+        }
+    }
+}
+
+impl Backoff {
+    fn next(&mut self) -> time::Duration {
+        let current = self.current;
+
+        self.current = (current * 2).min(MAX_BACKOFF);
+
+        current
+    }
+
+    fn reset(&mut self) {
+        self.current = INITIAL_BACKOFF;
+    }
+
+    /// ストリームが終了してから再接続するまでの待ち時間
+    ///
+    /// エラーで終了した場合のみ待ち時間を延ばす
+    fn on_finished(&mut self, result: &Result<()>) -> time::Duration {
+        if result.is_err() {
+            self.next()
+        } else {
+            self.reset();
+
+            RECONNECT_DELAY
+        }
+    }
+}
+
+/// 受け取ったログの分類
+#[derive(Debug, PartialEq, Eq)]
+enum Received {
+    /// 再接続前に受け取ったログ
+    Duplicate,
+    New,
+    /// 再接続前に受け取ったログが再送されずに、新しいログを受け取った
+    NewAfterGap,
+}
+
+/// 再接続したときに、既に受け取ったログを除くための位置
+///
+/// sinceTimeは秒単位で指定するため、再接続すると最後に受け取ったログと同じ秒のログが再送される。
+/// 最後に受け取ったログより前の時刻のログは時刻で除き、同じ時刻のログは内容で除く。
+#[derive(Debug, Default)]
+struct LogCursor {
+    /// 最後に受け取ったログの時刻
+    timestamp: Option<DateTime<Utc>>,
+    /// timestampと同じ時刻に受け取ったログ
+    lines: Vec<String>,
+    /// 再接続後にまだ再送されていない、timestampと同じ時刻のログ
+    pending: Vec<String>,
+}
+
+impl LogCursor {
+    fn reconnect(&mut self) {
+        self.pending = self.lines.clone();
+    }
+
+    fn receive(&mut self, timestamp: DateTime<Utc>, line: &str) -> Received {
+        let Some(last) = self.timestamp else {
+            self.timestamp = Some(timestamp);
+            self.lines = vec![line.to_string()];
+
+            return Received::New;
+        };
+
+        if timestamp < last {
+            return Received::Duplicate;
+        }
+
+        if timestamp == last {
+            if let Some(index) = self.pending.iter().position(|pending| pending == line) {
+                self.pending.remove(index);
+
+                return Received::Duplicate;
+            }
+        } else {
+            self.timestamp = Some(timestamp);
+            self.lines.clear();
+        }
+
+        self.lines.push(line.to_string());
+
+        // 同じ時刻のログは順番に再送されるため、新しいログより後に再送されることはない
+        if std::mem::take(&mut self.pending).is_empty() {
+            Received::New
+        } else {
+            Received::NewAfterGap
+        }
+    }
+}
+
 #[async_trait]
 impl AbortWorker for LogStreamer {
     async fn run(&self) {
-        let mut interval = tokio::time::interval(time::Duration::from_secs(3));
+        let mut cursor = LogCursor::default();
 
-        let mut last_timestamp: Option<DateTime<Utc>> = None;
+        let mut backoff = Backoff::default();
 
         let prefix = self.log_prefix();
 
         self.send_started_message().await;
 
         loop {
-            interval.tick().await;
+            let result = self.fetch(&prefix, &mut cursor, &mut backoff).await;
 
-            let result = self.fetch(&prefix, &mut last_timestamp).await;
+            if self.options.previous {
+                // 前回終了したコンテナのログは追加されないため、1回取得したら終了する
+                if let Err(err) = result {
+                    logger!(error, "{}", err);
 
-            if let Err(err) = result {
+                    self.send_error_message(&prefix, &err).await;
+                }
+
+                break;
+            }
+
+            if let Err(err) = &result {
                 logger!(error, "{}", err)
             } else if self.is_terminated.load(Ordering::Relaxed) {
-                // 正常終了は下記2パターン確認しているため、
-                // コンテナ終了時のみループを抜ける処理を組み込む。
-                //   - コンテナが終了している
-                //   - 長時間実行
+                // followしているストリームはコンテナの終了以外にも、
+                // 長時間実行やkubeletの再起動などで終了するため、コンテナ終了時のみループを抜ける
                 break;
             }
+
+            let wait = backoff.on_finished(&result);
+
+            logger!(
+                info,
+                "Reconnect log stream in {:?}. pod={} container={}",
+                wait,
+                self.pod_name(),
+                self.container_name()
+            );
+
+            time::sleep(wait).await;
+
+            cursor.reconnect();
         }
 
         self.send_finished_message().await;
@@ -145,8 +301,14 @@ impl LogStreamer {
         self
     }
 
-    async fn fetch(&self, prefix: &str, last_timestamp: &mut Option<DateTime<Utc>>) -> Result<()> {
-        let log_params = self.log_params(last_timestamp);
+    /// ストリームが終了するまでログを受け取る
+    async fn fetch(
+        &self,
+        prefix: &str,
+        cursor: &mut LogCursor,
+        backoff: &mut Backoff,
+    ) -> Result<()> {
+        let log_params = self.log_params(&cursor.timestamp);
 
         let api: Api<Pod> = Api::namespaced(self.client.to_client(), self.namespace());
 
@@ -158,26 +320,48 @@ impl LogStreamer {
             if let Ok((dt, content)) = chrono::DateTime::parse_and_remainder(&line, "%+") {
                 let dt: DateTime<Utc> = dt.into();
 
-                if last_timestamp.is_some_and(|lts| dt <= lts) {
-                    continue;
+                let raw_timestamp = &line[..line.len() - content.len()];
+
+                match cursor.receive(dt, &line) {
+                    Received::Duplicate => continue,
+                    Received::New => {}
+                    Received::NewAfterGap => {
+                        logger!(
+                            info,
+                            "Log stream may have missed lines. pod={} container={}",
+                            self.pod_name(),
+                            self.container_name()
+                        );
+
+                        buf.push(LogContent {
+                            timestamp: Some(dt),
+                            prefix: self.timestamp_column(dt, raw_timestamp, prefix),
+                            content: Color::Yellow.wrap("stream reconnected / possible gap"),
+                        });
+                    }
                 }
 
-                if self.is_exclude(content) || !self.is_include(content) {
+                backoff.reset();
+
+                if self.is_exclude(content)
+                    || !self.is_include(content)
+                    || !self.is_json_match(content)
+                {
                     continue;
                 }
 
                 buf.push(LogContent {
-                    prefix: prefix.to_string(),
+                    timestamp: Some(dt),
+                    prefix: self.timestamp_column(dt, raw_timestamp, prefix),
                     content: content.to_string(),
                 });
-
-                *last_timestamp = Some(dt);
             } else {
-                if self.is_exclude(&line) || !self.is_include(&line) {
+                if self.is_exclude(&line) || !self.is_include(&line) || !self.is_json_match(&line) {
                     continue;
                 }
 
                 buf.push(LogContent {
+                    timestamp: None,
                     prefix: prefix.to_string(),
                     content: line.to_string(),
                 });
@@ -187,6 +371,15 @@ impl LogStreamer {
         Ok(())
     }
 
+    fn timestamp_column(
+        &self,
+        timestamp: DateTime<Utc>,
+        raw_timestamp: &str,
+        prefix: &str,
+    ) -> String {
+        timestamp_column(&self.options.timestamps, timestamp, raw_timestamp, prefix)
+    }
+
     fn is_exclude(&self, s: &str) -> bool {
         self.options
             .exclude_log
@@ -202,23 +395,64 @@ impl LogStreamer {
         include.iter().any(|include| include.is_match(s))
     }
 
+    /// JSONの条件が指定されているときのみJSONとしてパースする
+    ///
+    /// 表示する条件があるときは、JSONではないログを除く
+    fn is_json_match(&self, s: &str) -> bool {
+        let LogStreamerOptions {
+            include_json,
+            exclude_json,
+            ..
+        } = &self.options;
+
+        if include_json.is_none() && exclude_json.is_none() {
+            return true;
+        }
+
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(s) else {
+            return include_json.is_none();
+        };
+
+        let is_exclude = exclude_json
+            .as_ref()
+            .is_some_and(|exclude| exclude.iter().any(|c| c.matches(&json)));
+
+        let is_include = include_json
+            .as_ref()
+            .is_none_or(|include| include.iter().all(|c| c.matches(&json)));
+
+        !is_exclude && is_include
+    }
+
     async fn send_started_message(&self) {
         let sign = Color::LightGreen.wrap("+");
 
         let mut buf = self.log_buffer.lock().await;
 
         buf.push(LogContent {
+            timestamp: None,
             prefix: sign,
             content: self.log_prefix_content(),
         });
     }
 
+    async fn send_error_message(&self, prefix: &str, err: &anyhow::Error) {
+        let mut buf = self.log_buffer.lock().await;
+
+        buf.push(LogContent {
+            timestamp: None,
+            prefix: prefix.to_string(),
+            content: Color::LightRed.wrap(err.to_string()),
+        });
+    }
+
     async fn send_finished_message(&self) {
         let sign = Color::LightRed.wrap("-");
 
         let mut buf = self.log_buffer.lock().await;
 
         buf.push(LogContent {
+            timestamp: None,
             prefix: sign,
             content: self.log_prefix_content(),
         });
@@ -305,14 +539,30 @@ impl LogStreamer {
         PREFIX_COLOR_LIST[index % PREFIX_COLOR_LIST.len()]
     }
 
+    /// 再接続時はsince/tailの指定を使わず、最後に受け取ったログの時刻から取得する
+    ///
+    /// ログを受け取る前に再接続した場合は、since/tailの指定を使う
     fn log_params(&self, last_timestamp: &Option<DateTime<Utc>>) -> LogParams {
-        LogParams {
-            follow: true,
+        let mut params = LogParams {
+            follow: !self.options.previous,
             container: Some(self.container_name().to_string()),
             timestamps: true,
+            previous: self.options.previous,
             since_time: *last_timestamp,
             ..Default::default()
+        };
+
+        if last_timestamp.is_none() {
+            match self.options.since {
+                Some(LogSince::Seconds(seconds)) => params.since_seconds = Some(seconds),
+                Some(LogSince::Time(time)) => params.since_time = Some(time),
+                None => {}
+            }
+
+            params.tail_lines = self.options.tail_lines;
         }
+
+        params
     }
 
     fn namespace(&self) -> &str {
@@ -327,3 +577,154 @@ impl LogStreamer {
         &self.target.container_name
     }
 }
+
+/// タイムスタンプを列として表示する場合は、プレフィックスの前に付ける
+///
+/// 受け取ったままのタイムスタンプを表示する場合はプレフィックスの後に付ける。
+/// ログの本文にはタイムスタンプを含めないため、本文をJSONとして扱える
+fn timestamp_column(
+    timestamps: &LogTimestamps,
+    timestamp: DateTime<Utc>,
+    raw_timestamp: &str,
+    prefix: &str,
+) -> String {
+    let column = match timestamps {
+        LogTimestamps::Utc => format_timestamp(&timestamp),
+        LogTimestamps::Local => format_timestamp(&timestamp.with_timezone(&Local)),
+        LogTimestamps::Raw => return format!("{} {}", prefix, raw_timestamp),
+        LogTimestamps::None => return prefix.to_string(),
+    };
+
+    format!("{} {}", Color::DarkGray.wrap(column), prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use super::super::log_projection::JsonProjection;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().into()
+    }
+
+    #[test]
+    fn 生のタイムスタンプを表示するときもjsonのキーの値を取り出せる() {
+        let line = r#"2024-01-01T00:00:00.123456789Z {"level":"info","msg":"ok"}"#;
+
+        let (dt, content) = DateTime::parse_and_remainder(line, "%+").unwrap();
+        let raw_timestamp = &line[..line.len() - content.len()];
+
+        let log = LogContent {
+            timestamp: Some(dt.into()),
+            prefix: timestamp_column(&LogTimestamps::Raw, dt.into(), raw_timestamp, "[app]"),
+            content: content.to_string(),
+        };
+
+        let mut projection =
+            JsonProjection::new(vec!["level".parse().unwrap(), "msg".parse().unwrap()]);
+
+        assert_eq!(
+            projection.project(&log),
+            "[app] 2024-01-01T00:00:00.123456789Z info  ok"
+        );
+    }
+
+    #[test]
+    fn 再接続後に再送された同じ時刻のログを除く() {
+        let mut cursor = LogCursor::default();
+
+        let t1 = time("2024-01-01T00:00:00.100Z");
+        let t2 = time("2024-01-01T00:00:00.200Z");
+
+        let actual = [
+            cursor.receive(t1, "a"),
+            cursor.receive(t2, "b"),
+            cursor.receive(t2, "b"),
+        ];
+
+        assert_eq!(actual, [Received::New, Received::New, Received::New]);
+
+        cursor.reconnect();
+
+        let actual = [
+            cursor.receive(t1, "a"),
+            cursor.receive(t2, "b"),
+            cursor.receive(t2, "b"),
+            cursor.receive(t2, "b"),
+            cursor.receive(time("2024-01-01T00:00:01Z"), "c"),
+        ];
+
+        assert_eq!(
+            actual,
+            [
+                Received::Duplicate,
+                Received::Duplicate,
+                Received::Duplicate,
+                Received::New,
+                Received::New
+            ]
+        );
+    }
+
+    #[test]
+    fn 受け取ったログが再送されない場合は欠落の可能性を示す() {
+        let mut cursor = LogCursor::default();
+
+        cursor.receive(time("2024-01-01T00:00:00.100Z"), "a");
+
+        cursor.reconnect();
+
+        let actual = [
+            cursor.receive(time("2024-01-01T00:00:01Z"), "b"),
+            cursor.receive(time("2024-01-01T00:00:02Z"), "c"),
+        ];
+
+        assert_eq!(actual, [Received::NewAfterGap, Received::New]);
+    }
+
+    #[test]
+    fn タイムスタンプの列はミリ秒まで表示する() {
+        let actual = format_timestamp(&time("2024-01-01T00:00:00.123456789Z"));
+
+        assert_eq!(actual, "2024-01-01T00:00:00.123Z");
+    }
+
+    #[test]
+    fn 再接続までの待ち時間は上限まで倍にする() {
+        let mut backoff = Backoff::default();
+
+        let actual: Vec<u64> = (0..7).map(|_| backoff.next().as_secs()).collect();
+
+        assert_eq!(actual, vec![1, 2, 4, 8, 16, 30, 30]);
+
+        backoff.reset();
+
+        assert_eq!(backoff.next(), INITIAL_BACKOFF);
+    }
+
+    #[test]
+    fn エラーで終了した場合のみ再接続までの待ち時間を延ばす() {
+        let mut backoff = Backoff::default();
+
+        let actual: Vec<u64> = (0..3)
+            .map(|_| {
+                backoff
+                    .on_finished(&Err(anyhow::anyhow!("error")))
+                    .as_secs()
+            })
+            .collect();
+
+        assert_eq!(actual, vec![1, 2, 4]);
+
+        assert_eq!(backoff.on_finished(&Ok(())), RECONNECT_DELAY);
+        assert_eq!(backoff.on_finished(&Ok(())), RECONNECT_DELAY);
+
+        assert_eq!(
+            backoff.on_finished(&Err(anyhow::anyhow!("error"))),
+            INITIAL_BACKOFF
+        );
+    }
+}
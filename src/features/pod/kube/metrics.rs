@@ -0,0 +1,558 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use futures::future::join_all;
+use k8s_openapi::api::core::v1::Pod;
+use tokio::task::AbortHandle;
+
+use crate::{
+    kube::{
+        apis::metrics::{PodMetrics, PodMetricsList},
+        informer::SharedInformer,
+        quantity::{
+            format_cpu_millis, format_memory_bytes, parse_cpu_millis, parse_memory_bytes,
+            percentage,
+        },
+        KubeClientRequest,
+    },
+    logger,
+    workers::kube::SharedTargetNamespaces,
+};
+
+/// metrics-serverの収集間隔より短くしても値は変わらないため、一定間隔で取得する
+const FETCH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 監視対象のnamespaceの変更を確認する間隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Podのリソース使用量
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PodUsage {
+    pub cpu_millis: u64,
+    pub memory_bytes: u64,
+}
+
+impl From<&PodMetrics> for PodUsage {
+    fn from(metrics: &PodMetrics) -> Self {
+        metrics
+            .containers
+            .iter()
+            .fold(Self::default(), |mut usage, container| {
+                if let Some(cpu) = container.usage.get("cpu").and_then(|q| parse_cpu_millis(q)) {
+                    usage.cpu_millis += cpu;
+                }
+
+                if let Some(memory) = container
+                    .usage
+                    .get("memory")
+                    .and_then(|q| parse_memory_bytes(q))
+                {
+                    usage.memory_bytes += memory;
+                }
+
+                usage
+            })
+    }
+}
+
+/// (namespace, name) をキーにしたPodの使用量
+pub type PodMetricsMap = HashMap<(String, String), PodUsage>;
+
+/// 指定したnamespaceのPodの使用量を取得する
+///   * all_namespacesがtrueの場合は、すべてのnamespaceの使用量を1回のリクエストで取得する
+///   * namespaceごとに取得する場合は、取得できたnamespaceの使用量のみを返す
+///   * metrics-serverがインストールされていないなど、1つも取得できない場合はエラーを返す
+pub async fn fetch_pod_metrics<C: KubeClientRequest>(
+    client: &C,
+    namespaces: &[String],
+    all_namespaces: bool,
+) -> Result<PodMetricsMap> {
+    let lists = if all_namespaces {
+        vec![
+            client
+                .request::<PodMetricsList>("/apis/metrics.k8s.io/v1beta1/pods")
+                .await?,
+        ]
+    } else {
+        let results = join_all(namespaces.iter().map(|ns| async move {
+            let path = format!("/apis/metrics.k8s.io/v1beta1/namespaces/{}/pods", ns);
+
+            client
+                .request::<PodMetricsList>(&path)
+                .await
+                .inspect_err(|e| {
+                    logger!(
+                        debug,
+                        "Pod metrics are not available: namespace={} {}",
+                        ns,
+                        e
+                    )
+                })
+        }))
+        .await;
+
+        let has_success = results.iter().any(Result::is_ok);
+
+        let mut lists = Vec::new();
+
+        for result in results {
+            match result {
+                Ok(list) => lists.push(list),
+                Err(e) if !has_success => return Err(e),
+                Err(_) => {}
+            }
+        }
+
+        lists
+    };
+
+    Ok(lists
+        .iter()
+        .flat_map(|list| list.items.iter())
+        .filter_map(|metrics| {
+            let metadata = metrics.metadata.as_ref()?;
+
+            Some((
+                (metadata.namespace.clone()?, metadata.name.clone()?),
+                PodUsage::from(metrics),
+            ))
+        })
+        .collect())
+}
+
+/// Podの使用量を一定間隔で取得し、次の取得まで保持する
+#[derive(Debug, Default)]
+pub struct PodMetricsCache {
+    /// 取得できなかった場合はNone
+    metrics: Option<PodMetricsMap>,
+    namespaces: Vec<String>,
+    all_namespaces: bool,
+    last_fetched: Option<Instant>,
+}
+
+impl PodMetricsCache {
+    pub async fn refresh<C: KubeClientRequest>(
+        &mut self,
+        client: &C,
+        namespaces: &[String],
+        all_namespaces: bool,
+    ) {
+        let is_fresh = self
+            .last_fetched
+            .is_some_and(|fetched| fetched.elapsed() < FETCH_INTERVAL);
+
+        if is_fresh && self.namespaces == namespaces && self.all_namespaces == all_namespaces {
+            return;
+        }
+
+        let metrics = match fetch_pod_metrics(client, namespaces, all_namespaces).await {
+            Ok(metrics) => Some(metrics),
+            Err(e) => {
+                // 取得できない状態が続く間は同じエラーを出力しない
+                if self.metrics.is_some() || self.last_fetched.is_none() {
+                    logger!(info, "Pod metrics are not available: {}", e);
+                }
+
+                None
+            }
+        };
+
+        self.metrics = metrics;
+        self.namespaces = namespaces.to_vec();
+        self.all_namespaces = all_namespaces;
+        self.last_fetched = Some(Instant::now());
+    }
+
+    pub fn metrics(&self) -> Option<&PodMetricsMap> {
+        self.metrics.as_ref()
+    }
+}
+
+/// Podの使用量をPodの一覧とは別のタスクで取得し、最後に取得した値を共有する
+///
+/// metrics-serverの応答が遅い場合でも、Podの一覧の更新を待たせないようにする。
+/// 破棄したときに取得するタスクを停止する
+pub struct PodMetricsRefresher {
+    metrics: Arc<RwLock<Option<PodMetricsMap>>>,
+    handle: AbortHandle,
+}
+
+impl PodMetricsRefresher {
+    pub fn spawn<C>(
+        client: C,
+        shared_target_namespaces: SharedTargetNamespaces,
+        informer: SharedInformer<Pod>,
+    ) -> Self
+    where
+        C: KubeClientRequest + Send + Sync + 'static,
+    {
+        let metrics = Arc::new(RwLock::new(None));
+
+        let task_metrics = metrics.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+            let mut cache = PodMetricsCache::default();
+
+            loop {
+                interval.tick().await;
+
+                let namespaces = shared_target_namespaces.read().await.clone();
+
+                cache
+                    .refresh(&client, &namespaces, informer.covers_all_namespaces())
+                    .await;
+
+                *task_metrics.write().expect("Failed to write pod metrics") =
+                    cache.metrics().cloned();
+            }
+        })
+        .abort_handle();
+
+        Self { metrics, handle }
+    }
+
+    /// 最後に取得した使用量
+    /// 取得できていない場合はNone
+    pub fn metrics(&self) -> Option<PodMetricsMap> {
+        self.metrics
+            .read()
+            .expect("Failed to read pod metrics")
+            .clone()
+    }
+}
+
+impl Drop for PodMetricsRefresher {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Podに設定されているリソースのrequestsとlimitsの合計
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PodResources {
+    cpu_requests: u64,
+    memory_requests: u64,
+    /// 1つでもlimitsが設定されていないコンテナがある場合は上限なしとしてNone
+    cpu_limits: Option<u64>,
+    memory_limits: Option<u64>,
+}
+
+impl From<&Pod> for PodResources {
+    fn from(pod: &Pod) -> Self {
+        let containers = pod
+            .spec
+            .as_ref()
+            .map(|spec| spec.containers.as_slice())
+            .unwrap_or_default();
+
+        let mut resources = Self {
+            cpu_limits: Some(0),
+            memory_limits: Some(0),
+            ..Default::default()
+        };
+
+        for container in containers {
+            let requests = container
+                .resources
+                .as_ref()
+                .and_then(|r| r.requests.as_ref());
+
+            let limits = container.resources.as_ref().and_then(|r| r.limits.as_ref());
+
+            let cpu_request = requests
+                .and_then(|r| r.get("cpu"))
+                .and_then(|q| parse_cpu_millis(&q.0));
+
+            let memory_request = requests
+                .and_then(|r| r.get("memory"))
+                .and_then(|q| parse_memory_bytes(&q.0));
+
+            let cpu_limit = limits
+                .and_then(|l| l.get("cpu"))
+                .and_then(|q| parse_cpu_millis(&q.0));
+
+            let memory_limit = limits
+                .and_then(|l| l.get("memory"))
+                .and_then(|q| parse_memory_bytes(&q.0));
+
+            resources.cpu_requests += cpu_request.unwrap_or_default();
+            resources.memory_requests += memory_request.unwrap_or_default();
+
+            resources.cpu_limits = resources.cpu_limits.zip(cpu_limit).map(|(a, b)| a + b);
+            resources.memory_limits = resources
+                .memory_limits
+                .zip(memory_limit)
+                .map(|(a, b)| a + b);
+        }
+
+        resources
+    }
+}
+
+/// CPU列とMEM列の値を返す
+///
+/// requestsやlimitsが設定されている場合は、それに対する使用量の割合を併記する。
+/// e.g. "120m (r:60% l:30%)"
+pub fn pod_usage_cells(pod: &Pod, usage: Option<&PodUsage>) -> [String; 2] {
+    let Some(usage) = usage else {
+        return ["-".to_string(), "-".to_string()];
+    };
+
+    let resources = PodResources::from(pod);
+
+    [
+        usage_cell(
+            format_cpu_millis(usage.cpu_millis),
+            usage.cpu_millis,
+            resources.cpu_requests,
+            resources.cpu_limits.unwrap_or_default(),
+        ),
+        usage_cell(
+            format_memory_bytes(usage.memory_bytes),
+            usage.memory_bytes,
+            resources.memory_requests,
+            resources.memory_limits.unwrap_or_default(),
+        ),
+    ]
+}
+
+fn usage_cell(formatted: String, usage: u64, requests: u64, limits: u64) -> String {
+    let ratios: Vec<String> = [("r", requests), ("l", limits)]
+        .into_iter()
+        .filter_map(|(label, total)| percentage(usage, total).map(|p| format!("{}:{}%", label, p)))
+        .collect();
+
+    if ratios.is_empty() {
+        formatted
+    } else {
+        format!("{} ({})", formatted, ratios.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::bail;
+    use indoc::indoc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+
+    use crate::{kube::mock::MockTestKubeClient, mock_expect};
+
+    fn pod(yaml: &str) -> Pod {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn pod_metrics_list() -> PodMetricsList {
+        serde_yaml::from_str(indoc! {
+            r#"
+            items:
+              - metadata:
+                  name: app
+                  namespace: default
+                timestamp: "2024-01-01T00:00:00Z"
+                window: 15s
+                containers:
+                  - name: a
+                    usage:
+                      cpu: 100m
+                      memory: 64Mi
+                  - name: b
+                    usage:
+                      cpu: "20000000n"
+                      memory: 32Mi
+            "#
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn podごとにコンテナの使用量を合計する() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            PodMetricsList,
+            eq("/apis/metrics.k8s.io/v1beta1/namespaces/default/pods"),
+            Ok(pod_metrics_list())
+        );
+
+        let actual = fetch_pod_metrics(&client, &["default".to_string()], false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual,
+            PodMetricsMap::from([(
+                ("default".to_string(), "app".to_string()),
+                PodUsage {
+                    cpu_millis: 120,
+                    memory_bytes: 96 * 1024 * 1024,
+                }
+            )])
+        );
+    }
+
+    #[tokio::test]
+    async fn metrics_serverがないときエラーを返す() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            PodMetricsList,
+            eq("/apis/metrics.k8s.io/v1beta1/namespaces/default/pods"),
+            bail!("the server could not find the requested resource")
+        );
+
+        let actual = fetch_pod_metrics(&client, &["default".to_string()], false).await;
+
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn 一部のnamespaceで取得できないときは取得できた使用量を返す() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            [
+                (
+                    PodMetricsList,
+                    eq("/apis/metrics.k8s.io/v1beta1/namespaces/default/pods"),
+                    Ok(pod_metrics_list())
+                ),
+                (
+                    PodMetricsList,
+                    eq("/apis/metrics.k8s.io/v1beta1/namespaces/kube-system/pods"),
+                    bail!("forbidden")
+                )
+            ]
+        );
+
+        let actual = fetch_pod_metrics(
+            &client,
+            &["default".to_string(), "kube-system".to_string()],
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            actual.into_keys().collect::<Vec<_>>(),
+            vec![("default".to_string(), "app".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn すべてのnamespaceを対象にするときは1回のリクエストで取得する() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            PodMetricsList,
+            eq("/apis/metrics.k8s.io/v1beta1/pods"),
+            Ok(pod_metrics_list())
+        );
+
+        let actual = fetch_pod_metrics(
+            &client,
+            &["default".to_string(), "kube-system".to_string()],
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            actual.into_keys().collect::<Vec<_>>(),
+            vec![("default".to_string(), "app".to_string())]
+        );
+    }
+
+    #[test]
+    fn requestsとlimitsが設定されているとき割合を併記する() {
+        let pod = pod(indoc! {
+            r#"
+            metadata:
+              name: app
+            spec:
+              containers:
+                - name: a
+                  resources:
+                    requests:
+                      cpu: 100m
+                      memory: 64Mi
+                    limits:
+                      cpu: 200m
+                      memory: 128Mi
+                - name: b
+                  resources:
+                    requests:
+                      cpu: 100m
+                    limits:
+                      cpu: 200m
+            "#
+        });
+
+        let usage = PodUsage {
+            cpu_millis: 120,
+            memory_bytes: 32 * 1024 * 1024,
+        };
+
+        assert_eq!(
+            pod_usage_cells(&pod, Some(&usage)),
+            ["120m (r:60% l:30%)".to_string(), "32Mi (r:50%)".to_string()]
+        );
+    }
+
+    #[test]
+    fn requestsとlimitsが設定されていないとき使用量のみ返す() {
+        let pod = pod(indoc! {
+            r#"
+            metadata:
+              name: app
+            spec:
+              containers:
+                - name: a
+            "#
+        });
+
+        let usage = PodUsage {
+            cpu_millis: 5,
+            memory_bytes: 10 * 1024 * 1024,
+        };
+
+        assert_eq!(
+            pod_usage_cells(&pod, Some(&usage)),
+            ["5m".to_string(), "10Mi".to_string()]
+        );
+    }
+
+    #[test]
+    fn 使用量が取得できていないときハイフンを返す() {
+        let pod = pod(indoc! {
+            r#"
+            metadata:
+              name: app
+            spec:
+              containers:
+                - name: a
+            "#
+        });
+
+        assert_eq!(
+            pod_usage_cells(&pod, None),
+            ["-".to_string(), "-".to_string()]
+        );
+    }
+}
@@ -1,17 +1,18 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, sync::Arc};
 
-use anyhow::Result;
 use async_trait::async_trait;
 use crossbeam::channel::Sender;
-use futures::future::try_join_all;
 use k8s_openapi::{api::core::v1::Pod, Resource as _};
+use kube::ResourceExt as _;
 use ratatui::style::{Color, Style};
 use regex::Regex;
+use tokio::sync::RwLock;
 
 use crate::{
+    features::pod::kube::metrics::{pod_usage_cells, PodMetricsMap, PodMetricsRefresher},
     kube::{
-        apis::v1_table::TableRow,
-        table::{get_resource_per_namespace, insert_ns, KubeTable, KubeTableRow},
+        informer::{ChangeDetector, SharedInformers},
+        table::{insert_ns, translate_timestamp_since, KubeTable, KubeTableRow},
         KubeClient,
     },
     message::Message,
@@ -47,11 +48,16 @@ pub struct PodHighlightRule {
     pub style: Style,
 }
 
+/// Podの一覧を絞り込むNode名
+pub type SharedPodNodeFilter = Arc<RwLock<Option<String>>>;
+
 #[derive(Clone)]
 pub struct PodPoller {
     tx: Sender<Message>,
     shared_target_namespaces: SharedTargetNamespaces,
+    shared_node_filter: SharedPodNodeFilter,
     kube_client: KubeClient,
+    informers: SharedInformers,
     config: PodConfig,
 }
 
@@ -59,13 +65,17 @@ impl PodPoller {
     pub fn new(
         tx: Sender<Message>,
         shared_target_namespaces: SharedTargetNamespaces,
+        shared_node_filter: SharedPodNodeFilter,
         kube_client: KubeClient,
+        informers: SharedInformers,
         config: PodConfig,
     ) -> Self {
         Self {
             tx,
             shared_target_namespaces,
+            shared_node_filter,
             kube_client,
+            informers,
             config,
         }
     }
@@ -78,12 +88,47 @@ impl Worker for PodPoller {
     async fn run(&self) -> Self::Output {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
 
-        let Self { tx, .. } = self;
+        let Self {
+            tx,
+            shared_target_namespaces,
+            shared_node_filter,
+            kube_client,
+            informers,
+            ..
+        } = self;
+
+        let informer = informers.pod();
+
+        let mut detector = ChangeDetector::default();
+
+        let metrics = PodMetricsRefresher::spawn(
+            kube_client.clone(),
+            shared_target_namespaces.clone(),
+            informer.clone(),
+        );
 
         loop {
             interval.tick().await;
 
-            let pod_info = self.get_pod_info().await;
+            let namespaces = shared_target_namespaces.read().await.clone();
+
+            informer.sync_namespaces(&namespaces);
+
+            let Some(pods) = informer.state() else {
+                continue;
+            };
+
+            let node_filter = shared_node_filter.read().await.clone();
+
+            let pods = pods.map(|pods| filter_by_node(pods, node_filter.as_deref()));
+
+            let metrics = metrics.metrics();
+
+            let pod_info = pods.map(|pods| self.pod_table(&namespaces, &pods, metrics.as_ref()));
+
+            if !detector.is_changed(&pod_info) {
+                continue;
+            }
 
             tx.send(Message::Kube(Kube::Pod(pod_info)))
                 .expect("Failed to Kube::Pod");
@@ -92,79 +137,479 @@ impl Worker for PodPoller {
 }
 
 impl PodPoller {
-    async fn get_pod_info(&self) -> Result<KubeTable> {
-        let namespaces = self.shared_target_namespaces.read().await;
+    /// metrics-serverから使用量を取得できない場合は、CPU列とMEM列を表示しない
+    fn pod_table(
+        &self,
+        namespaces: &[String],
+        pods: &[Arc<Pod>],
+        metrics: Option<&PodMetricsMap>,
+    ) -> KubeTable {
+        let insert_ns = insert_ns(namespaces);
 
-        let jobs = self.get_pods_per_namespace(&namespaces).await;
+        let mut header: Vec<String> = if metrics.is_some() {
+            ["NAME", "READY", "STATUS", "CPU", "MEM", "AGE"]
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        } else {
+            ["NAME", "READY", "STATUS", "AGE"]
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        };
 
-        let ok_only: Vec<KubeTableRow> = jobs?.into_iter().flatten().collect();
+        if insert_ns {
+            header.insert(0, "NAMESPACE".to_string());
+        }
 
         let mut table = KubeTable {
-            header: if namespaces.len() == 1 {
-                ["NAME", "READY", "STATUS", "AGE"]
-                    .iter()
-                    .map(ToString::to_string)
-                    .collect()
-            } else {
-                ["NAMESPACE", "NAME", "READY", "STATUS", "AGE"]
-                    .iter()
-                    .map(ToString::to_string)
-                    .collect()
-            },
+            header,
             ..Default::default()
         };
 
-        table.update_rows(ok_only);
+        table.update_rows(
+            pods.iter()
+                .map(|pod| self.pod_row(pod, insert_ns, metrics))
+                .collect(),
+        );
 
-        Ok(table)
+        table
     }
 
-    async fn get_pods_per_namespace(
-        &self,
-        namespaces: &[String],
-    ) -> Result<Vec<Vec<KubeTableRow>>> {
-        let insert_ns = insert_ns(namespaces);
-        try_join_all(namespaces.iter().map(|ns| {
-            get_resource_per_namespace(
-                &self.kube_client,
-                format!("api/v1/namespaces/{}/{}", ns, "pods"),
-                &["Name", "Ready", "Status", "Age"],
-                move |row: &TableRow, indexes: &[usize]| {
-                    let mut row: Vec<String> =
-                        indexes.iter().map(|i| row.cells[*i].to_string()).collect();
-
-                    let name = row[0].clone();
-
-                    let status = row[2].as_str();
-
-                    let color = self
-                        .config
-                        .pod_highlight_rules
-                        .iter()
-                        .find(|rule| rule.status_regex.is_match(status))
-                        .map(|rule| style_to_ansi(rule.style));
-
-                    if insert_ns {
-                        row.insert(0, ns.to_string())
-                    }
+    fn pod_row(&self, pod: &Pod, insert_ns: bool, metrics: Option<&PodMetricsMap>) -> KubeTableRow {
+        let namespace = pod.namespace().unwrap_or_default();
+        let name = pod.name_any();
 
-                    if let Some(color) = color {
-                        row.iter_mut()
-                            .for_each(|r| *r = format!("{}{}\x1b[0m", color, r))
-                    }
+        let PodStatusSummary { ready, status } = PodStatusSummary::from(pod);
+
+        let mut row = vec![name.clone(), ready, status];
+
+        if let Some(metrics) = metrics {
+            let usage = metrics.get(&(namespace.clone(), name.clone()));
+
+            row.extend(pod_usage_cells(pod, usage));
+        }
+
+        row.push(translate_timestamp_since(
+            pod.metadata.creation_timestamp.as_ref(),
+        ));
+
+        let color = self
+            .config
+            .pod_highlight_rules
+            .iter()
+            .find(|rule| rule.status_regex.is_match(&row[2]))
+            .map(|rule| style_to_ansi(rule.style));
+
+        if insert_ns {
+            row.insert(0, namespace.to_string())
+        }
+
+        if let Some(color) = color {
+            row.iter_mut()
+                .for_each(|r| *r = format!("{}{}\x1b[0m", color, r))
+        }
+
+        KubeTableRow {
+            namespace,
+            name,
+            row,
+            metadata: Some(BTreeMap::from([(
+                "kind".to_string(),
+                Pod::KIND.to_string(),
+            )])),
+        }
+    }
+}
+
+/// spec.nodeNameが一致するPodのみを返す
+fn filter_by_node(pods: Vec<Arc<Pod>>, node: Option<&str>) -> Vec<Arc<Pod>> {
+    let Some(node) = node else {
+        return pods;
+    };
+
+    pods.into_iter()
+        .filter(|pod| pod.spec.as_ref().and_then(|spec| spec.node_name.as_deref()) == Some(node))
+        .collect()
+}
+
+/// kubectl get podsのREADY列とSTATUS列に相当する値
+#[derive(Debug, PartialEq)]
+struct PodStatusSummary {
+    ready: String,
+    status: String,
+}
+
+impl From<&Pod> for PodStatusSummary {
+    /// kubectlのprintPodと同じ手順でREADYとSTATUSを算出する
+    fn from(pod: &Pod) -> Self {
+        let spec = pod.spec.as_ref();
+        let status = pod.status.as_ref();
+
+        let total_containers = spec.map(|s| s.containers.len()).unwrap_or_default();
+        let total_init_containers = spec
+            .and_then(|s| s.init_containers.as_ref())
+            .map(Vec::len)
+            .unwrap_or_default();
+
+        let mut ready_containers = 0;
+
+        let mut reason = status
+            .and_then(|s| s.reason.clone())
+            .filter(|r| !r.is_empty())
+            .or_else(|| status.and_then(|s| s.phase.clone()))
+            .unwrap_or_default();
+
+        let conditions = status.and_then(|s| s.conditions.as_ref());
 
-                    KubeTableRow {
-                        namespace: ns.to_string(),
-                        name,
-                        row,
-                        metadata: Some(BTreeMap::from([(
-                            "kind".to_string(),
-                            Pod::KIND.to_string(),
-                        )])),
+        let is_condition_true = |ty: &str| {
+            conditions.is_some_and(|conditions| {
+                conditions
+                    .iter()
+                    .any(|c| c.type_ == ty && c.status == "True")
+            })
+        };
+
+        let mut initializing = false;
+
+        let init_container_statuses = status
+            .and_then(|s| s.init_container_statuses.as_deref())
+            .unwrap_or_default();
+
+        for (i, container) in init_container_statuses.iter().enumerate() {
+            let state = container.state.as_ref();
+            let terminated = state.and_then(|s| s.terminated.as_ref());
+            let waiting = state.and_then(|s| s.waiting.as_ref());
+
+            match (terminated, waiting) {
+                (Some(terminated), _) if terminated.exit_code == 0 => continue,
+                (Some(terminated), _) => {
+                    reason = match terminated.reason.as_deref() {
+                        Some(r) if !r.is_empty() => format!("Init:{}", r),
+                        _ => match terminated.signal {
+                            Some(signal) if signal != 0 => format!("Init:Signal:{}", signal),
+                            _ => format!("Init:ExitCode:{}", terminated.exit_code),
+                        },
+                    };
+                }
+                (None, Some(waiting))
+                    if waiting
+                        .reason
+                        .as_deref()
+                        .is_some_and(|r| !r.is_empty() && r != "PodInitializing") =>
+                {
+                    reason = format!("Init:{}", waiting.reason.as_deref().unwrap_or_default());
+                }
+                _ => {
+                    // sidecarコンテナ（restartPolicy: Always）は起動済みであれば初期化完了とみなす
+                    if container.started == Some(true) && is_sidecar(pod, &container.name) {
+                        continue;
                     }
-                },
-            )
-        }))
-        .await
+
+                    reason = format!("Init:{}/{}", i, total_init_containers);
+                }
+            }
+
+            initializing = true;
+            break;
+        }
+
+        if !initializing || is_condition_true("Initialized") {
+            let mut has_running = false;
+
+            let container_statuses = status
+                .and_then(|s| s.container_statuses.as_deref())
+                .unwrap_or_default();
+
+            for container in container_statuses.iter().rev() {
+                let state = container.state.as_ref();
+                let terminated = state.and_then(|s| s.terminated.as_ref());
+                let waiting = state.and_then(|s| s.waiting.as_ref());
+                let running = state.and_then(|s| s.running.as_ref());
+
+                if let Some(r) = waiting
+                    .and_then(|w| w.reason.as_ref())
+                    .filter(|r| !r.is_empty())
+                {
+                    reason = r.to_string();
+                } else if let Some(terminated) = terminated {
+                    reason = match terminated.reason.as_deref() {
+                        Some(r) if !r.is_empty() => r.to_string(),
+                        _ => match terminated.signal {
+                            Some(signal) if signal != 0 => format!("Signal:{}", signal),
+                            _ => format!("ExitCode:{}", terminated.exit_code),
+                        },
+                    };
+                } else if container.ready && running.is_some() {
+                    has_running = true;
+                    ready_containers += 1;
+                }
+            }
+
+            if reason == "Completed" && has_running {
+                reason = if is_condition_true("Ready") {
+                    "Running".to_string()
+                } else {
+                    "NotReady".to_string()
+                };
+            }
+        }
+
+        if pod.metadata.deletion_timestamp.is_some() {
+            let is_terminal = status
+                .and_then(|s| s.phase.as_deref())
+                .is_some_and(|phase| phase == "Succeeded" || phase == "Failed");
+
+            if status.and_then(|s| s.reason.as_deref()) == Some("NodeLost") {
+                reason = "Unknown".to_string();
+            } else if !is_terminal {
+                reason = "Terminating".to_string();
+            }
+        }
+
+        Self {
+            ready: format!("{}/{}", ready_containers, total_containers),
+            status: reason,
+        }
+    }
+}
+
+fn is_sidecar(pod: &Pod, name: &str) -> bool {
+    pod.spec
+        .as_ref()
+        .and_then(|spec| spec.init_containers.as_ref())
+        .and_then(|containers| containers.iter().find(|c| c.name == name))
+        .and_then(|c| c.restart_policy.as_deref())
+        == Some("Always")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn pod(yaml: &str) -> Pod {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn 全てのコンテナが起動しているときrunningを返す() {
+        let pod = pod(indoc! {
+            r#"
+            metadata:
+              name: pod
+            spec:
+              containers:
+                - name: a
+                - name: b
+            status:
+              phase: Running
+              containerStatuses:
+                - name: a
+                  image: a
+                  imageID: a
+                  ready: true
+                  restartCount: 0
+                  state:
+                    running: {}
+                - name: b
+                  image: b
+                  imageID: b
+                  ready: true
+                  restartCount: 0
+                  state:
+                    running: {}
+            "#
+        });
+
+        assert_eq!(
+            PodStatusSummary::from(&pod),
+            PodStatusSummary {
+                ready: "2/2".into(),
+                status: "Running".into()
+            }
+        );
+    }
+
+    #[test]
+    fn コンテナが待機中のとき待機理由を返す() {
+        let pod = pod(indoc! {
+            r#"
+            metadata:
+              name: pod
+            spec:
+              containers:
+                - name: a
+            status:
+              phase: Running
+              containerStatuses:
+                - name: a
+                  image: a
+                  imageID: a
+                  ready: false
+                  restartCount: 5
+                  state:
+                    waiting:
+                      reason: CrashLoopBackOff
+            "#
+        });
+
+        assert_eq!(
+            PodStatusSummary::from(&pod),
+            PodStatusSummary {
+                ready: "0/1".into(),
+                status: "CrashLoopBackOff".into()
+            }
+        );
+    }
+
+    #[test]
+    fn initコンテナが実行中のとき進捗を返す() {
+        let pod = pod(indoc! {
+            r#"
+            metadata:
+              name: pod
+            spec:
+              initContainers:
+                - name: init-1
+                - name: init-2
+              containers:
+                - name: a
+            status:
+              phase: Pending
+              initContainerStatuses:
+                - name: init-1
+                  image: a
+                  imageID: a
+                  ready: true
+                  restartCount: 0
+                  state:
+                    terminated:
+                      exitCode: 0
+                - name: init-2
+                  image: a
+                  imageID: a
+                  ready: false
+                  restartCount: 0
+                  state:
+                    running: {}
+              containerStatuses:
+                - name: a
+                  image: a
+                  imageID: a
+                  ready: false
+                  restartCount: 0
+                  state:
+                    waiting:
+                      reason: PodInitializing
+            "#
+        });
+
+        assert_eq!(
+            PodStatusSummary::from(&pod),
+            PodStatusSummary {
+                ready: "0/1".into(),
+                status: "Init:1/2".into()
+            }
+        );
+    }
+
+    #[test]
+    fn 削除中のときterminatingを返す() {
+        let pod = pod(indoc! {
+            r#"
+            metadata:
+              name: pod
+              deletionTimestamp: "2024-01-01T00:00:00Z"
+            spec:
+              containers:
+                - name: a
+            status:
+              phase: Running
+              containerStatuses:
+                - name: a
+                  image: a
+                  imageID: a
+                  ready: true
+                  restartCount: 0
+                  state:
+                    running: {}
+            "#
+        });
+
+        assert_eq!(
+            PodStatusSummary::from(&pod),
+            PodStatusSummary {
+                ready: "1/1".into(),
+                status: "Terminating".into()
+            }
+        );
+    }
+
+    #[test]
+    fn 終了したコンテナに理由がないときexit_codeを返す() {
+        let pod = pod(indoc! {
+            r#"
+            metadata:
+              name: pod
+            spec:
+              containers:
+                - name: a
+            status:
+              phase: Failed
+              containerStatuses:
+                - name: a
+                  image: a
+                  imageID: a
+                  ready: false
+                  restartCount: 0
+                  state:
+                    terminated:
+                      exitCode: 137
+            "#
+        });
+
+        assert_eq!(
+            PodStatusSummary::from(&pod),
+            PodStatusSummary {
+                ready: "0/1".into(),
+                status: "ExitCode:137".into()
+            }
+        );
+    }
+
+    #[test]
+    fn node名を指定したときそのnodeで実行されているpodのみを返す() {
+        let pods = vec![
+            Arc::new(pod(indoc! {
+                r#"
+                metadata:
+                  name: a
+                spec:
+                  nodeName: node-1
+                  containers: []
+                "#
+            })),
+            Arc::new(pod(indoc! {
+                r#"
+                metadata:
+                  name: b
+                spec:
+                  nodeName: node-2
+                  containers: []
+                "#
+            })),
+        ];
+
+        let actual: Vec<String> = filter_by_node(pods.clone(), Some("node-1"))
+            .iter()
+            .map(|pod| pod.name_any())
+            .collect();
+
+        assert_eq!(actual, vec!["a"]);
+
+        assert_eq!(filter_by_node(pods, None).len(), 2);
     }
 }
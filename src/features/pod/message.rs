@@ -1,14 +1,28 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
 
 use crate::{message::Message, workers::kube::message::Kube};
 
-use super::kube::LogConfig;
+use super::kube::{LogConfig, LogExportFormat};
 
 #[derive(Debug)]
 pub enum LogMessage {
     Request(LogConfig),
     Response(Result<Vec<String>>),
     ToggleJsonPrettyPrint,
+    /// Noneのときはteeモードを終了する
+    Tee(Option<LogTeeRequest>),
+    /// teeモードで追記しているファイルのパス
+    /// Noneのときはteeモードが終了したことを表す
+    TeeResponse(Result<Option<PathBuf>>),
+}
+
+/// 受け取ったログを追記するファイル
+#[derive(Debug, Clone)]
+pub struct LogTeeRequest {
+    pub path: PathBuf,
+    pub format: LogExportFormat,
 }
 
 impl From<LogMessage> for Message {
@@ -16,3 +30,14 @@ impl From<LogMessage> for Message {
         Message::Kube(Kube::Log(m))
     }
 }
+
+/// spec.nodeNameでPodの一覧を絞り込む
+/// Noneのときは絞り込みを解除する
+#[derive(Debug)]
+pub struct PodNodeFilter(pub Option<String>);
+
+impl From<PodNodeFilter> for Message {
+    fn from(f: PodNodeFilter) -> Message {
+        Message::Kube(Kube::PodNodeFilter(f))
+    }
+}
@@ -1,4 +1,11 @@
+mod log_export;
+mod log_query_history;
+mod node_filter;
+mod saved_queries;
 mod tab;
 mod widgets;
 
+pub use log_export::*;
+pub use node_filter::*;
+pub use saved_queries::*;
 pub use tab::*;
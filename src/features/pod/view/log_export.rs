@@ -0,0 +1,291 @@
+use std::{cell::RefCell, path::Path, rc::Rc};
+
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    config::theme::WidgetThemeConfig,
+    features::{
+        component_id::{
+            POD_LOG_EXPORT_FORMAT_DIALOG_ID, POD_LOG_EXPORT_PATH_DIALOG_ID,
+            POD_LOG_EXPORT_RESULT_DIALOG_ID, POD_LOG_WIDGET_ID,
+        },
+        pod::{
+            kube::{expand_path, export_log, LogExportFormat},
+            message::{LogMessage, LogTeeRequest},
+        },
+    },
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{
+            single_select::{
+                FilterForm, FilterFormTheme, SelectForm, SelectFormTheme, SingleSelectTheme,
+            },
+            InputForm, InputFormTheme, Item, LiteralItem, SelectedItem, SingleSelect, Text,
+            TextTheme, Widget, WidgetBase, WidgetTheme, WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+use super::widgets::LOG_TITLE;
+
+/// ログの書き出し方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogExportMode {
+    /// 表示しているログをファイルに保存する
+    Save,
+    /// 受け取ったログをファイルに追記し続ける
+    Tee,
+}
+
+impl LogExportMode {
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Save => "Save Log",
+            Self::Tee => "Tee Log",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LogExportTarget {
+    mode: LogExportMode,
+    format: LogExportFormat,
+}
+
+/// 形式の選択ダイアログで選んだ内容を、パスの入力ダイアログと共有する
+type SharedLogExportTarget = Rc<RefCell<LogExportTarget>>;
+
+/// ログをファイルに書き出すためのダイアログ群
+pub struct LogExportDialogs {
+    pub format_dialog: Widget<'static>,
+    pub path_dialog: Widget<'static>,
+    pub result_dialog: Widget<'static>,
+    tx: Sender<Message>,
+    target: SharedLogExportTarget,
+}
+
+impl LogExportDialogs {
+    pub fn new(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Self {
+        let target = Rc::new(RefCell::new(LogExportTarget {
+            mode: LogExportMode::Save,
+            format: LogExportFormat::default(),
+        }));
+
+        Self {
+            format_dialog: format_widget(target.clone(), theme.clone()),
+            path_dialog: path_widget(tx.clone(), target.clone(), theme.clone()),
+            result_dialog: result_widget(theme),
+            tx: tx.clone(),
+            target,
+        }
+    }
+
+    /// 表示しているログを保存するため、書き出す形式の選択ダイアログを開く
+    pub fn open_save(&self) -> impl Fn(&mut Window) -> EventResult {
+        let target = self.target.clone();
+
+        move |w: &mut Window| {
+            open_format_dialog(w, &target, LogExportMode::Save);
+
+            EventResult::Nop
+        }
+    }
+
+    /// teeモードを開始するため、書き出す形式の選択ダイアログを開く
+    /// teeモードで実行中のときは終了する
+    pub fn toggle_tee(&self) -> impl Fn(&mut Window) -> EventResult {
+        let tx = self.tx.clone();
+        let target = self.target.clone();
+
+        move |w: &mut Window| {
+            if is_log_tee_running(w) {
+                tx.send(LogMessage::Tee(None).into())
+                    .expect("Failed to send LogMessage::Tee");
+            } else {
+                open_format_dialog(w, &target, LogExportMode::Tee);
+            }
+
+            EventResult::Nop
+        }
+    }
+}
+
+fn open_format_dialog(w: &mut Window, target: &SharedLogExportTarget, mode: LogExportMode) {
+    target.borrow_mut().mode = mode;
+
+    let items = LogExportFormat::ALL
+        .into_iter()
+        .map(|format| LiteralItem::from(format.to_string()))
+        .collect();
+
+    let dialog = w.find_widget_mut(POD_LOG_EXPORT_FORMAT_DIALOG_ID);
+
+    *dialog.widget_base_mut().title_mut() = mode.title().into();
+
+    dialog.update_widget_item(Item::Array(items));
+
+    w.open_dialog(POD_LOG_EXPORT_FORMAT_DIALOG_ID);
+}
+
+/// teeモードで追記しているファイルをLogウィジェットのタイトルに反映する
+/// Noneのときはteeモードを終了したことを表す
+///
+/// Logウィジェットをクリアしても表示が消えないように、append_titleではなくtitleに設定する
+pub fn update_log_tee_title(w: &mut Window, path: Option<&Path>) {
+    let title = match path {
+        Some(path) => format!("{} (tee: {})", LOG_TITLE, path.display()),
+        None => LOG_TITLE.to_string(),
+    };
+
+    *w.find_widget_mut(POD_LOG_WIDGET_ID)
+        .widget_base_mut()
+        .title_mut() = title.into();
+}
+
+/// teeモードで実行中かどうか
+fn is_log_tee_running(w: &Window) -> bool {
+    w.find_widget(POD_LOG_WIDGET_ID)
+        .widget_base()
+        .title()
+        .to_string()
+        != LOG_TITLE
+}
+
+/// 書き出しの結果をダイアログに表示する
+pub fn show_log_export_result(w: &mut Window, title: &str, lines: Vec<String>) {
+    let widget = w.find_widget_mut(POD_LOG_EXPORT_RESULT_DIALOG_ID);
+
+    *widget.widget_base_mut().title_mut() = title.into();
+
+    widget.update_widget_item(Item::Array(
+        lines.into_iter().map(LiteralItem::from).collect(),
+    ));
+
+    w.open_dialog(POD_LOG_EXPORT_RESULT_DIALOG_ID);
+}
+
+fn format_widget(target: SharedLogExportTarget, theme: WidgetThemeConfig) -> Widget<'static> {
+    let single_select_theme = SingleSelectTheme::default().status_style(theme.list.status);
+
+    let filter_form = FilterForm::builder()
+        .theme(FilterFormTheme::from(theme.clone()))
+        .build();
+
+    let select_form = SelectForm::builder()
+        .theme(SelectFormTheme::from(theme.clone()))
+        .on_select(move |w: &mut Window, v: &LiteralItem| {
+            let Ok(format) = v.item.parse::<LogExportFormat>() else {
+                return EventResult::Ignore;
+            };
+
+            let mode = {
+                let mut target = target.borrow_mut();
+
+                target.format = format;
+
+                target.mode
+            };
+
+            w.widget_clear(POD_LOG_EXPORT_PATH_DIALOG_ID);
+
+            let dialog = w.find_widget_mut(POD_LOG_EXPORT_PATH_DIALOG_ID);
+
+            *dialog.widget_base_mut().title_mut() = mode.title().into();
+            *dialog.widget_base_mut().append_title_mut() = Some(format!(" : {}", format).into());
+
+            w.open_dialog(POD_LOG_EXPORT_PATH_DIALOG_ID);
+
+            EventResult::Nop
+        })
+        .build();
+
+    let widget_base = WidgetBase::builder()
+        .title(LogExportMode::Save.title())
+        .theme(WidgetTheme::from(theme))
+        .build();
+
+    SingleSelect::builder()
+        .id(POD_LOG_EXPORT_FORMAT_DIALOG_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .select_form(select_form)
+        .theme(single_select_theme)
+        .build()
+        .into()
+}
+
+fn path_widget(
+    tx: Sender<Message>,
+    target: SharedLogExportTarget,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    let widget_base = WidgetBase::builder()
+        .title(LogExportMode::Save.title())
+        .theme(WidgetTheme::from(theme.clone()))
+        .build();
+
+    InputForm::builder()
+        .id(POD_LOG_EXPORT_PATH_DIALOG_ID)
+        .widget_base(widget_base)
+        .theme(InputFormTheme::from(theme))
+        .prefix("path: ")
+        .actions(UserEvent::from(KeyCode::Enter), move |w: &mut Window| {
+            let widget = w.find_widget(POD_LOG_EXPORT_PATH_DIALOG_ID);
+
+            let Some(SelectedItem::Literal { item, .. }) = widget.widget_item() else {
+                return EventResult::Ignore;
+            };
+
+            let LogExportTarget { mode, format } = *target.borrow();
+
+            let path = match expand_path(&item) {
+                Ok(path) => path,
+                Err(err) => {
+                    show_log_export_result(w, mode.title(), vec![err.to_string()]);
+
+                    return EventResult::Nop;
+                }
+            };
+
+            match mode {
+                LogExportMode::Save => {
+                    let lines = w.find_widget(POD_LOG_WIDGET_ID).as_text().lines();
+
+                    let message = match export_log(&path, lines, format) {
+                        Ok(count) => vec![format!("Saved {} lines to {}", count, path.display())],
+                        Err(err) => vec![format!("{:#}", err)],
+                    };
+
+                    show_log_export_result(w, mode.title(), message);
+                }
+                LogExportMode::Tee => {
+                    tx.send(LogMessage::Tee(Some(LogTeeRequest { path, format })).into())
+                        .expect("Failed to send LogMessage::Tee");
+
+                    w.close_dialog();
+                }
+            }
+
+            EventResult::Nop
+        })
+        .build()
+        .into()
+}
+
+fn result_widget(theme: WidgetThemeConfig) -> Widget<'static> {
+    let widget_base = WidgetBase::builder()
+        .title(LogExportMode::Save.title())
+        .theme(WidgetTheme::from(theme.clone()))
+        .build();
+
+    Text::builder()
+        .id(POD_LOG_EXPORT_RESULT_DIALOG_ID)
+        .widget_base(widget_base)
+        .theme(TextTheme::from(theme))
+        .wrap()
+        .build()
+        .into()
+}
@@ -0,0 +1,195 @@
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+use anyhow::{Context as _, Result};
+
+use crate::logger;
+
+/// 保持するクエリの上限
+const MAX_HISTORY: usize = 100;
+
+const HISTORY_FILE_NAME: &str = "log_query_history";
+
+/// ログクエリの履歴
+///
+/// 1行に1つのクエリを古い順に保存し、次回の起動時に読み込む
+#[derive(Debug, Default)]
+pub struct LogQueryHistory {
+    /// Noneのときはファイルに保存しない
+    path: Option<PathBuf>,
+
+    entries: Vec<String>,
+
+    /// 表示している履歴のインデックス
+    /// Noneのときは履歴を表示していない
+    cursor: Option<usize>,
+
+    /// 履歴を表示する前に入力していたクエリ
+    draft: String,
+}
+
+pub type SharedLogQueryHistory = Rc<RefCell<LogQueryHistory>>;
+
+impl LogQueryHistory {
+    /// ファイルが存在しない、または読み込めないときは空の履歴を返す
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let entries = path
+            .as_ref()
+            .and_then(|path| match std::fs::read_to_string(path) {
+                Ok(content) => Some(content),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+                Err(err) => {
+                    logger!(error, "Failed to read log query history: {}", err);
+                    None
+                }
+            })
+            .map(|content| {
+                content
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut history = Self {
+            path,
+            entries,
+            ..Default::default()
+        };
+
+        history.truncate();
+        history
+    }
+
+    /// 実行したクエリを末尾に追加してファイルに保存する
+    ///
+    /// 同じクエリがある場合は古い方を取り除く
+    pub fn push(&mut self, query: &str) {
+        self.cursor = None;
+        self.draft.clear();
+
+        let query = query.trim();
+
+        if query.is_empty() {
+            return;
+        }
+
+        self.entries.retain(|entry| entry != query);
+        self.entries.push(query.to_string());
+
+        self.truncate();
+
+        if let Err(err) = self.save() {
+            logger!(error, "{:?}", err);
+        }
+    }
+
+    /// 1つ前のクエリを返す
+    ///
+    /// 履歴を表示していないときは、入力中のクエリを退避して最新のクエリを返す
+    pub fn prev(&mut self, current: &str) -> Option<&str> {
+        let index = match self.cursor {
+            Some(index) => index.checked_sub(1)?,
+            None => {
+                let index = self.entries.len().checked_sub(1)?;
+                self.draft = current.to_string();
+                index
+            }
+        };
+
+        self.cursor = Some(index);
+
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// 1つ後のクエリを返す
+    ///
+    /// 最新のクエリより後は、履歴を表示する前に入力していたクエリを返す
+    pub fn next(&mut self) -> Option<&str> {
+        let index = self.cursor? + 1;
+
+        if index < self.entries.len() {
+            self.cursor = Some(index);
+            self.entries.get(index).map(String::as_str)
+        } else {
+            self.cursor = None;
+            Some(&self.draft)
+        }
+    }
+
+    fn truncate(&mut self) {
+        self.entries
+            .drain(..self.entries.len().saturating_sub(MAX_HISTORY));
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+
+        let mut content = self.entries.join("\n");
+        content.push('\n');
+
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write log query history: {}", path.display()))
+    }
+}
+
+/// 履歴を保存するファイルのパス
+///
+/// `$XDG_STATE_HOME/kubetui`、未設定のときは`~/.local/state/kubetui`に保存する
+pub fn log_query_history_path() -> Option<PathBuf> {
+    let dir = match std::env::var_os("XDG_STATE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::home_dir()?.join(".local").join("state"),
+    };
+
+    Some(dir.join("kubetui").join(HISTORY_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn 履歴を遡って入力中のクエリに戻る() {
+        let mut history = LogQueryHistory::default();
+
+        history.push("pod:app");
+        history.push("log:error");
+        history.push("pod:app");
+
+        assert_eq!(history.prev("tail:10"), Some("pod:app"));
+        assert_eq!(history.prev("pod:app"), Some("log:error"));
+        assert_eq!(history.prev("log:error"), None);
+        assert_eq!(history.next(), Some("pod:app"));
+        assert_eq!(history.next(), Some("tail:10"));
+        assert_eq!(history.next(), None);
+    }
+
+    #[test]
+    fn 実行したクエリをファイルに保存して読み込む() {
+        let dir =
+            std::env::temp_dir().join(format!("kubetui-log-query-history-{}", std::process::id()));
+
+        let path = dir.join("state").join(HISTORY_FILE_NAME);
+
+        let mut history = LogQueryHistory::load(Some(path.clone()));
+
+        history.push("pod:app");
+        history.push(" log:error ");
+
+        let actual = LogQueryHistory::load(Some(path)).entries;
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(actual, vec!["pod:app", "log:error"]);
+    }
+}
@@ -0,0 +1,28 @@
+use crossbeam::channel::Sender;
+
+use crate::{
+    features::{component_id::POD_WIDGET_ID, pod::message::PodNodeFilter},
+    message::Message,
+    ui::{widget::WidgetTrait as _, Window},
+};
+
+/// Podの一覧をNodeで絞り込み、絞り込んでいるNodeをPodウィジェットのタイトルに表示する
+/// Noneのときは絞り込みを解除する
+pub fn filter_pods_by_node(tx: &Sender<Message>, w: &mut Window, node: Option<String>) {
+    update_pod_title(w, node.as_deref());
+
+    tx.send(PodNodeFilter(node).into())
+        .expect("Failed to send PodNodeFilter");
+}
+
+/// 絞り込んでいるNodeをPodウィジェットのタイトルに反映する
+pub fn update_pod_title(w: &mut Window, node: Option<&str>) {
+    let title = match node {
+        Some(node) => format!("Pod (node: {})", node),
+        None => "Pod".to_string(),
+    };
+
+    *w.find_widget_mut(POD_WIDGET_ID)
+        .widget_base_mut()
+        .title_mut() = title.into();
+}
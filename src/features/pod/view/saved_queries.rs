@@ -0,0 +1,157 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crossbeam::channel::Sender;
+
+use crate::{
+    config::theme::WidgetThemeConfig,
+    features::component_id::{POD_LOG_QUERY_WIDGET_ID, POD_LOG_SAVED_QUERIES_DIALOG_ID},
+    kube::context::Namespace,
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            single_select::{
+                FilterForm, FilterFormTheme, SelectForm, SelectFormTheme, SingleSelectTheme,
+            },
+            Item, LiteralItem, SingleSelect, Widget, WidgetBase, WidgetTheme, WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+use super::{log_query_history::SharedLogQueryHistory, widgets::exec_log_query};
+
+/// config.yamlの`saved_queries`に定義したログクエリを選択して実行するダイアログ
+pub struct SavedQueriesDialog {
+    pub widget: Widget<'static>,
+}
+
+impl SavedQueriesDialog {
+    pub fn new(
+        tx: &Sender<Message>,
+        namespaces: Rc<RefCell<Namespace>>,
+        history: SharedLogQueryHistory,
+        saved_queries: &BTreeMap<String, String>,
+        theme: WidgetThemeConfig,
+    ) -> Self {
+        let mut widget = saved_queries_widget(tx.clone(), namespaces, history, theme);
+
+        widget.update_widget_item(Item::Array(saved_query_items(saved_queries)));
+
+        Self { widget }
+    }
+
+    pub fn open(&self) -> impl Fn(&mut Window) -> EventResult {
+        move |w: &mut Window| {
+            w.open_dialog(POD_LOG_SAVED_QUERIES_DIALOG_ID);
+
+            EventResult::Nop
+        }
+    }
+}
+
+/// 名前とクエリを表示し、選択したときに実行するクエリをメタデータに持たせる
+fn saved_query_items(saved_queries: &BTreeMap<String, String>) -> Vec<LiteralItem> {
+    saved_queries
+        .iter()
+        .map(|(name, query)| {
+            LiteralItem::new(
+                format!("{} ({})", name, query),
+                Some(BTreeMap::from([("query".to_string(), query.to_string())])),
+            )
+        })
+        .collect()
+}
+
+fn saved_queries_widget(
+    tx: Sender<Message>,
+    namespaces: Rc<RefCell<Namespace>>,
+    history: SharedLogQueryHistory,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    let single_select_theme = SingleSelectTheme::default().status_style(theme.list.status);
+
+    let filter_form = FilterForm::builder()
+        .theme(FilterFormTheme::from(theme.clone()))
+        .build();
+
+    let select_form = SelectForm::builder()
+        .theme(SelectFormTheme::from(theme.clone()))
+        .on_select(on_select(tx, namespaces, history))
+        .build();
+
+    let widget_base = WidgetBase::builder()
+        .title("Saved Queries")
+        .theme(WidgetTheme::from(theme))
+        .build();
+
+    SingleSelect::builder()
+        .id(POD_LOG_SAVED_QUERIES_DIALOG_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .select_form(select_form)
+        .theme(single_select_theme)
+        .build()
+        .into()
+}
+
+fn on_select(
+    tx: Sender<Message>,
+    namespaces: Rc<RefCell<Namespace>>,
+    history: SharedLogQueryHistory,
+) -> impl Fn(&mut Window, &LiteralItem) -> EventResult {
+    move |w: &mut Window, v: &LiteralItem| {
+        let Some(query) = v.metadata.as_ref().and_then(|m| m.get("query")) else {
+            return EventResult::Ignore;
+        };
+
+        w.close_dialog();
+
+        w.find_widget_mut(POD_LOG_QUERY_WIDGET_ID)
+            .update_widget_item(Item::Single(query.clone().into()));
+
+        exec_log_query(w, &tx, &namespaces.borrow(), &history, query.clone());
+
+        EventResult::Nop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn 保存したクエリを名前順に並べる() {
+        let saved_queries = BTreeMap::from([
+            ("payments".to_string(), "deployment/payments".to_string()),
+            (
+                "api-errors".to_string(),
+                "deployment/api log:error".to_string(),
+            ),
+        ]);
+
+        let actual: Vec<(String, Option<String>)> = saved_query_items(&saved_queries)
+            .into_iter()
+            .map(|item| {
+                let query = item.metadata.and_then(|m| m.get("query").cloned());
+                (item.item, query)
+            })
+            .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                (
+                    "api-errors (deployment/api log:error)".to_string(),
+                    Some("deployment/api log:error".to_string())
+                ),
+                (
+                    "payments (deployment/payments)".to_string(),
+                    Some("deployment/payments".to_string())
+                ),
+            ]
+        );
+    }
+}
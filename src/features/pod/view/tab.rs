@@ -5,7 +5,7 @@ use ratatui::layout::{Constraint, Direction};
 
 use crate::{
     clipboard::Clipboard,
-    config::theme::WidgetThemeConfig,
+    config::Config,
     features::component_id::{POD_TAB_ID, POD_WIDGET_ID},
     kube::context::Namespace,
     message::Message,
@@ -16,11 +16,18 @@ use crate::{
     },
 };
 
-use super::widgets::{log_query_help_widget, log_query_widget, log_widget, pod_widget};
+use super::{
+    log_export::LogExportDialogs,
+    log_query_history::{log_query_history_path, LogQueryHistory},
+    saved_queries::SavedQueriesDialog,
+    widgets::{log_query_help_widget, log_query_widget, log_widget, pod_widget},
+};
 
 pub struct PodTab {
     pub tab: Tab<'static>,
     pub log_query_help_dialog: Widget<'static>,
+    pub log_export_dialogs: LogExportDialogs,
+    pub saved_queries_dialog: SavedQueriesDialog,
 }
 
 impl PodTab {
@@ -30,11 +37,30 @@ impl PodTab {
         clipboard: &Option<Rc<RefCell<Clipboard>>>,
         split_direction: Direction,
         namespaces: Rc<RefCell<Namespace>>,
-        theme: WidgetThemeConfig,
+        config: &Config,
     ) -> Self {
-        let pod_widget = pod_widget(tx, theme.clone());
-        let log_query_widget = log_query_widget(tx, namespaces, theme.clone());
-        let log_widget = log_widget(tx, clipboard, theme);
+        let theme = config.theme.component.clone();
+
+        let pod_widget = pod_widget(tx, config.keybindings.pod.clone(), theme.clone());
+        let history = Rc::new(RefCell::new(
+            LogQueryHistory::load(log_query_history_path()),
+        ));
+        let saved_queries_dialog = SavedQueriesDialog::new(
+            tx,
+            namespaces.clone(),
+            history.clone(),
+            &config.saved_queries,
+            theme.clone(),
+        );
+        let log_query_widget = log_query_widget(tx, namespaces, history, theme.clone());
+        let log_export_dialogs = LogExportDialogs::new(tx, theme.clone());
+        let log_widget = log_widget(
+            tx,
+            clipboard,
+            &log_export_dialogs,
+            &saved_queries_dialog,
+            config,
+        );
         let log_query_help_widget = log_query_help_widget();
 
         let layout = TabLayout::new(layout, split_direction);
@@ -51,6 +77,8 @@ impl PodTab {
         Self {
             tab,
             log_query_help_dialog: log_query_help_widget,
+            log_export_dialogs,
+            saved_queries_dialog,
         }
     }
 }
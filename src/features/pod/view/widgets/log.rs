@@ -1,32 +1,54 @@
 use std::{cell::RefCell, rc::Rc};
 
 use crossbeam::channel::Sender;
-use ratatui::{crossterm::event::KeyCode, widgets::Block};
+use ratatui::widgets::Block;
 
 use crate::{
     clipboard::Clipboard,
-    config::theme::WidgetThemeConfig,
-    features::{component_id::POD_LOG_WIDGET_ID, pod::message::LogMessage},
-    message::{Message, UserEvent},
+    config::Config,
+    features::{
+        component_id::POD_LOG_WIDGET_ID,
+        pod::{
+            message::LogMessage,
+            view::{LogExportDialogs, SavedQueriesDialog},
+        },
+    },
+    message::Message,
     ui::{
         event::EventResult,
         widget::{
-            Item, SearchForm, SearchFormTheme, Text, TextTheme, Widget, WidgetBase, WidgetTheme,
-            WidgetTrait as _,
+            Item, SearchForm, SearchFormTheme, Text, TextHighlightRule, TextTheme, Widget,
+            WidgetBase, WidgetTheme, WidgetTrait as _,
         },
         Window,
     },
 };
 
+pub const LOG_TITLE: &str = "Log";
+
 pub fn log_widget(
     tx: &Sender<Message>,
     clipboard: &Option<Rc<RefCell<Clipboard>>>,
-    theme: WidgetThemeConfig,
+    log_export: &LogExportDialogs,
+    saved_queries: &SavedQueriesDialog,
+    config: &Config,
 ) -> Widget<'static> {
+    let theme = config.theme.component.clone();
+    let keybindings = &config.keybindings.log;
+
+    let highlight_rules: Vec<TextHighlightRule> = config
+        .theme
+        .log
+        .highlights
+        .iter()
+        .cloned()
+        .map(Into::into)
+        .collect();
+
     let widget_theme = WidgetTheme::from(theme.clone());
 
     let widget_base = WidgetBase::builder()
-        .title("Log")
+        .title(LOG_TITLE)
         .theme(widget_theme)
         .build();
 
@@ -41,18 +63,19 @@ pub fn log_widget(
         .widget_base(widget_base)
         .search_form(search_form)
         .theme(text_theme)
+        .highlight_rules(highlight_rules)
         .wrap()
         .follow()
+        .max_lines(config.buffer.log.max_lines())
         .block_injection(block_injection())
-        .action(UserEvent::from(KeyCode::Enter), add_blankline())
-        .action(
-            UserEvent::from(KeyCode::Char('f')),
+        .key_action(&keybindings.insert_blank_line, add_blankline())
+        .key_action(
+            &keybindings.toggle_json,
             toggle_json_pretty_print(tx.clone()),
         )
-        .action(
-            UserEvent::from(KeyCode::Char('p')),
-            toggle_json_pretty_print(tx.clone()),
-        );
+        .key_action(&keybindings.save, log_export.open_save())
+        .key_action(&keybindings.toggle_tee, log_export.toggle_tee())
+        .key_action(&keybindings.open_saved_queries, saved_queries.open());
 
     if let Some(cb) = clipboard {
         builder.clipboard(cb.clone())
@@ -69,7 +92,7 @@ fn block_injection() -> impl Fn(&Text, bool, bool) -> Block<'static> {
 
         let mut base = text.widget_base().clone();
 
-        *base.title_mut() = format!("Log [{}/{}]", index, size).into();
+        *base.title_mut() = format!("{} [{}/{}]", base.title(), index, size).into();
 
         base.render_block(text.can_activate() && is_active, is_mouse_over)
     }
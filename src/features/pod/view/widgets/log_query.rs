@@ -17,15 +17,19 @@ use crate::{
     ui::{
         event::EventResult,
         widget::{
-            InputFormBuilder, InputFormTheme, SelectedItem, Widget, WidgetBase, WidgetTrait as _,
+            InputFormBuilder, InputFormTheme, Item, SelectedItem, Widget, WidgetBase,
+            WidgetTrait as _,
         },
         Window,
     },
 };
 
+use super::super::log_query_history::SharedLogQueryHistory;
+
 pub fn log_query_widget(
     tx: &Sender<Message>,
     namespaces: Rc<RefCell<Namespace>>,
+    history: SharedLogQueryHistory,
     theme: WidgetThemeConfig,
 ) -> Widget<'static> {
     let tx = tx.clone();
@@ -41,7 +45,12 @@ pub fn log_query_widget(
         .id(POD_LOG_QUERY_WIDGET_ID)
         .widget_base(widget_base)
         .theme(input_theme)
-        .actions(UserEvent::from(KeyCode::Enter), exec_query(tx, namespaces))
+        .actions(
+            UserEvent::from(KeyCode::Enter),
+            exec_query(tx, namespaces, history.clone()),
+        )
+        .actions(UserEvent::from(KeyCode::Up), prev_query(history.clone()))
+        .actions(UserEvent::from(KeyCode::Down), next_query(history))
         .build()
         .into()
 }
@@ -49,6 +58,7 @@ pub fn log_query_widget(
 fn exec_query(
     tx: Sender<Message>,
     namespaces: Rc<RefCell<Namespace>>,
+    history: SharedLogQueryHistory,
 ) -> impl Fn(&mut Window) -> EventResult {
     move |w: &mut Window| {
         let widget = w.find_widget_mut(POD_LOG_QUERY_WIDGET_ID);
@@ -63,21 +73,63 @@ fn exec_query(
             return EventResult::Nop;
         }
 
-        w.widget_clear(POD_LOG_WIDGET_ID);
+        exec_log_query(w, &tx, &namespaces.borrow(), &history, item);
+
+        EventResult::Ignore
+    }
+}
+
+/// 選択しているNamespaceでログの取得を開始し、クエリを履歴に追加する
+pub fn exec_log_query(
+    w: &mut Window,
+    tx: &Sender<Message>,
+    namespaces: &Namespace,
+    history: &SharedLogQueryHistory,
+    query: String,
+) {
+    w.widget_clear(POD_LOG_WIDGET_ID);
+
+    history.borrow_mut().push(&query);
+
+    let prefix_type = if 1 < namespaces.len() {
+        LogPrefixType::All
+    } else {
+        LogPrefixType::PodAndContainer
+    };
 
-        let namespaces = namespaces.borrow();
+    let config = LogConfig::new(query, namespaces.to_owned(), prefix_type, false);
 
-        let prefix_type = if 1 < namespaces.len() {
-            LogPrefixType::All
-        } else {
-            LogPrefixType::PodAndContainer
+    tx.send(LogMessage::Request(config).into())
+        .expect("Failed to send LogMessage::Request");
+}
+
+/// 入力フォームに1つ前のクエリを表示する
+fn prev_query(history: SharedLogQueryHistory) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let widget = w.find_widget_mut(POD_LOG_QUERY_WIDGET_ID);
+
+        let current = match widget.widget_item() {
+            Some(SelectedItem::Literal { item, .. }) => item,
+            _ => String::new(),
         };
 
-        let config = LogConfig::new(item, namespaces.to_owned(), prefix_type, false);
+        if let Some(query) = history.borrow_mut().prev(&current) {
+            widget.update_widget_item(Item::Single(query.to_string().into()));
+        }
 
-        tx.send(LogMessage::Request(config).into())
-            .expect("Failed to send LogMessage::Request");
+        EventResult::Nop
+    }
+}
 
-        EventResult::Ignore
+/// 入力フォームに1つ後のクエリを表示する
+fn next_query(history: SharedLogQueryHistory) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let widget = w.find_widget_mut(POD_LOG_QUERY_WIDGET_ID);
+
+        if let Some(query) = history.borrow_mut().next() {
+            widget.update_widget_item(Item::Single(query.to_string().into()));
+        }
+
+        EventResult::Nop
     }
 }
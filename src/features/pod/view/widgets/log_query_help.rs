@@ -32,9 +32,17 @@ fn content() -> Vec<String> {
            !container:<regex>    (alias: !containers, !co, !c)
            log:<regex>           (alias: logs, lo, l)
            !log:<regex>          (alias: !logs, !lo, !l)
+           json:<key><op><value> (op: =, !=, >, >=, <, <=)
+           !json:<key><op><value>
            label:<selector>      (alias: labels)
            field:<selector>      (alias: fields)
+           fields:<key>[,<key>]...
            <resource>/<name>
+           since:<duration|time>
+           tail:<lines>
+           previous:<bool>
+           timestamps:<bool|utc|local>
+           ordered:<bool>
 
         Resources:
            pod            (alias: pods, po)
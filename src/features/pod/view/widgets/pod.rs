@@ -1,27 +1,37 @@
 use crossbeam::channel::Sender;
 
 use crate::{
-    config::theme::WidgetThemeConfig,
+    config::{keybindings::PodKeyBindings, theme::WidgetThemeConfig},
     features::{
-        component_id::{POD_LOG_QUERY_WIDGET_ID, POD_LOG_WIDGET_ID, POD_WIDGET_ID},
+        component_id::{
+            EXEC_CONTAINER_DIALOG_ID, POD_LOG_QUERY_WIDGET_ID, POD_LOG_WIDGET_ID, POD_WIDGET_ID,
+            PORT_FORWARD_TARGET_DIALOG_ID,
+        },
+        exec::message::ExecRequest,
         pod::{
             kube::{LogConfig, LogPrefixType},
             message::LogMessage,
+            view::filter_pods_by_node,
         },
+        port_forward::message::{PortForwardKind, PortForwardRequest},
     },
     kube::context::Namespace,
     message::Message,
     ui::{
         event::EventResult,
         widget::{
-            FilterForm, FilterFormTheme, Item, Table, TableItem, TableTheme, Widget, WidgetBase,
-            WidgetTheme, WidgetTrait as _,
+            FilterForm, FilterFormTheme, Item, SelectedItem, Table, TableItem, TableTheme, Widget,
+            WidgetBase, WidgetTheme, WidgetTrait as _,
         },
         Window, WindowAction,
     },
 };
 
-pub fn pod_widget(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Widget<'static> {
+pub fn pod_widget(
+    tx: &Sender<Message>,
+    keybindings: PodKeyBindings,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
     let tx = tx.clone();
 
     let widget_theme = WidgetTheme::from(theme.clone());
@@ -43,7 +53,13 @@ pub fn pod_widget(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Widget<'sta
         .theme(table_theme)
         .filtered_key("NAME")
         .block_injection(block_injection())
-        .on_select(on_select(tx))
+        .on_select(on_select(tx.clone()))
+        .key_action(&keybindings.exec, open_exec_dialog(tx.clone()))
+        .key_action(
+            &keybindings.port_forward,
+            open_port_forward_dialog(tx.clone()),
+        )
+        .key_action(&keybindings.clear_node_filter, clear_node_filter(tx))
         .build()
         .into()
 }
@@ -99,3 +115,85 @@ fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &TableItem) -> EventRe
         EventResult::WindowAction(WindowAction::Continue)
     }
 }
+
+fn open_exec_dialog(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let widget = w.find_widget(POD_WIDGET_ID);
+
+        let Some(SelectedItem::TableRow { metadata, .. }) = widget.widget_item() else {
+            return EventResult::Ignore;
+        };
+
+        let Some(ref metadata) = metadata else {
+            return EventResult::Ignore;
+        };
+
+        let Some(namespace) = metadata.get("namespace") else {
+            return EventResult::Ignore;
+        };
+
+        let Some(name) = metadata.get("name") else {
+            return EventResult::Ignore;
+        };
+
+        tx.send(
+            ExecRequest::Containers {
+                namespace: namespace.to_string(),
+                pod: name.to_string(),
+            }
+            .into(),
+        )
+        .expect("Failed to send ExecRequest::Containers");
+
+        w.widget_clear(EXEC_CONTAINER_DIALOG_ID);
+        w.open_dialog(EXEC_CONTAINER_DIALOG_ID);
+
+        EventResult::Nop
+    }
+}
+
+fn open_port_forward_dialog(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let widget = w.find_widget(POD_WIDGET_ID);
+
+        let Some(SelectedItem::TableRow { metadata, .. }) = widget.widget_item() else {
+            return EventResult::Ignore;
+        };
+
+        let Some(ref metadata) = metadata else {
+            return EventResult::Ignore;
+        };
+
+        let Some(namespace) = metadata.get("namespace") else {
+            return EventResult::Ignore;
+        };
+
+        let Some(name) = metadata.get("name") else {
+            return EventResult::Ignore;
+        };
+
+        tx.send(
+            PortForwardRequest::Targets {
+                kind: PortForwardKind::Pod,
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+            }
+            .into(),
+        )
+        .expect("Failed to send PortForwardRequest::Targets");
+
+        w.widget_clear(PORT_FORWARD_TARGET_DIALOG_ID);
+        w.open_dialog(PORT_FORWARD_TARGET_DIALOG_ID);
+
+        EventResult::Nop
+    }
+}
+
+/// NodeタブからNodeで絞り込んだ一覧を元に戻す
+fn clear_node_filter(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        filter_pods_by_node(&tx, w, None);
+
+        EventResult::Nop
+    }
+}
@@ -0,0 +1,7 @@
+mod forward;
+mod poller;
+mod target;
+
+pub use forward::*;
+pub use poller::*;
+pub use target::*;
@@ -0,0 +1,450 @@
+use std::{
+    collections::BTreeMap,
+    net::{Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use futures::future;
+use k8s_openapi::api::core::v1::Pod;
+use kube::{Api, Client};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _},
+    net::{TcpListener, TcpStream},
+    sync::RwLock,
+    task::{AbortHandle, JoinSet},
+};
+
+use crate::{
+    features::port_forward::message::PortForwardTarget,
+    kube::{
+        table::{KubeTable, KubeTableRow},
+        KubeClient,
+    },
+    logger,
+};
+
+pub type SharedPortForwards = Arc<RwLock<PortForwardManager>>;
+
+/// 接続の受け付けに失敗したときに、再び受け付けるまでの待ち時間の初期値
+const INITIAL_ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// 接続の受け付けに失敗したときに、再び受け付けるまでの待ち時間の上限
+const MAX_ACCEPT_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// ポートフォワードの転送量と状態
+#[derive(Debug, Default)]
+struct PortForwardStats {
+    /// ローカルからPodに送信したバイト数
+    sent: AtomicU64,
+    /// Podから受信したバイト数
+    received: AtomicU64,
+    /// 転送中の接続数
+    connections: AtomicUsize,
+    /// 直近の接続で発生したエラー
+    error: Mutex<Option<String>>,
+}
+
+impl PortForwardStats {
+    fn set_error(&self, error: Option<String>) {
+        *self.error.lock().expect("Failed to lock PortForwardStats") = error;
+    }
+
+    fn status(&self) -> String {
+        if let Some(error) = self
+            .error
+            .lock()
+            .expect("Failed to lock PortForwardStats")
+            .as_ref()
+        {
+            return format!("Error: {}", error);
+        }
+
+        match self.connections.load(Ordering::Relaxed) {
+            0 => "Listening".to_string(),
+            n => format!("Active({})", n),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PortForward {
+    id: usize,
+    target: PortForwardTarget,
+    /// 待ち受けに失敗した場合はErr
+    local_addr: Result<SocketAddr, String>,
+    stats: Arc<PortForwardStats>,
+    handle: Option<AbortHandle>,
+}
+
+impl Drop for PortForward {
+    fn drop(&mut self) {
+        if let Some(handle) = &self.handle {
+            handle.abort();
+        }
+    }
+}
+
+/// 実行中のポートフォワードを管理する
+///
+/// タブやnamespaceの切り替えとは独立して動作し、コンテキストの切り替え時にすべて停止する。
+pub struct PortForwardManager {
+    client: KubeClient,
+    forwards: Vec<PortForward>,
+    next_id: usize,
+}
+
+impl PortForwardManager {
+    pub fn shared(client: KubeClient) -> SharedPortForwards {
+        Arc::new(RwLock::new(Self {
+            client,
+            forwards: Vec::new(),
+            next_id: 0,
+        }))
+    }
+
+    /// ポートフォワードを開始する
+    ///
+    /// ローカルのポートはリソース上のポート番号と同じ番号を優先し、使用できない場合は空いているポートを割り当てて、そのことを一覧の状態に表示する。
+    /// 待ち受けに失敗した場合も一覧に残し、状態としてエラーを表示する。
+    pub async fn start(&mut self, target: PortForwardTarget) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let stats = Arc::new(PortForwardStats::default());
+
+        let (local_addr, handle) = match bind(target.port).await {
+            Ok(listener) => {
+                let local_addr = listener.local_addr().map_err(|e| e.to_string());
+
+                let handle = tokio::spawn(listen(
+                    listener,
+                    self.client.to_client(),
+                    target.clone(),
+                    stats.clone(),
+                ))
+                .abort_handle();
+
+                (local_addr, Some(handle))
+            }
+            Err(e) => (Err(e.to_string()), None),
+        };
+
+        logger!(
+            info,
+            "Start port-forward {} ({:?}) -> {}",
+            id,
+            local_addr,
+            target
+        );
+
+        self.forwards.push(PortForward {
+            id,
+            target,
+            local_addr,
+            stats,
+            handle,
+        });
+    }
+
+    pub fn stop(&mut self, id: usize) {
+        logger!(info, "Stop port-forward {}", id);
+
+        self.forwards.retain(|forward| forward.id != id);
+    }
+
+    pub fn stop_all(&mut self) {
+        logger!(info, "Stop all port-forwards");
+
+        self.forwards.clear();
+    }
+
+    pub fn table(&self) -> KubeTable {
+        let mut table = KubeTable {
+            header: ["LOCAL", "NAMESPACE", "TARGET", "SENT", "RECEIVED", "STATUS"]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            ..Default::default()
+        };
+
+        table.update_rows(
+            self.forwards
+                .iter()
+                .map(|forward| {
+                    let (local, status) = match &forward.local_addr {
+                        Ok(addr) => (
+                            addr.to_string(),
+                            listening_status(forward.target.port, addr, forward.stats.status()),
+                        ),
+                        Err(e) => ("-".to_string(), format!("Error: {}", e)),
+                    };
+
+                    KubeTableRow {
+                        namespace: forward.target.namespace.to_string(),
+                        name: forward.target.to_string(),
+                        metadata: Some(BTreeMap::from([(
+                            "id".to_string(),
+                            forward.id.to_string(),
+                        )])),
+                        row: vec![
+                            local,
+                            forward.target.namespace.to_string(),
+                            format!(
+                                "{} ({}:{})",
+                                forward.target, forward.target.pod, forward.target.pod_port
+                            ),
+                            format_bytes(forward.stats.sent.load(Ordering::Relaxed)),
+                            format_bytes(forward.stats.received.load(Ordering::Relaxed)),
+                            status,
+                        ],
+                    }
+                })
+                .collect(),
+        );
+
+        table
+    }
+}
+
+/// 希望したポートで待ち受けられず、別のポートに切り替えた場合はその旨を状態に付け加える
+fn listening_status(requested_port: u16, local_addr: &SocketAddr, status: String) -> String {
+    if local_addr.port() == requested_port {
+        status
+    } else {
+        format!("{} (port {} in use)", status, requested_port)
+    }
+}
+
+async fn bind(port: u16) -> Result<TcpListener> {
+    match TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await {
+        Ok(listener) => Ok(listener),
+        Err(e) => {
+            logger!(
+                warn,
+                "Failed to bind port {}: {}. Fallback to a random port",
+                port,
+                e
+            );
+
+            Ok(TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?)
+        }
+    }
+}
+
+/// ローカルのポートで接続を待ち受け、接続ごとにPodへのポートフォワードを張る
+/// このタスクが中断されると、転送中の接続もすべて中断される
+async fn listen(
+    listener: TcpListener,
+    client: Client,
+    target: PortForwardTarget,
+    stats: Arc<PortForwardStats>,
+) {
+    let mut connections = JoinSet::new();
+
+    let mut accept_failures = 0;
+
+    loop {
+        let conn = match listener.accept().await {
+            Ok((conn, _)) => {
+                accept_failures = 0;
+                conn
+            }
+            Err(e) => {
+                accept_failures += 1;
+
+                let delay = accept_retry_delay(accept_failures);
+
+                logger!(
+                    error,
+                    "Failed to accept connection for {}: {}. Retry in {:?}",
+                    target,
+                    e,
+                    delay
+                );
+
+                stats.set_error(Some(e.to_string()));
+
+                // ファイルディスクリプタの枯渇などはすぐには解消しないため、待ってから受け付け直す
+                tokio::time::sleep(delay).await;
+
+                continue;
+            }
+        };
+
+        let client = client.clone();
+        let target = target.clone();
+        let stats = stats.clone();
+
+        connections.spawn(async move {
+            stats.connections.fetch_add(1, Ordering::Relaxed);
+
+            let result = forward_connection(client, &target, conn, &stats).await;
+
+            stats.connections.fetch_sub(1, Ordering::Relaxed);
+
+            match result {
+                Ok(_) => stats.set_error(None),
+                Err(e) => {
+                    logger!(error, "Failed to port-forward {}: {}", target, e);
+                    stats.set_error(Some(e.to_string()));
+                }
+            }
+        });
+
+        // 終了した接続のタスクを回収する
+        while connections.try_join_next().is_some() {}
+    }
+}
+
+/// 接続の受け付けに連続してfailures回失敗したときの待ち時間
+///
+/// 失敗するたびに倍にし、上限で止める
+fn accept_retry_delay(failures: u32) -> Duration {
+    INITIAL_ACCEPT_RETRY_DELAY
+        .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+        .min(MAX_ACCEPT_RETRY_DELAY)
+}
+
+async fn forward_connection(
+    client: Client,
+    target: &PortForwardTarget,
+    mut conn: TcpStream,
+    stats: &PortForwardStats,
+) -> Result<()> {
+    let api: Api<Pod> = Api::namespaced(client, &target.namespace);
+
+    let mut forwarder = api.portforward(&target.pod, &[target.pod_port]).await?;
+
+    let upstream = forwarder
+        .take_stream(target.pod_port)
+        .ok_or_else(|| anyhow!("Failed to take stream for port {}", target.pod_port))?;
+
+    {
+        let (mut local_reader, mut local_writer) = conn.split();
+        let (mut upstream_reader, mut upstream_writer) = tokio::io::split(upstream);
+
+        future::try_join(
+            copy(&mut local_reader, &mut upstream_writer, &stats.sent),
+            copy(&mut upstream_reader, &mut local_writer, &stats.received),
+        )
+        .await?;
+    }
+
+    forwarder.join().await?;
+
+    Ok(())
+}
+
+/// readerからwriterへデータを転送し、転送したバイト数をcounterに加算する
+async fn copy<R, W>(reader: &mut R, writer: &mut W, counter: &AtomicU64) -> std::io::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut buf = [0u8; 8192];
+
+    loop {
+        let n = reader.read(&mut buf).await?;
+
+        if n == 0 {
+            break;
+        }
+
+        writer.write_all(&buf[..n]).await?;
+
+        counter.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    writer.shutdown().await
+}
+
+/// バイト数を人が読みやすい形式に変換する
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+
+        value /= 1024.0;
+        unit = next;
+    }
+
+    format!("{:.1}{}", value, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, "0B")]
+    #[case(1023, "1023B")]
+    #[case(1024, "1.0KiB")]
+    #[case(1536, "1.5KiB")]
+    #[case(10 * 1024 * 1024, "10.0MiB")]
+    #[case(3 * 1024 * 1024 * 1024, "3.0GiB")]
+    fn バイト数を単位付きの文字列に変換する(
+        #[case] bytes: u64,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_bytes(bytes), expected);
+    }
+
+    #[rstest]
+    #[case(8080, "Listening")]
+    #[case(80, "Listening (port 80 in use)")]
+    fn 希望したポートで待ち受けられなかったときは状態に表示する(
+        #[case] requested_port: u16,
+        #[case] expected: &str,
+    ) {
+        let local_addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 8080));
+
+        assert_eq!(
+            listening_status(requested_port, &local_addr, "Listening".to_string()),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(1, Duration::from_millis(100))]
+    #[case(2, Duration::from_millis(200))]
+    #[case(6, Duration::from_millis(3200))]
+    #[case(7, Duration::from_secs(5))]
+    #[case(100, Duration::from_secs(5))]
+    fn 接続の受け付けに失敗するたびに待ち時間を上限まで倍にする(
+        #[case] failures: u32,
+        #[case] expected: Duration,
+    ) {
+        assert_eq!(accept_retry_delay(failures), expected);
+    }
+
+    #[tokio::test]
+    async fn copyは転送したバイト数を加算する() {
+        let counter = AtomicU64::new(0);
+
+        let mut reader: &[u8] = b"hello world";
+        let mut writer = Vec::new();
+
+        copy(&mut reader, &mut writer, &counter).await.unwrap();
+
+        assert_eq!(writer, b"hello world");
+        assert_eq!(counter.load(Ordering::Relaxed), 11);
+    }
+}
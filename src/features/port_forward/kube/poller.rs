@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use crossbeam::channel::Sender;
+
+use crate::{
+    features::port_forward::message::PortForwardResponse,
+    kube::informer::ChangeDetector,
+    message::Message,
+    workers::kube::{Worker, WorkerResult},
+};
+
+use super::SharedPortForwards;
+
+/// 実行中のポートフォワードの一覧を定期的に送信する
+#[derive(Clone)]
+pub struct PortForwardPoller {
+    tx: Sender<Message>,
+    shared_port_forwards: SharedPortForwards,
+}
+
+impl PortForwardPoller {
+    pub fn new(tx: Sender<Message>, shared_port_forwards: SharedPortForwards) -> Self {
+        Self {
+            tx,
+            shared_port_forwards,
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for PortForwardPoller {
+    type Output = WorkerResult;
+
+    async fn run(&self) -> Self::Output {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
+
+        let Self {
+            tx,
+            shared_port_forwards,
+        } = self;
+
+        let mut detector = ChangeDetector::default();
+
+        loop {
+            interval.tick().await;
+
+            let table = Ok(shared_port_forwards.read().await.table());
+
+            if !detector.is_changed(&table) {
+                continue;
+            }
+
+            tx.send(PortForwardResponse::List(table).into())
+                .expect("Failed to send PortForwardResponse::List");
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use anyhow::{anyhow, Result};
+use k8s_openapi::{
+    api::core::v1::{Pod, Service},
+    apimachinery::pkg::util::intstr::IntOrString,
+};
+use kube::{api::ListParams, Api, ResourceExt as _};
+
+use crate::{
+    features::port_forward::message::{PortForwardKind, PortForwardTarget},
+    kube::KubeClient,
+};
+
+/// リソースが公開しているポートから、ポートフォワードの転送先の候補を取得する
+pub async fn fetch_port_forward_targets(
+    client: &KubeClient,
+    kind: PortForwardKind,
+    namespace: &str,
+    name: &str,
+) -> Result<Vec<PortForwardTarget>> {
+    match kind {
+        PortForwardKind::Pod => {
+            let api: Api<Pod> = Api::namespaced(client.to_client(), namespace);
+
+            let pod = api.get(name).await?;
+
+            Ok(pod_targets(&pod))
+        }
+        PortForwardKind::Service => {
+            let api: Api<Service> = Api::namespaced(client.to_client(), namespace);
+
+            let service = api.get(name).await?;
+
+            let pod = find_service_pod(client, &service).await?;
+
+            service_targets(&service, &pod)
+        }
+    }
+}
+
+/// Serviceのセレクタに一致するPodのうち、Runningのものを1つ選ぶ
+async fn find_service_pod(client: &KubeClient, service: &Service) -> Result<Pod> {
+    let selector = service
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.selector.as_ref())
+        .filter(|selector| !selector.is_empty())
+        .ok_or_else(|| anyhow!("Service {} has no selector", service.name_any()))?;
+
+    let label_selector = selector
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let api: Api<Pod> =
+        Api::namespaced(client.to_client(), &service.namespace().unwrap_or_default());
+
+    let pods = api
+        .list(&ListParams::default().labels(&label_selector))
+        .await?;
+
+    pods.items
+        .into_iter()
+        .find(|pod| {
+            pod.metadata.deletion_timestamp.is_none()
+                && pod
+                    .status
+                    .as_ref()
+                    .and_then(|status| status.phase.as_deref())
+                    == Some("Running")
+        })
+        .ok_or_else(|| anyhow!("No running pods found for service {}", service.name_any()))
+}
+
+/// Podが宣言しているコンテナポートを転送先にする
+///
+/// containerPortsは省略されることが多いため、宣言がない場合は空を返し、ポート番号の入力に任せる
+fn pod_targets(pod: &Pod) -> Vec<PortForwardTarget> {
+    let namespace = pod.namespace().unwrap_or_default();
+    let name = pod.name_any();
+
+    pod.spec
+        .iter()
+        .flat_map(|spec| &spec.containers)
+        .flat_map(|container| container.ports.iter().flatten())
+        .filter(|port| is_tcp(port.protocol.as_deref()))
+        .filter_map(|port| u16::try_from(port.container_port).ok())
+        .map(|port| PortForwardTarget::pod(&namespace, &name, port))
+        .collect()
+}
+
+/// ServiceのポートをPodのポートに解決する
+/// targetPortが名前で指定されている場合は、Podのコンテナポートの名前から解決する
+fn service_targets(service: &Service, pod: &Pod) -> Result<Vec<PortForwardTarget>> {
+    let namespace = service.namespace().unwrap_or_default();
+    let name = service.name_any();
+    let pod_name = pod.name_any();
+
+    let targets: Vec<PortForwardTarget> = service
+        .spec
+        .iter()
+        .flat_map(|spec| spec.ports.iter().flatten())
+        .filter(|port| is_tcp(port.protocol.as_deref()))
+        .filter_map(|port| {
+            let pod_port = match &port.target_port {
+                None => port.port,
+                Some(IntOrString::Int(target_port)) => *target_port,
+                Some(IntOrString::String(target_port)) => pod
+                    .spec
+                    .iter()
+                    .flat_map(|spec| &spec.containers)
+                    .flat_map(|container| container.ports.iter().flatten())
+                    .find(|p| p.name.as_ref() == Some(target_port))
+                    .map(|p| p.container_port)?,
+            };
+
+            Some(PortForwardTarget {
+                namespace: namespace.clone(),
+                kind: PortForwardKind::Service,
+                name: name.clone(),
+                port: u16::try_from(port.port).ok()?,
+                pod: pod_name.clone(),
+                pod_port: u16::try_from(pod_port).ok()?,
+            })
+        })
+        .collect();
+
+    if targets.is_empty() {
+        return Err(anyhow!("Service {} has no forwardable ports", name));
+    }
+
+    Ok(targets)
+}
+
+fn is_tcp(protocol: Option<&str>) -> bool {
+    protocol.is_none_or(|protocol| protocol == "TCP")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn pod() -> Pod {
+        serde_yaml::from_str(indoc! {r#"
+            apiVersion: v1
+            kind: Pod
+            metadata:
+              name: app-0
+              namespace: default
+            spec:
+              containers:
+                - name: app
+                  ports:
+                    - name: http
+                      containerPort: 8080
+                    - name: dns
+                      containerPort: 53
+                      protocol: UDP
+                - name: sidecar
+                  ports:
+                    - name: metrics
+                      containerPort: 9090
+                      protocol: TCP
+        "#})
+        .unwrap()
+    }
+
+    fn target(kind: PortForwardKind, name: &str, port: u16, pod_port: u16) -> PortForwardTarget {
+        PortForwardTarget {
+            namespace: "default".to_string(),
+            kind,
+            name: name.to_string(),
+            port,
+            pod: "app-0".to_string(),
+            pod_port,
+        }
+    }
+
+    #[test]
+    fn podのtcpのコンテナポートを転送先として返す() {
+        let actual = pod_targets(&pod());
+
+        assert_eq!(
+            actual,
+            vec![
+                target(PortForwardKind::Pod, "app-0", 8080, 8080),
+                target(PortForwardKind::Pod, "app-0", 9090, 9090),
+            ]
+        );
+    }
+
+    #[test]
+    fn podにコンテナポートがないとき空を返す() {
+        let pod: Pod = serde_yaml::from_str(indoc! {r#"
+            apiVersion: v1
+            kind: Pod
+            metadata:
+              name: app-0
+              namespace: default
+            spec:
+              containers:
+                - name: app
+        "#})
+        .unwrap();
+
+        assert_eq!(pod_targets(&pod), vec![]);
+    }
+
+    #[test]
+    fn serviceのtarget_portをpodのポートに解決する() {
+        let service: Service = serde_yaml::from_str(indoc! {r#"
+            apiVersion: v1
+            kind: Service
+            metadata:
+              name: app
+              namespace: default
+            spec:
+              selector:
+                app: app
+              ports:
+                - name: http
+                  port: 80
+                  targetPort: http
+                - name: metrics
+                  port: 9090
+                  targetPort: 9090
+                - name: admin
+                  port: 8081
+                - name: unknown
+                  port: 8082
+                  targetPort: unknown
+                - name: dns
+                  port: 53
+                  protocol: UDP
+        "#})
+        .unwrap();
+
+        let actual = service_targets(&service, &pod()).unwrap();
+
+        assert_eq!(
+            actual,
+            vec![
+                target(PortForwardKind::Service, "app", 80, 8080),
+                target(PortForwardKind::Service, "app", 9090, 9090),
+                target(PortForwardKind::Service, "app", 8081, 8081),
+            ]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{kube::table::KubeTable, message::Message, workers::kube::message::Kube};
+
+#[derive(Debug)]
+pub enum PortForwardMessage {
+    Request(PortForwardRequest),
+    Response(PortForwardResponse),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PortForwardKind {
+    Pod,
+    Service,
+}
+
+impl Display for PortForwardKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pod => write!(f, "pod"),
+            Self::Service => write!(f, "svc"),
+        }
+    }
+}
+
+/// ポートフォワードの転送先
+///
+/// Serviceを指定した場合も、開始時点でServiceのポートを解決したPodとポートを保持する
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortForwardTarget {
+    pub namespace: String,
+    pub kind: PortForwardKind,
+    pub name: String,
+    /// 指定したリソース上のポート番号（Serviceの場合はServiceのポート）
+    pub port: u16,
+    pub pod: String,
+    pub pod_port: u16,
+}
+
+impl PortForwardTarget {
+    /// Podのポートへの転送先
+    pub fn pod(namespace: impl Into<String>, name: impl Into<String>, port: u16) -> Self {
+        let name = name.into();
+
+        Self {
+            namespace: namespace.into(),
+            kind: PortForwardKind::Pod,
+            name: name.clone(),
+            port,
+            pod: name,
+            pod_port: port,
+        }
+    }
+}
+
+impl Display for PortForwardTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}:{}", self.kind, self.name, self.port)
+    }
+}
+
+#[derive(Debug)]
+pub enum PortForwardRequest {
+    /// リソースが公開しているポートの一覧を取得する
+    Targets {
+        kind: PortForwardKind,
+        namespace: String,
+        name: String,
+    },
+    Start(PortForwardTarget),
+    Stop(usize),
+}
+
+#[derive(Debug)]
+pub enum PortForwardResponse {
+    /// 転送先の候補
+    ///
+    /// Podがポートを宣言していない場合でもポート番号を入力して転送できるよう、要求したリソースを含める
+    Targets {
+        kind: PortForwardKind,
+        namespace: String,
+        name: String,
+        targets: Result<Vec<PortForwardTarget>>,
+    },
+    /// 実行中のポートフォワードの一覧
+    List(Result<KubeTable>),
+}
+
+impl From<PortForwardRequest> for Message {
+    fn from(req: PortForwardRequest) -> Self {
+        Message::Kube(Kube::PortForward(PortForwardMessage::Request(req)))
+    }
+}
+
+impl From<PortForwardResponse> for Message {
+    fn from(res: PortForwardResponse) -> Self {
+        Message::Kube(Kube::PortForward(PortForwardMessage::Response(res)))
+    }
+}
@@ -0,0 +1,195 @@
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    config::{keybindings::PortForwardKeyBindings, theme::ThemeConfig},
+    features::{
+        component_id::{PORT_FORWARD_DIALOG_ID, PORT_FORWARD_TARGET_DIALOG_ID},
+        port_forward::message::{PortForwardRequest, PortForwardTarget},
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            single_select::{
+                FilterForm, FilterFormTheme, SelectForm, SelectFormTheme, SingleSelectTheme,
+            },
+            FilterForm as TableFilterForm, FilterFormTheme as TableFilterFormTheme, LiteralItem,
+            SelectedItem, SingleSelect, Table, TableTheme, Widget, WidgetBase, WidgetTheme,
+            WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+/// ポートフォワードするポートを選択するダイアログ
+pub struct PortForwardTargetDialog {
+    pub widget: Widget<'static>,
+}
+
+impl PortForwardTargetDialog {
+    pub fn new(tx: &Sender<Message>, theme: ThemeConfig) -> Self {
+        Self {
+            widget: target_widget(tx.clone(), theme),
+        }
+    }
+}
+
+fn target_widget(tx: Sender<Message>, theme: ThemeConfig) -> Widget<'static> {
+    let widget_theme = WidgetTheme::from(theme.component.clone());
+    let filter_theme = FilterFormTheme::from(theme.component.clone());
+    let select_theme = SelectFormTheme::from(theme.component.clone());
+    let single_select_theme =
+        SingleSelectTheme::default().status_style(theme.component.list.status);
+
+    let filter_form = FilterForm::builder().theme(filter_theme).build();
+    let select_form = SelectForm::builder()
+        .theme(select_theme)
+        .on_select(on_select_target(tx.clone()))
+        .build();
+
+    let widget_base = WidgetBase::builder()
+        .title("Port Forward")
+        .theme(widget_theme)
+        .build();
+
+    SingleSelect::builder()
+        .id(PORT_FORWARD_TARGET_DIALOG_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .select_form(select_form)
+        .theme(single_select_theme)
+        .action(KeyCode::Enter, start_with_input_port(tx))
+        .build()
+        .into()
+}
+
+fn on_select_target(tx: Sender<Message>) -> impl Fn(&mut Window, &LiteralItem) -> EventResult {
+    move |w, v| {
+        let Some(metadata) = &v.metadata else {
+            return EventResult::Ignore;
+        };
+
+        let Some(key) = metadata.get("key") else {
+            return EventResult::Ignore;
+        };
+
+        let Ok(target) = serde_json::from_str::<PortForwardTarget>(key) else {
+            unreachable!()
+        };
+
+        tx.send(PortForwardRequest::Start(target).into())
+            .expect("Failed to send PortForwardRequest::Start");
+
+        w.open_dialog(PORT_FORWARD_DIALOG_ID);
+
+        EventResult::Nop
+    }
+}
+
+/// 入力したポート番号に一致する候補がないとき、そのポート番号でPodへのポートフォワードを開始する
+///
+/// 候補の項目には転送先のPodのnamespaceと名前を持たせているため、Serviceの場合は何もしない
+fn start_with_input_port(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let widget = w
+            .find_widget(PORT_FORWARD_TARGET_DIALOG_ID)
+            .as_single_select();
+
+        let Some(port) = widget
+            .filter()
+            .trim()
+            .parse::<u16>()
+            .ok()
+            .filter(|p| *p != 0)
+        else {
+            return EventResult::Ignore;
+        };
+
+        let Some(target) = widget.items().find_map(|item| {
+            let metadata = item.metadata.as_ref()?;
+
+            Some(PortForwardTarget::pod(
+                metadata.get("namespace")?,
+                metadata.get("name")?,
+                port,
+            ))
+        }) else {
+            return EventResult::Ignore;
+        };
+
+        tx.send(PortForwardRequest::Start(target).into())
+            .expect("Failed to send PortForwardRequest::Start");
+
+        w.open_dialog(PORT_FORWARD_DIALOG_ID);
+
+        EventResult::Nop
+    }
+}
+
+/// 実行中のポートフォワードの一覧を表示するダイアログ
+pub struct PortForwardDialog {
+    pub widget: Widget<'static>,
+}
+
+impl PortForwardDialog {
+    pub fn new(
+        tx: &Sender<Message>,
+        keybindings: PortForwardKeyBindings,
+        theme: ThemeConfig,
+    ) -> Self {
+        Self {
+            widget: list_widget(tx.clone(), keybindings, theme),
+        }
+    }
+}
+
+fn list_widget(
+    tx: Sender<Message>,
+    keybindings: PortForwardKeyBindings,
+    theme: ThemeConfig,
+) -> Widget<'static> {
+    let widget_theme = WidgetTheme::from(theme.component.clone());
+    let filter_theme = TableFilterFormTheme::from(theme.component.clone());
+    let table_theme = TableTheme::from(theme.component.clone());
+
+    let widget_base = WidgetBase::builder()
+        .title("Port Forwards")
+        .theme(widget_theme)
+        .build();
+
+    let filter_form = TableFilterForm::builder().theme(filter_theme).build();
+
+    Table::builder()
+        .id(PORT_FORWARD_DIALOG_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .theme(table_theme)
+        .filtered_key("TARGET")
+        .key_action(&keybindings.stop, stop(tx))
+        .build()
+        .into()
+}
+
+fn stop(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let widget = w.find_widget(PORT_FORWARD_DIALOG_ID);
+
+        let Some(SelectedItem::TableRow { metadata, .. }) = widget.widget_item() else {
+            return EventResult::Ignore;
+        };
+
+        let Some(id) = metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("id"))
+            .and_then(|id| id.parse().ok())
+        else {
+            return EventResult::Ignore;
+        };
+
+        tx.send(PortForwardRequest::Stop(id).into())
+            .expect("Failed to send PortForwardRequest::Stop");
+
+        EventResult::Nop
+    }
+}
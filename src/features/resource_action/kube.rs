@@ -0,0 +1,3 @@
+mod action;
+
+pub use action::*;
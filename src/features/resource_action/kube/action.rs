@@ -0,0 +1,319 @@
+use anyhow::Result;
+use chrono::{SecondsFormat, Utc};
+use k8s_openapi::{
+    api::batch::v1::{CronJob, Job},
+    apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference},
+    Resource as _,
+};
+use kube::{Resource, ResourceExt as _};
+use serde_json::json;
+
+use crate::{
+    features::resource_action::message::{ResourceAction, ResourceRef},
+    kube::KubeClientRequest,
+    logger,
+};
+
+/// kubectl rollout restartと同じく、Podテンプレートに付与するアノテーション
+const RESTARTED_AT_ANNOTATION: &str = "kubectl.kubernetes.io/restartedAt";
+
+/// kubectl create job --fromと同じく、CronJobから手動で作成したJobに付与するアノテーション
+const INSTANTIATE_ANNOTATION: &str = "cronjob.kubernetes.io/instantiate";
+
+/// リソースに対して操作を実行する
+pub async fn execute_resource_action<C: KubeClientRequest>(
+    client: &C,
+    target: &ResourceRef,
+    action: &ResourceAction,
+) -> Result<()> {
+    logger!(info, "Execute {:?} to {}", action, target);
+
+    let path = target.path();
+
+    match action {
+        ResourceAction::Delete => client.delete(&path).await,
+        ResourceAction::Restart => {
+            let restarted_at = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+            let patch = json!({
+                "spec": {
+                    "template": {
+                        "metadata": {
+                            "annotations": {
+                                RESTARTED_AT_ANNOTATION: restarted_at
+                            }
+                        }
+                    }
+                }
+            });
+
+            client.merge_patch(&path, &patch).await
+        }
+        ResourceAction::Scale(replicas) => {
+            let patch = json!({
+                "spec": {
+                    "replicas": replicas
+                }
+            });
+
+            client.merge_patch(&format!("{}/scale", path), &patch).await
+        }
+        ResourceAction::Cordon => {
+            let patch = json!({
+                "spec": {
+                    "unschedulable": true
+                }
+            });
+
+            client.merge_patch(&path, &patch).await
+        }
+        ResourceAction::Uncordon => {
+            let patch = json!({
+                "spec": {
+                    "unschedulable": null
+                }
+            });
+
+            client.merge_patch(&path, &patch).await
+        }
+        ResourceAction::Trigger => {
+            let cronjob: CronJob = client.request(&path).await?;
+
+            let job = job_from_cronjob(&cronjob);
+
+            client
+                .create(
+                    &Job::url_path(&(), target.namespace.as_deref()),
+                    &serde_json::to_value(job)?,
+                )
+                .await
+        }
+        ResourceAction::Suspend | ResourceAction::Resume => {
+            let patch = json!({
+                "spec": {
+                    "suspend": *action == ResourceAction::Suspend
+                }
+            });
+
+            client.merge_patch(&path, &patch).await
+        }
+    }
+}
+
+/// CronJobのjobTemplateから、CronJobが所有するJobを生成する
+///
+/// 名前の重複を避けるため、generateNameで名前を付ける
+fn job_from_cronjob(cronjob: &CronJob) -> Job {
+    let template = cronjob
+        .spec
+        .as_ref()
+        .map(|spec| spec.job_template.clone())
+        .unwrap_or_default();
+
+    let metadata = template.metadata.unwrap_or_default();
+
+    let mut annotations = metadata.annotations.unwrap_or_default();
+
+    annotations.insert(INSTANTIATE_ANNOTATION.to_string(), "manual".to_string());
+
+    Job {
+        metadata: ObjectMeta {
+            generate_name: Some(format!("{}-manual-", cronjob.name_any())),
+            namespace: cronjob.namespace(),
+            labels: metadata.labels,
+            annotations: Some(annotations),
+            owner_references: Some(vec![OwnerReference {
+                api_version: CronJob::API_VERSION.to_string(),
+                kind: CronJob::KIND.to_string(),
+                name: cronjob.name_any(),
+                uid: cronjob.uid().unwrap_or_default(),
+                controller: Some(true),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        },
+        spec: template.spec,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::anyhow;
+    use indoc::indoc;
+    use mockall::predicate::{always, eq, function};
+
+    use crate::{kube::mock::MockTestKubeClient, mock_expect};
+
+    fn deployment() -> ResourceRef {
+        ResourceRef {
+            kind: "Deployment".to_string(),
+            api_version: "apps/v1".to_string(),
+            plural: "deployments".to_string(),
+            namespace: Some("default".to_string()),
+            name: "app".to_string(),
+        }
+    }
+
+    fn cronjob() -> CronJob {
+        serde_yaml::from_str(indoc! {"
+            metadata:
+              name: backup
+              namespace: default
+              uid: abc-123
+            spec:
+              schedule: '0 0 * * *'
+              jobTemplate:
+                metadata:
+                  labels:
+                    app: backup
+                spec:
+                  template:
+                    spec:
+                      restartPolicy: Never
+                      containers:
+                        - name: backup
+                          image: backup:1.0
+        "})
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn deleteのときリソースのパスを削除する() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_delete()
+            .with(eq("/apis/apps/v1/namespaces/default/deployments/app"))
+            .returning(|_| Ok(()));
+
+        let actual = execute_resource_action(&client, &deployment(), &ResourceAction::Delete).await;
+
+        assert!(actual.is_ok());
+    }
+
+    #[tokio::test]
+    async fn scaleのときscaleサブリソースにレプリカ数を適用する() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_merge_patch()
+            .with(
+                eq("/apis/apps/v1/namespaces/default/deployments/app/scale"),
+                eq(json!({"spec": {"replicas": 3}})),
+            )
+            .returning(|_, _| Ok(()));
+
+        let actual =
+            execute_resource_action(&client, &deployment(), &ResourceAction::Scale(3)).await;
+
+        assert!(actual.is_ok());
+    }
+
+    #[tokio::test]
+    async fn restartのときpodテンプレートにアノテーションを付与する() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_merge_patch()
+            .with(
+                eq("/apis/apps/v1/namespaces/default/deployments/app"),
+                function(|patch: &serde_json::Value| {
+                    patch["spec"]["template"]["metadata"]["annotations"][RESTARTED_AT_ANNOTATION]
+                        .is_string()
+                }),
+            )
+            .returning(|_, _| Ok(()));
+
+        let actual =
+            execute_resource_action(&client, &deployment(), &ResourceAction::Restart).await;
+
+        assert!(actual.is_ok());
+    }
+
+    #[tokio::test]
+    async fn triggerのときjob_templateからjobを作成する() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            [(
+                CronJob,
+                eq("/apis/batch/v1/namespaces/default/cronjobs/backup"),
+                Ok(cronjob())
+            )]
+        );
+
+        client
+            .expect_create()
+            .with(
+                eq("/apis/batch/v1/namespaces/default/jobs"),
+                eq(json!({
+                    "apiVersion": "batch/v1",
+                    "kind": "Job",
+                    "metadata": {
+                        "generateName": "backup-manual-",
+                        "namespace": "default",
+                        "labels": {
+                            "app": "backup"
+                        },
+                        "annotations": {
+                            "cronjob.kubernetes.io/instantiate": "manual"
+                        },
+                        "ownerReferences": [
+                            {
+                                "apiVersion": "batch/v1",
+                                "kind": "CronJob",
+                                "name": "backup",
+                                "uid": "abc-123",
+                                "controller": true
+                            }
+                        ]
+                    },
+                    "spec": {
+                        "template": {
+                            "spec": {
+                                "restartPolicy": "Never",
+                                "containers": [
+                                    {
+                                        "name": "backup",
+                                        "image": "backup:1.0"
+                                    }
+                                ]
+                            }
+                        }
+                    }
+                })),
+            )
+            .returning(|_, _| Ok(()));
+
+        let target = ResourceRef {
+            kind: "CronJob".to_string(),
+            api_version: "batch/v1".to_string(),
+            plural: "cronjobs".to_string(),
+            namespace: Some("default".to_string()),
+            name: "backup".to_string(),
+        };
+
+        let actual = execute_resource_action(&client, &target, &ResourceAction::Trigger).await;
+
+        assert!(actual.is_ok());
+    }
+
+    #[tokio::test]
+    async fn 操作に失敗したときエラーを返す() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_merge_patch()
+            .with(always(), always())
+            .returning(|_, _| Err(anyhow!("forbidden")));
+
+        let actual = execute_resource_action(&client, &deployment(), &ResourceAction::Cordon).await;
+
+        assert!(actual.is_err());
+    }
+}
@@ -0,0 +1,374 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use anyhow::Result;
+use k8s_openapi::{
+    api::{
+        apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet},
+        batch::v1::{CronJob, Job},
+        core::v1::{ConfigMap, Node, Pod, Secret, Service},
+        networking::v1::{Ingress, NetworkPolicy},
+    },
+    Resource,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    kube::apis::networking::gateway::{
+        v1::{Gateway, HTTPRoute},
+        v1alpha2::{GRPCRoute, ReferenceGrant, TCPRoute, TLSRoute, UDPRoute},
+    },
+    message::Message,
+    workers::kube::message::Kube,
+};
+
+#[derive(Debug)]
+pub enum ResourceActionMessage {
+    Request(ResourceActionRequest),
+    Response(ResourceActionResponse),
+}
+
+/// 操作対象のリソース
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceRef {
+    pub kind: String,
+    /// e.g. v1, apps/v1, gateway.networking.k8s.io/v1
+    pub api_version: String,
+    /// e.g. pods, deployments
+    pub plural: String,
+    /// クラスタスコープのリソースの場合はNone
+    pub namespace: Option<String>,
+    pub name: String,
+}
+
+impl ResourceRef {
+    fn new<K: Resource>(namespace: Option<String>, name: String) -> Self {
+        Self {
+            kind: K::KIND.to_string(),
+            api_version: K::API_VERSION.to_string(),
+            plural: K::URL_PATH_SEGMENT.to_string(),
+            namespace,
+            name,
+        }
+    }
+
+    /// テーブルの行に付与されているメタデータから操作対象のリソースを生成する
+    /// 操作に対応していない種類のリソースの場合はNoneを返す
+    pub fn from_metadata(metadata: &BTreeMap<String, String>) -> Option<Self> {
+        let name = metadata.get("name")?.to_string();
+
+        let namespace = metadata
+            .get("namespace")
+            .filter(|ns| !ns.is_empty())
+            .cloned();
+
+        let kind = metadata.get("kind")?;
+
+        // APIタブの行はAPIリソースのグループ、バージョン、複数形の名前を持つため、そこからパスを生成する
+        if let (Some(version), Some(plural)) = (metadata.get("version"), metadata.get("plural")) {
+            let api_version = match metadata.get("group").filter(|group| !group.is_empty()) {
+                Some(group) => format!("{}/{}", group, version),
+                None => version.to_string(),
+            };
+
+            return Some(Self {
+                kind: kind.to_string(),
+                api_version,
+                plural: plural.to_string(),
+                namespace,
+                name,
+            });
+        }
+
+        let resource = match kind.as_str() {
+            Pod::KIND => Self::new::<Pod>(namespace, name),
+            ConfigMap::KIND => Self::new::<ConfigMap>(namespace, name),
+            Secret::KIND => Self::new::<Secret>(namespace, name),
+            Service::KIND => Self::new::<Service>(namespace, name),
+            Ingress::KIND => Self::new::<Ingress>(namespace, name),
+            NetworkPolicy::KIND => Self::new::<NetworkPolicy>(namespace, name),
+            Deployment::KIND => Self::new::<Deployment>(namespace, name),
+            StatefulSet::KIND => Self::new::<StatefulSet>(namespace, name),
+            DaemonSet::KIND => Self::new::<DaemonSet>(namespace, name),
+            ReplicaSet::KIND => Self::new::<ReplicaSet>(namespace, name),
+            Job::KIND => Self::new::<Job>(namespace, name),
+            CronJob::KIND => Self::new::<CronJob>(namespace, name),
+            Node::KIND => Self::new::<Node>(None, name),
+            kind @ (Gateway::KIND
+            | HTTPRoute::KIND
+            | GRPCRoute::KIND
+            | TCPRoute::KIND
+            | TLSRoute::KIND
+            | UDPRoute::KIND
+            | ReferenceGrant::KIND) => {
+                let version = metadata.get("version")?;
+
+                let plural = match kind {
+                    Gateway::KIND => Gateway::URL_PATH_SEGMENT,
+                    HTTPRoute::KIND => HTTPRoute::URL_PATH_SEGMENT,
+                    GRPCRoute::KIND => GRPCRoute::URL_PATH_SEGMENT,
+                    TCPRoute::KIND => TCPRoute::URL_PATH_SEGMENT,
+                    TLSRoute::KIND => TLSRoute::URL_PATH_SEGMENT,
+                    UDPRoute::KIND => UDPRoute::URL_PATH_SEGMENT,
+                    _ => ReferenceGrant::URL_PATH_SEGMENT,
+                };
+
+                Self {
+                    kind: kind.to_string(),
+                    api_version: format!("{}/{}", Gateway::GROUP, version),
+                    plural: plural.to_string(),
+                    namespace,
+                    name,
+                }
+            }
+            _ => return None,
+        };
+
+        Some(resource)
+    }
+
+    /// リソースのAPIパス
+    pub fn path(&self) -> String {
+        let prefix = if self.api_version.contains('/') {
+            format!("/apis/{}", self.api_version)
+        } else {
+            format!("/api/{}", self.api_version)
+        };
+
+        match &self.namespace {
+            Some(ns) => format!("{}/namespaces/{}/{}/{}", prefix, ns, self.plural, self.name),
+            None => format!("{}/{}/{}", prefix, self.plural, self.name),
+        }
+    }
+
+    /// リソースに対して実行できる操作の一覧
+    pub fn available_actions(&self) -> Vec<ResourceActionKind> {
+        let mut actions = vec![ResourceActionKind::Delete];
+
+        match self.kind.as_str() {
+            Deployment::KIND | StatefulSet::KIND => {
+                actions.extend([ResourceActionKind::Restart, ResourceActionKind::Scale]);
+            }
+            DaemonSet::KIND => {
+                actions.push(ResourceActionKind::Restart);
+            }
+            ReplicaSet::KIND => {
+                actions.push(ResourceActionKind::Scale);
+            }
+            CronJob::KIND => {
+                actions.extend([
+                    ResourceActionKind::Trigger,
+                    ResourceActionKind::Suspend,
+                    ResourceActionKind::Resume,
+                ]);
+            }
+            Node::KIND => {
+                actions.extend([ResourceActionKind::Cordon, ResourceActionKind::Uncordon]);
+            }
+            _ => {}
+        }
+
+        actions
+    }
+}
+
+impl Display for ResourceRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.kind.to_lowercase(), self.name)
+    }
+}
+
+/// メニューに表示する操作の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResourceActionKind {
+    Delete,
+    Restart,
+    Scale,
+    Cordon,
+    Uncordon,
+    Trigger,
+    Suspend,
+    Resume,
+}
+
+impl Display for ResourceActionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Delete => write!(f, "Delete"),
+            Self::Restart => write!(f, "Restart (rollout restart)"),
+            Self::Scale => write!(f, "Scale"),
+            Self::Cordon => write!(f, "Cordon"),
+            Self::Uncordon => write!(f, "Uncordon"),
+            Self::Trigger => write!(f, "Trigger now (create job)"),
+            Self::Suspend => write!(f, "Suspend"),
+            Self::Resume => write!(f, "Resume"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceAction {
+    Delete,
+    Restart,
+    Scale(u32),
+    Cordon,
+    Uncordon,
+    /// CronJobのjobTemplateからJobを作成する
+    Trigger,
+    Suspend,
+    Resume,
+}
+
+#[derive(Debug)]
+pub struct ResourceActionRequest {
+    pub target: ResourceRef,
+    pub action: ResourceAction,
+}
+
+#[derive(Debug)]
+pub struct ResourceActionResponse {
+    pub target: ResourceRef,
+    pub result: Result<()>,
+}
+
+impl From<ResourceActionRequest> for Message {
+    fn from(req: ResourceActionRequest) -> Self {
+        Message::Kube(Kube::ResourceAction(ResourceActionMessage::Request(req)))
+    }
+}
+
+impl From<ResourceActionResponse> for Message {
+    fn from(res: ResourceActionResponse) -> Self {
+        Message::Kube(Kube::ResourceAction(ResourceActionMessage::Response(res)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn metadata(kind: &str, namespace: &str, version: Option<&str>) -> BTreeMap<String, String> {
+        let mut metadata = BTreeMap::from([
+            ("kind".to_string(), kind.to_string()),
+            ("namespace".to_string(), namespace.to_string()),
+            ("name".to_string(), "foo".to_string()),
+        ]);
+
+        if let Some(version) = version {
+            metadata.insert("version".to_string(), version.to_string());
+        }
+
+        metadata
+    }
+
+    #[rstest]
+    #[case("Pod", "default", None, "/api/v1/namespaces/default/pods/foo")]
+    #[case(
+        "Deployment",
+        "default",
+        None,
+        "/apis/apps/v1/namespaces/default/deployments/foo"
+    )]
+    #[case("Node", "", None, "/api/v1/nodes/foo")]
+    #[case(
+        "HTTPRoute",
+        "default",
+        Some("v1beta1"),
+        "/apis/gateway.networking.k8s.io/v1beta1/namespaces/default/httproutes/foo"
+    )]
+    #[case(
+        "TCPRoute",
+        "default",
+        Some("v1alpha2"),
+        "/apis/gateway.networking.k8s.io/v1alpha2/namespaces/default/tcproutes/foo"
+    )]
+    #[case(
+        "ReferenceGrant",
+        "default",
+        Some("v1beta1"),
+        "/apis/gateway.networking.k8s.io/v1beta1/namespaces/default/referencegrants/foo"
+    )]
+    fn メタデータからリソースのパスを生成する(
+        #[case] kind: &str,
+        #[case] namespace: &str,
+        #[case] version: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let actual = ResourceRef::from_metadata(&metadata(kind, namespace, version)).unwrap();
+
+        assert_eq!(actual.path(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "Deployment",
+        "apps",
+        "default",
+        "/apis/apps/v1/namespaces/default/deployments/foo"
+    )]
+    #[case(
+        "ServiceAccount",
+        "",
+        "default",
+        "/api/v1/namespaces/default/serviceaccounts/foo"
+    )]
+    #[case(
+        "ClusterRole",
+        "rbac.authorization.k8s.io",
+        "",
+        "/apis/rbac.authorization.k8s.io/v1/clusterroles/foo"
+    )]
+    fn apiタブの行のメタデータからリソースのパスを生成する(
+        #[case] kind: &str,
+        #[case] group: &str,
+        #[case] namespace: &str,
+        #[case] expected: &str,
+    ) {
+        let mut metadata = metadata(kind, namespace, Some("v1"));
+
+        metadata.insert("group".to_string(), group.to_string());
+        metadata.insert("plural".to_string(), format!("{}s", kind.to_lowercase()));
+
+        let actual = ResourceRef::from_metadata(&metadata).unwrap();
+
+        assert_eq!(actual.path(), expected);
+    }
+
+    #[test]
+    fn 対応していないリソースのときnoneを返す() {
+        let actual = ResourceRef::from_metadata(&metadata("Event", "default", None));
+
+        assert_eq!(actual, None);
+    }
+
+    #[rstest]
+    #[case("Pod", vec![ResourceActionKind::Delete])]
+    #[case(
+        "Deployment",
+        vec![ResourceActionKind::Delete, ResourceActionKind::Restart, ResourceActionKind::Scale]
+    )]
+    #[case("DaemonSet", vec![ResourceActionKind::Delete, ResourceActionKind::Restart])]
+    #[case(
+        "CronJob",
+        vec![
+            ResourceActionKind::Delete,
+            ResourceActionKind::Trigger,
+            ResourceActionKind::Suspend,
+            ResourceActionKind::Resume
+        ]
+    )]
+    #[case(
+        "Node",
+        vec![ResourceActionKind::Delete, ResourceActionKind::Cordon, ResourceActionKind::Uncordon]
+    )]
+    fn リソースの種類に応じた操作を返す(
+        #[case] kind: &str,
+        #[case] expected: Vec<ResourceActionKind>,
+    ) {
+        let target = ResourceRef::from_metadata(&metadata(kind, "default", None)).unwrap();
+
+        assert_eq!(target.available_actions(), expected);
+    }
+}
@@ -0,0 +1,304 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    config::theme::ThemeConfig,
+    features::{
+        component_id::{
+            RESOURCE_ACTION_CONFIRM_DIALOG_ID, RESOURCE_ACTION_DIALOG_ID,
+            RESOURCE_ACTION_ERROR_DIALOG_ID, RESOURCE_ACTION_SCALE_DIALOG_ID,
+        },
+        resource_action::message::{
+            ResourceAction, ResourceActionKind, ResourceActionRequest, ResourceRef,
+        },
+    },
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{
+            single_select::{
+                FilterForm, FilterFormTheme, SelectForm, SelectFormTheme, SingleSelectTheme,
+            },
+            InputForm, InputFormTheme, Item, LiteralItem, SelectedItem, SingleSelect, Text,
+            TextTheme, Widget, WidgetBase, WidgetTheme, WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+const CONFIRM_DELETE: &str = "Delete";
+const CONFIRM_CANCEL: &str = "Cancel";
+
+/// メニューで選択したリソースを、確認ダイアログやレプリカ数の入力フォームと共有する
+type SharedTarget = Rc<RefCell<Option<ResourceRef>>>;
+
+/// テーブルで選択したリソースに対する操作のダイアログ群
+pub struct ResourceActionDialogs {
+    pub menu_dialog: Widget<'static>,
+    pub confirm_dialog: Widget<'static>,
+    pub scale_dialog: Widget<'static>,
+    pub error_dialog: Widget<'static>,
+    target: SharedTarget,
+}
+
+impl ResourceActionDialogs {
+    pub fn new(tx: &Sender<Message>, theme: ThemeConfig) -> Self {
+        let target = SharedTarget::default();
+
+        Self {
+            menu_dialog: menu_widget(tx.clone(), target.clone(), theme.clone()),
+            confirm_dialog: confirm_widget(tx.clone(), target.clone(), theme.clone()),
+            scale_dialog: scale_widget(tx.clone(), target.clone(), theme.clone()),
+            error_dialog: error_widget(theme),
+            target,
+        }
+    }
+
+    /// アクティブなテーブルで選択している行のリソースに対して、操作メニューを開く
+    pub fn open_menu(&self) -> impl Fn(&mut Window) -> EventResult {
+        let target = self.target.clone();
+
+        move |w: &mut Window| {
+            let widget = w.active_tab().active_widget();
+
+            let Some(SelectedItem::TableRow {
+                metadata: Some(metadata),
+                ..
+            }) = widget.widget_item()
+            else {
+                return EventResult::Ignore;
+            };
+
+            let Some(resource) = ResourceRef::from_metadata(&metadata) else {
+                return EventResult::Ignore;
+            };
+
+            let items = resource
+                .available_actions()
+                .into_iter()
+                .map(|action| {
+                    let json = serde_json::to_string(&action)
+                        .expect("ResourceActionKind is always serializable");
+
+                    LiteralItem::new(
+                        action.to_string(),
+                        Some(BTreeMap::from([("action".into(), json)])),
+                    )
+                })
+                .collect();
+
+            let menu = w.find_widget_mut(RESOURCE_ACTION_DIALOG_ID);
+
+            *menu.widget_base_mut().append_title_mut() = Some(format!(" : {}", resource).into());
+
+            menu.update_widget_item(Item::Array(items));
+
+            *target.borrow_mut() = Some(resource);
+
+            w.open_dialog(RESOURCE_ACTION_DIALOG_ID);
+
+            EventResult::Nop
+        }
+    }
+}
+
+fn single_select_widget(
+    id: &str,
+    title: &str,
+    theme: ThemeConfig,
+    on_select: impl Fn(&mut Window, &LiteralItem) -> EventResult + 'static,
+) -> Widget<'static> {
+    let widget_theme = WidgetTheme::from(theme.component.clone());
+    let filter_theme = FilterFormTheme::from(theme.component.clone());
+    let select_theme = SelectFormTheme::from(theme.component.clone());
+    let single_select_theme =
+        SingleSelectTheme::default().status_style(theme.component.list.status);
+
+    let filter_form = FilterForm::builder().theme(filter_theme).build();
+    let select_form = SelectForm::builder()
+        .theme(select_theme)
+        .on_select(on_select)
+        .build();
+
+    let widget_base = WidgetBase::builder()
+        .title(title)
+        .theme(widget_theme)
+        .build();
+
+    SingleSelect::builder()
+        .id(id)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .select_form(select_form)
+        .theme(single_select_theme)
+        .build()
+        .into()
+}
+
+fn menu_widget(tx: Sender<Message>, target: SharedTarget, theme: ThemeConfig) -> Widget<'static> {
+    single_select_widget(
+        RESOURCE_ACTION_DIALOG_ID,
+        "Action",
+        theme,
+        move |w: &mut Window, v: &LiteralItem| {
+            let Some(resource) = target.borrow().clone() else {
+                return EventResult::Ignore;
+            };
+
+            let Some(action) = v
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get("action"))
+                .and_then(|action| serde_json::from_str::<ResourceActionKind>(action).ok())
+            else {
+                return EventResult::Ignore;
+            };
+
+            let title = Some(format!(" : {}", resource).into());
+
+            match action {
+                ResourceActionKind::Delete => {
+                    let confirm = w.find_widget_mut(RESOURCE_ACTION_CONFIRM_DIALOG_ID);
+
+                    *confirm.widget_base_mut().append_title_mut() = title;
+
+                    confirm.update_widget_item(Item::Array(vec![
+                        CONFIRM_CANCEL.into(),
+                        CONFIRM_DELETE.into(),
+                    ]));
+
+                    w.open_dialog(RESOURCE_ACTION_CONFIRM_DIALOG_ID);
+                }
+                ResourceActionKind::Scale => {
+                    w.widget_clear(RESOURCE_ACTION_SCALE_DIALOG_ID);
+
+                    *w.find_widget_mut(RESOURCE_ACTION_SCALE_DIALOG_ID)
+                        .widget_base_mut()
+                        .append_title_mut() = title;
+
+                    w.open_dialog(RESOURCE_ACTION_SCALE_DIALOG_ID);
+                }
+                ResourceActionKind::Restart
+                | ResourceActionKind::Cordon
+                | ResourceActionKind::Uncordon
+                | ResourceActionKind::Trigger
+                | ResourceActionKind::Suspend
+                | ResourceActionKind::Resume => {
+                    let action = match action {
+                        ResourceActionKind::Restart => ResourceAction::Restart,
+                        ResourceActionKind::Cordon => ResourceAction::Cordon,
+                        ResourceActionKind::Trigger => ResourceAction::Trigger,
+                        ResourceActionKind::Suspend => ResourceAction::Suspend,
+                        ResourceActionKind::Resume => ResourceAction::Resume,
+                        _ => ResourceAction::Uncordon,
+                    };
+
+                    send_request(&tx, resource, action);
+
+                    w.close_dialog();
+                }
+            }
+
+            EventResult::Nop
+        },
+    )
+}
+
+fn confirm_widget(
+    tx: Sender<Message>,
+    target: SharedTarget,
+    theme: ThemeConfig,
+) -> Widget<'static> {
+    single_select_widget(
+        RESOURCE_ACTION_CONFIRM_DIALOG_ID,
+        "Delete?",
+        theme,
+        move |w: &mut Window, v: &LiteralItem| {
+            if v.item == CONFIRM_DELETE {
+                if let Some(resource) = target.borrow().clone() {
+                    send_request(&tx, resource, ResourceAction::Delete);
+                }
+            }
+
+            w.close_dialog();
+
+            EventResult::Nop
+        },
+    )
+}
+
+fn scale_widget(tx: Sender<Message>, target: SharedTarget, theme: ThemeConfig) -> Widget<'static> {
+    let input_theme = InputFormTheme::from(theme.component.clone());
+
+    let widget_base = WidgetBase::builder()
+        .title("Scale")
+        .theme(WidgetTheme::from(theme.component.clone()))
+        .build();
+
+    InputForm::builder()
+        .id(RESOURCE_ACTION_SCALE_DIALOG_ID)
+        .widget_base(widget_base)
+        .theme(input_theme)
+        .prefix("replicas: ")
+        .actions(UserEvent::from(KeyCode::Enter), move |w: &mut Window| {
+            let widget = w.find_widget(RESOURCE_ACTION_SCALE_DIALOG_ID);
+
+            let Some(SelectedItem::Literal { item, .. }) = widget.widget_item() else {
+                return EventResult::Ignore;
+            };
+
+            let Some(resource) = target.borrow().clone() else {
+                return EventResult::Ignore;
+            };
+
+            match item.trim().parse::<u32>() {
+                Ok(replicas) => {
+                    send_request(&tx, resource, ResourceAction::Scale(replicas));
+
+                    w.close_dialog();
+                }
+                Err(_) => {
+                    show_error(w, &resource, vec![format!("Invalid replicas: {:?}", item)]);
+                }
+            }
+
+            EventResult::Nop
+        })
+        .build()
+        .into()
+}
+
+fn error_widget(theme: ThemeConfig) -> Widget<'static> {
+    let widget_base = WidgetBase::builder()
+        .title("Error")
+        .theme(WidgetTheme::from(theme.component.clone()))
+        .build();
+
+    Text::builder()
+        .id(RESOURCE_ACTION_ERROR_DIALOG_ID)
+        .widget_base(widget_base)
+        .theme(TextTheme::from(theme.component))
+        .wrap()
+        .build()
+        .into()
+}
+
+/// 操作に失敗した理由をダイアログに表示する
+pub fn show_error(w: &mut Window, resource: &ResourceRef, lines: Vec<String>) {
+    let widget = w.find_widget_mut(RESOURCE_ACTION_ERROR_DIALOG_ID);
+
+    *widget.widget_base_mut().append_title_mut() = Some(format!(" : {}", resource).into());
+
+    widget.update_widget_item(Item::Array(
+        lines.into_iter().map(LiteralItem::from).collect(),
+    ));
+
+    w.open_dialog(RESOURCE_ACTION_ERROR_DIALOG_ID);
+}
+
+fn send_request(tx: &Sender<Message>, target: ResourceRef, action: ResourceAction) {
+    tx.send(ResourceActionRequest { target, action }.into())
+        .expect("Failed to send ResourceActionRequest");
+}
@@ -0,0 +1,10 @@
+mod description;
+mod history;
+mod job_history;
+mod rollout;
+mod workload;
+
+pub use description::*;
+pub use history::*;
+pub use job_history::*;
+pub use workload::*;
@@ -0,0 +1,765 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossbeam::channel::Sender;
+use k8s_openapi::{
+    api::{
+        apps::v1::{ControllerRevision, DaemonSet, Deployment, ReplicaSet, StatefulSet},
+        batch::v1::{CronJob, Job},
+        core::v1::Event,
+    },
+    apimachinery::pkg::apis::meta::v1::Time,
+    List, ListableResource, NamespaceResourceScope, Resource as _,
+};
+use kube::{Resource, ResourceExt};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use crate::{
+    features::workload::message::{WorkloadKind, WorkloadRequest, WorkloadResponse},
+    kube::KubeClientRequest,
+    logger,
+    message::Message,
+    workers::kube::AbortWorker,
+};
+
+use super::rollout::{job_state, Workload};
+
+const INTERVAL: u64 = 3;
+
+/// 表示するイベントの最大数
+const MAX_EVENTS: usize = 10;
+
+const DEPLOYMENT_REVISION_ANNOTATION: &str = "deployment.kubernetes.io/revision";
+
+#[derive(Clone)]
+pub struct WorkloadDescriptionWorker<C>
+where
+    C: KubeClientRequest,
+{
+    tx: Sender<Message>,
+    client: C,
+    req: WorkloadRequest,
+}
+
+impl<C> WorkloadDescriptionWorker<C>
+where
+    C: KubeClientRequest,
+{
+    pub fn new(tx: Sender<Message>, client: C, req: WorkloadRequest) -> Self {
+        Self { tx, client, req }
+    }
+}
+
+#[async_trait]
+impl<C> AbortWorker for WorkloadDescriptionWorker<C>
+where
+    C: KubeClientRequest,
+{
+    async fn run(&self) {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(INTERVAL));
+
+        loop {
+            interval.tick().await;
+
+            let description = fetch_workload_description(&self.client, &self.req).await;
+
+            if let Err(e) = &description {
+                logger!(error, "Failed to fetch workload description: {:?}", e);
+            }
+
+            self.tx
+                .send(WorkloadResponse::Description(description).into())
+                .expect("Failed to send WorkloadResponse::Description");
+        }
+    }
+}
+
+pub(super) async fn fetch_resource<C, K>(client: &C, namespace: &str, name: &str) -> Result<K>
+where
+    C: KubeClientRequest,
+    K: Resource<DynamicType = (), Scope = NamespaceResourceScope> + DeserializeOwned + 'static,
+{
+    client
+        .request(&format!("{}/{}", K::url_path(&(), Some(namespace)), name))
+        .await
+}
+
+/// ownerReferencesでownerに所有されているリソースを返す
+pub(super) async fn fetch_owned<C, K, O>(client: &C, owner: &O) -> Result<Vec<K>>
+where
+    C: KubeClientRequest,
+    K: Resource<DynamicType = (), Scope = NamespaceResourceScope>
+        + ListableResource
+        + DeserializeOwned
+        + 'static,
+    O: Resource<DynamicType = ()>,
+{
+    let namespace = owner.namespace().unwrap_or_default();
+
+    let list: List<K> = client.request(&K::url_path(&(), Some(&namespace))).await?;
+
+    let owner_kind = O::kind(&());
+    let owner_name = owner.name_any();
+
+    Ok(list
+        .items
+        .into_iter()
+        .filter(|resource| {
+            resource
+                .owner_references()
+                .iter()
+                .any(|r| r.kind == owner_kind && r.name == owner_name)
+        })
+        .collect())
+}
+
+/// ワークロードの状態と、ReplicaSet・リビジョン・Job、直近のイベントをYAML形式で返す
+async fn fetch_workload_description<C: KubeClientRequest>(
+    client: &C,
+    req: &WorkloadRequest,
+) -> Result<Vec<String>> {
+    let WorkloadRequest {
+        kind,
+        namespace,
+        name,
+    } = req;
+
+    let mut root = Mapping::new();
+
+    // イベントを表示する対象（種類, 名前）
+    let mut involved_objects = vec![(kind.kind().to_string(), name.to_string())];
+
+    match kind {
+        WorkloadKind::Deployment => {
+            let deployment: Deployment = fetch_resource(client, namespace, name).await?;
+
+            let mut workload = workload_value(&deployment);
+
+            if let Some(strategy) = deployment
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.strategy.as_ref())
+                .and_then(|strategy| strategy.type_.clone())
+            {
+                workload.insert("strategy".into(), strategy.into());
+            }
+
+            root.insert("workload".into(), workload.into());
+
+            if let Some(conditions) = deployment
+                .status
+                .as_ref()
+                .and_then(|s| s.conditions.as_ref())
+            {
+                root.insert("conditions".into(), conditions_value(conditions)?);
+            }
+
+            let replica_sets: Vec<ReplicaSet> = fetch_owned(client, &deployment).await?;
+
+            involved_objects.extend(
+                replica_sets
+                    .iter()
+                    .map(|rs| (ReplicaSet::KIND.to_string(), rs.name_any())),
+            );
+
+            root.insert("replicaSets".into(), replica_sets_value(&replica_sets));
+        }
+        WorkloadKind::StatefulSet => {
+            let statefulset: StatefulSet = fetch_resource(client, namespace, name).await?;
+
+            let mut workload = workload_value(&statefulset);
+
+            if let Some(strategy) = statefulset
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.update_strategy.as_ref())
+                .and_then(|strategy| strategy.type_.clone())
+            {
+                workload.insert("updateStrategy".into(), strategy.into());
+            }
+
+            root.insert("workload".into(), workload.into());
+
+            if let Some(conditions) = statefulset
+                .status
+                .as_ref()
+                .and_then(|s| s.conditions.as_ref())
+            {
+                root.insert("conditions".into(), conditions_value(conditions)?);
+            }
+
+            let revisions: Vec<ControllerRevision> = fetch_owned(client, &statefulset).await?;
+
+            root.insert("revisions".into(), revisions_value(&revisions));
+        }
+        WorkloadKind::DaemonSet => {
+            let daemonset: DaemonSet = fetch_resource(client, namespace, name).await?;
+
+            let mut workload = workload_value(&daemonset);
+
+            if let Some(strategy) = daemonset
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.update_strategy.as_ref())
+                .and_then(|strategy| strategy.type_.clone())
+            {
+                workload.insert("updateStrategy".into(), strategy.into());
+            }
+
+            root.insert("workload".into(), workload.into());
+
+            if let Some(conditions) = daemonset
+                .status
+                .as_ref()
+                .and_then(|s| s.conditions.as_ref())
+            {
+                root.insert("conditions".into(), conditions_value(conditions)?);
+            }
+
+            let revisions: Vec<ControllerRevision> = fetch_owned(client, &daemonset).await?;
+
+            root.insert("revisions".into(), revisions_value(&revisions));
+        }
+        WorkloadKind::Job => {
+            let job: Job = fetch_resource(client, namespace, name).await?;
+
+            root.insert("workload".into(), workload_value(&job).into());
+
+            if let Some(conditions) = job.status.as_ref().and_then(|s| s.conditions.as_ref()) {
+                root.insert("conditions".into(), conditions_value(conditions)?);
+            }
+        }
+        WorkloadKind::CronJob => {
+            let cronjob: CronJob = fetch_resource(client, namespace, name).await?;
+
+            let mut workload = workload_value(&cronjob);
+
+            if let Some(spec) = &cronjob.spec {
+                workload.insert("schedule".into(), spec.schedule.clone().into());
+            }
+
+            root.insert("workload".into(), workload.into());
+
+            let jobs: Vec<Job> = fetch_owned(client, &cronjob).await?;
+
+            involved_objects.extend(
+                jobs.iter()
+                    .map(|job| (Job::KIND.to_string(), job.name_any())),
+            );
+
+            root.insert("jobs".into(), jobs_value(&jobs));
+        }
+    }
+
+    // イベントを取得できない場合でも、ワークロードの情報は表示する
+    match client
+        .request::<List<Event>>(&Event::url_path(&(), Some(namespace)))
+        .await
+    {
+        Ok(events) => {
+            let events = recent_events(&events.items, &involved_objects);
+
+            if !events.is_empty() {
+                root.insert("events".into(), events.into());
+            }
+        }
+        Err(err) => {
+            logger!(error, "Failed to list events in {}: {}", namespace, err);
+        }
+    }
+
+    Ok(serde_yaml::to_string(&root)?
+        .lines()
+        .map(ToString::to_string)
+        .collect())
+}
+
+fn workload_value<K>(workload: &K) -> Mapping
+where
+    K: Resource<DynamicType = ()> + Workload,
+{
+    let mut value = Mapping::new();
+
+    value.insert("kind".into(), K::kind(&()).to_string().into());
+    value.insert("name".into(), workload.name_any().into());
+    value.insert(
+        "namespace".into(),
+        workload.namespace().unwrap_or_default().into(),
+    );
+
+    let replicas = workload.replicas();
+
+    let mut replicas_value = Mapping::new();
+
+    for (key, count) in [
+        ("desired", replicas.desired),
+        ("ready", replicas.ready),
+        ("upToDate", replicas.updated),
+        ("available", replicas.available),
+    ] {
+        if let Some(count) = count {
+            replicas_value.insert(key.into(), count.into());
+        }
+    }
+
+    if !replicas_value.is_empty() {
+        value.insert("replicas".into(), replicas_value.into());
+    }
+
+    let rollout = workload.rollout();
+
+    let mut rollout_value = Mapping::new();
+    rollout_value.insert("status".into(), rollout.state.to_string().into());
+    rollout_value.insert("message".into(), rollout.message.into());
+
+    value.insert("rollout".into(), rollout_value.into());
+
+    let images = workload.images();
+
+    if !images.is_empty() {
+        value.insert("images".into(), images.into());
+    }
+
+    value
+}
+
+/// conditionsから表示に必要な項目を取り出す
+///
+/// 種類ごとに型が異なるため、シリアライズした結果から取り出す
+fn conditions_value<T: Serialize>(conditions: &[T]) -> Result<Value> {
+    const KEYS: [&str; 5] = ["type", "status", "reason", "message", "lastTransitionTime"];
+
+    let conditions = conditions
+        .iter()
+        .map(|condition| {
+            let condition = serde_yaml::to_value(condition)?;
+
+            let mut value = Mapping::new();
+
+            for key in KEYS {
+                if let Some(v) = condition.get(key) {
+                    value.insert(key.into(), v.clone());
+                }
+            }
+
+            Ok(value.into())
+        })
+        .collect::<Result<Vec<Value>>>()?;
+
+    Ok(conditions.into())
+}
+
+fn format_time(time: Option<&Time>) -> Option<String> {
+    time.map(|time| time.0.to_rfc3339())
+}
+
+/// ReplicaSetのアノテーションに記録されているDeploymentのリビジョン
+pub(super) fn replica_set_revision(rs: &ReplicaSet) -> i64 {
+    rs.annotations()
+        .get(DEPLOYMENT_REVISION_ANNOTATION)
+        .and_then(|revision| revision.parse().ok())
+        .unwrap_or_default()
+}
+
+/// DeploymentのReplicaSetをリビジョンの降順で返す
+///
+/// 最新のリビジョンを現在のReplicaSetとする
+fn replica_sets_value(replica_sets: &[ReplicaSet]) -> Value {
+    let mut replica_sets: Vec<&ReplicaSet> = replica_sets.iter().collect();
+
+    replica_sets.sort_by_key(|rs| std::cmp::Reverse(replica_set_revision(rs)));
+
+    replica_sets
+        .iter()
+        .enumerate()
+        .map(|(i, rs)| {
+            let mut value = Mapping::new();
+
+            value.insert("revision".into(), replica_set_revision(rs).into());
+            value.insert("name".into(), rs.name_any().into());
+
+            let desired = rs.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+            let ready = rs
+                .status
+                .as_ref()
+                .and_then(|s| s.ready_replicas)
+                .unwrap_or_default();
+
+            value.insert("replicas".into(), format!("{}/{}", ready, desired).into());
+
+            let images: Vec<String> = rs
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.template.as_ref())
+                .and_then(|template| template.spec.as_ref())
+                .map(|spec| {
+                    spec.containers
+                        .iter()
+                        .filter_map(|c| c.image.clone())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            value.insert("images".into(), images.into());
+
+            if i == 0 {
+                value.insert("current".into(), true.into());
+            }
+
+            value.into()
+        })
+        .collect::<Vec<Value>>()
+        .into()
+}
+
+/// StatefulSetとDaemonSetのControllerRevisionをリビジョンの降順で返す
+fn revisions_value(revisions: &[ControllerRevision]) -> Value {
+    let mut revisions: Vec<&ControllerRevision> = revisions.iter().collect();
+
+    revisions.sort_by_key(|r| std::cmp::Reverse(r.revision));
+
+    revisions
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let mut value = Mapping::new();
+
+            value.insert("revision".into(), r.revision.into());
+            value.insert("name".into(), r.name_any().into());
+
+            if let Some(created) = format_time(r.creation_timestamp().as_ref()) {
+                value.insert("created".into(), created.into());
+            }
+
+            if i == 0 {
+                value.insert("current".into(), true.into());
+            }
+
+            value.into()
+        })
+        .collect::<Vec<Value>>()
+        .into()
+}
+
+/// CronJobから作成されたJobを新しい順に返す
+fn jobs_value(jobs: &[Job]) -> Value {
+    let mut jobs: Vec<&Job> = jobs.iter().collect();
+
+    jobs.sort_by_key(|job| std::cmp::Reverse(job.creation_timestamp()));
+
+    jobs.iter()
+        .map(|job| {
+            let mut value = Mapping::new();
+
+            value.insert("name".into(), job.name_any().into());
+            value.insert("status".into(), job_state(job).to_string().into());
+
+            let status = job.status.as_ref();
+
+            if let Some(start) = format_time(status.and_then(|s| s.start_time.as_ref())) {
+                value.insert("startTime".into(), start.into());
+            }
+
+            if let Some(completion) = format_time(status.and_then(|s| s.completion_time.as_ref())) {
+                value.insert("completionTime".into(), completion.into());
+            }
+
+            value.into()
+        })
+        .collect::<Vec<Value>>()
+        .into()
+}
+
+/// 対象のリソースのイベントを古い順に最大MAX_EVENTS件返す
+fn recent_events(events: &[Event], involved_objects: &[(String, String)]) -> Vec<String> {
+    let time = |event: &Event| {
+        event
+            .last_timestamp
+            .as_ref()
+            .map(|t| t.0)
+            .or_else(|| event.event_time.as_ref().map(|t| t.0))
+            .or_else(|| event.metadata.creation_timestamp.as_ref().map(|t| t.0))
+    };
+
+    let mut events: Vec<&Event> = events
+        .iter()
+        .filter(|event| {
+            let object = &event.involved_object;
+
+            involved_objects.iter().any(|(kind, name)| {
+                object.kind.as_deref() == Some(kind) && object.name.as_deref() == Some(name)
+            })
+        })
+        .collect();
+
+    events.sort_by_key(|event| time(event));
+
+    let skip = events.len().saturating_sub(MAX_EVENTS);
+
+    events
+        .into_iter()
+        .skip(skip)
+        .map(|event| {
+            let object = &event.involved_object;
+
+            format!(
+                "{} {} {} {}/{}: {}",
+                time(event)
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_else(|| "<unknown>".to_string()),
+                event.type_.as_deref().unwrap_or_default(),
+                event.reason.as_deref().unwrap_or_default(),
+                object.kind.as_deref().unwrap_or_default().to_lowercase(),
+                object.name.as_deref().unwrap_or_default(),
+                event.message.as_deref().unwrap_or_default().trim_end(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+
+    use crate::{kube::mock::MockTestKubeClient, mock_expect};
+
+    fn deployment() -> Deployment {
+        serde_yaml::from_str(indoc! {
+            r#"
+            metadata:
+              name: web
+              namespace: app
+              generation: 2
+            spec:
+              replicas: 2
+              selector: {}
+              strategy:
+                type: RollingUpdate
+              template:
+                spec:
+                  containers:
+                    - name: web
+                      image: nginx:1.27
+            status:
+              observedGeneration: 2
+              replicas: 3
+              readyReplicas: 2
+              updatedReplicas: 2
+              availableReplicas: 2
+              conditions:
+                - type: Available
+                  status: "True"
+                  reason: MinimumReplicasAvailable
+                  lastUpdateTime: "2024-01-01T00:00:00Z"
+            "#
+        })
+        .unwrap()
+    }
+
+    fn replica_sets() -> List<ReplicaSet> {
+        serde_yaml::from_str(indoc! {
+            r#"
+            items:
+              - metadata:
+                  name: web-1
+                  annotations:
+                    deployment.kubernetes.io/revision: "1"
+                  ownerReferences:
+                    - apiVersion: apps/v1
+                      kind: Deployment
+                      name: web
+                      uid: uid
+                spec:
+                  replicas: 1
+                  selector: {}
+                  template:
+                    spec:
+                      containers:
+                        - name: web
+                          image: nginx:1.26
+                status:
+                  replicas: 1
+              - metadata:
+                  name: web-2
+                  annotations:
+                    deployment.kubernetes.io/revision: "2"
+                  ownerReferences:
+                    - apiVersion: apps/v1
+                      kind: Deployment
+                      name: web
+                      uid: uid
+                spec:
+                  replicas: 2
+                  selector: {}
+                  template:
+                    spec:
+                      containers:
+                        - name: web
+                          image: nginx:1.27
+                status:
+                  replicas: 2
+                  readyReplicas: 2
+              - metadata:
+                  name: other-1
+                  ownerReferences:
+                    - apiVersion: apps/v1
+                      kind: Deployment
+                      name: other
+                      uid: other
+                spec:
+                  selector: {}
+                status:
+                  replicas: 1
+            "#
+        })
+        .unwrap()
+    }
+
+    fn events() -> List<Event> {
+        serde_yaml::from_str(indoc! {
+            r#"
+            items:
+              - metadata:
+                  name: event-2
+                involvedObject:
+                  kind: ReplicaSet
+                  name: web-2
+                type: Normal
+                reason: SuccessfulCreate
+                message: "Created pod: web-2-abcde"
+                lastTimestamp: "2024-01-01T00:01:00Z"
+              - metadata:
+                  name: event-1
+                involvedObject:
+                  kind: Deployment
+                  name: web
+                type: Normal
+                reason: ScalingReplicaSet
+                message: Scaled up replica set web-2 to 2
+                lastTimestamp: "2024-01-01T00:00:00Z"
+              - metadata:
+                  name: event-3
+                involvedObject:
+                  kind: Deployment
+                  name: other
+                type: Warning
+                reason: Other
+                message: other
+                lastTimestamp: "2024-01-01T00:02:00Z"
+            "#
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn deploymentの状態とreplicasetとイベントを返す() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            [
+                (
+                    Deployment,
+                    eq("/apis/apps/v1/namespaces/app/deployments/web"),
+                    Ok(deployment())
+                ),
+                (
+                    List<ReplicaSet>,
+                    eq("/apis/apps/v1/namespaces/app/replicasets"),
+                    Ok(replica_sets())
+                ),
+                (
+                    List<Event>,
+                    eq("/api/v1/namespaces/app/events"),
+                    Ok(events())
+                )
+            ]
+        );
+
+        let req = WorkloadRequest {
+            kind: WorkloadKind::Deployment,
+            namespace: "app".to_string(),
+            name: "web".to_string(),
+        };
+
+        let actual = fetch_workload_description(&client, &req).await.unwrap();
+
+        let expected: Vec<String> = indoc! {
+            r#"
+            workload:
+              kind: Deployment
+              name: web
+              namespace: app
+              replicas:
+                desired: 2
+                ready: 2
+                upToDate: 2
+                available: 2
+              rollout:
+                status: Progressing
+                message: 1 old replicas are pending termination
+              images:
+              - nginx:1.27
+              strategy: RollingUpdate
+            conditions:
+            - type: Available
+              status: 'True'
+              reason: MinimumReplicasAvailable
+            replicaSets:
+            - revision: 2
+              name: web-2
+              replicas: 2/2
+              images:
+              - nginx:1.27
+              current: true
+            - revision: 1
+              name: web-1
+              replicas: 0/1
+              images:
+              - nginx:1.26
+            events:
+            - '2024-01-01T00:00:00+00:00 Normal ScalingReplicaSet deployment/web: Scaled up replica set web-2 to 2'
+            - '2024-01-01T00:01:00+00:00 Normal SuccessfulCreate replicaset/web-2: Created pod: web-2-abcde'
+            "#
+        }
+        .lines()
+        .map(ToString::to_string)
+        .collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn controllerrevisionをリビジョンの降順で返す() {
+        let revisions: Vec<ControllerRevision> = serde_yaml::from_str(indoc! {
+            r#"
+            - metadata:
+                name: db-aaaa
+              revision: 1
+            - metadata:
+                name: db-bbbb
+              revision: 3
+            "#
+        })
+        .unwrap();
+
+        let actual = serde_yaml::to_string(&revisions_value(&revisions)).unwrap();
+
+        let expected = indoc! {
+            r#"
+            - revision: 3
+              name: db-bbbb
+              current: true
+            - revision: 1
+              name: db-aaaa
+            "#
+        };
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,578 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Result};
+use k8s_openapi::api::{
+    apps::v1::{Deployment, ReplicaSet},
+    core::v1::PodTemplateSpec,
+};
+use kube::{Resource, ResourceExt};
+use serde_json::{json, Value};
+
+use crate::{
+    features::workload::message::RolloutTarget,
+    kube::{
+        table::{KubeTable, KubeTableRow},
+        KubeClientRequest,
+    },
+    logger,
+    workers::kube::{color::fg::Color, diff::diff_lines},
+};
+
+use super::description::{fetch_owned, fetch_resource, replica_set_revision};
+
+const CHANGE_CAUSE_ANNOTATION: &str = "kubernetes.io/change-cause";
+
+/// ReplicaSetごとに付与され、Podテンプレートの差分には含めないラベル
+const POD_TEMPLATE_HASH_LABEL: &str = "pod-template-hash";
+
+/// Deploymentと、そのReplicaSetをリビジョンの降順で返す
+async fn fetch_revisions<C: KubeClientRequest>(
+    client: &C,
+    target: &RolloutTarget,
+) -> Result<(Deployment, Vec<ReplicaSet>)> {
+    let deployment: Deployment = fetch_resource(client, &target.namespace, &target.name).await?;
+
+    let mut replica_sets: Vec<ReplicaSet> = fetch_owned(client, &deployment).await?;
+
+    replica_sets.sort_by_key(|rs| std::cmp::Reverse(replica_set_revision(rs)));
+
+    Ok((deployment, replica_sets))
+}
+
+fn find_revision(replica_sets: &[ReplicaSet], revision: i64) -> Result<&ReplicaSet> {
+    replica_sets
+        .iter()
+        .find(|rs| replica_set_revision(rs) == revision)
+        .ok_or_else(|| {
+            anyhow!(
+                "unable to find the specified revision {} in history",
+                revision
+            )
+        })
+}
+
+/// ReplicaSetのPodテンプレートからpod-template-hashラベルを取り除いたもの
+fn pod_template(rs: &ReplicaSet) -> PodTemplateSpec {
+    let mut template = rs
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.template.clone())
+        .unwrap_or_default();
+
+    if let Some(labels) = template
+        .metadata
+        .as_mut()
+        .and_then(|metadata| metadata.labels.as_mut())
+    {
+        labels.remove(POD_TEMPLATE_HASH_LABEL);
+    }
+
+    template
+}
+
+fn images(template: &PodTemplateSpec) -> Vec<String> {
+    template
+        .spec
+        .as_ref()
+        .map(|spec| {
+            spec.containers
+                .iter()
+                .filter_map(|c| c.image.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// kubectl rollout historyと同じく、Deploymentのリビジョンの一覧を返す
+///
+/// 最新のリビジョンを現在のリビジョンとする
+pub async fn fetch_rollout_history<C: KubeClientRequest>(
+    client: &C,
+    target: &RolloutTarget,
+) -> Result<KubeTable> {
+    let (_, replica_sets) = fetch_revisions(client, target).await?;
+
+    let rows = replica_sets
+        .iter()
+        .enumerate()
+        .map(|(i, rs)| {
+            let revision = replica_set_revision(rs);
+
+            let change_cause = rs
+                .annotations()
+                .get(CHANGE_CAUSE_ANNOTATION)
+                .cloned()
+                .unwrap_or_else(|| "<none>".to_string());
+
+            let created = rs
+                .creation_timestamp()
+                .map(|time| time.0.to_rfc3339())
+                .unwrap_or_default();
+
+            let revision_column = if i == 0 {
+                format!("{} (current)", revision)
+            } else {
+                revision.to_string()
+            };
+
+            KubeTableRow {
+                namespace: target.namespace.clone(),
+                name: rs.name_any(),
+                metadata: Some(BTreeMap::from([(
+                    "revision".to_string(),
+                    revision.to_string(),
+                )])),
+                row: vec![
+                    revision_column,
+                    change_cause,
+                    images(&pod_template(rs)).join(","),
+                    created,
+                ],
+            }
+        })
+        .collect();
+
+    let mut table = KubeTable {
+        header: ["REVISION", "CHANGE-CAUSE", "IMAGES", "CREATED"]
+            .into_iter()
+            .map(ToString::to_string)
+            .collect(),
+        ..Default::default()
+    };
+
+    table.update_rows(rows);
+
+    Ok(table)
+}
+
+/// fromのリビジョンからtoのリビジョンへのPodテンプレートの差分を返す
+pub async fn fetch_rollout_diff<C: KubeClientRequest>(
+    client: &C,
+    target: &RolloutTarget,
+    from: i64,
+    to: i64,
+) -> Result<Vec<String>> {
+    let (_, replica_sets) = fetch_revisions(client, target).await?;
+
+    let old = serde_yaml::to_string(&pod_template(find_revision(&replica_sets, from)?))?;
+    let new = serde_yaml::to_string(&pod_template(find_revision(&replica_sets, to)?))?;
+
+    let mut lines = vec![
+        Color::Red.wrap(format!("--- revision {}", from)),
+        Color::Green.wrap(format!("+++ revision {}", to)),
+    ];
+
+    if old == new {
+        lines.push("No differences in the pod template".to_string());
+    } else {
+        lines.extend(diff_lines(
+            &old.lines().collect::<Vec<_>>(),
+            &new.lines().collect::<Vec<_>>(),
+        ));
+    }
+
+    Ok(lines)
+}
+
+/// kubectl rollout undoと同じく、指定したリビジョンのPodテンプレートをDeploymentに適用する
+///
+/// 現在のPodテンプレートと同じ場合は何もしない。
+/// 取得したDeploymentのresourceVersionを前提条件にするため、その後に更新されていた場合はエラーを返す
+pub async fn undo_rollout<C: KubeClientRequest>(
+    client: &C,
+    target: &RolloutTarget,
+    revision: i64,
+) -> Result<String> {
+    logger!(
+        info,
+        "Rollback deployment/{} to revision {}",
+        target.name,
+        revision
+    );
+
+    let (deployment, replica_sets) = fetch_revisions(client, target).await?;
+
+    let spec = deployment.spec.as_ref().cloned().unwrap_or_default();
+
+    if spec.paused.unwrap_or_default() {
+        bail!("cannot rollback a paused deployment; resume it first");
+    }
+
+    let rs = find_revision(&replica_sets, revision)?;
+
+    let template = pod_template(rs);
+
+    if template == spec.template {
+        return Ok(format!(
+            "skipped rollback (current template already matches revision {})",
+            revision
+        ));
+    }
+
+    let patch = json!({
+        "metadata": {
+            "resourceVersion": deployment.resource_version(),
+            "annotations": {
+                CHANGE_CAUSE_ANNOTATION: rs.annotations().get(CHANGE_CAUSE_ANNOTATION)
+            }
+        },
+        "spec": {
+            "template": replace_patch(
+                &serde_json::to_value(&spec.template)?,
+                &serde_json::to_value(&template)?
+            )
+        }
+    });
+
+    let path = format!(
+        "{}/{}",
+        Deployment::url_path(&(), Some(&target.namespace)),
+        target.name
+    );
+
+    if let Err(e) = client.merge_patch(&path, &patch).await {
+        if is_conflict(&e) {
+            bail!(
+                "deployment/{} was changed concurrently; reload the history and retry",
+                target.name
+            );
+        }
+
+        return Err(e);
+    }
+
+    Ok(format!(
+        "deployment/{} rolled back to revision {}",
+        target.name, revision
+    ))
+}
+
+/// 前提条件のresourceVersionが一致せずに更新が失敗したか
+fn is_conflict(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<kube::Error>(),
+        Some(kube::Error::Api(response)) if response.code == 409
+    )
+}
+
+/// currentをdesiredで置き換えるためのJSON Merge Patchを返す
+///
+/// desiredにないフィールドはnullにして削除する
+fn replace_patch(current: &Value, desired: &Value) -> Value {
+    match (current, desired) {
+        (Value::Object(current), Value::Object(desired)) => {
+            let mut patch = serde_json::Map::new();
+
+            for key in current.keys() {
+                if !desired.contains_key(key) {
+                    patch.insert(key.clone(), Value::Null);
+                }
+            }
+
+            for (key, value) in desired {
+                let value = match current.get(key) {
+                    Some(current) => replace_patch(current, value),
+                    None => value.clone(),
+                };
+
+                patch.insert(key.clone(), value);
+            }
+
+            Value::Object(patch)
+        }
+        _ => desired.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use k8s_openapi::List;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+
+    use crate::{kube::mock::MockTestKubeClient, mock_expect};
+
+    fn target() -> RolloutTarget {
+        RolloutTarget {
+            namespace: "app".to_string(),
+            name: "web".to_string(),
+        }
+    }
+
+    fn deployment() -> Deployment {
+        serde_yaml::from_str(indoc! {"
+            metadata:
+              name: web
+              namespace: app
+              resourceVersion: '100'
+            spec:
+              selector: {}
+              template:
+                metadata:
+                  labels:
+                    app: web
+                spec:
+                  containers:
+                    - name: web
+                      image: web:2.0
+                      env:
+                        - name: DEBUG
+                          value: 'true'
+        "})
+        .unwrap()
+    }
+
+    fn replica_sets() -> List<ReplicaSet> {
+        serde_yaml::from_str(indoc! {"
+            items:
+              - metadata:
+                  name: web-1
+                  namespace: app
+                  creationTimestamp: 2024-01-01T00:00:00Z
+                  annotations:
+                    deployment.kubernetes.io/revision: '1'
+                  ownerReferences:
+                    - apiVersion: apps/v1
+                      kind: Deployment
+                      name: web
+                      uid: uid
+                spec:
+                  selector: {}
+                  template:
+                    metadata:
+                      labels:
+                        app: web
+                        pod-template-hash: abc
+                    spec:
+                      containers:
+                        - name: web
+                          image: web:1.0
+              - metadata:
+                  name: web-2
+                  namespace: app
+                  creationTimestamp: 2024-01-02T00:00:00Z
+                  annotations:
+                    deployment.kubernetes.io/revision: '2'
+                    kubernetes.io/change-cause: enable debug
+                  ownerReferences:
+                    - apiVersion: apps/v1
+                      kind: Deployment
+                      name: web
+                      uid: uid
+                spec:
+                  selector: {}
+                  template:
+                    metadata:
+                      labels:
+                        app: web
+                        pod-template-hash: def
+                    spec:
+                      containers:
+                        - name: web
+                          image: web:2.0
+                          env:
+                            - name: DEBUG
+                              value: 'true'
+              - metadata:
+                  name: other
+                  namespace: app
+                  annotations:
+                    deployment.kubernetes.io/revision: '3'
+                spec:
+                  selector: {}
+        "})
+        .unwrap()
+    }
+
+    fn client() -> MockTestKubeClient {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            [
+                (
+                    Deployment,
+                    eq("/apis/apps/v1/namespaces/app/deployments/web"),
+                    Ok(deployment())
+                ),
+                (
+                    List<ReplicaSet>,
+                    eq("/apis/apps/v1/namespaces/app/replicasets"),
+                    Ok(replica_sets())
+                )
+            ]
+        );
+
+        client
+    }
+
+    #[tokio::test]
+    async fn リビジョンの一覧を降順で返す() {
+        let actual = fetch_rollout_history(&client(), &target()).await.unwrap();
+
+        let rows: Vec<Vec<String>> = actual.rows.into_iter().map(|row| row.row).collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    "2 (current)".to_string(),
+                    "enable debug".to_string(),
+                    "web:2.0".to_string(),
+                    "2024-01-02T00:00:00+00:00".to_string(),
+                ],
+                vec![
+                    "1".to_string(),
+                    "<none>".to_string(),
+                    "web:1.0".to_string(),
+                    "2024-01-01T00:00:00+00:00".to_string(),
+                ],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn pod_template_hashを除いたpodテンプレートの差分を返す() {
+        let actual = fetch_rollout_diff(&client(), &target(), 1, 2)
+            .await
+            .unwrap();
+
+        let expected = vec![
+            Color::Red.wrap("--- revision 1"),
+            Color::Green.wrap("+++ revision 2"),
+            "  metadata:".to_string(),
+            "    labels:".to_string(),
+            "      app: web".to_string(),
+            "  spec:".to_string(),
+            "    containers:".to_string(),
+            Color::Red.wrap("-   - image: web:1.0"),
+            Color::Green.wrap("+   - env:"),
+            Color::Green.wrap("+     - name: DEBUG"),
+            Color::Green.wrap("+       value: 'true'"),
+            Color::Green.wrap("+     image: web:2.0"),
+            "      name: web".to_string(),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn 指定したリビジョンのpodテンプレートでdeploymentを更新する() {
+        let mut client = client();
+
+        client
+            .expect_merge_patch()
+            .with(
+                eq("/apis/apps/v1/namespaces/app/deployments/web"),
+                eq(json!({
+                    "metadata": {
+                        "resourceVersion": "100",
+                        "annotations": {
+                            "kubernetes.io/change-cause": null
+                        }
+                    },
+                    "spec": {
+                        "template": {
+                            "metadata": {
+                                "labels": {
+                                    "app": "web"
+                                }
+                            },
+                            "spec": {
+                                "containers": [
+                                    {
+                                        "name": "web",
+                                        "image": "web:1.0"
+                                    }
+                                ]
+                            }
+                        }
+                    }
+                })),
+            )
+            .returning(|_, _| Ok(()));
+
+        let actual = undo_rollout(&client, &target(), 1).await.unwrap();
+
+        assert_eq!(actual, "deployment/web rolled back to revision 1");
+    }
+
+    #[tokio::test]
+    async fn 更新が競合したときは再試行を促すエラーを返す() {
+        let mut client = client();
+
+        client.expect_merge_patch().returning(|_, _| {
+            Err(kube::Error::Api(kube::core::ErrorResponse {
+                status: "Failure".to_string(),
+                message: "the object has been modified".to_string(),
+                reason: "Conflict".to_string(),
+                code: 409,
+            })
+            .into())
+        });
+
+        let actual = undo_rollout(&client, &target(), 1).await.unwrap_err();
+
+        assert_eq!(
+            actual.to_string(),
+            "deployment/web was changed concurrently; reload the history and retry"
+        );
+    }
+
+    #[tokio::test]
+    async fn 現在と同じリビジョンへのロールバックはスキップする() {
+        let actual = undo_rollout(&client(), &target(), 2).await.unwrap();
+
+        assert_eq!(
+            actual,
+            "skipped rollback (current template already matches revision 2)"
+        );
+    }
+
+    #[test]
+    fn 置き換え後にないフィールドはnullにする() {
+        let current = json!({
+            "metadata": {
+                "labels": {
+                    "app": "web",
+                    "tier": "frontend"
+                },
+                "annotations": {
+                    "foo": "bar"
+                }
+            }
+        });
+
+        let desired = json!({
+            "metadata": {
+                "labels": {
+                    "app": "web"
+                }
+            }
+        });
+
+        assert_eq!(
+            replace_patch(&current, &desired),
+            json!({
+                "metadata": {
+                    "labels": {
+                        "app": "web",
+                        "tier": null
+                    },
+                    "annotations": null
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn 存在しないリビジョンはエラーを返す() {
+        let replica_sets = replica_sets().items;
+
+        assert!(find_revision(&replica_sets, 5).is_err());
+    }
+}
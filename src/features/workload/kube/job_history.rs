@@ -0,0 +1,187 @@
+use anyhow::Result;
+use chrono::Utc;
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use kube::ResourceExt;
+
+use crate::{
+    features::workload::message::JobHistoryRequest,
+    kube::{
+        table::{human_duration, translate_timestamp_since, KubeTable, KubeTableRow},
+        KubeClientRequest,
+    },
+};
+
+use super::{
+    description::{fetch_owned, fetch_resource},
+    rollout::job_state,
+};
+
+/// CronJobが作成したJobを新しい順に返す
+pub async fn fetch_job_history<C: KubeClientRequest>(
+    client: &C,
+    req: &JobHistoryRequest,
+) -> Result<KubeTable> {
+    let cronjob: CronJob = fetch_resource(client, &req.namespace, &req.name).await?;
+
+    let mut jobs: Vec<Job> = fetch_owned(client, &cronjob).await?;
+
+    jobs.sort_by_key(|job| std::cmp::Reverse(job.creation_timestamp()));
+
+    let rows = jobs
+        .iter()
+        .map(|job| {
+            let status = job.status.clone().unwrap_or_default();
+
+            KubeTableRow {
+                namespace: req.namespace.clone(),
+                name: job.name_any(),
+                metadata: None,
+                row: vec![
+                    job.name_any(),
+                    job_state(job).to_string(),
+                    status.succeeded.unwrap_or_default().to_string(),
+                    status.failed.unwrap_or_default().to_string(),
+                    duration(job),
+                    translate_timestamp_since(job.creation_timestamp().as_ref()),
+                ],
+            }
+        })
+        .collect();
+
+    let mut table = KubeTable {
+        header: ["NAME", "STATUS", "SUCCEEDED", "FAILED", "DURATION", "AGE"]
+            .into_iter()
+            .map(ToString::to_string)
+            .collect(),
+        ..Default::default()
+    };
+
+    table.update_rows(rows);
+
+    Ok(table)
+}
+
+/// kubectl get jobsのDURATION列と同じく、開始から完了までの時間を返す
+///
+/// 完了していない場合は現在までの時間を返す
+fn duration(job: &Job) -> String {
+    let Some(status) = job.status.as_ref() else {
+        return "-".to_string();
+    };
+
+    let Some(start) = status.start_time.as_ref() else {
+        return "-".to_string();
+    };
+
+    let end = status
+        .completion_time
+        .as_ref()
+        .map_or_else(Utc::now, |time| time.0);
+
+    human_duration(end - start.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use k8s_openapi::List;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+
+    use crate::{kube::mock::MockTestKubeClient, mock_expect};
+
+    fn cronjob() -> CronJob {
+        serde_yaml::from_str(indoc! {"
+            metadata:
+              name: backup
+              namespace: app
+            spec:
+              schedule: '0 0 * * *'
+              jobTemplate: {}
+        "})
+        .unwrap()
+    }
+
+    fn jobs() -> List<Job> {
+        serde_yaml::from_str(indoc! {"
+            items:
+              - metadata:
+                  name: backup-1
+                  namespace: app
+                  creationTimestamp: 2024-01-01T00:00:00Z
+                  ownerReferences:
+                    - apiVersion: batch/v1
+                      kind: CronJob
+                      name: backup
+                      uid: uid
+                status:
+                  startTime: 2024-01-01T00:00:00Z
+                  completionTime: 2024-01-01T00:01:30Z
+                  succeeded: 1
+                  conditions:
+                    - type: Complete
+                      status: 'True'
+              - metadata:
+                  name: backup-2
+                  namespace: app
+                  creationTimestamp: 2024-01-02T00:00:00Z
+                  ownerReferences:
+                    - apiVersion: batch/v1
+                      kind: CronJob
+                      name: backup
+                      uid: uid
+                status:
+                  startTime: 2024-01-02T00:00:00Z
+                  failed: 6
+                  conditions:
+                    - type: Failed
+                      status: 'True'
+              - metadata:
+                  name: other
+                  namespace: app
+                  creationTimestamp: 2024-01-03T00:00:00Z
+        "})
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn cronjobが作成したjobを新しい順に返す() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request,
+            [
+                (
+                    CronJob,
+                    eq("/apis/batch/v1/namespaces/app/cronjobs/backup"),
+                    Ok(cronjob())
+                ),
+                (
+                    List<Job>,
+                    eq("/apis/batch/v1/namespaces/app/jobs"),
+                    Ok(jobs())
+                )
+            ]
+        );
+
+        let req = JobHistoryRequest {
+            namespace: "app".to_string(),
+            name: "backup".to_string(),
+        };
+
+        let actual = fetch_job_history(&client, &req).await.unwrap();
+
+        let rows: Vec<&[String]> = actual.rows.iter().map(|row| &row.row[..5]).collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                ["backup-2", "Failed", "0", "6", &duration(&jobs().items[1])],
+                ["backup-1", "Complete", "1", "0", "90s"],
+            ]
+        );
+    }
+}
@@ -0,0 +1,604 @@
+use k8s_openapi::api::{
+    apps::v1::{DaemonSet, Deployment, StatefulSet},
+    batch::v1::{CronJob, Job},
+    core::v1::PodTemplateSpec,
+};
+use strum::Display;
+
+/// ロールアウトの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum RolloutState {
+    Complete,
+    Progressing,
+    Paused,
+    Failed,
+    Running,
+    Suspended,
+    Active,
+    Idle,
+}
+
+/// ロールアウトの状態と、`kubectl rollout status` と同等の説明
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rollout {
+    pub state: RolloutState,
+    pub message: String,
+}
+
+impl Rollout {
+    fn new(state: RolloutState, message: impl Into<String>) -> Self {
+        Self {
+            state,
+            message: message.into(),
+        }
+    }
+}
+
+/// テーブルに表示するレプリカ数
+///
+/// リソースの種類に該当する値がない場合はNone
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Replicas {
+    pub desired: Option<i32>,
+    pub ready: Option<i32>,
+    pub updated: Option<i32>,
+    pub available: Option<i32>,
+}
+
+/// Workloadタブで扱うリソースに共通する情報
+pub trait Workload {
+    fn replicas(&self) -> Replicas;
+
+    fn rollout(&self) -> Rollout;
+
+    fn pod_template(&self) -> Option<&PodTemplateSpec>;
+
+    /// Podテンプレートのコンテナイメージ
+    fn images(&self) -> Vec<String> {
+        self.pod_template()
+            .and_then(|template| template.spec.as_ref())
+            .map(|spec| {
+                spec.init_containers
+                    .iter()
+                    .flatten()
+                    .chain(&spec.containers)
+                    .filter_map(|container| container.image.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// generationの変更がコントローラーに反映されているかを返す
+fn is_observed(generation: Option<i64>, observed_generation: Option<i64>) -> bool {
+    generation.unwrap_or_default() <= observed_generation.unwrap_or_default()
+}
+
+const WAITING_FOR_SPEC_UPDATE: &str = "Waiting for spec update to be observed";
+
+impl Workload for Deployment {
+    fn replicas(&self) -> Replicas {
+        let status = self.status.clone().unwrap_or_default();
+
+        Replicas {
+            desired: Some(self.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1)),
+            ready: Some(status.ready_replicas.unwrap_or_default()),
+            updated: Some(status.updated_replicas.unwrap_or_default()),
+            available: Some(status.available_replicas.unwrap_or_default()),
+        }
+    }
+
+    fn rollout(&self) -> Rollout {
+        use RolloutState::*;
+
+        let status = self.status.clone().unwrap_or_default();
+
+        if self.spec.as_ref().and_then(|s| s.paused) == Some(true) {
+            return Rollout::new(Paused, "Rollout is paused");
+        }
+
+        if !is_observed(self.metadata.generation, status.observed_generation) {
+            return Rollout::new(Progressing, WAITING_FOR_SPEC_UPDATE);
+        }
+
+        let deadline_exceeded = status.conditions.iter().flatten().any(|c| {
+            c.type_ == "Progressing" && c.reason.as_deref() == Some("ProgressDeadlineExceeded")
+        });
+
+        if deadline_exceeded {
+            return Rollout::new(Failed, "Exceeded its progress deadline");
+        }
+
+        let Replicas {
+            desired,
+            updated,
+            available,
+            ..
+        } = self.replicas();
+
+        let (desired, updated, available) = (
+            desired.unwrap_or_default(),
+            updated.unwrap_or_default(),
+            available.unwrap_or_default(),
+        );
+
+        let current = status.replicas.unwrap_or_default();
+
+        if updated < desired {
+            return Rollout::new(
+                Progressing,
+                format!(
+                    "{} out of {} new replicas have been updated",
+                    updated, desired
+                ),
+            );
+        }
+
+        if updated < current {
+            return Rollout::new(
+                Progressing,
+                format!("{} old replicas are pending termination", current - updated),
+            );
+        }
+
+        if available < updated {
+            return Rollout::new(
+                Progressing,
+                format!(
+                    "{} of {} updated replicas are available",
+                    available, updated
+                ),
+            );
+        }
+
+        Rollout::new(Complete, "Successfully rolled out")
+    }
+
+    fn pod_template(&self) -> Option<&PodTemplateSpec> {
+        self.spec.as_ref().map(|spec| &spec.template)
+    }
+}
+
+impl Workload for StatefulSet {
+    fn replicas(&self) -> Replicas {
+        let status = self.status.clone().unwrap_or_default();
+
+        Replicas {
+            desired: Some(self.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1)),
+            ready: Some(status.ready_replicas.unwrap_or_default()),
+            updated: Some(status.updated_replicas.unwrap_or_default()),
+            available: Some(status.available_replicas.unwrap_or_default()),
+        }
+    }
+
+    fn rollout(&self) -> Rollout {
+        use RolloutState::*;
+
+        let status = self.status.clone().unwrap_or_default();
+
+        if status.observed_generation.is_none()
+            || !is_observed(self.metadata.generation, status.observed_generation)
+        {
+            return Rollout::new(Progressing, WAITING_FOR_SPEC_UPDATE);
+        }
+
+        let Replicas {
+            desired,
+            ready,
+            updated,
+            ..
+        } = self.replicas();
+
+        let (desired, ready, updated) = (
+            desired.unwrap_or_default(),
+            ready.unwrap_or_default(),
+            updated.unwrap_or_default(),
+        );
+
+        if ready < desired {
+            return Rollout::new(
+                Progressing,
+                format!("Waiting for {} pods to be ready", desired - ready),
+            );
+        }
+
+        let update_strategy = self
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.update_strategy.as_ref());
+
+        if update_strategy.and_then(|s| s.type_.as_deref()) == Some("OnDelete") {
+            return Rollout::new(
+                Complete,
+                "Pods are updated when they are deleted (OnDelete)",
+            );
+        }
+
+        let partition = update_strategy
+            .and_then(|s| s.rolling_update.as_ref())
+            .and_then(|r| r.partition)
+            .unwrap_or_default();
+
+        if 0 < partition {
+            if updated < desired - partition {
+                return Rollout::new(
+                    Progressing,
+                    format!(
+                        "Waiting for partitioned roll out to finish: {} out of {} new pods have been updated",
+                        updated,
+                        desired - partition
+                    ),
+                );
+            }
+
+            return Rollout::new(Complete, "Partitioned roll out complete");
+        }
+
+        let revision = status.update_revision.unwrap_or_default();
+
+        if status.current_revision.as_deref() != Some(revision.as_str()) {
+            return Rollout::new(
+                Progressing,
+                format!(
+                    "Waiting for rolling update to complete: {} pods at revision {}",
+                    updated, revision
+                ),
+            );
+        }
+
+        Rollout::new(
+            Complete,
+            format!(
+                "Rolling update complete: {} pods at revision {}",
+                ready, revision
+            ),
+        )
+    }
+
+    fn pod_template(&self) -> Option<&PodTemplateSpec> {
+        self.spec.as_ref().map(|spec| &spec.template)
+    }
+}
+
+impl Workload for DaemonSet {
+    fn replicas(&self) -> Replicas {
+        let status = self.status.clone().unwrap_or_default();
+
+        Replicas {
+            desired: Some(status.desired_number_scheduled),
+            ready: Some(status.number_ready),
+            updated: Some(status.updated_number_scheduled.unwrap_or_default()),
+            available: Some(status.number_available.unwrap_or_default()),
+        }
+    }
+
+    fn rollout(&self) -> Rollout {
+        use RolloutState::*;
+
+        let status = self.status.clone().unwrap_or_default();
+
+        if !is_observed(self.metadata.generation, status.observed_generation) {
+            return Rollout::new(Progressing, WAITING_FOR_SPEC_UPDATE);
+        }
+
+        let desired = status.desired_number_scheduled;
+        let updated = status.updated_number_scheduled.unwrap_or_default();
+        let available = status.number_available.unwrap_or_default();
+
+        if updated < desired {
+            return Rollout::new(
+                Progressing,
+                format!("{} out of {} new pods have been updated", updated, desired),
+            );
+        }
+
+        if available < desired {
+            return Rollout::new(
+                Progressing,
+                format!("{} of {} updated pods are available", available, desired),
+            );
+        }
+
+        Rollout::new(Complete, "Successfully rolled out")
+    }
+
+    fn pod_template(&self) -> Option<&PodTemplateSpec> {
+        self.spec.as_ref().map(|spec| &spec.template)
+    }
+}
+
+/// Jobの状態
+///
+/// CronJobから作成されたJobの一覧でも使う
+pub fn job_state(job: &Job) -> RolloutState {
+    let condition = |type_: &str| {
+        job.status
+            .iter()
+            .flat_map(|status| status.conditions.iter().flatten())
+            .any(|c| c.type_ == type_ && c.status == "True")
+    };
+
+    if condition("Complete") {
+        RolloutState::Complete
+    } else if condition("Failed") {
+        RolloutState::Failed
+    } else if job.spec.as_ref().and_then(|spec| spec.suspend) == Some(true) {
+        RolloutState::Suspended
+    } else {
+        RolloutState::Running
+    }
+}
+
+impl Workload for Job {
+    fn replicas(&self) -> Replicas {
+        let status = self.status.clone().unwrap_or_default();
+
+        Replicas {
+            desired: self.spec.as_ref().and_then(|spec| spec.completions),
+            ready: Some(status.ready.unwrap_or_default()),
+            updated: None,
+            available: None,
+        }
+    }
+
+    fn rollout(&self) -> Rollout {
+        let status = self.status.clone().unwrap_or_default();
+
+        let succeeded = status.succeeded.unwrap_or_default();
+        let failed = status.failed.unwrap_or_default();
+
+        let completions = match self.spec.as_ref().and_then(|spec| spec.completions) {
+            Some(completions) => completions.to_string(),
+            None => "-".to_string(),
+        };
+
+        Rollout::new(
+            job_state(self),
+            format!(
+                "{}/{} completions succeeded, {} failed",
+                succeeded, completions, failed
+            ),
+        )
+    }
+
+    fn pod_template(&self) -> Option<&PodTemplateSpec> {
+        self.spec.as_ref().map(|spec| &spec.template)
+    }
+}
+
+impl Workload for CronJob {
+    fn replicas(&self) -> Replicas {
+        Replicas::default()
+    }
+
+    fn rollout(&self) -> Rollout {
+        let active = self
+            .status
+            .as_ref()
+            .and_then(|status| status.active.as_ref())
+            .map(Vec::len)
+            .unwrap_or_default();
+
+        let last_schedule = self
+            .status
+            .as_ref()
+            .and_then(|status| status.last_schedule_time.as_ref())
+            .map(|time| time.0.to_rfc3339())
+            .unwrap_or_else(|| "<none>".to_string());
+
+        let state = if self.spec.as_ref().and_then(|spec| spec.suspend) == Some(true) {
+            RolloutState::Suspended
+        } else if 0 < active {
+            RolloutState::Active
+        } else {
+            RolloutState::Idle
+        };
+
+        Rollout::new(
+            state,
+            format!("{} active jobs, last schedule: {}", active, last_schedule),
+        )
+    }
+
+    fn pod_template(&self) -> Option<&PodTemplateSpec> {
+        self.spec
+            .as_ref()
+            .and_then(|spec| spec.job_template.spec.as_ref())
+            .map(|spec| &spec.template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn deployment(yaml: &str) -> Deployment {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[rstest]
+    #[case(
+        indoc! {"
+            metadata:
+              generation: 2
+            spec:
+              replicas: 3
+              selector: {}
+              template: {}
+            status:
+              observedGeneration: 2
+              replicas: 3
+              readyReplicas: 3
+              updatedReplicas: 3
+              availableReplicas: 3
+        "},
+        Rollout::new(RolloutState::Complete, "Successfully rolled out")
+    )]
+    #[case(
+        indoc! {"
+            metadata:
+              generation: 3
+            spec:
+              replicas: 3
+              selector: {}
+              template: {}
+            status:
+              observedGeneration: 2
+        "},
+        Rollout::new(RolloutState::Progressing, WAITING_FOR_SPEC_UPDATE)
+    )]
+    #[case(
+        indoc! {"
+            metadata:
+              generation: 2
+            spec:
+              replicas: 3
+              selector: {}
+              template: {}
+            status:
+              observedGeneration: 2
+              replicas: 4
+              updatedReplicas: 1
+        "},
+        Rollout::new(RolloutState::Progressing, "1 out of 3 new replicas have been updated")
+    )]
+    #[case(
+        indoc! {"
+            metadata:
+              generation: 2
+            spec:
+              replicas: 3
+              selector: {}
+              template: {}
+            status:
+              observedGeneration: 2
+              replicas: 4
+              updatedReplicas: 3
+        "},
+        Rollout::new(RolloutState::Progressing, "1 old replicas are pending termination")
+    )]
+    #[case(
+        indoc! {"
+            metadata:
+              generation: 2
+            spec:
+              replicas: 3
+              selector: {}
+              template: {}
+            status:
+              observedGeneration: 2
+              replicas: 3
+              updatedReplicas: 3
+              availableReplicas: 2
+        "},
+        Rollout::new(RolloutState::Progressing, "2 of 3 updated replicas are available")
+    )]
+    #[case(
+        indoc! {"
+            metadata:
+              generation: 2
+            spec:
+              replicas: 3
+              selector: {}
+              template: {}
+            status:
+              observedGeneration: 2
+              conditions:
+              - type: Progressing
+                status: 'False'
+                reason: ProgressDeadlineExceeded
+        "},
+        Rollout::new(RolloutState::Failed, "Exceeded its progress deadline")
+    )]
+    fn deploymentのロールアウトの状態を返す(
+        #[case] yaml: &str,
+        #[case] expected: Rollout,
+    ) {
+        assert_eq!(deployment(yaml).rollout(), expected);
+    }
+
+    #[test]
+    fn statefulsetはリビジョンが揃うまで進行中とする() {
+        let statefulset: StatefulSet = serde_yaml::from_str(indoc! {"
+            metadata:
+              generation: 1
+            spec:
+              replicas: 2
+              selector: {}
+              serviceName: web
+              template: {}
+            status:
+              observedGeneration: 1
+              replicas: 2
+              readyReplicas: 2
+              updatedReplicas: 1
+              currentRevision: web-1
+              updateRevision: web-2
+        "})
+        .unwrap();
+
+        assert_eq!(
+            statefulset.rollout(),
+            Rollout::new(
+                RolloutState::Progressing,
+                "Waiting for rolling update to complete: 1 pods at revision web-2"
+            )
+        );
+    }
+
+    #[test]
+    fn daemonsetのレプリカ数はスケジュールされたnode数から求める() {
+        let daemonset: DaemonSet = serde_yaml::from_str(indoc! {"
+            spec:
+              selector: {}
+              template: {}
+            status:
+              currentNumberScheduled: 3
+              desiredNumberScheduled: 3
+              numberMisscheduled: 0
+              numberReady: 2
+              numberAvailable: 2
+              updatedNumberScheduled: 3
+        "})
+        .unwrap();
+
+        assert_eq!(
+            daemonset.replicas(),
+            Replicas {
+                desired: Some(3),
+                ready: Some(2),
+                updated: Some(3),
+                available: Some(2),
+            }
+        );
+
+        assert_eq!(
+            daemonset.rollout(),
+            Rollout::new(
+                RolloutState::Progressing,
+                "2 of 3 updated pods are available"
+            )
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "status: {conditions: [{type: Complete, status: 'True'}]}",
+        RolloutState::Complete
+    )]
+    #[case(
+        "status: {conditions: [{type: Failed, status: 'True'}]}",
+        RolloutState::Failed
+    )]
+    #[case("spec: {suspend: true, template: {}}", RolloutState::Suspended)]
+    #[case("status: {active: 1}", RolloutState::Running)]
+    fn jobの状態を返す(#[case] yaml: &str, #[case] expected: RolloutState) {
+        let job: Job = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(job_state(&job), expected);
+    }
+}
@@ -0,0 +1,302 @@
+use std::{collections::BTreeMap, sync::Arc, time};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use crossbeam::channel::Sender;
+use k8s_openapi::{
+    api::{
+        apps::v1::{DaemonSet, Deployment, StatefulSet},
+        batch::v1::{CronJob, Job},
+    },
+    Resource,
+};
+use kube::ResourceExt;
+
+use crate::{
+    features::workload::message::WorkloadResponse,
+    kube::{
+        informer::{ChangeDetector, SharedInformers},
+        table::{insert_ns, translate_timestamp_since, KubeTable, KubeTableRow},
+    },
+    message::Message,
+    workers::kube::{SharedTargetNamespaces, Worker, WorkerResult},
+};
+
+use super::rollout::Workload;
+
+#[derive(Clone)]
+pub struct WorkloadPoller {
+    tx: Sender<Message>,
+    shared_target_namespaces: SharedTargetNamespaces,
+    informers: SharedInformers,
+}
+
+impl WorkloadPoller {
+    pub fn new(
+        tx: Sender<Message>,
+        shared_target_namespaces: SharedTargetNamespaces,
+        informers: SharedInformers,
+    ) -> Self {
+        Self {
+            tx,
+            shared_target_namespaces,
+            informers,
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for WorkloadPoller {
+    type Output = WorkerResult;
+
+    async fn run(&self) -> Self::Output {
+        let mut interval = tokio::time::interval(time::Duration::from_secs(1));
+
+        let Self {
+            tx,
+            shared_target_namespaces,
+            informers,
+        } = self;
+
+        let mut deployment_informer = informers.namespaced::<Deployment>();
+        let mut statefulset_informer = informers.namespaced::<StatefulSet>();
+        let mut daemonset_informer = informers.namespaced::<DaemonSet>();
+        let mut job_informer = informers.namespaced::<Job>();
+        let mut cronjob_informer = informers.namespaced::<CronJob>();
+
+        let mut detector = ChangeDetector::default();
+
+        loop {
+            interval.tick().await;
+
+            let target_namespaces = shared_target_namespaces.read().await.clone();
+
+            deployment_informer.sync_namespaces(&target_namespaces);
+            statefulset_informer.sync_namespaces(&target_namespaces);
+            daemonset_informer.sync_namespaces(&target_namespaces);
+            job_informer.sync_namespaces(&target_namespaces);
+            cronjob_informer.sync_namespaces(&target_namespaces);
+
+            let (
+                Some(deployments),
+                Some(statefulsets),
+                Some(daemonsets),
+                Some(jobs),
+                Some(cronjobs),
+            ) = (
+                deployment_informer.state(),
+                statefulset_informer.state(),
+                daemonset_informer.state(),
+                job_informer.state(),
+                cronjob_informer.state(),
+            )
+            else {
+                continue;
+            };
+
+            let table = WorkloadTableBuilder::new(&target_namespaces)
+                .extend(deployments)
+                .and_then(|builder| builder.extend(statefulsets))
+                .and_then(|builder| builder.extend(daemonsets))
+                .and_then(|builder| builder.extend(jobs))
+                .and_then(|builder| builder.extend(cronjobs))
+                .map(WorkloadTableBuilder::build);
+
+            if !detector.is_changed(&table) {
+                continue;
+            }
+
+            tx.send(WorkloadResponse::List(table).into())
+                .expect("Failed to send WorkloadResponse::List");
+        }
+    }
+}
+
+/// ワークロードの一覧からテーブルを生成する
+///
+/// 行はリソースの種類ごとに追加した順に並ぶ
+struct WorkloadTableBuilder {
+    insert_ns: bool,
+    rows: Vec<KubeTableRow>,
+}
+
+impl WorkloadTableBuilder {
+    fn new(namespaces: &[String]) -> Self {
+        Self {
+            insert_ns: insert_ns(namespaces),
+            rows: Vec::new(),
+        }
+    }
+
+    /// informerから取得した一覧を追加する
+    /// 取得に失敗している場合はエラーを返す
+    fn extend<K>(mut self, workloads: Result<Vec<Arc<K>>>) -> Result<Self>
+    where
+        K: Resource + ResourceExt + Workload,
+    {
+        let insert_ns = self.insert_ns;
+
+        self.rows.extend(
+            workloads?
+                .iter()
+                .map(|workload| workload_row(workload.as_ref(), insert_ns)),
+        );
+
+        Ok(self)
+    }
+
+    fn build(self) -> KubeTable {
+        let mut header = vec![
+            "KIND",
+            "NAME",
+            "DESIRED",
+            "READY",
+            "UP-TO-DATE",
+            "AVAILABLE",
+            "STATUS",
+            "AGE",
+        ];
+
+        if self.insert_ns {
+            header.insert(0, "NAMESPACE");
+        }
+
+        let mut table = KubeTable {
+            header: header.into_iter().map(ToString::to_string).collect(),
+            ..Default::default()
+        };
+
+        table.update_rows(self.rows);
+
+        table
+    }
+}
+
+fn workload_row<K>(workload: &K, insert_ns: bool) -> KubeTableRow
+where
+    K: Resource + ResourceExt + Workload,
+{
+    let namespace = workload.namespace().unwrap_or_default();
+    let name = workload.name_any();
+
+    let replicas = workload.replicas();
+
+    let count = |n: Option<i32>| n.map_or_else(|| "-".to_string(), |n| n.to_string());
+
+    let mut row = vec![
+        K::KIND.to_string(),
+        name.clone(),
+        count(replicas.desired),
+        count(replicas.ready),
+        count(replicas.updated),
+        count(replicas.available),
+        workload.rollout().state.to_string(),
+        translate_timestamp_since(workload.meta().creation_timestamp.as_ref()),
+    ];
+
+    if insert_ns {
+        row.insert(0, namespace.clone());
+    }
+
+    KubeTableRow {
+        namespace,
+        name,
+        metadata: Some(BTreeMap::from([("kind".to_string(), K::KIND.to_string())])),
+        row,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn deployment() -> Arc<Deployment> {
+        Arc::new(
+            serde_yaml::from_str(indoc! {"
+                metadata:
+                  name: web
+                  namespace: app
+                spec:
+                  replicas: 3
+                  selector: {}
+                  template: {}
+                status:
+                  replicas: 3
+                  readyReplicas: 3
+                  updatedReplicas: 3
+                  availableReplicas: 3
+            "})
+            .unwrap(),
+        )
+    }
+
+    fn cronjob() -> Arc<CronJob> {
+        Arc::new(
+            serde_yaml::from_str(indoc! {"
+                metadata:
+                  name: backup
+                  namespace: app
+                spec:
+                  schedule: '0 0 * * *'
+                  jobTemplate: {}
+                  suspend: true
+            "})
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn 種類ごとにレプリカ数とロールアウトの状態を表示する() {
+        let actual = WorkloadTableBuilder::new(&["app".to_string()])
+            .extend(Ok(vec![deployment()]))
+            .and_then(|builder| builder.extend(Ok(vec![cronjob()])))
+            .unwrap()
+            .build();
+
+        assert_eq!(
+            actual.header,
+            vec![
+                "KIND",
+                "NAME",
+                "DESIRED",
+                "READY",
+                "UP-TO-DATE",
+                "AVAILABLE",
+                "STATUS",
+                "AGE"
+            ]
+        );
+
+        let rows: Vec<&[String]> = actual.rows.iter().map(|row| &row.row[..7]).collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                ["Deployment", "web", "3", "3", "3", "3", "Complete"],
+                ["CronJob", "backup", "-", "-", "-", "-", "Suspended"],
+            ]
+        );
+
+        assert_eq!(
+            actual.rows[1].metadata,
+            Some(BTreeMap::from([(
+                "kind".to_string(),
+                "CronJob".to_string()
+            )]))
+        );
+    }
+
+    #[test]
+    fn 複数のnamespaceを対象にしているときnamespace列を表示する() {
+        let actual = WorkloadTableBuilder::new(&["app".to_string(), "db".to_string()])
+            .extend(Ok(vec![deployment()]))
+            .unwrap()
+            .build();
+
+        assert_eq!(actual.header[0], "NAMESPACE");
+        assert_eq!(actual.rows[0].row[0], "app");
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use k8s_openapi::{
+    api::{
+        apps::v1::{DaemonSet, Deployment, StatefulSet},
+        batch::v1::{CronJob, Job},
+    },
+    Resource as _,
+};
+
+use crate::{kube::table::KubeTable, message::Message, workers::kube::message::Kube};
+
+/// Workloadタブで扱うリソースの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkloadKind {
+    Deployment,
+    StatefulSet,
+    DaemonSet,
+    Job,
+    CronJob,
+}
+
+impl WorkloadKind {
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        match kind {
+            Deployment::KIND => Some(Self::Deployment),
+            StatefulSet::KIND => Some(Self::StatefulSet),
+            DaemonSet::KIND => Some(Self::DaemonSet),
+            Job::KIND => Some(Self::Job),
+            CronJob::KIND => Some(Self::CronJob),
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Deployment => Deployment::KIND,
+            Self::StatefulSet => StatefulSet::KIND,
+            Self::DaemonSet => DaemonSet::KIND,
+            Self::Job => Job::KIND,
+            Self::CronJob => CronJob::KIND,
+        }
+    }
+
+    /// ワークロードのPodのログを取得するためのログクエリ
+    ///
+    /// CronJobはログクエリで指定できないため、Noneを返す
+    pub fn log_query(&self, name: &str) -> Option<String> {
+        let prefix = match self {
+            Self::Deployment => "deploy",
+            Self::StatefulSet => "sts",
+            Self::DaemonSet => "ds",
+            Self::Job => "job",
+            Self::CronJob => return None,
+        };
+
+        Some(format!("{}/{}", prefix, name))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkloadRequest {
+    pub kind: WorkloadKind,
+    pub namespace: String,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub enum WorkloadResponse {
+    List(Result<KubeTable>),
+    Description(Result<Vec<String>>),
+}
+
+/// ロールアウト履歴を扱うDeployment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RolloutTarget {
+    pub namespace: String,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub enum RolloutRequest {
+    /// リビジョンの一覧
+    History(RolloutTarget),
+    /// fromのリビジョンからtoのリビジョンへのPodテンプレートの差分
+    Diff {
+        target: RolloutTarget,
+        from: i64,
+        to: i64,
+    },
+    /// 指定したリビジョンにロールバックする
+    Undo {
+        target: RolloutTarget,
+        revision: i64,
+    },
+}
+
+#[derive(Debug)]
+pub enum RolloutResponse {
+    History(Result<KubeTable>),
+    Diff(Result<Vec<String>>),
+    /// ロールバックの結果を表すメッセージ
+    Undo(Result<String>),
+}
+
+/// Jobの履歴を表示するCronJob
+#[derive(Debug, Clone)]
+pub struct JobHistoryRequest {
+    pub namespace: String,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub enum WorkloadMessage {
+    Request(WorkloadRequest),
+    Response(WorkloadResponse),
+    RolloutRequest(RolloutRequest),
+    RolloutResponse(RolloutResponse),
+    JobHistoryRequest(JobHistoryRequest),
+    JobHistoryResponse(Result<KubeTable>),
+}
+
+impl From<WorkloadMessage> for Kube {
+    fn from(m: WorkloadMessage) -> Self {
+        Self::Workload(m)
+    }
+}
+
+impl From<WorkloadMessage> for Message {
+    fn from(m: WorkloadMessage) -> Self {
+        Self::Kube(m.into())
+    }
+}
+
+impl From<WorkloadRequest> for Message {
+    fn from(req: WorkloadRequest) -> Self {
+        WorkloadMessage::Request(req).into()
+    }
+}
+
+impl From<WorkloadResponse> for Message {
+    fn from(res: WorkloadResponse) -> Self {
+        WorkloadMessage::Response(res).into()
+    }
+}
+
+impl From<RolloutRequest> for Message {
+    fn from(req: RolloutRequest) -> Self {
+        WorkloadMessage::RolloutRequest(req).into()
+    }
+}
+
+impl From<RolloutResponse> for Message {
+    fn from(res: RolloutResponse) -> Self {
+        WorkloadMessage::RolloutResponse(res).into()
+    }
+}
+
+impl From<JobHistoryRequest> for Message {
+    fn from(req: JobHistoryRequest) -> Self {
+        WorkloadMessage::JobHistoryRequest(req).into()
+    }
+}
@@ -0,0 +1,8 @@
+mod job_history;
+mod rollout;
+mod tab;
+mod widgets;
+
+pub use job_history::*;
+pub use rollout::*;
+pub use tab::*;
@@ -0,0 +1,120 @@
+use crossbeam::channel::Sender;
+use k8s_openapi::{api::batch::v1::CronJob, Resource as _};
+
+use crate::{
+    config::theme::WidgetThemeConfig,
+    features::{
+        component_id::{WORKLOAD_JOB_HISTORY_DIALOG_ID, WORKLOAD_WIDGET_ID},
+        workload::message::{JobHistoryRequest, WorkloadKind},
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            FilterForm, FilterFormTheme, SelectedItem, Table, TableItem, TableTheme, Widget,
+            WidgetBase, WidgetTheme, WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+use super::widgets::open_logs_in_pod_tab;
+
+/// CronJobが作成したJobの一覧を表示するダイアログ
+pub struct JobHistoryDialog {
+    pub widget: Widget<'static>,
+    tx: Sender<Message>,
+}
+
+impl JobHistoryDialog {
+    pub fn new(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Self {
+        Self {
+            widget: history_widget(tx.clone(), theme),
+            tx: tx.clone(),
+        }
+    }
+
+    /// Workloadテーブルで選択しているCronJobのJobの一覧を開く
+    pub fn open(&self) -> impl Fn(&mut Window) -> EventResult {
+        let tx = self.tx.clone();
+
+        move |w: &mut Window| {
+            let widget = w.find_widget(WORKLOAD_WIDGET_ID);
+
+            let Some(SelectedItem::TableRow { metadata, .. }) = widget.widget_item() else {
+                return EventResult::Ignore;
+            };
+
+            let Some(ref metadata) = metadata else {
+                return EventResult::Ignore;
+            };
+
+            let (Some(namespace), Some(name), Some(CronJob::KIND)) = (
+                metadata.get("namespace"),
+                metadata.get("name"),
+                metadata.get("kind").map(String::as_str),
+            ) else {
+                return EventResult::Ignore;
+            };
+
+            w.widget_clear(WORKLOAD_JOB_HISTORY_DIALOG_ID);
+
+            *w.find_widget_mut(WORKLOAD_JOB_HISTORY_DIALOG_ID)
+                .widget_base_mut()
+                .append_title_mut() = Some(format!(" : cronjob/{}", name).into());
+
+            tx.send(
+                JobHistoryRequest {
+                    namespace: namespace.to_string(),
+                    name: name.to_string(),
+                }
+                .into(),
+            )
+            .expect("Failed to send JobHistoryRequest");
+
+            w.open_dialog(WORKLOAD_JOB_HISTORY_DIALOG_ID);
+
+            EventResult::Nop
+        }
+    }
+}
+
+fn history_widget(tx: Sender<Message>, theme: WidgetThemeConfig) -> Widget<'static> {
+    let widget_base = WidgetBase::builder()
+        .title("Job History")
+        .theme(WidgetTheme::from(theme.clone()))
+        .build();
+
+    let filter_form = FilterForm::builder()
+        .theme(FilterFormTheme::from(theme.clone()))
+        .build();
+
+    Table::builder()
+        .id(WORKLOAD_JOB_HISTORY_DIALOG_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .theme(TableTheme::from(theme))
+        .filtered_key("NAME")
+        .on_select(move |w: &mut Window, v: &TableItem| {
+            let Some(ref metadata) = v.metadata else {
+                return EventResult::Ignore;
+            };
+
+            let (Some(namespace), Some(query)) = (
+                metadata.get("namespace"),
+                metadata
+                    .get("name")
+                    .and_then(|name| WorkloadKind::Job.log_query(name)),
+            ) else {
+                return EventResult::Ignore;
+            };
+
+            w.close_dialog();
+
+            open_logs_in_pod_tab(w, &tx, namespace, query);
+
+            EventResult::Nop
+        })
+        .build()
+        .into()
+}
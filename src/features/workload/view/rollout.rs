@@ -0,0 +1,343 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crossbeam::channel::Sender;
+use k8s_openapi::{api::apps::v1::Deployment, Resource as _};
+
+use crate::{
+    config::theme::WidgetThemeConfig,
+    features::{
+        component_id::{
+            WORKLOAD_ROLLOUT_CONFIRM_DIALOG_ID, WORKLOAD_ROLLOUT_HISTORY_DIALOG_ID,
+            WORKLOAD_ROLLOUT_MENU_DIALOG_ID, WORKLOAD_ROLLOUT_RESULT_DIALOG_ID, WORKLOAD_WIDGET_ID,
+        },
+        workload::message::{RolloutRequest, RolloutTarget},
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            single_select::{
+                FilterForm, FilterFormTheme, SelectForm, SelectFormTheme, SingleSelectTheme,
+            },
+            FilterForm as TableFilterForm, FilterFormTheme as TableFilterFormTheme, Item,
+            LiteralItem, SelectedItem, SingleSelect, Table, TableItem, TableTheme, Text, TextTheme,
+            Widget, WidgetBase, WidgetTheme, WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+const CONFIRM_ROLLBACK: &str = "Rollback";
+const CONFIRM_CANCEL: &str = "Cancel";
+
+/// ロールアウト履歴を開いたDeploymentと、履歴で選択したリビジョン
+#[derive(Debug, Default)]
+struct RolloutSelection {
+    target: RolloutTarget,
+    revision: i64,
+}
+
+/// Workloadテーブルで選択したDeploymentを、リビジョンのメニューや確認ダイアログと共有する
+type SharedRolloutSelection = Rc<RefCell<RolloutSelection>>;
+
+/// Deploymentのロールアウト履歴を表示し、差分の確認やロールバックを行うダイアログ群
+pub struct RolloutDialogs {
+    pub history_dialog: Widget<'static>,
+    pub menu_dialog: Widget<'static>,
+    pub confirm_dialog: Widget<'static>,
+    pub result_dialog: Widget<'static>,
+    tx: Sender<Message>,
+    selection: SharedRolloutSelection,
+}
+
+impl RolloutDialogs {
+    pub fn new(tx: &Sender<Message>, theme: WidgetThemeConfig) -> Self {
+        let selection = SharedRolloutSelection::default();
+
+        Self {
+            history_dialog: history_widget(selection.clone(), theme.clone()),
+            menu_dialog: menu_widget(tx.clone(), selection.clone(), theme.clone()),
+            confirm_dialog: confirm_widget(tx.clone(), selection.clone(), theme.clone()),
+            result_dialog: result_widget(theme),
+            tx: tx.clone(),
+            selection,
+        }
+    }
+
+    /// Workloadテーブルで選択しているDeploymentのロールアウト履歴を開く
+    pub fn open_history(&self) -> impl Fn(&mut Window) -> EventResult {
+        let tx = self.tx.clone();
+        let selection = self.selection.clone();
+
+        move |w: &mut Window| {
+            let widget = w.find_widget(WORKLOAD_WIDGET_ID);
+
+            let Some(SelectedItem::TableRow { metadata, .. }) = widget.widget_item() else {
+                return EventResult::Ignore;
+            };
+
+            let Some(ref metadata) = metadata else {
+                return EventResult::Ignore;
+            };
+
+            let (Some(namespace), Some(name), Some(Deployment::KIND)) = (
+                metadata.get("namespace"),
+                metadata.get("name"),
+                metadata.get("kind").map(String::as_str),
+            ) else {
+                return EventResult::Ignore;
+            };
+
+            let target = RolloutTarget {
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+            };
+
+            selection.borrow_mut().target = target.clone();
+
+            w.widget_clear(WORKLOAD_ROLLOUT_HISTORY_DIALOG_ID);
+
+            *w.find_widget_mut(WORKLOAD_ROLLOUT_HISTORY_DIALOG_ID)
+                .widget_base_mut()
+                .append_title_mut() = Some(format!(" : deployment/{}", name).into());
+
+            tx.send(RolloutRequest::History(target).into())
+                .expect("Failed to send RolloutRequest::History");
+
+            w.open_dialog(WORKLOAD_ROLLOUT_HISTORY_DIALOG_ID);
+
+            EventResult::Nop
+        }
+    }
+}
+
+fn revision(metadata: &Option<BTreeMap<String, String>>) -> Option<i64> {
+    metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("revision"))
+        .and_then(|revision| revision.parse().ok())
+}
+
+fn history_widget(selection: SharedRolloutSelection, theme: WidgetThemeConfig) -> Widget<'static> {
+    let widget_base = WidgetBase::builder()
+        .title("Rollout History")
+        .theme(WidgetTheme::from(theme.clone()))
+        .build();
+
+    let filter_form = TableFilterForm::builder()
+        .theme(TableFilterFormTheme::from(theme.clone()))
+        .build();
+
+    Table::builder()
+        .id(WORKLOAD_ROLLOUT_HISTORY_DIALOG_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .theme(TableTheme::from(theme))
+        .filtered_key("REVISION")
+        .on_select(move |w: &mut Window, v: &TableItem| {
+            let Some(selected) = revision(&v.metadata) else {
+                return EventResult::Ignore;
+            };
+
+            let others: Vec<i64> = w
+                .find_widget(WORKLOAD_ROLLOUT_HISTORY_DIALOG_ID)
+                .as_table()
+                .items()
+                .iter()
+                .filter_map(|item| revision(&item.metadata))
+                .filter(|revision| *revision != selected)
+                .collect();
+
+            // 他のリビジョンからの差分を先に並べ、ロールバックは最後に置く
+            let mut items: Vec<LiteralItem> = others
+                .into_iter()
+                .map(|from| {
+                    LiteralItem::new(
+                        format!("Diff revision {} -> {}", from, selected),
+                        Some(BTreeMap::from([("from".into(), from.to_string())])),
+                    )
+                })
+                .collect();
+
+            items.push(LiteralItem::new(
+                format!("Rollback to revision {}", selected),
+                None,
+            ));
+
+            let target = {
+                let mut selection = selection.borrow_mut();
+
+                selection.revision = selected;
+
+                selection.target.clone()
+            };
+
+            let menu = w.find_widget_mut(WORKLOAD_ROLLOUT_MENU_DIALOG_ID);
+
+            *menu.widget_base_mut().append_title_mut() =
+                Some(format!(" : deployment/{} revision {}", target.name, selected).into());
+
+            menu.update_widget_item(Item::Array(items));
+
+            w.open_dialog(WORKLOAD_ROLLOUT_MENU_DIALOG_ID);
+
+            EventResult::Nop
+        })
+        .build()
+        .into()
+}
+
+fn single_select_widget(
+    id: &str,
+    title: &str,
+    theme: WidgetThemeConfig,
+    on_select: impl Fn(&mut Window, &LiteralItem) -> EventResult + 'static,
+) -> Widget<'static> {
+    let single_select_theme = SingleSelectTheme::default().status_style(theme.list.status);
+
+    let filter_form = FilterForm::builder()
+        .theme(FilterFormTheme::from(theme.clone()))
+        .build();
+
+    let select_form = SelectForm::builder()
+        .theme(SelectFormTheme::from(theme.clone()))
+        .on_select(on_select)
+        .build();
+
+    let widget_base = WidgetBase::builder()
+        .title(title)
+        .theme(WidgetTheme::from(theme))
+        .build();
+
+    SingleSelect::builder()
+        .id(id)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .select_form(select_form)
+        .theme(single_select_theme)
+        .build()
+        .into()
+}
+
+fn menu_widget(
+    tx: Sender<Message>,
+    selection: SharedRolloutSelection,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    single_select_widget(
+        WORKLOAD_ROLLOUT_MENU_DIALOG_ID,
+        "Revision",
+        theme,
+        move |w: &mut Window, v: &LiteralItem| {
+            let RolloutSelection { target, revision } = &*selection.borrow();
+
+            // 差分以外の項目はロールバック
+            let Some(from) = v
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get("from"))
+                .and_then(|from| from.parse().ok())
+            else {
+                let confirm = w.find_widget_mut(WORKLOAD_ROLLOUT_CONFIRM_DIALOG_ID);
+
+                *confirm.widget_base_mut().append_title_mut() =
+                    Some(format!(" : deployment/{} to revision {}", target.name, revision).into());
+
+                confirm.update_widget_item(Item::Array(vec![
+                    CONFIRM_CANCEL.into(),
+                    CONFIRM_ROLLBACK.into(),
+                ]));
+
+                w.open_dialog(WORKLOAD_ROLLOUT_CONFIRM_DIALOG_ID);
+
+                return EventResult::Nop;
+            };
+
+            w.widget_clear(WORKLOAD_ROLLOUT_RESULT_DIALOG_ID);
+
+            *w.find_widget_mut(WORKLOAD_ROLLOUT_RESULT_DIALOG_ID)
+                .widget_base_mut()
+                .append_title_mut() = Some(
+                format!(
+                    " : deployment/{} revision {} -> {}",
+                    target.name, from, revision
+                )
+                .into(),
+            );
+
+            tx.send(
+                RolloutRequest::Diff {
+                    target: target.clone(),
+                    from,
+                    to: *revision,
+                }
+                .into(),
+            )
+            .expect("Failed to send RolloutRequest::Diff");
+
+            w.open_dialog(WORKLOAD_ROLLOUT_RESULT_DIALOG_ID);
+
+            EventResult::Nop
+        },
+    )
+}
+
+fn confirm_widget(
+    tx: Sender<Message>,
+    selection: SharedRolloutSelection,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    single_select_widget(
+        WORKLOAD_ROLLOUT_CONFIRM_DIALOG_ID,
+        "Rollback?",
+        theme,
+        move |w: &mut Window, v: &LiteralItem| {
+            if v.item != CONFIRM_ROLLBACK {
+                w.close_dialog();
+
+                return EventResult::Nop;
+            }
+
+            let RolloutSelection { target, revision } = &*selection.borrow();
+
+            w.widget_clear(WORKLOAD_ROLLOUT_RESULT_DIALOG_ID);
+
+            *w.find_widget_mut(WORKLOAD_ROLLOUT_RESULT_DIALOG_ID)
+                .widget_base_mut()
+                .append_title_mut() = Some(
+                format!(
+                    " : rollback deployment/{} to revision {}",
+                    target.name, revision
+                )
+                .into(),
+            );
+
+            tx.send(
+                RolloutRequest::Undo {
+                    target: target.clone(),
+                    revision: *revision,
+                }
+                .into(),
+            )
+            .expect("Failed to send RolloutRequest::Undo");
+
+            w.open_dialog(WORKLOAD_ROLLOUT_RESULT_DIALOG_ID);
+
+            EventResult::Nop
+        },
+    )
+}
+
+fn result_widget(theme: WidgetThemeConfig) -> Widget<'static> {
+    let widget_base = WidgetBase::builder()
+        .title("Rollout")
+        .theme(WidgetTheme::from(theme.clone()))
+        .build();
+
+    Text::builder()
+        .id(WORKLOAD_ROLLOUT_RESULT_DIALOG_ID)
+        .widget_base(widget_base)
+        .theme(TextTheme::from(theme))
+        .build()
+        .into()
+}
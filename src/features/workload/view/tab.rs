@@ -0,0 +1,72 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossbeam::channel::Sender;
+use ratatui::layout::{Constraint, Direction};
+
+use crate::{
+    clipboard::Clipboard,
+    config::{keybindings::WorkloadKeyBindings, theme::WidgetThemeConfig},
+    features::{
+        component_id::WORKLOAD_TAB_ID,
+        workload::view::{
+            widgets::{description_widget, workload_widget},
+            JobHistoryDialog, RolloutDialogs,
+        },
+    },
+    message::Message,
+    ui::{
+        tab::{LayoutElement, NestedLayoutElement, NestedWidgetLayout, TabLayout},
+        Tab,
+    },
+};
+
+pub struct WorkloadTab {
+    pub tab: Tab<'static>,
+    pub rollout_dialogs: RolloutDialogs,
+    pub job_history_dialog: JobHistoryDialog,
+}
+
+impl WorkloadTab {
+    pub fn new(
+        title: &'static str,
+        tx: &Sender<Message>,
+        clipboard: &Option<Rc<RefCell<Clipboard>>>,
+        split_direction: Direction,
+        keybindings: WorkloadKeyBindings,
+        theme: WidgetThemeConfig,
+    ) -> Self {
+        let rollout_dialogs = RolloutDialogs::new(tx, theme.clone());
+        let job_history_dialog = JobHistoryDialog::new(tx, theme.clone());
+
+        let workload_widget = workload_widget(
+            tx,
+            keybindings,
+            &rollout_dialogs,
+            &job_history_dialog,
+            theme.clone(),
+        );
+        let description_widget = description_widget(clipboard, theme);
+
+        let layout = TabLayout::new(layout, split_direction);
+
+        WorkloadTab {
+            tab: Tab::new(
+                WORKLOAD_TAB_ID,
+                title,
+                [workload_widget, description_widget],
+                layout,
+            ),
+            rollout_dialogs,
+            job_history_dialog,
+        }
+    }
+}
+
+fn layout(split_direction: Direction) -> NestedWidgetLayout {
+    NestedWidgetLayout::default()
+        .direction(split_direction)
+        .nested_widget_layout([
+            NestedLayoutElement(Constraint::Percentage(50), LayoutElement::WidgetIndex(0)),
+            NestedLayoutElement(Constraint::Percentage(50), LayoutElement::WidgetIndex(1)),
+        ])
+}
@@ -0,0 +1,5 @@
+mod description;
+mod workload;
+
+pub(super) use description::*;
+pub(super) use workload::*;
@@ -0,0 +1,166 @@
+use crossbeam::channel::Sender;
+
+use crate::{
+    config::{keybindings::WorkloadKeyBindings, theme::WidgetThemeConfig},
+    features::{
+        component_id::{
+            POD_LOG_QUERY_WIDGET_ID, POD_LOG_WIDGET_ID, POD_TAB_ID, WORKLOAD_DESCRIPTION_WIDGET_ID,
+            WORKLOAD_WIDGET_ID,
+        },
+        pod::{
+            kube::{LogConfig, LogPrefixType},
+            message::LogMessage,
+        },
+        workload::{
+            message::{WorkloadKind, WorkloadRequest},
+            view::{JobHistoryDialog, RolloutDialogs},
+        },
+    },
+    kube::context::Namespace,
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            FilterForm, FilterFormTheme, Item, SelectedItem, Table, TableItem, TableTheme, Widget,
+            WidgetBase, WidgetTheme, WidgetTrait as _,
+        },
+        Window, WindowAction,
+    },
+};
+
+pub fn workload_widget(
+    tx: &Sender<Message>,
+    keybindings: WorkloadKeyBindings,
+    rollout: &RolloutDialogs,
+    job_history: &JobHistoryDialog,
+    theme: WidgetThemeConfig,
+) -> Widget<'static> {
+    let tx = tx.clone();
+
+    let widget_theme = WidgetTheme::from(theme.clone());
+    let filter_theme = FilterFormTheme::from(theme.clone());
+    let table_theme = TableTheme::from(theme.clone());
+
+    let widget_base = WidgetBase::builder()
+        .title("Workload")
+        .theme(widget_theme)
+        .build();
+
+    let filter_form = FilterForm::builder().theme(filter_theme).build();
+
+    Table::builder()
+        .id(WORKLOAD_WIDGET_ID)
+        .widget_base(widget_base)
+        .filter_form(filter_form)
+        .theme(table_theme)
+        .filtered_key("NAME")
+        .block_injection(block_injection())
+        .on_select(on_select(tx.clone()))
+        .key_action(&keybindings.show_logs, show_logs(tx))
+        .key_action(&keybindings.rollout_history, rollout.open_history())
+        .key_action(&keybindings.job_history, job_history.open())
+        .build()
+        .into()
+}
+
+fn block_injection() -> impl Fn(&Table) -> WidgetBase {
+    |table: &Table| {
+        let index = if let Some(index) = table.state().selected() {
+            index + 1
+        } else {
+            0
+        };
+
+        let mut base = table.widget_base().clone();
+
+        *base.append_title_mut() = Some(format!(" [{}/{}]", index, table.items().len()).into());
+
+        base
+    }
+}
+
+fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &TableItem) -> EventResult {
+    move |w: &mut Window, v: &TableItem| {
+        w.widget_clear(WORKLOAD_DESCRIPTION_WIDGET_ID);
+
+        let Some(ref metadata) = v.metadata else {
+            return EventResult::Ignore;
+        };
+
+        let (Some(namespace), Some(name), Some(kind)) = (
+            metadata.get("namespace"),
+            metadata.get("name"),
+            metadata
+                .get("kind")
+                .and_then(|kind| WorkloadKind::from_kind(kind)),
+        ) else {
+            return EventResult::Ignore;
+        };
+
+        *(w.find_widget_mut(WORKLOAD_DESCRIPTION_WIDGET_ID)
+            .widget_base_mut()
+            .append_title_mut()) = Some((format!(" : {}/{}", kind.kind(), name)).into());
+
+        tx.send(
+            WorkloadRequest {
+                kind,
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+            }
+            .into(),
+        )
+        .expect("Failed to send WorkloadRequest");
+
+        EventResult::WindowAction(WindowAction::Continue)
+    }
+}
+
+/// 選択しているワークロードのPodのログを、ログクエリを使ってPodタブに表示する
+fn show_logs(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let widget = w.find_widget(WORKLOAD_WIDGET_ID);
+
+        let Some(SelectedItem::TableRow { metadata, .. }) = widget.widget_item() else {
+            return EventResult::Ignore;
+        };
+
+        let Some(ref metadata) = metadata else {
+            return EventResult::Ignore;
+        };
+
+        let (Some(namespace), Some(query)) = (
+            metadata.get("namespace"),
+            metadata
+                .get("kind")
+                .and_then(|kind| WorkloadKind::from_kind(kind))
+                .zip(metadata.get("name"))
+                .and_then(|(kind, name)| kind.log_query(name)),
+        ) else {
+            return EventResult::Ignore;
+        };
+
+        open_logs_in_pod_tab(w, &tx, namespace, query);
+
+        EventResult::Nop
+    }
+}
+
+/// ログクエリをPodタブに入力してログの取得を開始し、Podタブに切り替える
+pub fn open_logs_in_pod_tab(w: &mut Window, tx: &Sender<Message>, namespace: &str, query: String) {
+    w.widget_clear(POD_LOG_WIDGET_ID);
+
+    w.find_widget_mut(POD_LOG_QUERY_WIDGET_ID)
+        .update_widget_item(Item::Single(query.clone().into()));
+
+    let config = LogConfig::new(
+        query,
+        Namespace(vec![namespace.to_string()]),
+        LogPrefixType::PodAndContainer,
+        false,
+    );
+
+    tx.send(LogMessage::Request(config).into())
+        .expect("Failed to send LogMessage::Request");
+
+    w.activate_tab_by_id(POD_TAB_ID);
+}
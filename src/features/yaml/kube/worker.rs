@@ -10,7 +10,7 @@ use crate::{
     kube::KubeClientRequest,
     logger,
     message::Message,
-    workers::kube::AbortWorker,
+    workers::kube::{mask::mask_secret_values, AbortWorker},
 };
 
 #[derive(Debug, Clone)]
@@ -29,6 +29,7 @@ where
     client: C,
     req: YamlTarget,
     shared_api_resources: SharedApiResources,
+    mask_secrets: bool,
 }
 
 impl<C: KubeClientRequest> YamlWorker<C> {
@@ -43,8 +44,15 @@ impl<C: KubeClientRequest> YamlWorker<C> {
             client,
             req,
             shared_api_resources,
+            mask_secrets: false,
         }
     }
+
+    /// Secretの値をマスクするか
+    pub fn mask_secrets(mut self, mask_secrets: bool) -> Self {
+        self.mask_secrets = mask_secrets;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -69,6 +77,7 @@ impl<C: KubeClientRequest> AbortWorker for YamlWorker<C> {
                 kind,
                 name.to_string(),
                 namespace.to_string(),
+                self.mask_secrets,
             )
             .await;
 
@@ -86,6 +95,7 @@ async fn fetch_resource_yaml<C: KubeClientRequest>(
     kind: &ApiResource,
     name: String,
     ns: String,
+    mask_secrets: bool,
 ) -> Result<Vec<String>> {
     logger!(
         info,
@@ -99,6 +109,41 @@ async fn fetch_resource_yaml<C: KubeClientRequest>(
         .iter()
         .find(|api| *api == kind)
         .ok_or_else(|| anyhow!("Can't get {} from API resource", kind))?;
+
+    fetch_api_resource_yaml(client, api, &name, &ns, mask_secrets).await
+}
+
+/// 指定したAPIリソースのyamlを取得する
+///
+/// mask_secretsがtrueの場合は、Secretの値をマスクする
+pub async fn fetch_api_resource_yaml<C: KubeClientRequest>(
+    client: &C,
+    api: &ApiResource,
+    name: &str,
+    ns: &str,
+    mask_secrets: bool,
+) -> Result<Vec<String>> {
+    let mut yaml_data = fetch_api_resource_value(client, api, name, ns).await?;
+
+    if mask_secrets {
+        mask_secret_values(&mut yaml_data);
+    }
+
+    let yaml_string = serde_yaml::to_string(&yaml_data)?
+        .lines()
+        .map(ToString::to_string)
+        .collect();
+
+    Ok(yaml_string)
+}
+
+/// 指定したAPIリソースを取得し、managedFieldsを除いた値を返す
+pub async fn fetch_api_resource_value<C: KubeClientRequest>(
+    client: &C,
+    api: &ApiResource,
+    name: &str,
+    ns: &str,
+) -> Result<Value> {
     // json string data
     let kind = api.name();
     let path = if api.is_namespaced() {
@@ -126,10 +171,79 @@ async fn fetch_resource_yaml<C: KubeClientRequest>(
         md.remove("managedFields");
     }
 
-    let yaml_string = serde_yaml::to_string(&yaml_data)?
-        .lines()
-        .map(ToString::to_string)
-        .collect();
+    Ok(yaml_data)
+}
 
-    Ok(yaml_string)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use kube::discovery::Scope;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+
+    use crate::{kube::mock::MockTestKubeClient, mock_expect};
+
+    fn secret_api() -> ApiResource {
+        ApiResource::Api {
+            name: "secrets".to_string(),
+            kind: "Secret".to_string(),
+            version: "v1".to_string(),
+            scope: Scope::Namespaced,
+        }
+    }
+
+    fn secret_json() -> anyhow::Result<String> {
+        Ok(r#"{"apiVersion":"v1","kind":"Secret","metadata":{"name":"db","namespace":"default"},"data":{"password":"c2VjcmV0"}}"#.to_string())
+    }
+
+    #[tokio::test]
+    async fn secretの値をマスクする() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request_text,
+            eq("api/v1/namespaces/default/secrets/db"),
+            secret_json()
+        );
+
+        let actual = fetch_api_resource_yaml(&client, &secret_api(), "db", "default", true)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual.join("\n"),
+            indoc! {"
+                apiVersion: v1
+                kind: Secret
+                metadata:
+                  name: db
+                  namespace: default
+                data:
+                  password: '********'"}
+        );
+    }
+
+    #[tokio::test]
+    async fn マスクしない場合はsecretの値をそのまま返す() {
+        let mut client = MockTestKubeClient::new();
+
+        mock_expect!(
+            client,
+            request_text,
+            eq("api/v1/namespaces/default/secrets/db"),
+            secret_json()
+        );
+
+        let actual = fetch_api_resource_yaml(&client, &secret_api(), "db", "default", false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual.last().map(String::as_str),
+            Some("  password: c2VjcmV0")
+        );
+    }
 }
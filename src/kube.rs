@@ -1,6 +1,8 @@
 pub mod apis;
 mod client;
 pub mod context;
+pub mod informer;
+pub mod quantity;
 pub mod table;
 
 pub use client::*;
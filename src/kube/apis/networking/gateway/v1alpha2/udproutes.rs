@@ -6,6 +6,20 @@ use kube::CustomResource;
 use serde::{Serialize, Deserialize};
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::Condition;
 
+impl k8s_openapi::Resource for UDPRoute {
+    const API_VERSION: &'static str = "gateway.networking.k8s.io/v1alpha2";
+
+    const GROUP: &'static str = "gateway.networking.k8s.io";
+
+    const KIND: &'static str = "UDPRoute";
+
+    const VERSION: &'static str = "v1alpha2";
+
+    const URL_PATH_SEGMENT: &'static str = "udproutes";
+
+    type Scope = k8s_openapi::NamespaceResourceScope;
+}
+
 #[derive(CustomResource, Serialize, Deserialize, Clone, Debug)]
 #[kube(group = "gateway.networking.k8s.io", version = "v1alpha2", kind = "UDPRoute", plural = "udproutes")]
 #[kube(namespaced)]
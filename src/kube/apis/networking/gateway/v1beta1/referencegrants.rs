@@ -5,6 +5,20 @@
 use kube::CustomResource;
 use serde::{Serialize, Deserialize};
 
+impl k8s_openapi::Resource for ReferenceGrant {
+    const API_VERSION: &'static str = "gateway.networking.k8s.io/v1beta1";
+
+    const GROUP: &'static str = "gateway.networking.k8s.io";
+
+    const KIND: &'static str = "ReferenceGrant";
+
+    const VERSION: &'static str = "v1beta1";
+
+    const URL_PATH_SEGMENT: &'static str = "referencegrants";
+
+    type Scope = k8s_openapi::NamespaceResourceScope;
+}
+
 #[derive(CustomResource, Serialize, Deserialize, Clone, Debug)]
 #[kube(group = "gateway.networking.k8s.io", version = "v1beta1", kind = "ReferenceGrant", plural = "referencegrants")]
 #[kube(namespaced)]
@@ -1,17 +1,14 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use http::header::{HeaderValue, ACCEPT};
-use k8s_openapi::NamespaceResourceScope;
 use kube::{
-    api::{GetParams, Request},
-    Api, Client, Resource,
+    api::{DeleteParams, GetParams, Patch, PatchParams, PostParams, Request},
+    Client,
 };
 use serde::de::DeserializeOwned;
 
 use crate::logger;
 
-use super::apis::v1_table::Table;
-
 const TABLE_REQUEST_HEADER: &str = "application/json;as=Table;v=v1;g=meta.k8s.io,application/json;as=Table;v=v1beta1;g=meta.k8s.io,application/json";
 
 fn remove_slash(path: &str) -> &str {
@@ -77,14 +74,19 @@ impl KubeClient {
 pub trait KubeClientRequest: Send + Sync {
     async fn table_request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T>;
 
-    async fn table_namespaced<K>(&self, namespace: &str) -> Result<Table>
-    where
-        K: Resource<DynamicType = (), Scope = NamespaceResourceScope> + 'static;
-
     async fn request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T>;
 
     async fn request_text(&self, path: &str) -> Result<String>;
 
+    /// pathで指定したリソースを削除する
+    async fn delete(&self, path: &str) -> Result<()>;
+
+    /// pathで指定したリソースにJSON Merge Patchを適用する
+    async fn merge_patch(&self, path: &str, patch: &serde_json::Value) -> Result<()>;
+
+    /// pathで指定したコレクションにリソースを作成する
+    async fn create(&self, path: &str, body: &serde_json::Value) -> Result<()>;
+
     fn client(&self) -> &Client;
 }
 
@@ -94,16 +96,6 @@ impl KubeClientRequest for KubeClient {
         self.inner_request(path, TABLE_REQUEST_HEADER).await
     }
 
-    async fn table_namespaced<K>(&self, namespace: &str) -> Result<Table>
-    where
-        K: Resource<DynamicType = (), Scope = NamespaceResourceScope> + 'static,
-    {
-        let api: Api<K> = Api::namespaced(self.client.clone(), namespace);
-
-        self.inner_request(api.resource_url(), TABLE_REQUEST_HEADER)
-            .await
-    }
-
     async fn request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T> {
         self.inner_request(path, "application/json").await
     }
@@ -124,6 +116,46 @@ impl KubeClientRequest for KubeClient {
         ret.map_err(Into::into)
     }
 
+    async fn delete(&self, path: &str) -> Result<()> {
+        let request = Request::new(&self.server_url);
+
+        let request = request.delete(remove_slash(path), &DeleteParams::default())?;
+
+        logger!(info, "HTTP request {:?}", request);
+
+        self.client.request_text(request).await?;
+
+        Ok(())
+    }
+
+    async fn merge_patch(&self, path: &str, patch: &serde_json::Value) -> Result<()> {
+        let request = Request::new(&self.server_url);
+
+        let request = request.patch(
+            remove_slash(path),
+            &PatchParams::default(),
+            &Patch::Merge(patch),
+        )?;
+
+        logger!(info, "HTTP request {:?}", request);
+
+        self.client.request_text(request).await?;
+
+        Ok(())
+    }
+
+    async fn create(&self, path: &str, body: &serde_json::Value) -> Result<()> {
+        let request = Request::new(format!("{}/{}", self.server_url, remove_slash(path)));
+
+        let request = request.create(&PostParams::default(), serde_json::to_vec(body)?)?;
+
+        logger!(info, "HTTP request {:?}", request);
+
+        self.client.request_text(request).await?;
+
+        Ok(())
+    }
+
     fn client(&self) -> &Client {
         &self.client
     }
@@ -131,9 +163,7 @@ impl KubeClientRequest for KubeClient {
 
 #[cfg(test)]
 pub mod mock {
-    use super::{DeserializeOwned, KubeClientRequest, Result, Table};
-    use k8s_openapi::NamespaceResourceScope;
-    use kube::Resource;
+    use super::{DeserializeOwned, KubeClientRequest, Result};
     use mockall::mock;
 
     mock! {
@@ -145,9 +175,11 @@ pub mod mock {
         #[async_trait::async_trait]
         impl KubeClientRequest for TestKubeClient {
             async fn table_request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T>;
-            async fn table_namespaced<K: Resource<DynamicType=(), Scope = NamespaceResourceScope> + 'static>(&self, ns: &str) -> Result<Table>;
             async fn request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T>;
             async fn request_text(&self, path: &str) -> Result<String>;
+            async fn delete(&self, path: &str) -> Result<()>;
+            async fn merge_patch(&self, path: &str, patch: &serde_json::Value) -> Result<()>;
+            async fn create(&self, path: &str, body: &serde_json::Value) -> Result<()>;
             fn client(&self) -> &kube::Client;
         }
     }
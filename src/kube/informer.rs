@@ -0,0 +1,721 @@
+use std::{
+    fmt::Debug,
+    pin::pin,
+    sync::{Arc, Mutex, OnceLock, RwLock},
+};
+
+use anyhow::{anyhow, Result};
+use futures::{stream::BoxStream, StreamExt as _};
+use k8s_openapi::{
+    api::core::v1::{Namespace, Pod, Secret},
+    NamespaceResourceScope,
+};
+use kube::{
+    core::PartialObjectMeta,
+    runtime::{
+        reflector::{self, Store},
+        watcher::{self, metadata_watcher},
+        WatchStreamExt as _,
+    },
+    Api, Client, Resource, ResourceExt as _,
+};
+use serde::de::DeserializeOwned;
+use tokio::task::AbortHandle;
+
+use crate::logger;
+
+/// Informerで扱うリソースが満たすべき制約
+pub trait InformerResource:
+    Resource<DynamicType = (), Scope = NamespaceResourceScope>
+    + Clone
+    + DeserializeOwned
+    + Debug
+    + Send
+    + Sync
+    + 'static
+{
+}
+
+impl<K> InformerResource for K where
+    K: Resource<DynamicType = (), Scope = NamespaceResourceScope>
+        + Clone
+        + DeserializeOwned
+        + Debug
+        + Send
+        + Sync
+        + 'static
+{
+}
+
+/// Reflectorで扱うリソースが満たすべき制約
+///
+/// クラスタスコープのリソースも扱うため、InformerResourceよりも制約が緩い
+pub trait ReflectorResource: Resource<DynamicType = ()> + Clone + Send + Sync + 'static {}
+
+impl<K> ReflectorResource for K where K: Resource<DynamicType = ()> + Clone + Send + Sync + 'static {}
+
+type WatchStream<K> = BoxStream<'static, watcher::Result<watcher::Event<K>>>;
+
+#[derive(Debug, Default)]
+struct ReflectorStatus {
+    /// 初回のリスト取得が完了しているか
+    ready: bool,
+    /// 直近に発生したwatchのエラー
+    /// 正常なイベントを受け取った時点でクリアする
+    error: Option<String>,
+}
+
+/// watchを行い、結果をローカルのストアに反映する
+struct Reflector<K: ReflectorResource> {
+    /// watchの対象とするnamespace
+    /// Noneのときはすべてのnamespaceを対象にする
+    namespace: Option<String>,
+    store: Store<K>,
+    status: Arc<RwLock<ReflectorStatus>>,
+    handle: AbortHandle,
+}
+
+impl<K: ReflectorResource> Reflector<K> {
+    fn spawn(namespace: Option<String>, stream: WatchStream<K>) -> Self {
+        let (store, writer) = reflector::store();
+
+        let status = Arc::new(RwLock::new(ReflectorStatus::default()));
+
+        let stream = reflector::reflector(writer, stream).default_backoff();
+
+        let task_status = status.clone();
+        let task_scope = scope(namespace.as_deref()).to_string();
+
+        let handle = tokio::spawn(async move {
+            let mut stream = pin!(stream);
+
+            while let Some(event) = stream.next().await {
+                let mut status = task_status
+                    .write()
+                    .expect("Failed to write reflector status");
+
+                match event {
+                    Ok(event) => {
+                        if let watcher::Event::InitDone = event {
+                            status.ready = true;
+                        }
+
+                        status.error = None;
+                    }
+                    Err(e) => {
+                        logger!(
+                            error,
+                            "Failed to watch {}: namespace={} {}",
+                            K::kind(&()),
+                            task_scope,
+                            e
+                        );
+
+                        status.error = Some(e.to_string());
+
+                        // 権限がない場合は再試行しても成功しないため、エラーを残してwatchを終了する
+                        if is_forbidden(&e) {
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+        .abort_handle();
+
+        Self {
+            namespace,
+            store,
+            status,
+            handle,
+        }
+    }
+}
+
+impl<K: ReflectorResource> Reflector<K> {
+    /// watchの状態を返す
+    ///   * watchでエラーが発生している場合はErrを返す
+    ///   * 初回のリスト取得が完了していない場合はNoneを返す
+    fn check(&self) -> Option<Result<()>> {
+        let status = self.status.read().expect("Failed to read reflector status");
+
+        if let Some(error) = &status.error {
+            return Some(Err(anyhow!(
+                "Failed to watch {}: namespace={} {}",
+                K::kind(&()),
+                scope(self.namespace.as_deref()),
+                error
+            )));
+        }
+
+        status.ready.then_some(Ok(()))
+    }
+}
+
+impl<K: ReflectorResource> Drop for Reflector<K> {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// 権限がないためにwatchが失敗したか
+fn is_forbidden(e: &watcher::Error) -> bool {
+    match e {
+        watcher::Error::InitialListFailed(kube::Error::Api(response))
+        | watcher::Error::WatchStartFailed(kube::Error::Api(response))
+        | watcher::Error::WatchFailed(kube::Error::Api(response))
+        | watcher::Error::WatchError(response) => response.code == 403,
+        _ => false,
+    }
+}
+
+/// ログに出力するwatchの対象
+fn scope(namespace: Option<&str>) -> &str {
+    namespace.unwrap_or("*")
+}
+
+fn api<K: InformerResource>(client: Client, namespace: Option<&str>) -> Api<K> {
+    match namespace {
+        Some(ns) => Api::namespaced(client, ns),
+        None => Api::all(client),
+    }
+}
+
+/// watchを開始する関数
+/// namespaceがNoneのときはすべてのnamespaceを対象にする
+type WatchFn<K> = fn(Client, Option<&str>, watcher::Config) -> WatchStream<K>;
+
+/// リソース全体をwatchする
+fn watch_object<K: InformerResource>(
+    client: Client,
+    namespace: Option<&str>,
+    config: watcher::Config,
+) -> WatchStream<K> {
+    watcher::watcher(api(client, namespace), config).boxed()
+}
+
+/// メタデータのみをwatchする
+fn watch_metadata<K: InformerResource>(
+    client: Client,
+    namespace: Option<&str>,
+    config: watcher::Config,
+) -> WatchStream<PartialObjectMeta<K>> {
+    metadata_watcher(api::<K>(client, namespace), config).boxed()
+}
+
+/// クラスタ内のnamespaceの一覧
+///
+/// 監視対象がすべてのnamespaceを含むかを判定するために使うため、メタデータのみをwatchする。
+/// すべてのnamespaceを対象に起動した場合（-A）のみwatchし、それ以外はnamespaceごとにwatchする
+#[derive(Clone)]
+struct ClusterNamespaces {
+    /// Noneのときはwatchせず、常にnamespaceごとにwatchする
+    reflector: Option<Arc<Reflector<PartialObjectMeta<Namespace>>>>,
+}
+
+impl ClusterNamespaces {
+    fn spawn(client: Client) -> Self {
+        let stream =
+            metadata_watcher(Api::<Namespace>::all(client), watcher::Config::default()).boxed();
+
+        Self {
+            reflector: Some(Arc::new(Reflector::spawn(None, stream))),
+        }
+    }
+
+    fn disabled() -> Self {
+        Self { reflector: None }
+    }
+
+    /// 指定したnamespaceがクラスタ内のすべてのnamespaceを含むかを返す
+    ///   * 初回のリスト取得が完了していない場合はNoneを返す
+    ///   * namespaceの一覧を取得できない場合や、watchしていない場合はfalseを返す
+    fn is_covered_by(&self, namespaces: &[String]) -> Option<bool> {
+        let Some(reflector) = &self.reflector else {
+            return Some(false);
+        };
+
+        let status = reflector
+            .status
+            .read()
+            .expect("Failed to read reflector status");
+
+        if !status.ready {
+            return status.error.as_ref().map(|_| false);
+        }
+
+        let cluster_namespaces: Vec<String> = reflector
+            .store
+            .state()
+            .iter()
+            .map(|ns| ns.name_any())
+            .collect();
+
+        Some(covers_all(&cluster_namespaces, namespaces))
+    }
+}
+
+fn covers_all(cluster_namespaces: &[String], namespaces: &[String]) -> bool {
+    !cluster_namespaces.is_empty() && cluster_namespaces.iter().all(|ns| namespaces.contains(ns))
+}
+
+/// 監視対象のnamespaceのリソースのみを残し、namespaceの指定順、namespace内では名前順に並べる
+fn sort_by_namespaces<K: Resource>(resources: Vec<Arc<K>>, namespaces: &[String]) -> Vec<Arc<K>> {
+    let mut resources: Vec<(usize, String, Arc<K>)> = resources
+        .into_iter()
+        .filter_map(|resource| {
+            let order = namespaces
+                .iter()
+                .position(|ns| resource.meta().namespace.as_ref() == Some(ns))?;
+
+            Some((order, resource.name_any(), resource))
+        })
+        .collect();
+
+    resources.sort_by(|(a_order, a_name, _), (b_order, b_name, _)| {
+        (a_order, a_name).cmp(&(b_order, b_name))
+    });
+
+    resources
+        .into_iter()
+        .map(|(_, _, resource)| resource)
+        .collect()
+}
+
+/// 監視対象のnamespaceのリソースの一覧をローカルのキャッシュとして保持する
+///
+/// 一定間隔でリストを取得し直す代わりに、変更があったリソースのみを受け取って
+/// キャッシュを更新する。
+/// 監視対象がクラスタ内のすべてのnamespaceを含む場合は、namespaceごとではなく
+/// 1つのwatchですべてのnamespaceを監視する。
+pub struct NamespacedInformer<K: InformerResource> {
+    client: Client,
+    watch: WatchFn<K>,
+    cluster_namespaces: ClusterNamespaces,
+    /// 監視対象のnamespace
+    /// Noneのときはまだwatchを開始していない
+    namespaces: Option<Vec<String>>,
+    /// すべてのnamespaceを監視するときに使う、他のPollerと共有しているInformer
+    /// Noneのときは必要になったときにwatchを開始する
+    cluster_informer: Option<LazyClusterInformer<K>>,
+    reflectors: Vec<Arc<Reflector<K>>>,
+}
+
+impl<K: InformerResource> NamespacedInformer<K> {
+    fn new(client: Client, cluster_namespaces: ClusterNamespaces, watch: WatchFn<K>) -> Self {
+        Self {
+            client,
+            watch,
+            cluster_namespaces,
+            namespaces: None,
+            cluster_informer: None,
+            reflectors: Vec::new(),
+        }
+    }
+
+    /// すべてのnamespaceを監視するときは、新たにwatchを開始せずに指定したInformerのキャッシュを使う
+    fn cluster_informer(mut self, informer: &LazyClusterInformer<K>) -> Self {
+        self.cluster_informer = Some(informer.clone());
+        self
+    }
+
+    /// 監視対象のnamespaceを更新する
+    ///   * 引き続き対象となるnamespaceのwatchはそのまま維持する
+    ///   * 新たに対象となったnamespaceのwatchを開始する
+    ///   * 対象外となったnamespaceのwatchを停止する
+    ///   * クラスタ内のnamespaceの一覧を取得するまではwatchを開始しない
+    pub fn sync_namespaces(&mut self, namespaces: &[String]) {
+        let Some(is_all) = self.cluster_namespaces.is_covered_by(namespaces) else {
+            return;
+        };
+
+        let scopes: Vec<Option<String>> = if is_all {
+            vec![None]
+        } else {
+            namespaces.iter().cloned().map(Some).collect()
+        };
+
+        let mut current = std::mem::take(&mut self.reflectors);
+
+        self.reflectors = scopes
+            .into_iter()
+            .map(|ns| {
+                if let Some(index) = current.iter().position(|r| r.namespace == ns) {
+                    current.remove(index)
+                } else if let (None, Some(informer)) = (&ns, &self.cluster_informer) {
+                    informer.get().reflector
+                } else {
+                    let stream = (self.watch)(
+                        self.client.clone(),
+                        ns.as_deref(),
+                        watcher::Config::default(),
+                    );
+
+                    Arc::new(Reflector::spawn(ns, stream))
+                }
+            })
+            .collect();
+
+        self.namespaces = Some(namespaces.to_vec());
+    }
+
+    /// 1つのwatchですべてのnamespaceを監視しているか
+    pub fn covers_all_namespaces(&self) -> bool {
+        self.reflectors.iter().any(|r| r.namespace.is_none())
+    }
+
+    /// キャッシュしているリソースの一覧を返す
+    ///   * 並び順はnamespaceの指定順、namespace内では名前順
+    ///   * watchでエラーが発生している場合はErrを返す
+    ///   * 初回のリスト取得が完了していない場合はNoneを返す
+    pub fn state(&self) -> Option<Result<Vec<Arc<K>>>> {
+        let namespaces = self.namespaces.as_ref()?;
+
+        let mut is_ready = true;
+
+        for reflector in &self.reflectors {
+            match reflector.check() {
+                Some(Ok(())) => {}
+                Some(Err(e)) => return Some(Err(e)),
+                None => is_ready = false,
+            }
+        }
+
+        if !is_ready {
+            return None;
+        }
+
+        let resources = self
+            .reflectors
+            .iter()
+            .flat_map(|reflector| reflector.store.state())
+            .collect();
+
+        Some(Ok(sort_by_namespaces(resources, namespaces)))
+    }
+}
+
+/// すべてのnamespace、またはクラスタスコープのリソースの一覧をローカルのキャッシュとして保持する
+pub struct ClusterInformer<K: ReflectorResource> {
+    reflector: Arc<Reflector<K>>,
+}
+
+impl<K: ReflectorResource> Clone for ClusterInformer<K> {
+    fn clone(&self) -> Self {
+        Self {
+            reflector: self.reflector.clone(),
+        }
+    }
+}
+
+impl<K> ClusterInformer<K>
+where
+    K: ReflectorResource + DeserializeOwned + Debug,
+{
+    fn spawn(client: Client) -> Self {
+        let stream = watcher::watcher(Api::<K>::all(client), watcher::Config::default()).boxed();
+
+        Self {
+            reflector: Arc::new(Reflector::spawn(None, stream)),
+        }
+    }
+
+    /// キャッシュしているリソースの一覧を名前順に返す
+    ///   * watchでエラーが発生している場合はErrを返す
+    ///   * 初回のリスト取得が完了していない場合はNoneを返す
+    pub fn state(&self) -> Option<Result<Vec<Arc<K>>>> {
+        Some(self.reflector.check()?.map(|()| {
+            let mut state = self.reflector.store.state();
+            state.sort_by_key(|r| r.name_any());
+            state
+        }))
+    }
+}
+
+/// 初めて使うときにwatchを開始するClusterInformer
+///
+/// 一度開始したwatchは、複製したすべてのLazyClusterInformerで共有する
+struct LazyClusterInformer<K: ReflectorResource> {
+    client: Client,
+    informer: Arc<OnceLock<ClusterInformer<K>>>,
+}
+
+impl<K: ReflectorResource> Clone for LazyClusterInformer<K> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            informer: self.informer.clone(),
+        }
+    }
+}
+
+impl<K> LazyClusterInformer<K>
+where
+    K: ReflectorResource + DeserializeOwned + Debug,
+{
+    fn new(client: Client) -> Self {
+        Self {
+            client,
+            informer: Arc::default(),
+        }
+    }
+
+    fn get(&self) -> ClusterInformer<K> {
+        self.informer
+            .get_or_init(|| ClusterInformer::spawn(self.client.clone()))
+            .clone()
+    }
+}
+
+/// 複数のPollerで共有するInformer
+pub struct SharedInformer<K: InformerResource> {
+    inner: Arc<Mutex<NamespacedInformer<K>>>,
+}
+
+impl<K: InformerResource> Clone for SharedInformer<K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K: InformerResource> SharedInformer<K> {
+    fn new(informer: NamespacedInformer<K>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(informer)),
+        }
+    }
+
+    /// 監視対象のnamespaceを更新する
+    ///
+    /// 共有しているPollerはすべて同じ対象のnamespaceを指定する
+    pub fn sync_namespaces(&self, namespaces: &[String]) {
+        self.inner
+            .lock()
+            .expect("Failed to lock informer")
+            .sync_namespaces(namespaces);
+    }
+
+    /// 1つのwatchですべてのnamespaceを監視しているか
+    pub fn covers_all_namespaces(&self) -> bool {
+        self.inner
+            .lock()
+            .expect("Failed to lock informer")
+            .covers_all_namespaces()
+    }
+
+    /// キャッシュしているリソースの一覧を返す
+    pub fn state(&self) -> Option<Result<Vec<Arc<K>>>> {
+        self.inner.lock().expect("Failed to lock informer").state()
+    }
+}
+
+/// コンテキストごとに作成し、Pollerに渡すInformer
+///
+/// クラスタ内のnamespaceの一覧と、複数のPollerで使うリソースのキャッシュを共有する
+#[derive(Clone)]
+pub struct SharedInformers {
+    client: Client,
+    cluster_namespaces: ClusterNamespaces,
+    cluster_pod: LazyClusterInformer<Pod>,
+    pod: SharedInformer<Pod>,
+    secret_metadata: SharedInformer<PartialObjectMeta<Secret>>,
+}
+
+impl SharedInformers {
+    /// all_namespacesがtrueの場合のみ、クラスタ内のnamespaceの一覧をwatchする
+    ///
+    /// すべてのnamespaceのPodのwatchは、必要になるまで開始しない
+    pub fn new(client: Client, all_namespaces: bool) -> Self {
+        let cluster_namespaces = if all_namespaces {
+            ClusterNamespaces::spawn(client.clone())
+        } else {
+            ClusterNamespaces::disabled()
+        };
+
+        let cluster_pod = LazyClusterInformer::new(client.clone());
+
+        let pod = SharedInformer::new(
+            NamespacedInformer::new(client.clone(), cluster_namespaces.clone(), watch_object)
+                .cluster_informer(&cluster_pod),
+        );
+
+        let secret_metadata = SharedInformer::new(NamespacedInformer::new(
+            client.clone(),
+            cluster_namespaces.clone(),
+            watch_metadata::<Secret>,
+        ));
+
+        Self {
+            client,
+            cluster_namespaces,
+            cluster_pod,
+            pod,
+            secret_metadata,
+        }
+    }
+
+    /// 呼び出し元のPollerのみで使うInformerを作成する
+    pub fn namespaced<K: InformerResource>(&self) -> NamespacedInformer<K> {
+        NamespacedInformer::new(
+            self.client.clone(),
+            self.cluster_namespaces.clone(),
+            watch_object,
+        )
+    }
+
+    /// クラスタスコープのリソースのInformerを作成する
+    pub fn cluster<K>(&self) -> ClusterInformer<K>
+    where
+        K: ReflectorResource + DeserializeOwned + Debug,
+    {
+        ClusterInformer::spawn(self.client.clone())
+    }
+
+    /// PodPollerとNetworkPollerで共有するPodのInformer
+    pub fn pod(&self) -> SharedInformer<Pod> {
+        self.pod.clone()
+    }
+
+    /// すべてのnamespaceのPodのInformer
+    ///
+    /// NodePollerで使い、PodPollerとNetworkPollerがすべてのnamespaceを監視するときも共有する
+    /// 初めて呼び出したときにwatchを開始する
+    pub fn cluster_pod(&self) -> ClusterInformer<Pod> {
+        self.cluster_pod.get()
+    }
+
+    /// ConfigPollerとHelmPollerで共有するSecretのInformer
+    ///
+    /// Helmのリリースなど大きな値を持つSecretをキャッシュしないように、メタデータのみを保持する
+    /// 値が必要な場合は、個別にSecretを取得する
+    pub fn secret_metadata(&self) -> SharedInformer<PartialObjectMeta<Secret>> {
+        self.secret_metadata.clone()
+    }
+}
+
+/// 直前に送信した内容を保持し、内容に変化があったかを判定する
+#[derive(Debug)]
+pub struct ChangeDetector<T> {
+    last: Option<std::result::Result<T, String>>,
+}
+
+impl<T> Default for ChangeDetector<T> {
+    fn default() -> Self {
+        Self { last: None }
+    }
+}
+
+impl<T: PartialEq + Clone> ChangeDetector<T> {
+    /// 前回の内容と異なる場合にtrueを返し、内容を保持する
+    pub fn is_changed(&mut self, current: &Result<T>) -> bool {
+        let current = match current {
+            Ok(v) => Ok(v.clone()),
+            Err(e) => Err(format!("{:#}", e)),
+        };
+
+        if self.last.as_ref() == Some(&current) {
+            return false;
+        }
+
+        self.last = Some(current);
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn pod(namespace: &str, name: &str) -> Arc<Pod> {
+        Arc::new(Pod {
+            metadata: kube::api::ObjectMeta {
+                namespace: Some(namespace.to_string()),
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn 監視対象のnamespaceのリソースのみを指定順と名前順に並べる() {
+        let resources = vec![
+            pod("b", "web"),
+            pod("a", "web"),
+            pod("c", "web"),
+            pod("b", "api"),
+        ];
+
+        let actual: Vec<(String, String)> =
+            sort_by_namespaces(resources, &["b".to_string(), "a".to_string()])
+                .iter()
+                .map(|pod| (pod.namespace().unwrap_or_default(), pod.name_any()))
+                .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                ("b".to_string(), "api".to_string()),
+                ("b".to_string(), "web".to_string()),
+                ("a".to_string(), "web".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn クラスタ内のすべてのnamespaceを含む場合のみtrueを返す() {
+        let cluster = ["a".to_string(), "b".to_string()];
+
+        assert_eq!(
+            covers_all(&cluster, &["b".to_string(), "a".to_string()]),
+            true
+        );
+        assert_eq!(covers_all(&cluster, &["a".to_string()]), false);
+        assert_eq!(covers_all(&[], &["a".to_string()]), false);
+    }
+
+    #[test]
+    fn 権限がない場合のみwatchのエラーをforbiddenとみなす() {
+        let response = |code| kube::core::ErrorResponse {
+            status: "Failure".to_string(),
+            message: String::default(),
+            reason: String::default(),
+            code,
+        };
+
+        assert_eq!(
+            is_forbidden(&watcher::Error::InitialListFailed(kube::Error::Api(
+                response(403)
+            ))),
+            true
+        );
+        assert_eq!(
+            is_forbidden(&watcher::Error::WatchError(response(403))),
+            true
+        );
+        assert_eq!(
+            is_forbidden(&watcher::Error::WatchFailed(kube::Error::Api(response(
+                500
+            )))),
+            false
+        );
+        assert_eq!(is_forbidden(&watcher::Error::NoResourceVersion), false);
+    }
+
+    #[test]
+    fn change_detectorは内容が変化したときのみtrueを返す() {
+        let mut detector = ChangeDetector::default();
+
+        assert_eq!(detector.is_changed(&Ok(vec!["a"])), true);
+        assert_eq!(detector.is_changed(&Ok(vec!["a"])), false);
+        assert_eq!(detector.is_changed(&Ok(vec!["a", "b"])), true);
+        assert_eq!(detector.is_changed(&Err(anyhow!("error"))), true);
+        assert_eq!(detector.is_changed(&Err(anyhow!("error"))), false);
+        assert_eq!(detector.is_changed(&Ok(vec!["a", "b"])), true);
+    }
+}
@@ -0,0 +1,120 @@
+//! resource.Quantity の文字列表現を数値に変換する
+
+/// Quantityを基本単位の数値に変換する
+///
+/// e.g. "250m" -> 0.25, "1Gi" -> 1073741824, "1e3" -> 1000
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    let quantity = quantity.trim();
+
+    const BINARY_SUFFIXES: [(&str, f64); 6] = [
+        ("Ki", 1024.0),
+        ("Mi", 1048576.0),
+        ("Gi", 1073741824.0),
+        ("Ti", 1099511627776.0),
+        ("Pi", 1125899906842624.0),
+        ("Ei", 1152921504606846976.0),
+    ];
+
+    for (suffix, multiplier) in BINARY_SUFFIXES {
+        if let Some(number) = quantity.strip_suffix(suffix) {
+            return number.parse::<f64>().ok().map(|n| n * multiplier);
+        }
+    }
+
+    // 末尾が数字の場合は指数表記を含めてそのまま数値として扱う
+    let Some(last) = quantity.chars().last().filter(|c| c.is_ascii_alphabetic()) else {
+        return quantity.parse::<f64>().ok();
+    };
+
+    let multiplier = match last {
+        'n' => 1e-9,
+        'u' => 1e-6,
+        'm' => 1e-3,
+        'k' => 1e3,
+        'M' => 1e6,
+        'G' => 1e9,
+        'T' => 1e12,
+        'P' => 1e15,
+        'E' => 1e18,
+        _ => return None,
+    };
+
+    quantity[..quantity.len() - 1]
+        .parse::<f64>()
+        .ok()
+        .map(|n| n * multiplier)
+}
+
+/// CPUのQuantityをミリコア単位に変換する
+pub fn parse_cpu_millis(quantity: &str) -> Option<u64> {
+    parse_quantity(quantity).map(|cpu| (cpu * 1000.0).round() as u64)
+}
+
+/// メモリのQuantityをバイト単位に変換する
+pub fn parse_memory_bytes(quantity: &str) -> Option<u64> {
+    parse_quantity(quantity).map(|memory| memory.round() as u64)
+}
+
+/// kubectl topと同じく、CPUをミリコア単位で表示する
+pub fn format_cpu_millis(millis: u64) -> String {
+    format!("{}m", millis)
+}
+
+/// kubectl topと同じく、メモリをMiB単位で表示する
+pub fn format_memory_bytes(bytes: u64) -> String {
+    format!("{}Mi", bytes / (1024 * 1024))
+}
+
+/// 使用量の割合をパーセントで返す
+pub fn percentage(usage: u64, total: u64) -> Option<u64> {
+    (total != 0).then(|| usage * 100 / total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("250m", Some(250))]
+    #[case("1", Some(1000))]
+    #[case("1.5", Some(1500))]
+    #[case("123456789n", Some(123))]
+    #[case("500000u", Some(500))]
+    #[case("invalid", None)]
+    fn cpuのquantityをミリコアに変換する(
+        #[case] quantity: &str,
+        #[case] expected: Option<u64>,
+    ) {
+        assert_eq!(parse_cpu_millis(quantity), expected);
+    }
+
+    #[rstest]
+    #[case("128974848", Some(128974848))]
+    #[case("1Ki", Some(1024))]
+    #[case("64Mi", Some(67108864))]
+    #[case("1Gi", Some(1073741824))]
+    #[case("1k", Some(1000))]
+    #[case("1M", Some(1000000))]
+    #[case("1e3", Some(1000))]
+    #[case("1E", Some(1000000000000000000))]
+    #[case("1Xi", None)]
+    fn メモリのquantityをバイトに変換する(
+        #[case] quantity: &str,
+        #[case] expected: Option<u64>,
+    ) {
+        assert_eq!(parse_memory_bytes(quantity), expected);
+    }
+
+    #[test]
+    fn 合計が0のときパーセントを返さない() {
+        assert_eq!(percentage(100, 0), None);
+    }
+
+    #[test]
+    fn 使用量の割合をパーセントで返す() {
+        assert_eq!(percentage(50, 200), Some(25));
+    }
+}
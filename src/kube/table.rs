@@ -1,13 +1,9 @@
 use std::collections::BTreeMap;
 
-use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
 
-use crate::kube::{
-    apis::v1_table::{Table, TableRow},
-    KubeClient, KubeClientRequest as _,
-};
-
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct KubeTableRow {
     pub namespace: String,
     pub name: String,
@@ -15,7 +11,7 @@ pub struct KubeTableRow {
     pub row: Vec<String>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct KubeTable {
     pub header: Vec<String>,
     pub rows: Vec<KubeTableRow>,
@@ -73,22 +69,101 @@ pub fn insert_ns(namespaces: &[String]) -> bool {
     namespaces.len() != 1
 }
 
-pub async fn get_resource_per_namespace<F>(
-    client: &KubeClient,
-    path: String,
-    target_values: &[&str],
-    create_cells: F,
-) -> Result<Vec<KubeTableRow>>
-where
-    F: Fn(&TableRow, &[usize]) -> KubeTableRow,
-{
-    let table: Table = client.table_request(&path).await?;
-
-    let indexes = table.find_indexes(target_values);
-
-    Ok(table
-        .rows
-        .iter()
-        .map(|row| (create_cells)(row, &indexes))
-        .collect())
+/// kubectlのAGE列と同じ形式で、指定した時刻からの経過時間を返す
+pub fn translate_timestamp_since(timestamp: Option<&Time>) -> String {
+    match timestamp {
+        Some(Time(timestamp)) => human_duration(Utc::now() - timestamp),
+        None => "<unknown>".to_string(),
+    }
+}
+
+/// kubectlのAGE列と同じ形式で、指定した時刻からの経過時間を返す
+pub fn translate_datetime_since(timestamp: &DateTime<Utc>) -> String {
+    human_duration(Utc::now() - timestamp)
+}
+
+/// k8s.io/apimachinery/pkg/util/duration.HumanDuration と同等の変換を行う
+pub fn human_duration(d: Duration) -> String {
+    let seconds = d.num_seconds();
+
+    if seconds < -1 {
+        return "<invalid>".to_string();
+    } else if seconds < 0 {
+        return "0s".to_string();
+    } else if seconds < 60 * 2 {
+        return format!("{}s", seconds);
+    }
+
+    let minutes = d.num_minutes();
+
+    if minutes < 10 {
+        let s = seconds % 60;
+        if s == 0 {
+            return format!("{}m", minutes);
+        }
+        return format!("{}m{}s", minutes, s);
+    } else if minutes < 60 * 3 {
+        return format!("{}m", minutes);
+    }
+
+    let hours = d.num_hours();
+
+    if hours < 8 {
+        let m = minutes % 60;
+        if m == 0 {
+            return format!("{}h", hours);
+        }
+        format!("{}h{}m", hours, m)
+    } else if hours < 48 {
+        format!("{}h", hours)
+    } else if hours < 24 * 8 {
+        let h = hours % 24;
+        if h == 0 {
+            return format!("{}d", hours / 24);
+        }
+        format!("{}d{}h", hours / 24, h)
+    } else if hours < 24 * 365 * 2 {
+        format!("{}d", hours / 24)
+    } else if hours < 24 * 365 * 8 {
+        let dy = (hours / 24) % 365;
+        if dy == 0 {
+            return format!("{}y", hours / 24 / 365);
+        }
+        format!("{}y{}d", hours / 24 / 365, dy)
+    } else {
+        format!("{}y", hours / 24 / 365)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Duration::seconds(-2), "<invalid>")]
+    #[case(Duration::milliseconds(-500), "0s")]
+    #[case(Duration::seconds(0), "0s")]
+    #[case(Duration::seconds(119), "119s")]
+    #[case(Duration::seconds(120), "2m")]
+    #[case(Duration::seconds(9 * 60 + 59), "9m59s")]
+    #[case(Duration::minutes(10), "10m")]
+    #[case(Duration::minutes(179), "179m")]
+    #[case(Duration::minutes(3 * 60 + 30), "3h30m")]
+    #[case(Duration::hours(8), "8h")]
+    #[case(Duration::hours(47), "47h")]
+    #[case(Duration::hours(48), "2d")]
+    #[case(Duration::hours(24 * 7 + 23), "7d23h")]
+    #[case(Duration::days(300), "300d")]
+    #[case(Duration::days(365 * 2 + 10), "2y10d")]
+    #[case(Duration::days(365 * 3), "3y")]
+    #[case(Duration::days(365 * 9), "9y")]
+    fn human_durationはkubectlと同じ形式で返す(
+        #[case] duration: Duration,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(human_duration(duration), expected);
+    }
 }
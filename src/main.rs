@@ -20,7 +20,7 @@ use ratatui::crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 
-use crate::{app::App, cmd::Command, config::Config, logging::Logger};
+use crate::{app::App, cmd::Command, config::Config, logging::Logger, ui::key::init_key_bindings};
 
 macro_rules! enable_raw_mode {
     () => {
@@ -85,6 +85,11 @@ fn main() -> Result<()> {
 
     let config = Config::load(config_load_option)?;
 
+    init_key_bindings(
+        config.keybindings.key_map.clone(),
+        config.keybindings.widget.clone(),
+    );
+
     enable_raw_mode!();
 
     let result = App::run(command, config);
@@ -1,6 +1,7 @@
 mod callback;
 pub mod dialog;
 pub mod event;
+pub mod key;
 pub mod tab;
 pub mod widget;
 mod window;
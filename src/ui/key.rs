@@ -0,0 +1,461 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use anyhow::{bail, Result};
+use once_cell::sync::OnceCell;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::message::UserEvent;
+
+/// 1つのキー入力
+///
+/// 設定ファイルでは`n`、`N`、`ctrl-p`、`enter`のような文字列で指定する
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// 大文字のときはSHIFTを付けるように正規化する
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        match code {
+            KeyCode::Char(c) if c.is_ascii_uppercase() => Self {
+                code,
+                modifiers: modifiers | KeyModifiers::SHIFT,
+            },
+            KeyCode::Char(c)
+                if c.is_ascii_lowercase() && modifiers.contains(KeyModifiers::SHIFT) =>
+            {
+                Self {
+                    code: KeyCode::Char(c.to_ascii_uppercase()),
+                    modifiers,
+                }
+            }
+            _ => Self { code, modifiers },
+        }
+    }
+
+    pub fn code(&self) -> KeyCode {
+        self.code
+    }
+
+    pub fn matches(&self, ev: KeyEvent) -> bool {
+        *self == Self::from(ev)
+    }
+}
+
+impl From<char> for Key {
+    fn from(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+}
+
+impl From<KeyCode> for Key {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(ev: KeyEvent) -> Self {
+        Self::new(ev.code, ev.modifiers)
+    }
+}
+
+impl From<Key> for UserEvent {
+    fn from(key: Key) -> Self {
+        UserEvent::Key(KeyEvent::new(key.code, key.modifiers))
+    }
+}
+
+const NAMED_KEYS: &[(&str, KeyCode)] = &[
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("BS", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PgUp", KeyCode::PageUp),
+    ("PgDn", KeyCode::PageDown),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Space", KeyCode::Char(' ')),
+];
+
+/// 表示名以外で受け付けるキーの名前
+const KEY_ALIASES: &[(&str, KeyCode)] = &[
+    ("escape", KeyCode::Esc),
+    ("backspace", KeyCode::Backspace),
+    ("del", KeyCode::Delete),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+fn parse_key_code(s: &str) -> Option<KeyCode> {
+    let mut chars = s.chars();
+
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    if let Some(n) = s
+        .strip_prefix(['f', 'F'])
+        .and_then(|n| n.parse::<u8>().ok())
+    {
+        return (1..=12).contains(&n).then_some(KeyCode::F(n));
+    }
+
+    NAMED_KEYS
+        .iter()
+        .chain(KEY_ALIASES)
+        .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        .map(|(_, code)| *code)
+}
+
+impl FromStr for Key {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s.trim();
+
+        // `-`そのものをキーとして指定できるように、2文字以上残っているときのみ修飾キーとして扱う
+        while let Some((modifier, key)) = rest.split_once('-').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!("Unknown modifier key: {}", s),
+            };
+
+            rest = key;
+        }
+
+        let Some(code) = parse_key_code(rest) else {
+            bail!("Unknown key: {}", s);
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            // 大文字はSHIFTを表記しなくても区別できる
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => {
+                if self.modifiers.contains(KeyModifiers::SHIFT) {
+                    write!(f, "Shift-")?;
+                }
+
+                write!(f, "F{}", n)
+            }
+            code => {
+                if self.modifiers.contains(KeyModifiers::SHIFT) {
+                    write!(f, "Shift-")?;
+                }
+
+                match NAMED_KEYS.iter().find(|(_, c)| *c == code) {
+                    Some((name, _)) => write!(f, "{}", name),
+                    None => write!(f, "{:?}", code),
+                }
+            }
+        }
+    }
+}
+
+impl From<Key> for String {
+    fn from(key: Key) -> Self {
+        key.to_string()
+    }
+}
+
+/// 1つの操作に割り当てるキーの一覧
+///
+/// 設定ファイルでは`q`のような1つのキーか、`[q, esc]`のようなリストで指定する
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct KeyBinding(Vec<Key>);
+
+impl KeyBinding {
+    pub fn new(keys: impl IntoIterator<Item = Key>) -> Self {
+        Self(keys.into_iter().collect())
+    }
+
+    pub fn keys(&self) -> &[Key] {
+        &self.0
+    }
+
+    pub fn matches(&self, ev: KeyEvent) -> bool {
+        self.0.iter().any(|key| key.matches(ev))
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBinding {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrMany {
+            One(Key),
+            Many(Vec<Key>),
+        }
+
+        match OneOrMany::deserialize(deserializer)? {
+            OneOrMany::One(key) => Ok(Self(vec![key])),
+            OneOrMany::Many(keys) => Ok(Self(keys)),
+        }
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let keys: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+
+        write!(f, "{}", keys.join(" "))
+    }
+}
+
+/// 入力されたキーを別のキーとして扱うための対応表
+///
+/// デフォルトはEmacs風のキーバインド
+/// 設定ファイルで指定した対応はデフォルトに上書きし、変換先に`null`を指定したキーは取り除く
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct KeyMap(HashMap<Key, Key>);
+
+impl<'de> Deserialize<'de> for KeyMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let entries = HashMap::<Key, Option<Key>>::deserialize(deserializer)?;
+
+        let mut key_map = Self::default();
+
+        for (from, to) in entries {
+            match to {
+                Some(to) => {
+                    key_map.0.insert(from, to);
+                }
+                None => {
+                    key_map.0.remove(&from);
+                }
+            }
+        }
+
+        Ok(key_map)
+    }
+}
+
+impl KeyMap {
+    pub fn get(&self, key: &Key) -> Option<&Key> {
+        self.0.get(key)
+    }
+
+    /// ヘルプ表示用に、変換先のキーごとにまとめた一覧を返す
+    pub fn entries(&self) -> Vec<(Key, Key)> {
+        let mut entries: Vec<(Key, Key)> = self.0.iter().map(|(from, to)| (*from, *to)).collect();
+
+        entries.sort_by_key(|(from, to)| (to.to_string(), from.to_string()));
+
+        entries
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let ctrl = |c: char| Key::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+        Self(HashMap::from([
+            (ctrl('p'), Key::from(KeyCode::Up)),
+            (ctrl('n'), Key::from(KeyCode::Down)),
+            (ctrl('b'), Key::from(KeyCode::Left)),
+            (ctrl('f'), Key::from(KeyCode::Right)),
+            (ctrl('u'), Key::from(KeyCode::PageUp)),
+            (ctrl('d'), Key::from(KeyCode::PageDown)),
+            (ctrl('h'), Key::from(KeyCode::Delete)),
+            (Key::from(KeyCode::Backspace), Key::from(KeyCode::Delete)),
+            (ctrl('a'), Key::from(KeyCode::Home)),
+            (ctrl('e'), Key::from(KeyCode::End)),
+            (ctrl('['), Key::from(KeyCode::Esc)),
+        ]))
+    }
+}
+
+/// ウィジェット共通の操作に割り当てるキー
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct WidgetKeyBindings {
+    /// 次の行へ移動する
+    pub down: KeyBinding,
+    /// 前の行へ移動する
+    pub up: KeyBinding,
+    /// 最初の行へ移動する
+    pub first: KeyBinding,
+    /// 最後の行へ移動する
+    pub last: KeyBinding,
+    /// テキストの検索またはテーブルのフィルターを開始する
+    pub search: KeyBinding,
+    /// 次の検索結果へ移動する
+    pub next_match: KeyBinding,
+    /// 前の検索結果へ移動する
+    pub prev_match: KeyBinding,
+    /// 検索またはフィルターを解除する
+    pub cancel: KeyBinding,
+    /// 次のウィジェットにフォーカスを移す
+    pub next_widget: KeyBinding,
+    /// 前のウィジェットにフォーカスを移す
+    pub prev_widget: KeyBinding,
+}
+
+impl Default for WidgetKeyBindings {
+    fn default() -> Self {
+        Self {
+            down: KeyBinding::new([Key::from('j')]),
+            up: KeyBinding::new([Key::from('k')]),
+            first: KeyBinding::new([Key::from('g')]),
+            last: KeyBinding::new([Key::from('G')]),
+            search: KeyBinding::new([Key::from('/')]),
+            next_match: KeyBinding::new([Key::from('n')]),
+            prev_match: KeyBinding::new([Key::from('N')]),
+            cancel: KeyBinding::new([Key::from('q')]),
+            next_widget: KeyBinding::new([Key::from(KeyCode::Tab)]),
+            prev_widget: KeyBinding::new([Key::from(KeyCode::BackTab)]),
+        }
+    }
+}
+
+static KEY_MAP: OnceCell<KeyMap> = OnceCell::new();
+
+static WIDGET_KEY_BINDINGS: OnceCell<WidgetKeyBindings> = OnceCell::new();
+
+/// 設定ファイルで指定したキーバインドを反映する
+/// ウィジェットを生成する前に1度だけ呼び出す
+pub fn init_key_bindings(key_map: KeyMap, widget: WidgetKeyBindings) {
+    KEY_MAP
+        .set(key_map)
+        .expect("Error: key map is already initialized");
+
+    WIDGET_KEY_BINDINGS
+        .set(widget)
+        .expect("Error: widget key bindings are already initialized");
+}
+
+pub fn key_map() -> &'static KeyMap {
+    KEY_MAP.get_or_init(KeyMap::default)
+}
+
+pub fn widget_key_bindings() -> &'static WidgetKeyBindings {
+    WIDGET_KEY_BINDINGS.get_or_init(WidgetKeyBindings::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("n", Key::from('n'))]
+    #[case("N", Key::new(KeyCode::Char('N'), KeyModifiers::SHIFT))]
+    #[case("shift-n", Key::new(KeyCode::Char('N'), KeyModifiers::SHIFT))]
+    #[case("?", Key::from('?'))]
+    #[case("-", Key::from('-'))]
+    #[case("ctrl-p", Key::new(KeyCode::Char('p'), KeyModifiers::CONTROL))]
+    #[case("Ctrl-[", Key::new(KeyCode::Char('['), KeyModifiers::CONTROL))]
+    #[case("ctrl--", Key::new(KeyCode::Char('-'), KeyModifiers::CONTROL))]
+    #[case("alt-ctrl-x", Key::new(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT))]
+    #[case("enter", Key::from(KeyCode::Enter))]
+    #[case("Esc", Key::from(KeyCode::Esc))]
+    #[case("pagedown", Key::from(KeyCode::PageDown))]
+    #[case("space", Key::from(' '))]
+    #[case("f5", Key::from(KeyCode::F(5)))]
+    fn 文字列からキーをパースする(#[case] s: &str, #[case] expected: Key) {
+        assert_eq!(s.parse::<Key>().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("hyper-x")]
+    #[case("ctrl-foo")]
+    #[case("f13")]
+    fn 不正なキーはエラーを返す(#[case] s: &str) {
+        assert!(s.parse::<Key>().is_err());
+    }
+
+    #[rstest]
+    #[case("ctrl-p", "Ctrl-p")]
+    #[case("N", "N")]
+    #[case("backspace", "BS")]
+    #[case("shift-tab", "Shift-Tab")]
+    #[case("space", "Space")]
+    fn キーを表示用の文字列に変換する(#[case] s: &str, #[case] expected: &str) {
+        let key = s.parse::<Key>().unwrap();
+
+        assert_eq!(key.to_string(), expected);
+        assert_eq!(key.to_string().parse::<Key>().unwrap(), key);
+    }
+
+    #[test]
+    fn 入力されたキーと一致するか判定する() {
+        let binding = KeyBinding::new([Key::from('N'), Key::from(KeyCode::Esc)]);
+
+        assert!(binding.matches(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT)));
+        assert!(binding.matches(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::NONE)));
+        assert!(binding.matches(KeyEvent::from(KeyCode::Esc)));
+        assert!(!binding.matches(KeyEvent::from(KeyCode::Char('n'))));
+    }
+
+    #[test]
+    fn 単一のキーとリストのどちらでも指定できる() {
+        let yaml = indoc! {r#"
+            down: ctrl-j
+            up: [k, "ctrl-k"]
+        "#};
+
+        let actual: WidgetKeyBindings = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            actual.down,
+            KeyBinding::new([Key::new(KeyCode::Char('j'), KeyModifiers::CONTROL)])
+        );
+        assert_eq!(
+            actual.up,
+            KeyBinding::new([
+                Key::from('k'),
+                Key::new(KeyCode::Char('k'), KeyModifiers::CONTROL)
+            ])
+        );
+        assert_eq!(actual.search, WidgetKeyBindings::default().search);
+    }
+}
@@ -1,31 +1,16 @@
 use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent},
+    crossterm::event::{KeyCode, KeyEvent, MouseEvent},
     layout::Rect,
 };
 
-pub fn key_event_to_code(key: KeyEvent) -> KeyCode {
-    use KeyCode::*;
-
-    match key.code {
-        Char('p') if key.modifiers == KeyModifiers::CONTROL => Up,
-        Char('n') if key.modifiers == KeyModifiers::CONTROL => Down,
-
-        Char('b') if key.modifiers == KeyModifiers::CONTROL => Left,
-        Char('f') if key.modifiers == KeyModifiers::CONTROL => Right,
-
-        Char('u') if key.modifiers == KeyModifiers::CONTROL => PageUp,
-        Char('d') if key.modifiers == KeyModifiers::CONTROL => PageDown,
-
-        Char('h') if key.modifiers == KeyModifiers::CONTROL => Delete,
-        Backspace => Delete,
+use super::key::{key_map, Key};
 
-        Char('a') if key.modifiers == KeyModifiers::CONTROL => Home,
-        Char('e') if key.modifiers == KeyModifiers::CONTROL => End,
-
-        Char('[') if key.modifiers == KeyModifiers::CONTROL => Esc,
-
-        _ => key.code,
-    }
+/// 設定したキーの対応表に従って、入力されたキーを変換する
+pub fn key_event_to_code(key: KeyEvent) -> KeyCode {
+    key_map()
+        .get(&Key::from(key))
+        .map(Key::code)
+        .unwrap_or(key.code)
 }
 
 pub trait MousePosition {
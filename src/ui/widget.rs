@@ -63,10 +63,27 @@ impl PartialOrd for LiteralItem {
     }
 }
 
+/// テーブルの行の種類
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TableItemKind {
+    /// データの行
+    #[default]
+    Data,
+    /// テーブルの途中に表示する列の見出し
+    ///
+    /// フィルターの対象外とする
+    Header,
+    /// テーブルを区切る見出し
+    ///
+    /// フィルターの対象外とする
+    Separator,
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct TableItem {
     pub metadata: Option<BTreeMap<String, String>>,
     pub item: Vec<String>,
+    pub kind: TableItemKind,
 }
 
 impl TableItem {
@@ -74,8 +91,30 @@ impl TableItem {
         Self {
             item: item.into(),
             metadata,
+            kind: TableItemKind::Data,
+        }
+    }
+
+    /// テーブルの途中に表示する列の見出しの行
+    pub fn header(item: impl Into<Vec<String>>) -> Self {
+        Self {
+            kind: TableItemKind::Header,
+            ..Self::new(item, None)
         }
     }
+
+    /// テーブルを区切る見出しの行
+    pub fn separator(item: impl Into<Vec<String>>) -> Self {
+        Self {
+            kind: TableItemKind::Separator,
+            ..Self::new(item, None)
+        }
+    }
+
+    /// フィルターの対象となるデータの行か
+    pub fn is_data(&self) -> bool {
+        self.kind == TableItemKind::Data
+    }
 }
 
 impl<T> From<T> for LiteralItem
@@ -14,6 +14,7 @@ use crate::{
     define_callback,
     ui::{
         event::{Callback, EventResult},
+        key::widget_key_bindings,
         key_event_to_code,
         util::{MousePosition, RectContainsPoint},
         Window,
@@ -362,19 +363,35 @@ impl WidgetTrait for List<'_> {
     }
 
     fn on_key_event(&mut self, ev: KeyEvent) -> EventResult {
+        let keys = widget_key_bindings();
+
         match key_event_to_code(ev) {
-            KeyCode::Char('j') | KeyCode::Down | KeyCode::PageDown => {
+            KeyCode::Down | KeyCode::PageDown => {
+                self.select_next(1);
+            }
+
+            _ if keys.down.matches(ev) => {
                 self.select_next(1);
             }
 
-            KeyCode::Char('k') | KeyCode::Up | KeyCode::PageUp => {
+            KeyCode::Up | KeyCode::PageUp => {
                 self.select_prev(1);
             }
 
-            KeyCode::Char('G') | KeyCode::End => {
+            _ if keys.up.matches(ev) => {
+                self.select_prev(1);
+            }
+
+            KeyCode::End => {
+                self.select_last();
+            }
+            _ if keys.last.matches(ev) => {
                 self.select_last();
             }
-            KeyCode::Char('g') | KeyCode::Home => {
+            KeyCode::Home => {
+                self.select_first();
+            }
+            _ if keys.first.matches(ev) => {
                 self.select_first();
             }
 
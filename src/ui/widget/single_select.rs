@@ -17,7 +17,7 @@ use crate::{
     ui::{
         event::{Callback, EventResult},
         util::RectContainsPoint,
-        widget::{Item, RenderTrait, SelectedItem, WidgetBase, WidgetTrait},
+        widget::{Item, LiteralItem, RenderTrait, SelectedItem, WidgetBase, WidgetTrait},
     },
 };
 
@@ -213,6 +213,14 @@ impl SingleSelect<'_> {
         self.filter_form.move_cursor_end();
     }
 
+    pub fn filter(&self) -> String {
+        self.filter_form.content()
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &LiteralItem> {
+        self.select_form.items()
+    }
+
     pub fn clear_filter(&mut self) {
         self.filter_form.clear();
 
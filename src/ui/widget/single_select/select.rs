@@ -155,6 +155,11 @@ impl SelectForm<'_> {
         self.list_widget.widget_item()
     }
 
+    /// フィルターで絞り込む前のすべての項目
+    pub fn items(&self) -> impl Iterator<Item = &LiteralItem> {
+        self.list_items.iter()
+    }
+
     pub fn update_filter(&mut self, filter: impl Into<String>) {
         self.filter = filter.into();
         self.list_widget
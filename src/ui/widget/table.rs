@@ -2,7 +2,7 @@
 mod filter;
 mod item;
 
-use std::rc::Rc;
+use std::{cell::RefCell, rc::Rc};
 
 use ratatui::{
     crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
@@ -15,10 +15,12 @@ use ratatui::{
 };
 
 use crate::{
+    clipboard::Clipboard,
     define_callback, logger,
     message::UserEvent,
     ui::{
         event::{Callback, EventResult},
+        key::{widget_key_bindings, KeyBinding},
         key_event_to_code,
         util::{MousePosition, RectContainsPoint},
         Window,
@@ -26,7 +28,9 @@ use crate::{
 };
 
 use super::{
-    base::WidgetBase, styled_graphemes, Item, RenderTrait, SelectedItem, TableItem, WidgetTrait,
+    base::WidgetBase,
+    styled_graphemes::{self, StyledGraphemes as _},
+    Item, RenderTrait, SelectedItem, TableItem, WidgetTrait,
 };
 
 pub use filter::{FilterForm, FilterFormTheme};
@@ -75,6 +79,7 @@ pub struct TableBuilder {
     actions: Vec<(UserEvent, Callback)>,
     block_injection: Option<RenderBlockInjection>,
     highlight_injection: Option<RenderHighlightInjection>,
+    clipboard: Option<Rc<RefCell<Clipboard>>>,
 }
 
 #[allow(dead_code)]
@@ -134,6 +139,19 @@ impl TableBuilder {
         self
     }
 
+    /// 操作に割り当てた全てのキーでコールバックを呼び出す
+    pub fn key_action<F>(self, key_binding: &KeyBinding, cb: F) -> Self
+    where
+        F: Into<Callback>,
+    {
+        let cb = cb.into();
+
+        key_binding
+            .keys()
+            .iter()
+            .fold(self, |builder, key| builder.action(*key, cb.clone()))
+    }
+
     pub fn block_injection<F>(mut self, block_injection: F) -> Self
     where
         F: Into<RenderBlockInjection>,
@@ -150,6 +168,12 @@ impl TableBuilder {
         self
     }
 
+    /// マウスのドラッグで選択した行をクリップボードにコピーする
+    pub fn clipboard(mut self, clipboard: Rc<RefCell<Clipboard>>) -> Self {
+        self.clipboard = Some(clipboard);
+        self
+    }
+
     pub fn build(self) -> Table<'static> {
         let mut table = Table {
             id: self.id,
@@ -162,6 +186,7 @@ impl TableBuilder {
             highlight_injection: self.highlight_injection,
             filtered_key: self.filtered_key.clone(),
             filter_form: self.filter_form,
+            clipboard: self.clipboard,
             ..Default::default()
         };
 
@@ -236,6 +261,9 @@ pub struct Table<'a> {
     actions: Vec<(UserEvent, Callback)>,
     block_injection: Option<RenderBlockInjection>,
     highlight_injection: Option<RenderHighlightInjection>,
+    clipboard: Option<Rc<RefCell<Clipboard>>>,
+    /// ドラッグを開始した行のインデックス
+    drag_start: Option<usize>,
 }
 
 impl Table<'_> {
@@ -486,30 +514,15 @@ impl WidgetTrait for Table<'_> {
                     return EventResult::Nop;
                 }
 
-                let offset_index = self.state.offset();
-                let offset_bound = self.row_bounds[offset_index];
-                let offset_row = offset_bound.0;
-
-                let header_margin = if self.items.header().is_empty() {
-                    0
-                } else {
-                    ROW_START_INDEX
-                };
-
-                if let Some((index, _)) =
-                    self.row_bounds[offset_index..]
-                        .iter()
-                        .enumerate()
-                        .find(|(_, b)| {
-                            let b = (
-                                b.0.saturating_sub(offset_row) + header_margin,
-                                b.1.saturating_sub(offset_row) + header_margin,
-                            );
-
-                            b.0 <= row && row <= b.1
-                        })
-                {
-                    self.state.select(Some(index + offset_index));
+                if let Some(index) = self.row_index_at(row) {
+                    self.state.select(Some(index));
+
+                    // クリップボードを使う場合は、ドラッグかクリックかをボタンを離したときに判断する
+                    if self.clipboard.is_some() {
+                        self.drag_start = Some(index);
+
+                        return EventResult::Nop;
+                    }
 
                     if let Some(cb) = self.on_select_callback() {
                         return EventResult::Callback(cb);
@@ -519,6 +532,32 @@ impl WidgetTrait for Table<'_> {
                 }
             }
 
+            MouseEventKind::Drag(MouseButton::Left) if self.drag_start.is_some() => {
+                if let Some(index) = self.row_index_at(row) {
+                    self.state.select(Some(index));
+                }
+
+                return EventResult::Nop;
+            }
+
+            MouseEventKind::Up(MouseButton::Left) => {
+                if let Some(start) = self.drag_start.take() {
+                    let end = self.state.selected().unwrap_or(start);
+
+                    if start == end {
+                        if let Some(cb) = self.on_select_callback() {
+                            return EventResult::Callback(cb);
+                        }
+
+                        return EventResult::Ignore;
+                    }
+
+                    self.copy_rows(start.min(end), start.max(end));
+
+                    return EventResult::Nop;
+                }
+            }
+
             MouseEventKind::ScrollDown => {
                 self.select_next(1);
                 return EventResult::Nop;
@@ -534,29 +573,51 @@ impl WidgetTrait for Table<'_> {
     }
 
     fn on_key_event(&mut self, ev: KeyEvent) -> EventResult {
+        let keys = widget_key_bindings();
+
         match self.mode {
             Mode::Normal | Mode::FilterConfirm => match key_event_to_code(ev) {
-                KeyCode::Char('j') | KeyCode::Down | KeyCode::PageDown => {
+                KeyCode::Down | KeyCode::PageDown => {
                     self.select_next(1);
                 }
 
-                KeyCode::Char('k') | KeyCode::Up | KeyCode::PageUp => {
+                _ if keys.down.matches(ev) => {
+                    self.select_next(1);
+                }
+
+                KeyCode::Up | KeyCode::PageUp => {
                     self.select_prev(1);
                 }
 
-                KeyCode::Char('G') | KeyCode::End => {
+                _ if keys.up.matches(ev) => {
+                    self.select_prev(1);
+                }
+
+                KeyCode::End => {
+                    self.select_last();
+                }
+
+                _ if keys.last.matches(ev) => {
                     self.select_last();
                 }
 
-                KeyCode::Char('g') | KeyCode::Home => {
+                KeyCode::Home => {
+                    self.select_first();
+                }
+
+                _ if keys.first.matches(ev) => {
                     self.select_first();
                 }
 
-                KeyCode::Char('/') => {
+                _ if keys.search.matches(ev) => {
                     self.mode.filter_input();
                 }
 
-                KeyCode::Char('q') | KeyCode::Esc if self.mode.is_filter_confirm() => {
+                KeyCode::Esc if self.mode.is_filter_confirm() => {
+                    self.filter_cancel();
+                }
+
+                _ if keys.cancel.matches(ev) && self.mode.is_filter_confirm() => {
                     self.filter_cancel();
                 }
 
@@ -637,6 +698,61 @@ impl WidgetTrait for Table<'_> {
 }
 
 impl Table<'_> {
+    /// 表示領域内の行の位置から、その行に表示しているアイテムのインデックスを求める
+    fn row_index_at(&self, row: usize) -> Option<usize> {
+        let offset_index = self.state.offset();
+        let offset_row = self.row_bounds.get(offset_index)?.0;
+
+        let header_margin = if self.items.header().is_empty() {
+            0
+        } else {
+            ROW_START_INDEX
+        };
+
+        self.row_bounds[offset_index..]
+            .iter()
+            .position(|b| {
+                let b = (
+                    b.0.saturating_sub(offset_row) + header_margin,
+                    b.1.saturating_sub(offset_row) + header_margin,
+                );
+
+                b.0 <= row && row <= b.1
+            })
+            .map(|index| index + offset_index)
+    }
+
+    /// 指定した範囲の行を、カラムをタブで区切ったテキストにする
+    fn rows_text(&self, start: usize, end: usize) -> String {
+        self.items()
+            .get(start..=end)
+            .unwrap_or_default()
+            .iter()
+            .map(|item| {
+                item.item
+                    .iter()
+                    .map(|column| column.styled_graphemes_symbols().concat())
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn copy_rows(&self, start: usize, end: usize) {
+        let Some(clipboard) = &self.clipboard else {
+            return;
+        };
+
+        let contents = self.rows_text(start, end);
+
+        logger!(info, "Clipboard saved '{}'", contents);
+
+        if let Err(e) = clipboard.borrow_mut().set_contents(contents) {
+            logger!(error, "Clipboard Error '{}'", e);
+        }
+    }
+
     fn on_select_callback(&self) -> Option<Callback> {
         self.on_select.clone().and_then(|cb| {
             self.selected_item()
@@ -680,6 +796,40 @@ impl Table<'_> {
     }
 }
 
+impl Table<'_> {
+    /// 区切りの行の見出しを列の幅に関係なく行全体に表示する
+    fn render_separators(&self, f: &mut Frame<'_>, area: Rect) {
+        let selection_width = if self.state.selected().is_some() {
+            HIGHLIGHT_SYMBOL.styled_graphemes_width() as u16
+        } else {
+            0
+        };
+
+        let item_margin = self.items.item_margin();
+
+        let mut y = area.y + self.items.header().height_with_margin();
+
+        for row in self.items.rendered_items().iter().skip(self.state.offset()) {
+            if area.bottom() <= y {
+                break;
+            }
+
+            if let Some(separator) = &row.separator {
+                let separator_area = Rect::new(
+                    area.x + selection_width,
+                    y,
+                    area.width.saturating_sub(selection_width),
+                    1,
+                );
+
+                f.render_widget(separator.clone(), separator_area);
+            }
+
+            y = y.saturating_add(row.height as u16 + item_margin);
+        }
+    }
+}
+
 impl RenderTrait for Table<'_> {
     fn render(&mut self, f: &mut Frame<'_>, is_active: bool, is_mouse_over: bool) {
         let widget_base = if let Some(block_injection) = &self.block_injection {
@@ -699,6 +849,8 @@ impl RenderTrait for Table<'_> {
             let paragraph = Paragraph::new(" No data".dark_gray()).block(block);
             f.render_widget(paragraph, chunk);
         } else {
+            let inner_chunk = block.inner(chunk);
+
             let constraints = constraints(self.items.digits());
 
             let highlight_style = self.render_highlight_style();
@@ -719,6 +871,8 @@ impl RenderTrait for Table<'_> {
             }
 
             f.render_stateful_widget(widget, chunk, &mut self.state);
+
+            self.render_separators(f, inner_chunk);
         }
 
         match self.mode {
@@ -780,6 +934,83 @@ mod tests {
 
             assert_eq!(item.digits(), vec![3, 3])
         }
+
+        #[test]
+        fn 区切りの行はカラム幅に含めない() {
+            let item = InnerItem::builder()
+                .header(["A".to_string(), "B".to_string()])
+                .items([
+                    TableItem::separator(["[ deployments.v1.apps ]".to_string()]),
+                    TableItem::new(["abc".to_string(), "abcde".to_string()], None),
+                ])
+                .max_width(usize::MAX)
+                .build();
+
+            assert_eq!(item.digits(), vec![3, 5])
+        }
+    }
+
+    mod 区切りの行 {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn 見出しを列の幅に関係なく行全体に表示する() {
+            let backend = TestBackend::new(30, 6);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            let mut table = Table::builder()
+                .items([
+                    TableItem::separator(["[ deployments.v1.apps ]".to_string()]),
+                    TableItem::header(["NAME".to_string(), "AGE".to_string()]),
+                    TableItem::new(["app".to_string(), "1d".to_string()], None),
+                ])
+                .build();
+
+            table.update_chunk(Rect::new(0, 0, 30, 6));
+
+            terminal
+                .draw(|f| {
+                    table.render(f, true, false);
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer();
+
+            let actual: Vec<String> = (1..4)
+                .map(|y| (1..29).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+                .collect();
+
+            assert_eq!(
+                actual,
+                vec![
+                    " [ deployments.v1.apps ]    ",
+                    " NAME   AGE                 ",
+                    " app    1d                  ",
+                ]
+            );
+        }
+    }
+
+    mod クリップボード {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn 選択した範囲の行をカラムをタブで区切ったテキストにする() {
+            let table = Table::builder()
+                .items([
+                    TableItem::new(vec!["Item-0".to_string(), "Value-0".to_string()], None),
+                    TableItem::new(
+                        vec!["Item-1".to_string(), "\x1b[31mValue-1\x1b[39m".to_string()],
+                        None,
+                    ),
+                    TableItem::new(vec!["Item-2".to_string(), "Value-2".to_string()], None),
+                ])
+                .build();
+
+            assert_eq!(table.rows_text(1, 2), "Item-1\tValue-1\nItem-2\tValue-2");
+        }
     }
 
     mod 選択アイテムの切り替え {
@@ -1,11 +1,14 @@
-use ratatui::widgets::{Cell, Row};
+use ratatui::{
+    text::Line,
+    widgets::{Cell, Row},
+};
 use std::ops::Deref;
 
 use crate::{
     logger,
     ui::widget::{
         line::convert_lines_to_styled_lines, styled_graphemes::StyledGraphemes, wrap::wrap_line,
-        TableItem,
+        TableItem, TableItemKind,
     },
 };
 
@@ -62,6 +65,8 @@ impl InnerItemBuilder {
 pub struct InnerRow<'a> {
     pub row: Row<'a>,
     pub height: usize,
+    /// 区切りの行の場合は列の幅に関係なく表示する見出し
+    pub separator: Option<Line<'a>>,
 }
 
 #[derive(Debug, Default)]
@@ -140,6 +145,11 @@ impl InnerItem<'_> {
             self.original_items
                 .iter()
                 .filter_map(|item| {
+                    // テーブルの途中の見出しはフィルターの対象外とする
+                    if !item.is_data() {
+                        return Some(item.clone());
+                    }
+
                     let choice = item.item[self.filtered_index()]
                         .styled_graphemes_symbols()
                         .concat();
@@ -161,16 +171,25 @@ impl InnerItem<'_> {
     fn inner_update_rendered_items(&mut self) {
         self.digits = Digits::new(&self.filtered_items, &self.header.original, self.max_width);
 
-        if self.digits.is_empty() {
-            return;
-        }
-
         let mut need_margin = false;
 
         self.rendered_items = self
             .filtered_items
             .iter()
             .map(|row| {
+                if row.kind == TableItemKind::Separator {
+                    let separator =
+                        convert_lines_to_styled_lines(&row.item[..1.min(row.item.len())])
+                            .into_iter()
+                            .next();
+
+                    return InnerRow {
+                        row: Row::default().height(1),
+                        height: 1,
+                        separator,
+                    };
+                }
+
                 let mut row_height = 1;
 
                 let cells: Vec<Cell> = row
@@ -195,6 +214,7 @@ impl InnerItem<'_> {
                 InnerRow {
                     row: Row::new(cells).height(row_height as u16),
                     height: row_height,
+                    separator: None,
                 }
             })
             .collect();
@@ -268,36 +288,39 @@ impl Header<'_> {
     pub fn is_empty(&self) -> bool {
         self.original.is_empty()
     }
+
+    /// 下の余白を含めた見出しの高さ
+    pub fn height_with_margin(&self) -> u16 {
+        if self.is_empty() {
+            0
+        } else {
+            1 + HEADER_BOTTOM_MARGIN
+        }
+    }
 }
 
 #[derive(Debug, Default)]
 struct Digits(Vec<usize>);
 
 impl Digits {
+    /// 区切りの行は列の幅に含めない
     fn new(items: &[TableItem], header: &[String], max_width: usize) -> Self {
         if items.is_empty() {
             return Self::default();
         }
 
-        let mut digits: Vec<usize> = if header.is_empty() {
-            items[0]
-                .item
-                .iter()
-                .map(|i| i.styled_graphemes_width())
-                .collect()
-        } else {
-            header.iter().map(|h| h.styled_graphemes_width()).collect()
-        };
+        let mut digits: Vec<usize> = header.iter().map(|h| h.styled_graphemes_width()).collect();
 
-        for row in items {
+        for row in items
+            .iter()
+            .filter(|row| row.kind != TableItemKind::Separator)
+        {
             for (i, col) in row.item.iter().enumerate() {
                 let len = col.styled_graphemes_width();
-                if digits.len() < i {
-                    break;
-                }
 
-                if digits[i] < len {
-                    digits[i] = len
+                match digits.get_mut(i) {
+                    Some(digit) => *digit = (*digit).max(len),
+                    None => digits.push(len),
                 }
             }
         }
@@ -346,6 +369,46 @@ impl Deref for Digits {
 mod tests {
     use super::*;
 
+    mod filter {
+        use super::*;
+        use pretty_assertions::assert_eq;
+        use std::collections::BTreeMap;
+
+        #[test]
+        fn 見出しの行はフィルターに関係なく残る() {
+            let mut item = InnerItem::builder()
+                .items(vec![
+                    TableItem::separator(vec!["[ pods ]".to_string()]),
+                    TableItem::header(vec!["NAME".to_string()]),
+                    TableItem::new(vec!["foo".to_string()], Some(BTreeMap::default())),
+                    TableItem::new(vec!["bar".to_string()], Some(BTreeMap::default())),
+                ])
+                .build();
+
+            item.update_filter("bar");
+
+            let actual: Vec<_> = item.items().iter().map(|i| i.item[0].as_str()).collect();
+
+            assert_eq!(actual, vec!["[ pods ]", "NAME", "bar"]);
+        }
+
+        #[test]
+        fn metadataを持たないデータの行もフィルターの対象とする() {
+            let mut item = InnerItem::builder()
+                .items(vec![
+                    TableItem::new(vec!["foo".to_string()], None),
+                    TableItem::new(vec!["bar".to_string()], None),
+                ])
+                .build();
+
+            item.update_filter("bar");
+
+            let actual: Vec<_> = item.items().iter().map(|i| i.item[0].as_str()).collect();
+
+            assert_eq!(actual, vec!["bar"]);
+        }
+    }
+
     mod filtered_index {
         use super::*;
         use pretty_assertions::assert_eq;
@@ -1,3 +1,4 @@
+mod highlight_rule;
 mod item;
 mod render;
 mod search;
@@ -18,6 +19,7 @@ use crate::{
     message::UserEvent,
     ui::{
         event::{Callback, EventResult},
+        key::{widget_key_bindings, KeyBinding},
         key_event_to_code,
         util::{MousePosition, RectContainsPoint},
     },
@@ -34,6 +36,7 @@ use self::{
     render::{Render, Scroll},
 };
 
+pub use highlight_rule::{TextHighlightRule, TextHighlightScope};
 pub use item::{SearchHighlightFocusStyle, SearchHighlightMatchesStyle, SearchHighlightStyle};
 pub use render::SelectionStyle;
 pub use search::{SearchForm, SearchFormTheme};
@@ -178,6 +181,8 @@ pub struct TextBuilder {
     block_injection: Option<RenderBlockInjection>,
     actions: Vec<(UserEvent, Callback)>,
     clipboard: Option<Rc<RefCell<Clipboard>>>,
+    max_lines: Option<usize>,
+    highlight_rules: Vec<TextHighlightRule>,
 }
 
 impl TextBuilder {
@@ -216,6 +221,20 @@ impl TextBuilder {
         self
     }
 
+    /// 保持する行数の上限
+    /// Noneのときは上限なし
+    pub fn max_lines(mut self, max_lines: Option<usize>) -> Self {
+        self.max_lines = max_lines;
+        self
+    }
+
+    /// 正規表現にマッチした文字列にスタイルを適用する
+    /// ANSIエスケープシーケンスによるスタイルに重ねて適用する
+    pub fn highlight_rules(mut self, highlight_rules: Vec<TextHighlightRule>) -> Self {
+        self.highlight_rules = highlight_rules;
+        self
+    }
+
     pub fn theme(mut self, theme: TextTheme) -> Self {
         self.theme = theme;
         self
@@ -230,6 +249,19 @@ impl TextBuilder {
         self
     }
 
+    /// 操作に割り当てた全てのキーでコールバックを呼び出す
+    pub fn key_action<F>(self, key_binding: &KeyBinding, cb: F) -> Self
+    where
+        F: Into<Callback>,
+    {
+        let cb = cb.into();
+
+        key_binding
+            .keys()
+            .iter()
+            .fold(self, |builder, key| builder.action(*key, cb.clone()))
+    }
+
     pub fn block_injection<F>(mut self, block_injection: F) -> Self
     where
         F: Into<RenderBlockInjection>,
@@ -248,7 +280,11 @@ impl TextBuilder {
             id: self.id,
             widget_base: self.widget_base,
             search_form: self.search_form,
-            item: TextItem::new(self.item, None, self.theme.search.clone()),
+            item: TextItem::new(self.item, None, self.theme.search.clone())
+                .with_max_lines(self.max_lines)
+                .with_highlight_rules(self.highlight_rules.clone()),
+            max_lines: self.max_lines,
+            highlight_rules: self.highlight_rules,
             wrap: self.wrap,
             follow: self.follow,
             theme: self.theme,
@@ -277,6 +313,8 @@ pub struct Text {
     block_injection: Option<RenderBlockInjection>,
     actions: Vec<(UserEvent, Callback)>,
     clipboard: Option<Rc<RefCell<Clipboard>>>,
+    max_lines: Option<usize>,
+    highlight_rules: Vec<TextHighlightRule>,
 }
 
 impl Text {
@@ -458,6 +496,18 @@ impl Text {
     fn is_bottom(&self) -> bool {
         self.scroll_y_last_index() <= self.scroll.y
     }
+
+    /// 古い行が削除されたとき、表示している内容と範囲選択の位置が変わらないように削除した行数分だけ上にずらす
+    fn shift_up(&mut self, lines: usize) {
+        self.scroll.y = self.scroll.y.saturating_sub(lines);
+
+        if let Some(highlight_content) = &mut self.highlight_content {
+            let area = &mut highlight_content.area;
+
+            area.start.y = area.start.y.saturating_sub(lines);
+            area.end.y = area.end.y.saturating_sub(lines);
+        }
+    }
 }
 
 impl Text {
@@ -465,6 +515,12 @@ impl Text {
         (self.scroll.y, self.scroll_y_last_index())
     }
 
+    /// 保持している行の文字列を返す
+    /// ANSIエスケープシーケンスは取り除かない
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.item.lines()
+    }
+
     fn match_action(&self, ev: UserEvent) -> Option<&Callback> {
         self.actions
             .iter()
@@ -534,12 +590,16 @@ impl WidgetTrait for Text {
     fn append_widget_item(&mut self, item: Item) {
         let is_bottom = self.is_bottom();
 
-        match item {
+        let evicted = match item {
             Item::Single(i) => self.item.push(i),
             Item::Array(i) => self.item.extend(i),
             _ => {
                 unreachable!()
             }
+        };
+
+        if 0 < evicted {
+            self.shift_up(evicted);
         }
 
         if self.follow && is_bottom {
@@ -695,13 +755,23 @@ impl WidgetTrait for Text {
     fn on_key_event(&mut self, ev: KeyEvent) -> EventResult {
         use KeyCode::*;
 
+        let keys = widget_key_bindings();
+
         match self.mode {
             Mode::Normal | Mode::SearchConfirm => match key_event_to_code(ev) {
-                Char('j') | Down => {
+                Down => {
                     self.select_next(1);
                 }
 
-                Char('k') | Up => {
+                _ if keys.down.matches(ev) => {
+                    self.select_next(1);
+                }
+
+                Up => {
+                    self.select_prev(1);
+                }
+
+                _ if keys.up.matches(ev) => {
                     self.select_prev(1);
                 }
 
@@ -713,11 +783,19 @@ impl WidgetTrait for Text {
                     self.select_prev(self.chunk.height as usize);
                 }
 
-                Char('G') | End => {
+                End => {
+                    self.select_last();
+                }
+
+                _ if keys.last.matches(ev) => {
                     self.select_last();
                 }
 
-                Char('g') | Home => {
+                Home => {
+                    self.select_first();
+                }
+
+                _ if keys.first.matches(ev) => {
                     self.select_first();
                 }
 
@@ -729,19 +807,23 @@ impl WidgetTrait for Text {
                     self.scroll_right(1);
                 }
 
-                Char('/') => {
+                _ if keys.search.matches(ev) => {
                     self.search();
                 }
 
-                Char('q') | Esc if self.mode.is_search_confirm() => {
+                Esc if self.mode.is_search_confirm() => {
                     self.search_cancel();
                 }
 
-                Char('n') if !self.mode.is_normal() => {
+                _ if keys.cancel.matches(ev) && self.mode.is_search_confirm() => {
+                    self.search_cancel();
+                }
+
+                _ if keys.next_match.matches(ev) && !self.mode.is_normal() => {
                     self.search_next();
                 }
 
-                Char('N') if !self.mode.is_normal() => {
+                _ if keys.prev_match.matches(ev) && !self.mode.is_normal() => {
                     self.search_prev();
                 }
 
@@ -811,7 +893,9 @@ impl WidgetTrait for Text {
             None
         };
 
-        self.item = TextItem::new(vec![], wrap_width, self.theme.search.clone());
+        self.item = TextItem::new(vec![], wrap_width, self.theme.search.clone())
+            .with_max_lines(self.max_lines)
+            .with_highlight_rules(self.highlight_rules.clone());
         self.search_cancel();
 
         *(self.widget_base.append_title_mut()) = None;
@@ -957,6 +1041,41 @@ mod tests {
                     assert_eq!(text.scroll.y, 0);
                 }
             }
+
+            mod 上限超過 {
+                use super::*;
+
+                #[test]
+                fn 古い行が削除されたとき表示している行が変わらないようにスクロール位置を調整() {
+                    let mut text = Text::builder()
+                        .items((0..10).map(|i| i.to_string()).collect::<Vec<_>>())
+                        .max_lines(Some(10))
+                        .build();
+
+                    text.update_chunk(Rect::new(0, 0, 10, 7));
+
+                    text.select_first();
+                    text.select_next(3);
+
+                    text.highlight_content = Some(HighlightContent {
+                        area: HighlightArea::new().start(0, 4).end(1, 5),
+                        follow: false,
+                    });
+
+                    text.append_widget_item(Item::Array(vec![
+                        LiteralItem::new("10", None),
+                        LiteralItem::new("11", None),
+                    ]));
+
+                    assert_eq!(text.scroll.y, 1);
+
+                    let area = text.highlight_content.as_ref().unwrap().area;
+
+                    assert_eq!((area.start.y, area.end.y), (2, 3));
+
+                    assert_eq!(text.lines().next(), Some("2"));
+                }
+            }
         }
 
         #[test]
@@ -0,0 +1,136 @@
+use ratatui::style::Style;
+use regex::Regex;
+
+use crate::ui::widget::styled_graphemes::StyledGrapheme;
+
+/// 正規表現にマッチした文字列に適用するスタイル
+#[derive(Debug, Clone)]
+pub struct TextHighlightRule {
+    pub regex: Regex,
+    pub style: Style,
+    pub scope: TextHighlightScope,
+}
+
+/// スタイルを適用する範囲
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextHighlightScope {
+    /// マッチした箇所のみ
+    #[default]
+    Match,
+
+    /// マッチした行全体
+    Line,
+}
+
+/// ANSIエスケープシーケンスを解釈した後のスタイルにルールのスタイルを重ねる
+///
+/// 指定していない属性は元のスタイルを引き継ぐ
+pub fn apply_highlight_rules(graphemes: &mut [StyledGrapheme], rules: &[TextHighlightRule]) {
+    if rules.is_empty() {
+        return;
+    }
+
+    let mut line = String::new();
+
+    // 各graphemeの開始位置（バイト）
+    let offsets: Vec<usize> = graphemes
+        .iter()
+        .map(|g| {
+            let offset = line.len();
+            line.push_str(g.symbol());
+            offset
+        })
+        .collect();
+
+    for rule in rules {
+        match rule.scope {
+            TextHighlightScope::Match => {
+                for m in rule.regex.find_iter(&line) {
+                    let start = offsets.partition_point(|offset| *offset < m.start());
+                    let end = offsets.partition_point(|offset| *offset < m.end());
+
+                    graphemes[start..end]
+                        .iter_mut()
+                        .for_each(|g| *g.style_mut() = g.style().patch(rule.style));
+                }
+            }
+            TextHighlightScope::Line => {
+                if rule.regex.is_match(&line) {
+                    graphemes
+                        .iter_mut()
+                        .for_each(|g| *g.style_mut() = g.style().patch(rule.style));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use ratatui::style::{Color, Modifier};
+
+    use crate::ui::widget::styled_graphemes::StyledGraphemes as _;
+
+    fn styles(graphemes: &[StyledGrapheme]) -> Vec<Style> {
+        graphemes.iter().map(|g| *g.style()).collect()
+    }
+
+    #[test]
+    fn マッチした箇所のスタイルに重ねて適用する() {
+        let line = "\x1b[32mok\x1b[0m id=ab";
+
+        let mut graphemes = line.styled_graphemes();
+
+        let rules = [
+            TextHighlightRule {
+                regex: Regex::new("k id=a").unwrap(),
+                style: Style::default().add_modifier(Modifier::UNDERLINED),
+                scope: TextHighlightScope::Match,
+            },
+            TextHighlightRule {
+                regex: Regex::new("ab").unwrap(),
+                style: Style::default().fg(Color::Red),
+                scope: TextHighlightScope::Match,
+            },
+        ];
+
+        apply_highlight_rules(&mut graphemes, &rules);
+
+        let underlined = Style::default().add_modifier(Modifier::UNDERLINED);
+
+        assert_eq!(
+            styles(&graphemes),
+            vec![
+                Style::default().fg(Color::Green),
+                underlined.fg(Color::Green),
+                underlined,
+                underlined,
+                underlined,
+                underlined,
+                underlined.fg(Color::Red),
+                Style::default().fg(Color::Red),
+            ]
+        );
+    }
+
+    #[test]
+    fn 行全体に適用する場合はマッチした行のすべての文字に適用する() {
+        let mut graphemes = "WARN disk".styled_graphemes();
+
+        let rules = [TextHighlightRule {
+            regex: Regex::new("^WARN").unwrap(),
+            style: Style::default().fg(Color::Yellow),
+            scope: TextHighlightScope::Line,
+        }];
+
+        apply_highlight_rules(&mut graphemes, &rules);
+
+        assert_eq!(
+            styles(&graphemes),
+            vec![Style::default().fg(Color::Yellow); 9]
+        );
+    }
+}
@@ -1,10 +1,16 @@
-use super::wrap::WrapTrait;
+use super::{
+    highlight_rule::{apply_highlight_rules, TextHighlightRule},
+    wrap::WrapTrait,
+};
 use crate::ui::widget::{
     styled_graphemes::{StyledGrapheme, StyledGraphemes},
     LiteralItem,
 };
 use ratatui::style::{Color, Modifier, Style};
-use std::ops::{Deref, Range};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    ops::{Deref, Range},
+};
 
 use search::Search;
 
@@ -31,7 +37,7 @@ struct Highlights {
     word: String,
 
     /// wordにマッチする場所に関するデータ
-    item: Vec<Highlight>,
+    item: VecDeque<Highlight>,
 
     /// 選択しているインデックス
     selected_index: usize,
@@ -96,12 +102,31 @@ impl Deref for SearchHighlightFocusStyle {
 #[derive(Debug, Default)]
 pub struct TextItem {
     /// 1行分のgraphemesに分割した文字列リスト
-    lines: Vec<Line>,
+    lines: VecDeque<Line>,
 
     /// 折り返しを考慮した描画のためのデータリスト
     /// item設定時に生成される
+    ///
+    /// 描画にはスライスが必要なため、削除した行は先頭に残しておきまとめて詰める
     wrapped_lines: Vec<WrappedLine>,
 
+    /// 先頭の行のインデックス
+    ///
+    /// Line、WrappedLine、Highlightのインデックスと行番号は削除した行を含めて数えるため、
+    /// 上限を超えて古い行を削除しても残った行の値を書き換えずに済む
+    first_line_index: usize,
+
+    /// 先頭の折り返し後の行の行番号
+    first_line_number: usize,
+
+    /// wrapped_linesの先頭の要素の行番号
+    ///
+    /// 削除済みでまだ詰めていない要素を含む
+    wrapped_lines_offset: usize,
+
+    /// 折り返し後の行の文字数ごとの行数
+    chars_count: CharsCount,
+
     /// ハイライト情報
     /// - ハイライト箇所の復旧に使用
     /// - ハイライト箇所へのジャンプに使用
@@ -110,11 +135,15 @@ pub struct TextItem {
     /// 折り返しサイズ
     wrap_width: Option<usize>,
 
-    /// 1行あたりの最大文字数
-    max_chars: usize,
-
     /// ハイライトスタイル
     highlight_style: SearchHighlightStyle,
+
+    /// 保持する行数の上限
+    /// 上限を超えた場合は古い行から削除する
+    max_lines: Option<usize>,
+
+    /// 正規表現にマッチした文字列に適用するスタイル
+    highlight_rules: Vec<TextHighlightRule>,
 }
 
 type Graphemes = Vec<StyledGrapheme>;
@@ -126,36 +155,60 @@ impl TextItem {
         wrap_width: Option<usize>,
         highlight_style: SearchHighlightStyle,
     ) -> Self {
-        let (lines, wrapped_lines) = Self::new_or_extend(literal_item, wrap_width, 0, 0);
+        let (lines, wrapped_lines) = Self::new_or_extend(literal_item, wrap_width, 0, 0, &[]);
 
         let wrapped_lines: Vec<_> = wrapped_lines.into_iter().flatten().collect();
 
-        let max_chars = wrapped_lines
-            .iter()
-            .map(|l| l.line().len())
-            .max()
-            .unwrap_or_default();
+        let chars_count = CharsCount::from_wrapped_lines(&wrapped_lines);
 
         Self {
-            lines,
+            lines: lines.into(),
             wrapped_lines,
+            first_line_index: 0,
+            first_line_number: 0,
+            wrapped_lines_offset: 0,
+            chars_count,
             highlights: None,
             wrap_width,
-            max_chars,
             highlight_style,
+            max_lines: None,
+            highlight_rules: Vec::new(),
         }
     }
 
-    pub fn update(&mut self, item: Vec<LiteralItem>) {
+    pub fn with_max_lines(mut self, max_lines: Option<usize>) -> Self {
+        self.max_lines = max_lines;
+        self.evict_overflow();
+        self
+    }
+
+    /// 既存の行と、以降に追加する行にスタイルを適用する
+    pub fn with_highlight_rules(mut self, highlight_rules: Vec<TextHighlightRule>) -> Self {
+        self.lines
+            .iter_mut()
+            .for_each(|line| apply_highlight_rules(&mut line.graphemes, &highlight_rules));
+
+        self.highlight_rules = highlight_rules;
+        self
+    }
+
+    pub fn update(&mut self, mut item: Vec<LiteralItem>) {
         let wrap_width = self.wrap_width;
         let highlights = self.highlights.clone();
+        let prev_line_number = self.highlight_selected_line_number();
         let highlight_style = self.highlight_style.clone();
+        let max_lines = self.max_lines;
+        let highlight_rules = std::mem::take(&mut self.highlight_rules);
 
-        let mut new = Self::new(item, wrap_width, highlight_style);
+        if let Some(max_lines) = max_lines {
+            item.drain(..item.len().saturating_sub(max_lines));
+        }
 
-        if let Some(highlights) = highlights {
-            let prev_line_number = highlights.item[highlights.selected_index].line_number;
+        let mut new = Self::new(item, wrap_width, highlight_style)
+            .with_max_lines(max_lines)
+            .with_highlight_rules(highlight_rules);
 
+        if let (Some(highlights), Some(prev_line_number)) = (highlights, prev_line_number) {
             new.highlight(&highlights.word);
 
             new.select_nearest_highlight(prev_line_number);
@@ -164,10 +217,13 @@ impl TextItem {
         *self = new;
     }
 
-    pub fn push(&mut self, item: LiteralItem) {
-        let graphemes = item.item.styled_graphemes();
+    /// 1行追加し、上限を超えて削除した折り返し後の行数を返す
+    pub fn push(&mut self, item: LiteralItem) -> usize {
+        let mut graphemes = item.item.styled_graphemes();
 
-        let line_number = self.wrapped_lines.len();
+        apply_highlight_rules(&mut graphemes, &self.highlight_rules);
+
+        let line_number = self.next_line_number();
 
         #[allow(clippy::needless_collect)]
         let wrappers: Wrappers = graphemes
@@ -175,7 +231,7 @@ impl TextItem {
             .map(|w| w as *const [StyledGrapheme])
             .collect();
 
-        let line_index = self.lines.len();
+        let line_index = self.next_line_index();
 
         let wrapped_lines: Vec<WrappedLine> = wrappers
             .into_iter()
@@ -185,12 +241,7 @@ impl TextItem {
             })
             .collect();
 
-        self.max_chars = wrapped_lines
-            .iter()
-            .map(|l| l.line().len())
-            .max()
-            .unwrap_or_default()
-            .max(self.max_chars);
+        self.chars_count.extend(&wrapped_lines);
 
         let line = Line {
             line_index,
@@ -200,55 +251,56 @@ impl TextItem {
             wrapped_lines: line_number..(line_number + wrapped_lines.len()),
         };
 
-        self.lines.push(line);
+        self.lines.push_back(line);
         self.wrapped_lines.extend(wrapped_lines);
 
         if let Some(highlights) = &mut self.highlights {
-            let pushed_line_index = self.lines.len() - 1;
-            let line = &mut self.lines[pushed_line_index];
+            let offset = self.wrapped_lines_offset;
+            let line = self.lines.back_mut().expect("pushed line");
 
             if let Some(hls) = line.highlight_word(
                 &highlights.word,
-                &self.wrapped_lines[line.wrapped_lines.clone()],
+                wrapped_slice(&self.wrapped_lines, offset, &line.wrapped_lines),
                 *self.highlight_style.matches,
             ) {
                 highlights.item.extend(hls);
             }
         }
+
+        self.evict_overflow()
     }
 
-    pub fn extend(&mut self, item: Vec<LiteralItem>) {
+    /// 複数行追加し、上限を超えて削除した折り返し後の行数を返す
+    pub fn extend(&mut self, item: Vec<LiteralItem>) -> usize {
         let extend_len = item.len();
 
         let (lines, wrapped_lines) = Self::new_or_extend(
             item,
             self.wrap_width,
-            self.wrapped_lines.len(),
-            self.lines.len(),
+            self.next_line_number(),
+            self.next_line_index(),
+            &self.highlight_rules,
         );
 
-        self.max_chars = wrapped_lines
+        wrapped_lines
             .iter()
-            .flatten()
-            .map(|l| l.line().len())
-            .max()
-            .unwrap_or_default()
-            .max(self.max_chars);
+            .for_each(|wrapped_lines| self.chars_count.extend(wrapped_lines));
 
         self.lines.extend(lines);
         self.wrapped_lines
             .extend(wrapped_lines.into_iter().flatten());
 
         if let Some(highlights) = &mut self.highlights {
+            let offset = self.wrapped_lines_offset;
             let lines_len = self.lines.len();
-            let lines = &mut self.lines[(lines_len - extend_len)..];
 
-            let hls: Vec<Highlight> = lines
-                .iter_mut()
+            let hls: Vec<Highlight> = self
+                .lines
+                .range_mut((lines_len - extend_len)..)
                 .filter_map(|line| {
                     line.highlight_word(
                         &highlights.word,
-                        &self.wrapped_lines[line.wrapped_lines.clone()],
+                        wrapped_slice(&self.wrapped_lines, offset, &line.wrapped_lines),
                         *self.highlight_style.matches,
                     )
                 })
@@ -257,6 +309,88 @@ impl TextItem {
 
             highlights.item.extend(hls);
         }
+
+        self.evict_overflow()
+    }
+
+    /// 次に追加する行のインデックス
+    fn next_line_index(&self) -> usize {
+        self.first_line_index + self.lines.len()
+    }
+
+    /// 次に追加する折り返し後の行の行番号
+    fn next_line_number(&self) -> usize {
+        self.wrapped_lines_offset + self.wrapped_lines.len()
+    }
+
+    /// 上限を超えた古い行を削除し、削除した折り返し後の行数を返す
+    ///
+    /// 残った行のインデックスと行番号は書き換えず、先頭の位置だけを進める
+    fn evict_overflow(&mut self) -> usize {
+        let Some(max_lines) = self.max_lines else {
+            return 0;
+        };
+
+        if self.lines.len() <= max_lines {
+            return 0;
+        }
+
+        let evict_len = self.lines.len() - max_lines;
+
+        let evict_line_number = self
+            .lines
+            .get(evict_len)
+            .map(|line| line.line_number)
+            .unwrap_or(self.next_line_number());
+
+        let evict_wrapped_len = evict_line_number - self.first_line_number;
+
+        self.chars_count.remove(wrapped_slice(
+            &self.wrapped_lines,
+            self.wrapped_lines_offset,
+            &(self.first_line_number..evict_line_number),
+        ));
+
+        // Lineを移動してもgraphemesのヒープ領域は移動しないため、WrappedLineのポインターは有効なまま
+        self.lines.drain(..evict_len);
+
+        self.first_line_index += evict_len;
+        self.first_line_number = evict_line_number;
+
+        // 削除済みの要素が残りの要素より多くなったときにまとめて詰める
+        let evicted_len = self.first_line_number - self.wrapped_lines_offset;
+
+        if self.wrapped_lines.len() - evicted_len <= evicted_len {
+            self.wrapped_lines.drain(..evicted_len);
+            self.wrapped_lines_offset = self.first_line_number;
+        }
+
+        let mut focus_index = None;
+
+        if let Some(highlights) = &mut self.highlights {
+            // ハイライトは行の順番に並んでいる
+            let evict_highlight_len = highlights
+                .item
+                .partition_point(|hl| hl.line_index < self.first_line_index);
+
+            highlights.item.drain(..evict_highlight_len);
+
+            if highlights.item.is_empty() {
+                self.highlights = None;
+            } else if highlights.selected_index < evict_highlight_len {
+                // 選択していたハイライトが削除された場合は先頭のハイライトを選択する
+                highlights.selected_index = 0;
+                focus_index = Some(0);
+            } else {
+                highlights.selected_index -= evict_highlight_len;
+            }
+        }
+
+        if let Some(index) = focus_index {
+            self.highlight_focus(index);
+        }
+
+        evict_wrapped_len
     }
 
     /// Vec<LiteralItem>からLine, WrappedLineを生成する
@@ -269,10 +403,15 @@ impl TextItem {
         wrap_width: Option<usize>,
         start_line_number: usize,
         lines_len: usize,
+        highlight_rules: &[TextHighlightRule],
     ) -> (Vec<Line>, Vec<Vec<WrappedLine>>) {
         let graphemes_list: Vec<Graphemes> = literal_item
             .iter()
-            .map(|item| item.item.styled_graphemes())
+            .map(|item| {
+                let mut graphemes = item.item.styled_graphemes();
+                apply_highlight_rules(&mut graphemes, highlight_rules);
+                graphemes
+            })
             .collect();
 
         #[allow(clippy::needless_collect)]
@@ -331,7 +470,14 @@ impl TextItem {
     }
 
     pub fn max_chars(&self) -> usize {
-        self.max_chars
+        self.chars_count.max()
+    }
+
+    /// 折り返す前の行の文字列を返す
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines
+            .iter()
+            .map(|line| line.literal_item.item.as_str())
     }
 }
 
@@ -339,13 +485,15 @@ impl TextItem {
     pub fn highlight(&mut self, word: &str) {
         self.clear_highlight();
 
-        let highlight_words: Vec<_> = self
+        let offset = self.wrapped_lines_offset;
+
+        let highlight_words: VecDeque<_> = self
             .lines
             .iter_mut()
             .filter_map(|line| {
                 line.highlight_word(
                     word,
-                    &self.wrapped_lines[line.wrapped_lines.clone()],
+                    wrapped_slice(&self.wrapped_lines, offset, &line.wrapped_lines),
                     *self.highlight_style.matches,
                 )
             })
@@ -366,7 +514,7 @@ impl TextItem {
     pub fn clear_highlight(&mut self) {
         if let Some(highlights) = &mut self.highlights {
             highlights.item.iter().for_each(|hl| {
-                let line = &mut self.lines[hl.line_index];
+                let line = &mut self.lines[hl.line_index - self.first_line_index];
                 line.clear_highlight(hl.range.clone(), &hl.styles);
             });
         }
@@ -378,7 +526,7 @@ impl TextItem {
         if let Some(highlights) = &mut self.highlights {
             let hl = &highlights.item[index];
 
-            let line = &mut self.lines[hl.line_index];
+            let line = &mut self.lines[hl.line_index - self.first_line_index];
             let graphemes = &mut line.graphemes[hl.range.clone()];
 
             graphemes
@@ -391,7 +539,7 @@ impl TextItem {
         if let Some(highlights) = &mut self.highlights {
             let hl = &highlights.item[index];
 
-            let line = &mut self.lines[hl.line_index];
+            let line = &mut self.lines[hl.line_index - self.first_line_index];
             let graphemes = &mut line.graphemes[hl.range.clone()];
 
             graphemes
@@ -400,13 +548,15 @@ impl TextItem {
 
             highlights.selected_index = index;
 
-            Some(hl.line_number)
+            Some(hl.line_number - self.first_line_number)
         } else {
             None
         }
     }
 
     pub fn select_nearest_highlight(&mut self, scroll_index: usize) -> Option<usize> {
+        let line_number = self.first_line_number + scroll_index;
+
         if let Some(highlights) = &mut self.highlights {
             let index = highlights.selected_index;
 
@@ -414,7 +564,7 @@ impl TextItem {
                 .item
                 .iter()
                 .enumerate()
-                .min_by_key(|(_, hl)| hl.line_number.abs_diff(scroll_index))
+                .min_by_key(|(_, hl)| hl.line_number.abs_diff(line_number))
                 .map(|(i, _)| i)
                 .unwrap_or(0);
 
@@ -473,13 +623,13 @@ impl TextItem {
     pub fn highlight_selected_line_number(&self) -> Option<usize> {
         self.highlights
             .as_ref()
-            .map(|h| h.item[h.selected_index].line_number)
+            .map(|h| h.item[h.selected_index].line_number - self.first_line_number)
     }
 }
 
 impl TextItem {
     pub fn wrapped_lines(&self) -> &[WrappedLine] {
-        &self.wrapped_lines
+        &self.wrapped_lines[(self.first_line_number - self.wrapped_lines_offset)..]
     }
 
     pub fn rewrap(&mut self, wrap_width: usize) {
@@ -497,13 +647,15 @@ impl TextItem {
             })
             .collect();
 
+        // 削除済みの要素は詰め、先頭の行番号はそのまま引き継ぐ
+        let offset = self.first_line_number;
+
         let mut wrapped_lines = Vec::with_capacity(wrappers_list.len());
-        let mut line_number = 0;
+        let mut line_number = offset;
         self.lines
             .iter_mut()
             .zip(wrappers_list)
-            .enumerate()
-            .for_each(|(i, (line, wrapped))| {
+            .for_each(|(line, wrapped)| {
                 let wrapped_len = wrapped.len();
 
                 line.line_number = line_number;
@@ -512,7 +664,7 @@ impl TextItem {
                 let new_wrapped_lines: Vec<WrappedLine> = wrapped
                     .into_iter()
                     .map(|w| WrappedLine {
-                        line_index: i,
+                        line_index: line.line_index,
                         slice_ptr: w,
                     })
                     .collect();
@@ -523,14 +675,16 @@ impl TextItem {
             });
 
         self.wrapped_lines = wrapped_lines.into_iter().flatten().collect();
+        self.wrapped_lines_offset = offset;
+        self.chars_count = CharsCount::from_wrapped_lines(&self.wrapped_lines);
 
         if let Some(highlights) = &mut self.highlights {
             highlights.item.iter_mut().for_each(|hl| {
-                let line = &self.lines[hl.line_index];
+                let line = &self.lines[hl.line_index - self.first_line_index];
 
                 hl.line_number = highlight_line_number(
                     hl.range.start,
-                    &self.wrapped_lines[line.wrapped_lines.clone()],
+                    wrapped_slice(&self.wrapped_lines, offset, &line.wrapped_lines),
                     line.line_number,
                 );
             });
@@ -538,6 +692,58 @@ impl TextItem {
     }
 }
 
+/// 行番号の範囲に対応するWrappedLineのスライスを返す
+///
+/// offsetはwrapped_linesの先頭の要素の行番号
+fn wrapped_slice<'a>(
+    wrapped_lines: &'a [WrappedLine],
+    offset: usize,
+    range: &Range<usize>,
+) -> &'a [WrappedLine] {
+    &wrapped_lines[(range.start - offset)..(range.end - offset)]
+}
+
+/// 折り返し後の行の文字数ごとの行数
+///
+/// 古い行を削除したときに、すべての行を走査せずに最大文字数を求めるために使う
+#[derive(Debug, Default)]
+struct CharsCount(BTreeMap<usize, usize>);
+
+impl CharsCount {
+    fn from_wrapped_lines(wrapped_lines: &[WrappedLine]) -> Self {
+        let mut chars_count = Self::default();
+        chars_count.extend(wrapped_lines);
+        chars_count
+    }
+
+    fn extend(&mut self, wrapped_lines: &[WrappedLine]) {
+        wrapped_lines.iter().for_each(|wrapped_line| {
+            *self.0.entry(wrapped_line.line().len()).or_default() += 1;
+        });
+    }
+
+    fn remove(&mut self, wrapped_lines: &[WrappedLine]) {
+        wrapped_lines.iter().for_each(|wrapped_line| {
+            let chars = wrapped_line.line().len();
+
+            if let Some(count) = self.0.get_mut(&chars) {
+                *count -= 1;
+
+                if *count == 0 {
+                    self.0.remove(&chars);
+                }
+            }
+        });
+    }
+
+    fn max(&self) -> usize {
+        self.0
+            .last_key_value()
+            .map(|(chars, _)| *chars)
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug)]
 struct Line {
     line_index: usize,
@@ -547,7 +753,6 @@ struct Line {
     /// ベースとなる１行分の文字列データ
     ///
     /// この文字列のポインターを駆使していく
-    #[allow(dead_code)]
     literal_item: LiteralItem,
 
     /// 目でみたときの１文字ずつに分割した配列
@@ -779,6 +984,165 @@ mod tests {
             assert_eq!(wrapped_lines[5].slice_ptr, &lines[2].graphemes[2..]);
         }
 
+        #[test]
+        fn 上限を超えたとき古い行を削除して先頭の位置を進める() {
+            let mut item = TextItem::new(
+                vec![LiteralItem::new("0123456789", None)],
+                Some(5),
+                SearchHighlightStyle::default(),
+            )
+            .with_max_lines(Some(2));
+
+            let evicted = item.extend(vec![
+                LiteralItem::new("abc", None),
+                LiteralItem::new("あいうえ", None),
+            ]);
+
+            assert_eq!(evicted, 2);
+
+            assert_eq!(item.first_line_index, 1);
+            assert_eq!(item.first_line_number, 2);
+
+            let wrapped_lines = item.wrapped_lines();
+            let lines = &item.lines;
+
+            assert_eq!(lines.len(), 2);
+
+            assert_eq!(lines[0].literal_item.item, "abc");
+            assert_eq!(lines[0].line_index, 1);
+            assert_eq!(lines[0].line_number, 2);
+            assert_eq!(lines[0].wrapped_lines, 2..3);
+
+            assert_eq!(lines[1].line_index, 2);
+            assert_eq!(lines[1].line_number, 3);
+            assert_eq!(lines[1].wrapped_lines, 3..5);
+
+            assert_eq!(wrapped_lines.len(), 3);
+            assert_eq!(wrapped_lines[0].line_index, 1);
+            assert_eq!(wrapped_lines[0].slice_ptr, &lines[0].graphemes[..]);
+            assert_eq!(wrapped_lines[1].line_index, 2);
+            assert_eq!(wrapped_lines[1].slice_ptr, &lines[1].graphemes[..2]);
+            assert_eq!(wrapped_lines[2].line_index, 2);
+            assert_eq!(wrapped_lines[2].slice_ptr, &lines[1].graphemes[2..]);
+        }
+
+        #[test]
+        fn 上限を超えて削除した行が残りの行以上になったとき折り返し後の行を詰める() {
+            let mut item = TextItem::new(
+                vec![LiteralItem::new("a", None), LiteralItem::new("b", None)],
+                None,
+                SearchHighlightStyle::default(),
+            )
+            .with_max_lines(Some(2));
+
+            item.push(LiteralItem::new("c", None));
+
+            assert_eq!(item.wrapped_lines.len(), 3);
+            assert_eq!(item.wrapped_lines_offset, 0);
+
+            item.push(LiteralItem::new("d", None));
+
+            assert_eq!(item.wrapped_lines.len(), 2);
+            assert_eq!(item.wrapped_lines_offset, 2);
+
+            let actual: Vec<String> = item
+                .wrapped_lines()
+                .iter()
+                .map(|line| line.line().iter().map(StyledGrapheme::symbol).collect())
+                .collect();
+
+            assert_eq!(actual, vec!["c", "d"]);
+        }
+
+        #[test]
+        fn 上限を超えて削除した行の文字数を最大文字数から除く() {
+            let mut item = TextItem::new(
+                vec![
+                    LiteralItem::new("0123456789", None),
+                    LiteralItem::new("01234", None),
+                ],
+                None,
+                SearchHighlightStyle::default(),
+            )
+            .with_max_lines(Some(2));
+
+            assert_eq!(item.max_chars(), 10);
+
+            item.push(LiteralItem::new("012", None));
+
+            assert_eq!(item.max_chars(), 5);
+        }
+
+        #[test]
+        fn 上限を超えたとき削除した行のハイライトを取り除く() {
+            let mut item = TextItem::new(
+                vec![
+                    LiteralItem::new("hello world", None),
+                    LiteralItem::new("hoge world", None),
+                ],
+                Some(5),
+                SearchHighlightStyle::default(),
+            )
+            .with_max_lines(Some(2));
+
+            item.highlight("world");
+
+            item.push(LiteralItem::new("world", None));
+
+            let highlights = item.highlights.as_ref().unwrap();
+
+            assert_eq!(
+                highlights
+                    .item
+                    .iter()
+                    .map(|hl| (hl.line_index, hl.range.clone(), hl.line_number))
+                    .collect::<Vec<_>>(),
+                vec![(1, 5..10, 3), (2, 0..5, 5)]
+            );
+
+            assert_eq!(item.highlight_status(), (1, 2));
+            assert_eq!(item.highlight_selected_line_number(), Some(0));
+
+            assert_eq!(
+                item.lines[0].graphemes[5..10]
+                    .iter()
+                    .map(|g| *g.style())
+                    .collect::<Vec<_>>(),
+                vec![*SearchHighlightFocusStyle::default(); 5]
+            );
+        }
+
+        #[test]
+        fn 上限を超えてハイライトがなくなったときハイライトを解除する() {
+            let mut item = TextItem::new(
+                vec![LiteralItem::new("hello world", None)],
+                None,
+                SearchHighlightStyle::default(),
+            )
+            .with_max_lines(Some(1));
+
+            item.highlight("world");
+
+            item.push(LiteralItem::new("hoge", None));
+
+            assert!(item.highlights.is_none());
+            assert_eq!(item.highlight_status(), (0, 0));
+        }
+
+        #[test]
+        fn 上限を超えて更新したとき末尾の行を残す() {
+            let mut item = TextItem::new(vec![], None, SearchHighlightStyle::default())
+                .with_max_lines(Some(2));
+
+            item.update(vec![
+                LiteralItem::new("a", None),
+                LiteralItem::new("b", None),
+                LiteralItem::new("c", None),
+            ]);
+
+            assert_eq!(item.lines().collect::<Vec<_>>(), vec!["b", "c"]);
+        }
+
         #[test]
         fn highlight() {
             let mut item = TextItem::new(
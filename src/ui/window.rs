@@ -11,11 +11,15 @@ use ratatui::{
 
 use unicode_width::UnicodeWidthStr;
 
-use crate::{define_callback, logger, message::UserEvent, workers::kube::message::Kube};
+use crate::{
+    define_callback, features::exec::message::ExecTarget, logger, message::UserEvent,
+    workers::kube::message::Kube,
+};
 
 use super::{
     dialog::Dialog,
     event::{Callback, EventResult},
+    key::{widget_key_bindings, KeyBinding},
     util::{key_event_to_code, MousePosition, RectContainsPoint},
     widget::{Widget, WidgetTrait},
     Tab,
@@ -188,6 +192,19 @@ impl<'a> WindowBuilder<'a> {
         self
     }
 
+    /// 操作に割り当てた全てのキーでコールバックを呼び出す
+    pub fn key_action<F>(self, key_binding: &KeyBinding, cb: F) -> Self
+    where
+        F: Into<Callback>,
+    {
+        let cb = cb.into();
+
+        key_binding
+            .keys()
+            .iter()
+            .fold(self, |builder, key| builder.action(*key, cb.clone()))
+    }
+
     pub fn dialogs(mut self, dialogs: impl Into<Vec<Dialog<'a>>>) -> Self {
         self.dialogs = dialogs.into();
         self
@@ -533,6 +550,8 @@ pub enum WindowAction {
     CloseWindow,
     Continue,
     UpdateContents(Kube),
+    /// 画面を一時停止してコンテナ内のシェルに端末を明け渡す
+    Exec(ExecTarget),
 }
 
 // Event
@@ -566,11 +585,11 @@ impl Window<'_> {
 
         match active_tab.on_key_event(ev) {
             EventResult::Ignore => match key_event_to_code(ev) {
-                KeyCode::Tab => {
+                _ if widget_key_bindings().next_widget.matches(ev) => {
                     self.activate_next_widget();
                 }
 
-                KeyCode::BackTab => {
+                _ if widget_key_bindings().prev_widget.matches(ev) => {
                     self.activate_prev_widget();
                 }
 
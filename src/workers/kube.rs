@@ -1,6 +1,8 @@
 pub mod color;
 mod config;
 mod controller;
+pub mod diff;
+pub mod mask;
 pub mod message;
 mod store;
 mod worker;
@@ -20,6 +20,9 @@ pub struct KubeWorkerConfig {
     pub api_config: ApiConfig,
     pub apis_config: ApisConfig,
     pub yaml_config: YamlConfig,
+    pub read_only: bool,
+    /// Secretの値をマスクして表示するか
+    pub mask_secrets: bool,
 }
 
 pub struct Context(String);
@@ -1,4 +1,7 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
@@ -20,19 +23,52 @@ use crate::{
         },
         config::{
             kube::{ConfigPoller, ConfigsDataWorker},
-            message::ConfigMessage,
+            message::{ConfigMessage, ConfigRequest, SecretReveal},
         },
         context::message::{ContextMessage, ContextRequest, ContextResponse},
         event::kube::{EventConfig, EventPoller},
+        exec::{
+            kube::{fetch_exec_targets, ExecWorker},
+            message::{ExecMessage, ExecRequest, ExecResponse},
+        },
         get::{kube::yaml::GetYamlWorker, message::GetMessage},
+        helm::{
+            kube::{fetch_release_content, fetch_release_diff, HelmPoller},
+            message::{HelmMessage, HelmRequest, HelmResponse},
+        },
         namespace::message::{NamespaceMessage, NamespaceRequest, NamespaceResponse},
         network::{
-            kube::{NetworkDescriptionWorker, NetworkPoller},
-            message::NetworkMessage,
+            kube::{check_reachability, NetworkDescriptionWorker, NetworkPoller},
+            message::{NetworkMessage, NetworkResponse},
+        },
+        node::{
+            kube::{NodeDescriptionWorker, NodePoller, SharedNodeTabOpened},
+            message::NodeMessage,
         },
         pod::{
-            kube::{LogConfig, LogWorker, PodConfig, PodPoller},
-            message::LogMessage,
+            kube::{
+                LogConfig, LogTee, LogWorker, PodConfig, PodPoller, SharedLogTee,
+                SharedPodNodeFilter,
+            },
+            message::{LogMessage, PodNodeFilter},
+        },
+        port_forward::{
+            kube::{
+                fetch_port_forward_targets, PortForwardManager, PortForwardPoller,
+                SharedPortForwards,
+            },
+            message::{PortForwardMessage, PortForwardRequest, PortForwardResponse},
+        },
+        resource_action::{
+            kube::execute_resource_action,
+            message::{ResourceActionMessage, ResourceActionRequest, ResourceActionResponse},
+        },
+        workload::{
+            kube::{
+                fetch_job_history, fetch_rollout_diff, fetch_rollout_history, undo_rollout,
+                WorkloadDescriptionWorker, WorkloadPoller,
+            },
+            message::{RolloutRequest, RolloutResponse, WorkloadMessage},
         },
         yaml::{
             kube::{FetchResourceList, YamlWorker},
@@ -40,7 +76,7 @@ use crate::{
         },
         StyledApiResource,
     },
-    kube::KubeClient,
+    kube::{informer::SharedInformers, KubeClient},
     logger,
     message::Message,
     workers::kube::message::Kube,
@@ -137,6 +173,11 @@ pub struct KubeController {
     api_config: ApiConfig,
     apis_config: ApisConfig,
     yaml_config: YamlConfig,
+    read_only: bool,
+    mask_secrets: bool,
+    all_namespaces: bool,
+    /// コンテキストを切り替えても、一度開いたNodeタブの状態を引き継ぐ
+    node_tab_opened: SharedNodeTabOpened,
 }
 
 impl KubeController {
@@ -155,6 +196,8 @@ impl KubeController {
             api_config,
             apis_config,
             yaml_config,
+            read_only,
+            mask_secrets,
         } = config;
 
         let kubeconfig = read_kubeconfig(kubeconfig)?;
@@ -190,6 +233,10 @@ impl KubeController {
             api_config,
             apis_config,
             yaml_config,
+            read_only,
+            mask_secrets,
+            all_namespaces,
+            node_tab_opened: SharedNodeTabOpened::default(),
         })
     }
 
@@ -205,6 +252,10 @@ impl KubeController {
             api_config,
             apis_config,
             yaml_config,
+            read_only,
+            mask_secrets,
+            all_namespaces,
+            node_tab_opened,
         } = self;
 
         loop {
@@ -230,6 +281,10 @@ impl KubeController {
             let shared_target_namespaces = Arc::new(RwLock::new(target_namespaces.to_vec()));
             let shared_target_api_resources = Arc::new(RwLock::new(target_api_resources.to_vec()));
             let shared_api_resources = ApiResources::shared();
+            let shared_port_forwards = PortForwardManager::shared(client.clone());
+            let shared_pod_node_filter = SharedPodNodeFilter::default();
+            let shared_log_tee = SharedLogTee::default();
+            let shared_informers = SharedInformers::new(client.to_client(), all_namespaces);
 
             let contexts = kubeconfig
                 .contexts
@@ -237,43 +292,85 @@ impl KubeController {
                 .map(|ctx| ctx.name.to_string())
                 .collect();
 
+            let event_controller_state = EventControllerState {
+                target_namespaces: shared_target_namespaces.clone(),
+                target_api_resources: shared_target_api_resources.clone(),
+                api_resources: shared_api_resources.clone(),
+                port_forwards: shared_port_forwards.clone(),
+                pod_node_filter: shared_pod_node_filter.clone(),
+                log_tee: shared_log_tee,
+                node_tab_opened: node_tab_opened.clone(),
+            };
+
+            let event_controller_config = EventControllerConfig {
+                apis_config: apis_config.clone(),
+                yaml_config: yaml_config.clone(),
+                read_only,
+                mask_secrets,
+            };
+
             let event_controller_handle = EventController::new(
-                shared_target_namespaces.clone(),
                 client.clone(),
                 tx.clone(),
                 rx.clone(),
                 contexts,
-                shared_target_api_resources.clone(),
-                shared_api_resources.clone(),
-                apis_config.clone(),
-                yaml_config.clone(),
+                event_controller_state,
+                event_controller_config,
             )
             .spawn();
 
             let pod_handle = PodPoller::new(
                 tx.clone(),
                 shared_target_namespaces.clone(),
+                shared_pod_node_filter,
                 client.clone(),
+                shared_informers.clone(),
                 pod_config.clone(),
             )
             .spawn();
 
-            let config_handle =
-                ConfigPoller::new(tx.clone(), shared_target_namespaces.clone(), client.clone())
-                    .spawn();
+            let config_handle = ConfigPoller::new(
+                tx.clone(),
+                shared_target_namespaces.clone(),
+                shared_informers.clone(),
+            )
+            .spawn();
 
             let network_handle = NetworkPoller::new(
                 tx.clone(),
                 shared_target_namespaces.clone(),
-                client.clone(),
+                shared_informers.clone(),
                 shared_api_resources.clone(),
             )
             .spawn();
 
-            let event_handle = EventPoller::new(
+            let node_handle = NodePoller::new(
+                tx.clone(),
+                client.clone(),
+                shared_informers.clone(),
+                node_tab_opened.clone(),
+            )
+            .spawn();
+
+            let workload_handle = WorkloadPoller::new(
+                tx.clone(),
+                shared_target_namespaces.clone(),
+                shared_informers.clone(),
+            )
+            .spawn();
+
+            let helm_handle = HelmPoller::new(
                 tx.clone(),
                 shared_target_namespaces.clone(),
                 client.clone(),
+                shared_informers.clone(),
+            )
+            .spawn();
+
+            let event_handle = EventPoller::new(
+                tx.clone(),
+                shared_target_namespaces.clone(),
+                shared_informers,
                 event_config.clone(),
             )
             .spawn();
@@ -288,13 +385,20 @@ impl KubeController {
             )
             .spawn();
 
+            let port_forward_handle =
+                PortForwardPoller::new(tx.clone(), shared_port_forwards.clone()).spawn();
+
             let mut handles = vec![
                 event_controller_handle,
                 pod_handle,
                 config_handle,
                 network_handle,
+                node_handle,
+                workload_handle,
+                helm_handle,
                 event_handle,
                 api_handle,
+                port_forward_handle,
             ];
 
             while !handles.is_empty() {
@@ -307,6 +411,8 @@ impl KubeController {
                         WorkerResult::ChangedContext(ctx) => {
                             Self::abort(&handles);
 
+                            shared_port_forwards.write().await.stop_all();
+
                             let target_namespaces = shared_target_namespaces.read().await;
                             let target_api_resources = shared_target_api_resources.read().await;
 
@@ -324,6 +430,9 @@ impl KubeController {
                     },
                     Err(e) => {
                         Self::abort(&handles);
+
+                        shared_port_forwards.write().await.stop_all();
+
                         tx.send(Message::Error(anyhow!("KubeProcess Error: {:?}", e)))?;
                     }
                 }
@@ -340,43 +449,106 @@ impl KubeController {
 
 #[derive(Clone)]
 struct EventController {
-    shared_target_namespaces: SharedTargetNamespaces,
     kube_client: KubeClient,
     tx: Sender<Message>,
     rx: Receiver<Message>,
     contexts: Vec<String>,
-    shared_target_api_resources: SharedTargetApiResources,
-    shared_api_resources: SharedApiResources,
+    state: EventControllerState,
+    config: EventControllerConfig,
+}
+
+/// EventControllerがPollerと共有する状態
+#[derive(Clone)]
+struct EventControllerState {
+    target_namespaces: SharedTargetNamespaces,
+    target_api_resources: SharedTargetApiResources,
+    api_resources: SharedApiResources,
+    port_forwards: SharedPortForwards,
+    pod_node_filter: SharedPodNodeFilter,
+    log_tee: SharedLogTee,
+    node_tab_opened: SharedNodeTabOpened,
+}
+
+/// EventControllerの動作に関する設定
+#[derive(Clone)]
+struct EventControllerConfig {
     apis_config: ApisConfig,
     yaml_config: YamlConfig,
+    /// リソースを変更する操作、execとport-forwardを受け付けない
+    read_only: bool,
+    /// Secretの値をマスクして表示する
+    mask_secrets: bool,
+}
+
+/// 読み取り専用モードで拒否した操作のエラー
+fn read_only_error() -> anyhow::Error {
+    anyhow!("kubetui is running in read-only mode")
 }
 
 impl EventController {
     fn new(
-        shared_target_namespaces: SharedTargetNamespaces,
         kube_client: KubeClient,
         tx: Sender<Message>,
         rx: Receiver<Message>,
         contexts: Vec<String>,
-        shared_target_api_resources: SharedTargetApiResources,
-        shared_api_resources: SharedApiResources,
-        apis_config: ApisConfig,
-        yaml_config: YamlConfig,
+        state: EventControllerState,
+        config: EventControllerConfig,
     ) -> Self {
         Self {
-            shared_target_namespaces,
             kube_client,
             tx,
             rx,
             contexts,
-            shared_target_api_resources,
-            shared_api_resources,
-            apis_config,
-            yaml_config,
+            state,
+            config,
         }
     }
 }
 
+struct ConfigHandle {
+    handler: AbortHandle,
+    req: ConfigRequest,
+    decode: bool,
+    reveal: SecretReveal,
+}
+
+impl ConfigHandle {
+    fn abort(&self) {
+        self.handler.abort();
+    }
+
+    fn toggle_secret_decode(&mut self, tx: Sender<Message>, client: KubeClient) {
+        if !matches!(self.req, ConfigRequest::Secret(_)) {
+            return;
+        }
+
+        self.abort();
+
+        self.decode = !self.decode;
+
+        self.respawn(tx, client);
+    }
+
+    fn reveal_secret(&mut self, tx: Sender<Message>, client: KubeClient, reveal: SecretReveal) {
+        if !matches!(self.req, ConfigRequest::Secret(_)) {
+            return;
+        }
+
+        self.abort();
+
+        self.reveal = reveal;
+
+        self.respawn(tx, client);
+    }
+
+    fn respawn(&mut self, tx: Sender<Message>, client: KubeClient) {
+        self.handler = ConfigsDataWorker::new(tx, client, self.req.clone())
+            .decode(self.decode)
+            .reveal(self.reveal.clone())
+            .spawn();
+    }
+}
+
 struct LogHandle {
     handler: AbortHandle,
     config: LogConfig,
@@ -387,12 +559,17 @@ impl LogHandle {
         self.handler.abort();
     }
 
-    fn toggle_json_pretty_print(&mut self, tx: Sender<Message>, client: KubeClient) {
+    fn toggle_json_pretty_print(
+        &mut self,
+        tx: Sender<Message>,
+        client: KubeClient,
+        tee: SharedLogTee,
+    ) {
         self.abort();
 
         self.config.json_pretty_print = !self.config.json_pretty_print;
 
-        self.handler = LogWorker::new(tx, client, self.config.clone()).spawn();
+        self.handler = LogWorker::new(tx, client, tee, self.config.clone()).spawn();
     }
 }
 
@@ -402,21 +579,35 @@ impl Worker for EventController {
 
     async fn run(&self) -> Self::Output {
         let mut log_handler: Option<LogHandle> = None;
-        let mut config_handler: Option<AbortHandle> = None;
+        let mut config_handler: Option<ConfigHandle> = None;
         let mut network_handler: Option<AbortHandle> = None;
+        let mut node_handler: Option<AbortHandle> = None;
+        let mut workload_handler: Option<AbortHandle> = None;
         let mut yaml_handler: Option<AbortHandle> = None;
         let mut get_handler: Option<AbortHandle> = None;
 
         let EventController {
-            shared_target_namespaces,
             kube_client,
             tx,
             rx,
             contexts,
-            shared_target_api_resources,
-            shared_api_resources,
-            apis_config,
-            yaml_config,
+            state:
+                EventControllerState {
+                    target_namespaces: shared_target_namespaces,
+                    target_api_resources: shared_target_api_resources,
+                    api_resources: shared_api_resources,
+                    port_forwards: shared_port_forwards,
+                    pod_node_filter: shared_pod_node_filter,
+                    log_tee: shared_log_tee,
+                    node_tab_opened,
+                },
+            config:
+                EventControllerConfig {
+                    apis_config,
+                    yaml_config,
+                    read_only,
+                    mask_secrets,
+                },
         } = self;
 
         loop {
@@ -456,6 +647,11 @@ impl Worker for EventController {
                                 network_handler = None;
                             }
 
+                            if let Some(handler) = workload_handler {
+                                handler.abort();
+                                workload_handler = None;
+                            }
+
                             if let Some(handler) = yaml_handler {
                                 handler.abort();
                                 yaml_handler = None;
@@ -476,8 +672,13 @@ impl Worker for EventController {
                             handler.abort();
                         }
 
-                        let abort_handle =
-                            LogWorker::new(tx, kube_client.clone(), req.clone()).spawn();
+                        let abort_handle = LogWorker::new(
+                            tx,
+                            kube_client.clone(),
+                            shared_log_tee.clone(),
+                            req.clone(),
+                        )
+                        .spawn();
 
                         log_handler = Some(LogHandle {
                             handler: abort_handle,
@@ -489,22 +690,73 @@ impl Worker for EventController {
 
                     Kube::Log(LogMessage::ToggleJsonPrettyPrint) => {
                         if let Some(ref mut handler) = log_handler {
-                            handler.toggle_json_pretty_print(tx.clone(), kube_client.clone());
+                            handler.toggle_json_pretty_print(
+                                tx.clone(),
+                                kube_client.clone(),
+                                shared_log_tee.clone(),
+                            );
                             task::yield_now().await;
                         }
                     }
 
+                    Kube::Log(LogMessage::Tee(req)) => {
+                        let res = match req.map(|req| LogTee::open(req.path, req.format)) {
+                            Some(Ok(tee)) => {
+                                let path = tee.path().to_path_buf();
+
+                                logger!(info, "Start writing logs to {}", path.display());
+
+                                *shared_log_tee.lock().await = Some(tee);
+
+                                Ok(Some(path))
+                            }
+                            Some(Err(err)) => Err(err),
+                            None => {
+                                *shared_log_tee.lock().await = None;
+
+                                Ok(None)
+                            }
+                        };
+
+                        tx.send(LogMessage::TeeResponse(res).into())
+                            .expect("Failed to send LogMessage::TeeResponse");
+                    }
+
                     Kube::Config(ConfigMessage::Request(req)) => {
                         if let Some(handler) = config_handler {
                             handler.abort();
                         }
 
-                        config_handler =
-                            Some(ConfigsDataWorker::new(tx, kube_client.clone(), req).spawn());
+                        let reveal = SecretReveal::new(*mask_secrets);
+
+                        let handler = ConfigsDataWorker::new(tx, kube_client.clone(), req.clone())
+                            .reveal(reveal.clone())
+                            .spawn();
+
+                        config_handler = Some(ConfigHandle {
+                            handler,
+                            req,
+                            decode: true,
+                            reveal,
+                        });
 
                         task::yield_now().await;
                     }
 
+                    Kube::Config(ConfigMessage::ToggleSecretDecode) => {
+                        if let Some(ref mut handler) = config_handler {
+                            handler.toggle_secret_decode(tx.clone(), kube_client.clone());
+                            task::yield_now().await;
+                        }
+                    }
+
+                    Kube::Config(ConfigMessage::RevealSecret(reveal)) => {
+                        if let Some(ref mut handler) = config_handler {
+                            handler.reveal_secret(tx.clone(), kube_client.clone(), reveal);
+                            task::yield_now().await;
+                        }
+                    }
+
                     Kube::Api(ApiMessage::Request(req)) => {
                         use ApiRequest::*;
                         match req {
@@ -543,6 +795,14 @@ impl Worker for EventController {
                                 h.abort();
                             }
 
+                            if let Some(h) = node_handler {
+                                h.abort();
+                            }
+
+                            if let Some(h) = workload_handler {
+                                h.abort();
+                            }
+
                             if let Some(h) = yaml_handler {
                                 h.abort();
                             }
@@ -600,6 +860,7 @@ impl Worker for EventController {
                                         shared_api_resources.clone(),
                                         req,
                                     )
+                                    .mask_secrets(*mask_secrets)
                                     .spawn(),
                                 );
 
@@ -613,8 +874,11 @@ impl Worker for EventController {
                             handler.abort();
                         }
 
-                        get_handler =
-                            Some(GetYamlWorker::new(tx, kube_client.clone(), req).spawn());
+                        get_handler = Some(
+                            GetYamlWorker::new(tx, kube_client.clone(), req)
+                                .mask_secrets(*mask_secrets)
+                                .spawn(),
+                        );
 
                         task::yield_now().await;
                     }
@@ -636,6 +900,194 @@ impl Worker for EventController {
 
                         task::yield_now().await;
                     }
+
+                    Kube::Network(NetworkMessage::Reachability(query)) => {
+                        let result = check_reachability(kube_client, &query).await;
+
+                        tx.send(NetworkResponse::Reachability(result).into())
+                            .expect("Failed to send NetworkResponse::Reachability");
+                    }
+
+                    Kube::Node(NodeMessage::Request(req)) => {
+                        if let Some(handler) = node_handler {
+                            handler.abort();
+                        }
+
+                        node_handler =
+                            Some(NodeDescriptionWorker::new(tx, kube_client.clone(), req).spawn());
+
+                        task::yield_now().await;
+                    }
+
+                    Kube::Node(NodeMessage::Opened) => {
+                        node_tab_opened.store(true, Ordering::Relaxed);
+                    }
+
+                    Kube::Workload(WorkloadMessage::Request(req)) => {
+                        if let Some(handler) = workload_handler {
+                            handler.abort();
+                        }
+
+                        workload_handler = Some(
+                            WorkloadDescriptionWorker::new(tx, kube_client.clone(), req).spawn(),
+                        );
+
+                        task::yield_now().await;
+                    }
+
+                    Kube::Workload(WorkloadMessage::JobHistoryRequest(req)) => {
+                        let res = fetch_job_history(kube_client, &req).await;
+
+                        tx.send(WorkloadMessage::JobHistoryResponse(res).into())
+                            .expect("Failed to send WorkloadMessage::JobHistoryResponse");
+                    }
+
+                    Kube::Workload(WorkloadMessage::RolloutRequest(req)) => {
+                        let res = match req {
+                            RolloutRequest::History(target) => RolloutResponse::History(
+                                fetch_rollout_history(kube_client, &target).await,
+                            ),
+                            RolloutRequest::Diff { target, from, to } => RolloutResponse::Diff(
+                                fetch_rollout_diff(kube_client, &target, from, to).await,
+                            ),
+                            RolloutRequest::Undo { target, revision } => {
+                                let result = if *read_only {
+                                    Err(read_only_error())
+                                } else {
+                                    undo_rollout(kube_client, &target, revision).await
+                                };
+
+                                RolloutResponse::Undo(result)
+                            }
+                        };
+
+                        tx.send(res.into()).expect("Failed to send RolloutResponse");
+                    }
+
+                    Kube::Helm(HelmMessage::Request(req)) => {
+                        let res = match req {
+                            HelmRequest::Show {
+                                target,
+                                revision,
+                                view,
+                            } => {
+                                fetch_release_content(
+                                    kube_client,
+                                    &target,
+                                    revision,
+                                    view,
+                                    *mask_secrets,
+                                )
+                                .await
+                            }
+                            HelmRequest::Diff {
+                                target,
+                                view,
+                                from,
+                                to,
+                            } => {
+                                fetch_release_diff(
+                                    kube_client,
+                                    &target,
+                                    view,
+                                    from,
+                                    to,
+                                    *mask_secrets,
+                                )
+                                .await
+                            }
+                        };
+
+                        tx.send(HelmResponse::Content(res).into())
+                            .expect("Failed to send HelmResponse::Content");
+                    }
+
+                    Kube::PodNodeFilter(PodNodeFilter(node)) => {
+                        *shared_pod_node_filter.write().await = node;
+                    }
+
+                    Kube::Exec(ExecMessage::Request(req)) => match req {
+                        ExecRequest::Containers { .. } if *read_only => {
+                            tx.send(ExecResponse::Containers(Err(read_only_error())).into())
+                                .expect("Failed to send ExecResponse::Containers");
+                        }
+                        ExecRequest::Start { .. } if *read_only => {
+                            tx.send(ExecResponse::Finished(Err(read_only_error())).into())
+                                .expect("Failed to send ExecResponse::Finished");
+                        }
+                        ExecRequest::Containers { namespace, pod } => {
+                            let targets = fetch_exec_targets(kube_client, &namespace, &pod).await;
+
+                            tx.send(ExecResponse::Containers(targets).into())
+                                .expect("Failed to send ExecResponse::Containers");
+                        }
+                        ExecRequest::Start { target, input } => {
+                            ExecWorker::new(tx, kube_client.clone(), target, input).spawn();
+
+                            task::yield_now().await;
+                        }
+                    },
+
+                    Kube::PortForward(PortForwardMessage::Request(req)) => match req {
+                        PortForwardRequest::Targets {
+                            kind,
+                            namespace,
+                            name,
+                        } if *read_only => {
+                            tx.send(
+                                PortForwardResponse::Targets {
+                                    kind,
+                                    namespace,
+                                    name,
+                                    targets: Err(read_only_error()),
+                                }
+                                .into(),
+                            )
+                            .expect("Failed to send PortForwardResponse::Targets");
+                        }
+                        PortForwardRequest::Start(target) if *read_only => {
+                            logger!(error, "Rejected port-forward {} in read-only mode", target);
+                        }
+                        PortForwardRequest::Targets {
+                            kind,
+                            namespace,
+                            name,
+                        } => {
+                            let targets =
+                                fetch_port_forward_targets(kube_client, kind, &namespace, &name)
+                                    .await;
+
+                            tx.send(
+                                PortForwardResponse::Targets {
+                                    kind,
+                                    namespace,
+                                    name,
+                                    targets,
+                                }
+                                .into(),
+                            )
+                            .expect("Failed to send PortForwardResponse::Targets");
+                        }
+                        PortForwardRequest::Start(target) => {
+                            shared_port_forwards.write().await.start(target).await;
+                        }
+                        PortForwardRequest::Stop(id) => {
+                            shared_port_forwards.write().await.stop(id);
+                        }
+                    },
+                    Kube::ResourceAction(ResourceActionMessage::Request(
+                        ResourceActionRequest { target, action },
+                    )) => {
+                        let result = if *read_only {
+                            Err(read_only_error())
+                        } else {
+                            execute_resource_action(kube_client, &target, &action).await
+                        };
+
+                        tx.send(ResourceActionResponse { target, result }.into())
+                            .expect("Failed to send ResourceActionResponse");
+                    }
+
                     _ => unreachable!(),
                 },
                 Ok(_) => unreachable!(),
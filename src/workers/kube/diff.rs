@@ -0,0 +1,63 @@
+use super::color::fg::Color;
+
+/// 最長共通部分列をもとに行単位の差分を返す
+///
+/// 削除した行は`-`、追加した行は`+`を先頭に付けて色を付ける
+pub fn diff_lines(old: &[&str], new: &[&str]) -> Vec<String> {
+    let (n, m) = (old.len(), new.len());
+
+    // lcs[i][j]: old[i..]とnew[j..]の最長共通部分列の長さ
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+
+    let (mut i, mut j) = (0, 0);
+
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(Color::Red.wrap(format!("- {}", old[i])));
+            i += 1;
+        } else {
+            lines.push(Color::Green.wrap(format!("+ {}", new[j])));
+            j += 1;
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn 削除した行と追加した行に印を付ける() {
+        let actual = diff_lines(&["a", "b", "c"], &["a", "c", "d"]);
+
+        assert_eq!(
+            actual,
+            vec![
+                "  a".to_string(),
+                Color::Red.wrap("- b"),
+                "  c".to_string(),
+                Color::Green.wrap("+ d"),
+            ]
+        );
+    }
+}
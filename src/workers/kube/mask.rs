@@ -0,0 +1,240 @@
+use serde_yaml::Value;
+
+/// マスクした値の代わりに表示する文字列
+pub const MASKED_VALUE: &str = "********";
+
+const LAST_APPLIED_CONFIGURATION: &str = "kubectl.kubernetes.io/last-applied-configuration";
+
+/// Secretのdata・stringDataの値をマスクする
+///
+/// kubectl applyで作成したSecretは、last-applied-configurationのアノテーションに値をそのまま含むため、
+/// アノテーションの値もマスクする
+pub fn mask_secret_values(yaml: &mut Value) {
+    if !is_secret(yaml) {
+        return;
+    }
+
+    for field in ["data", "stringData"] {
+        if let Some(Value::Mapping(data)) = yaml.get_mut(field) {
+            data.values_mut().for_each(|value| *value = masked_value());
+        }
+    }
+
+    if let Some(Value::Mapping(annotations)) = yaml
+        .get_mut("metadata")
+        .and_then(|metadata| metadata.get_mut("annotations"))
+    {
+        if let Some(value) = annotations.get_mut(LAST_APPLIED_CONFIGURATION) {
+            *value = masked_value();
+        }
+    }
+}
+
+fn is_secret(yaml: &Value) -> bool {
+    yaml.get("apiVersion").and_then(Value::as_str) == Some("v1")
+        && yaml.get("kind").and_then(Value::as_str) == Some("Secret")
+}
+
+/// 複数のドキュメントを含むマニフェストのうち、Secretのドキュメントの値をマスクする
+///
+/// Secret以外のドキュメントは、コメントや書式を含めてそのまま残す。
+/// Secretのドキュメントは、先頭のコメント（helmの# Source:など）を残して整形し直す
+pub fn mask_manifest_secrets(manifest: &str) -> String {
+    let mut documents: Vec<Vec<&str>> = vec![Vec::new()];
+
+    for line in manifest.lines() {
+        if line.starts_with("---") {
+            documents.push(vec![line]);
+        } else if let Some(document) = documents.last_mut() {
+            document.push(line);
+        }
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+
+    for document in documents {
+        let (separator, body) = match document.first() {
+            Some(first) if first.starts_with("---") => (Some(*first), &document[1..]),
+            _ => (None, &document[..]),
+        };
+
+        lines.extend(separator.map(ToString::to_string));
+
+        let masked = serde_yaml::from_str::<Value>(&body.join("\n"))
+            .ok()
+            .filter(is_secret)
+            .and_then(|mut yaml| {
+                mask_secret_values(&mut yaml);
+
+                serde_yaml::to_string(&yaml).ok()
+            });
+
+        match masked {
+            Some(masked) => {
+                lines.extend(
+                    body.iter()
+                        .take_while(|line| line.starts_with('#'))
+                        .map(ToString::to_string),
+                );
+
+                lines.extend(masked.lines().map(ToString::to_string));
+            }
+            None => lines.extend(body.iter().map(ToString::to_string)),
+        }
+    }
+
+    let mut masked = lines.join("\n");
+
+    if manifest.ends_with('\n') {
+        masked.push('\n');
+    }
+
+    masked
+}
+
+/// helmのvaluesのように、値のすべてをマスクしてキー名のみを残す
+///
+/// nullは値を持たないためそのまま残す
+pub fn mask_json_values(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => map.values_mut().for_each(mask_json_values),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(mask_json_values),
+        serde_json::Value::Null => {}
+        _ => *value = serde_json::Value::String(MASKED_VALUE.to_string()),
+    }
+}
+
+fn masked_value() -> Value {
+    Value::String(MASKED_VALUE.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn secretの値をマスクしてキー名は残す() {
+        let mut yaml: Value = serde_yaml::from_str(indoc! {r#"
+            apiVersion: v1
+            kind: Secret
+            metadata:
+              name: db
+              annotations:
+                kubectl.kubernetes.io/last-applied-configuration: '{"stringData":{"password":"secret"}}'
+                note: keep
+            data:
+              username: YWRtaW4=
+            stringData:
+              password: secret
+            type: Opaque
+        "#})
+        .unwrap();
+
+        mask_secret_values(&mut yaml);
+
+        assert_eq!(
+            serde_yaml::to_string(&yaml).unwrap(),
+            indoc! {"
+                apiVersion: v1
+                kind: Secret
+                metadata:
+                  name: db
+                  annotations:
+                    kubectl.kubernetes.io/last-applied-configuration: '********'
+                    note: keep
+                data:
+                  username: '********'
+                stringData:
+                  password: '********'
+                type: Opaque
+            "}
+        );
+    }
+
+    #[test]
+    fn secret以外はマスクしない() {
+        let yaml = indoc! {"
+            apiVersion: v1
+            kind: ConfigMap
+            data:
+              password: secret
+        "};
+
+        let mut actual: Value = serde_yaml::from_str(yaml).unwrap();
+
+        mask_secret_values(&mut actual);
+
+        assert_eq!(serde_yaml::to_string(&actual).unwrap(), yaml);
+    }
+
+    #[test]
+    fn マニフェストのsecretのドキュメントのみマスクする() {
+        let manifest = indoc! {"
+            ---
+            # Source: web/templates/secret.yaml
+            apiVersion: v1
+            kind: Secret
+            metadata:
+              name: web
+            stringData:
+              password: secret
+            ---
+            # Source: web/templates/configmap.yaml
+            apiVersion: v1
+            kind: ConfigMap
+            data:
+              password:   plain
+        "};
+
+        assert_eq!(
+            mask_manifest_secrets(manifest),
+            indoc! {"
+                ---
+                # Source: web/templates/secret.yaml
+                apiVersion: v1
+                kind: Secret
+                metadata:
+                  name: web
+                stringData:
+                  password: '********'
+                ---
+                # Source: web/templates/configmap.yaml
+                apiVersion: v1
+                kind: ConfigMap
+                data:
+                  password:   plain
+            "}
+        );
+    }
+
+    #[test]
+    fn jsonの値をすべてマスクしてキー名とnullは残す() {
+        let mut actual = serde_json::json!({
+            "password": "secret",
+            "replicaCount": 2,
+            "hosts": ["a.example.com", "b.example.com"],
+            "ingress": null,
+            "database": {
+                "enabled": true
+            }
+        });
+
+        mask_json_values(&mut actual);
+
+        assert_eq!(
+            actual,
+            serde_json::json!({
+                "password": "********",
+                "replicaCount": "********",
+                "hosts": ["********", "********"],
+                "ingress": null,
+                "database": {
+                    "enabled": "********"
+                }
+            })
+        );
+    }
+}
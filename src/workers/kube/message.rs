@@ -2,10 +2,20 @@ use anyhow::Result;
 
 use crate::{
     features::{
-        api_resources::message::ApiMessage, config::message::ConfigMessage,
-        context::message::ContextMessage, get::message::GetMessage,
-        namespace::message::NamespaceMessage, network::message::NetworkMessage,
-        pod::message::LogMessage, yaml::message::YamlMessage,
+        api_resources::message::ApiMessage,
+        config::message::ConfigMessage,
+        context::message::ContextMessage,
+        exec::message::ExecMessage,
+        get::message::GetMessage,
+        helm::message::HelmMessage,
+        namespace::message::NamespaceMessage,
+        network::message::NetworkMessage,
+        node::message::NodeMessage,
+        pod::message::{LogMessage, PodNodeFilter},
+        port_forward::message::PortForwardMessage,
+        resource_action::message::ResourceActionMessage,
+        workload::message::WorkloadMessage,
+        yaml::message::YamlMessage,
     },
     kube::table::KubeTable,
     message::Message,
@@ -25,11 +35,18 @@ pub enum Kube {
     Event(Result<Vec<String>>),
     Namespace(NamespaceMessage),
     Pod(Result<KubeTable>),
+    PodNodeFilter(PodNodeFilter),
     Log(LogMessage),
     Config(ConfigMessage),
     Network(NetworkMessage),
+    Node(NodeMessage),
+    Workload(WorkloadMessage),
+    Helm(HelmMessage),
     Yaml(YamlMessage),
     Get(GetMessage),
+    Exec(ExecMessage),
+    PortForward(PortForwardMessage),
+    ResourceAction(ResourceActionMessage),
 }
 
 impl From<Kube> for Message {
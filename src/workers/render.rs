@@ -3,21 +3,41 @@ mod window;
 
 use std::{
     cell::RefCell,
-    io::{self},
+    io::{self, Write as _},
     rc::Rc,
 };
 
 use anyhow::Result;
 use crossbeam::channel::{Receiver, Sender};
-use ratatui::{backend::CrosstermBackend, layout::Direction, Terminal, TerminalOptions, Viewport};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    crossterm::{
+        cursor::Show,
+        event::{
+            DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+            Event as CEvent,
+        },
+        execute,
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+    },
+    layout::Direction,
+    Terminal, TerminalOptions, Viewport,
+};
+use tokio::sync::mpsc;
 
 use crate::{
-    config::theme::ThemeConfig,
+    config::Config,
+    features::{
+        component_id::NODE_TAB_ID,
+        exec::message::{ExecMessage, ExecRequest, ExecResponse, ExecTarget},
+        node::message::NodeMessage,
+    },
     kube::context::{Context, Namespace},
     logger,
     message::Message,
     panic_set_hook,
     ui::WindowAction,
+    workers::{kube::message::Kube, InputRedirect},
 };
 
 use self::{
@@ -30,7 +50,9 @@ pub struct Render {
     rx: Receiver<Message>,
     tx_shutdown: Sender<Result<()>>,
     direction: Direction,
-    theme: ThemeConfig,
+    config: Config,
+    input_redirect: InputRedirect,
+    read_only: bool,
 }
 
 impl Render {
@@ -39,14 +61,18 @@ impl Render {
         rx: Receiver<Message>,
         tx_shutdown: Sender<Result<()>>,
         direction: Direction,
-        theme: ThemeConfig,
+        config: Config,
+        input_redirect: InputRedirect,
+        read_only: bool,
     ) -> Self {
         Self {
             direction,
             tx,
             rx,
             tx_shutdown,
-            theme,
+            config,
+            input_redirect,
+            read_only,
         }
     }
 
@@ -85,7 +111,8 @@ impl Render {
             self.tx.clone(),
             context.clone(),
             namespace.clone(),
-            self.theme.clone(),
+            self.config.clone(),
+            self.read_only,
         )
         .build();
 
@@ -98,11 +125,24 @@ impl Render {
 
         terminal.clear()?;
 
+        let mut last_active_tab_id = String::default();
+
         loop {
             terminal.draw(|f| {
                 window.render(f);
             })?;
 
+            // Nodeの一覧はNodeタブを開くまで取得しないため、開いたことを通知する
+            if window.active_tab_id() != last_active_tab_id {
+                last_active_tab_id = window.active_tab_id().to_string();
+
+                if last_active_tab_id == NODE_TAB_ID {
+                    self.tx
+                        .send(NodeMessage::Opened.into())
+                        .expect("Failed to send NodeMessage::Opened");
+                }
+            }
+
             match window_action(&mut window, &self.rx) {
                 WindowAction::Continue => {}
                 WindowAction::CloseWindow => {
@@ -116,6 +156,107 @@ impl Render {
                         &mut namespace.borrow_mut(),
                     );
                 }
+                WindowAction::Exec(target) => {
+                    self.exec(&mut terminal, target, |ev| {
+                        update_contents(
+                            &mut window,
+                            ev,
+                            &mut context.borrow_mut(),
+                            &mut namespace.borrow_mut(),
+                        );
+                    })?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 画面描画を一時停止し、コンテナ内のシェルに端末を明け渡す
+    ///
+    /// シェルの終了を待つ間に受け取ったkubeからのメッセージはon_kubeに渡す。
+    /// ユーザー入力はシェルに転送されるため、この間に描画側に届くことはない。
+    fn exec<B: Backend>(
+        &self,
+        terminal: &mut Terminal<B>,
+        target: ExecTarget,
+        mut on_kube: impl FnMut(Kube),
+    ) -> Result<()> {
+        logger!(info, "exec start {:?}", target);
+
+        let (tx_input, rx_input) = mpsc::unbounded_channel();
+
+        self.input_redirect.set(tx_input);
+
+        execute!(
+            io::stdout(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableFocusChange,
+            Show
+        )?;
+
+        self.tx
+            .send(
+                ExecRequest::Start {
+                    target,
+                    input: rx_input,
+                }
+                .into(),
+            )
+            .expect("Failed to send ExecRequest::Start");
+
+        let result = loop {
+            match self.rx.recv().expect("Failed to recv") {
+                Message::Kube(Kube::Exec(ExecMessage::Response(ExecResponse::Finished(res)))) => {
+                    break res;
+                }
+                Message::Kube(ev) => on_kube(ev),
+                Message::Error(err) => {
+                    logger!(error, "Error: {:?}", err);
+                }
+                Message::User(_) | Message::Tick => {}
+            }
+        };
+
+        if let Err(e) = result {
+            self.wait_any_key(&e)?;
+        }
+
+        self.input_redirect.clear();
+
+        execute!(
+            io::stdout(),
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange
+        )?;
+
+        terminal.clear()?;
+
+        logger!(info, "exec end");
+
+        Ok(())
+    }
+
+    /// execに失敗した理由を表示し、キー入力を待つ
+    fn wait_any_key(&self, err: &anyhow::Error) -> Result<()> {
+        let (tx_input, mut rx_input) = mpsc::unbounded_channel();
+
+        self.input_redirect.set(tx_input);
+
+        let mut stdout = io::stdout();
+
+        write!(
+            stdout,
+            "\r\n\x1b[31m[kubetui] {}\x1b[39m\r\nPress any key to return\r\n",
+            format!("{:#}", err).replace('\n', "\r\n")
+        )?;
+        stdout.flush()?;
+
+        while let Some(ev) = rx_input.blocking_recv() {
+            if let CEvent::Key(_) = ev {
+                break;
             }
         }
 
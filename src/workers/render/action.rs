@@ -5,20 +5,40 @@ use crossbeam::channel::Receiver;
 
 use crate::{
     features::{
-        api_resources::message::{ApiMessage, ApiResponse},
+        api_resources::{
+            kube::ApiTable,
+            message::{ApiMessage, ApiResponse},
+        },
         component_id::{
-            API_DIALOG_ID, API_WIDGET_ID, CONFIG_RAW_DATA_WIDGET_ID, CONFIG_WIDGET_ID,
-            CONTEXT_DIALOG_ID, EVENT_WIDGET_ID, MULTIPLE_NAMESPACES_DIALOG_ID,
-            NETWORK_DESCRIPTION_WIDGET_ID, NETWORK_WIDGET_ID, POD_LOG_WIDGET_ID, POD_WIDGET_ID,
-            SINGLE_NAMESPACE_DIALOG_ID, YAML_DIALOG_ID, YAML_KIND_DIALOG_ID, YAML_NAME_DIALOG_ID,
-            YAML_NOT_FOUND_DIALOG_ID, YAML_WIDGET_ID,
+            API_DIALOG_ID, API_WIDGET_ID, CONFIG_RAW_DATA_WIDGET_ID, CONFIG_REVEAL_DIALOG_ID,
+            CONFIG_WIDGET_ID, CONTEXT_DIALOG_ID, EVENT_WIDGET_ID, EXEC_CONTAINER_DIALOG_ID,
+            HELM_CONTENT_WIDGET_ID, HELM_WIDGET_ID, MULTIPLE_NAMESPACES_DIALOG_ID,
+            NETWORK_DESCRIPTION_WIDGET_ID, NETWORK_REACHABILITY_RESULT_DIALOG_ID,
+            NETWORK_WIDGET_ID, NODE_DESCRIPTION_WIDGET_ID, NODE_WIDGET_ID, POD_LOG_WIDGET_ID,
+            POD_WIDGET_ID, PORT_FORWARD_DIALOG_ID, PORT_FORWARD_TARGET_DIALOG_ID,
+            SINGLE_NAMESPACE_DIALOG_ID, WORKLOAD_DESCRIPTION_WIDGET_ID,
+            WORKLOAD_JOB_HISTORY_DIALOG_ID, WORKLOAD_ROLLOUT_HISTORY_DIALOG_ID,
+            WORKLOAD_ROLLOUT_RESULT_DIALOG_ID, WORKLOAD_WIDGET_ID, YAML_DIALOG_ID,
+            YAML_KIND_DIALOG_ID, YAML_NAME_DIALOG_ID, YAML_NOT_FOUND_DIALOG_ID, YAML_WIDGET_ID,
         },
-        config::message::ConfigMessage,
+        config::{message::ConfigMessage, view::reveal_items},
         context::message::{ContextMessage, ContextResponse},
+        exec::message::{ExecMessage, ExecResponse, ExecTarget},
         get::message::{GetMessage, GetResponse},
+        helm::message::{HelmMessage, HelmResponse},
         namespace::message::{NamespaceMessage, NamespaceResponse},
         network::message::{NetworkMessage, NetworkResponse},
-        pod::message::LogMessage,
+        node::message::{NodeMessage, NodeResponse},
+        pod::{
+            message::LogMessage,
+            view::{show_log_export_result, update_log_tee_title, update_pod_title},
+        },
+        port_forward::message::{PortForwardKind, PortForwardMessage, PortForwardResponse},
+        resource_action::{
+            message::{ResourceActionMessage, ResourceActionResponse},
+            view::show_error,
+        },
+        workload::message::{RolloutResponse, WorkloadMessage, WorkloadResponse},
         yaml::message::{YamlMessage, YamlResourceListItem, YamlResponse},
     },
     kube::{
@@ -122,10 +142,7 @@ fn update_widget_item_for_table(window: &mut Window, id: &str, table: Result<Kub
                                     item_metadata.extend(metadata);
                                 }
 
-                                TableItem {
-                                    metadata: Some(item_metadata),
-                                    item: row,
-                                }
+                                TableItem::new(row, Some(item_metadata))
                             },
                         )
                         .collect(),
@@ -150,10 +167,7 @@ fn update_widget_item_for_table(window: &mut Window, id: &str, table: Result<Kub
                                 item_metadata.extend(metadata);
                             }
 
-                            TableItem {
-                                metadata: Some(item_metadata),
-                                item: row,
-                            }
+                            TableItem::new(row, Some(item_metadata))
                         },
                     )
                     .collect();
@@ -168,6 +182,73 @@ fn update_widget_item_for_table(window: &mut Window, id: &str, table: Result<Kub
     }
 }
 
+/// リソースごとに見出し・ヘッダー・行を並べてAPIタブのテーブルを更新する
+///
+/// 見出しとヘッダーの行はmetadataを持たないため、選択やフィルターの対象にならない
+fn update_widget_item_for_api_tables(window: &mut Window, tables: Result<Vec<ApiTable>>) {
+    let widget = window.find_widget_mut(API_WIDGET_ID);
+    let w = widget.as_mut_table();
+
+    let tables = match tables {
+        Ok(tables) => tables,
+        Err(e) => {
+            let rows: Vec<TableItem> = vec![vec![error_format!("{:?}", e)].into()];
+            w.update_header_and_rows(&["ERROR".to_string()], &rows);
+            return;
+        }
+    };
+
+    let rows: Vec<TableItem> = tables
+        .into_iter()
+        .flat_map(
+            |ApiTable {
+                 resource,
+                 title,
+                 header,
+                 rows,
+             }| {
+                let key =
+                    serde_json::to_string(&resource).expect("ApiResource is always serializable");
+
+                let mut items = vec![TableItem::separator(vec![title])];
+
+                if !rows.is_empty() {
+                    items.push(TableItem::header(header));
+                }
+
+                items.extend(rows.into_iter().map(
+                    |KubeTableRow {
+                         namespace,
+                         name,
+                         row,
+                         ..
+                     }| {
+                        let metadata = BTreeMap::from([
+                            ("namespace".to_string(), namespace),
+                            ("name".to_string(), name),
+                            ("key".to_string(), key.clone()),
+                            ("kind".to_string(), resource.kind().to_string()),
+                            ("group".to_string(), resource.group().to_string()),
+                            ("version".to_string(), resource.version().to_string()),
+                            ("plural".to_string(), resource.name().to_string()),
+                        ]);
+
+                        TableItem::new(row, Some(metadata))
+                    },
+                ));
+
+                items
+            },
+        )
+        .collect();
+
+    if w.equal_header(&[]) {
+        w.update_widget_item(Item::Table(rows));
+    } else {
+        w.update_header_and_rows(&[], &rows);
+    }
+}
+
 fn update_widget_item_for_vec(window: &mut Window, id: &str, vec: Result<Vec<String>>) {
     let widget = window.find_widget_mut(id);
     match vec {
@@ -212,6 +293,17 @@ pub fn update_contents(
             }
         }
 
+        Kube::Log(LogMessage::TeeResponse(res)) => match res {
+            Ok(path) => {
+                update_log_tee_title(window, path.as_deref());
+            }
+            Err(e) => {
+                update_log_tee_title(window, None);
+
+                show_log_export_result(window, "Tee Log", vec![format!("{:#}", e)]);
+            }
+        },
+
         Kube::Config(ConfigMessage::Response(res)) => {
             use crate::features::config::message::ConfigResponse::*;
 
@@ -222,6 +314,11 @@ pub fn update_contents(
                 Data(data) => {
                     update_widget_item_for_vec(window, CONFIG_RAW_DATA_WIDGET_ID, data);
                 }
+                SecretKeys(keys) => {
+                    window
+                        .find_widget_mut(CONFIG_REVEAL_DIALOG_ID)
+                        .update_widget_item(Item::Array(reveal_items(&keys)));
+                }
             }
         }
 
@@ -272,6 +369,10 @@ pub fn update_contents(
             context.update(ctx);
             namespace.update(ns.clone());
 
+            // Nodeによる絞り込みとteeモードはコンテキストごとに初期化される
+            update_pod_title(window, None);
+            update_log_tee_title(window, None);
+
             window
                 .find_widget_mut(MULTIPLE_NAMESPACES_DIALOG_ID)
                 .update_widget_item(Item::Array(
@@ -328,8 +429,8 @@ pub fn update_contents(
                         }
                     }
                 }
-                Poll(apis) => {
-                    update_widget_item_for_vec(window, API_WIDGET_ID, apis);
+                Poll(tables) => {
+                    update_widget_item_for_api_tables(window, tables);
                 }
             }
         }
@@ -429,9 +530,234 @@ pub fn update_contents(
                 Yaml(res) => {
                     update_widget_item_for_vec(window, NETWORK_DESCRIPTION_WIDGET_ID, res);
                 }
+                Reachability(res) => {
+                    update_widget_item_for_vec(window, NETWORK_REACHABILITY_RESULT_DIALOG_ID, res);
+                }
+            }
+        }
+
+        Kube::Node(NodeMessage::Response(ev)) => match ev {
+            NodeResponse::List(res) => update_widget_item_for_table(window, NODE_WIDGET_ID, res),
+            NodeResponse::Description(res) => {
+                update_widget_item_for_vec(window, NODE_DESCRIPTION_WIDGET_ID, res);
+            }
+        },
+
+        Kube::Workload(WorkloadMessage::Response(ev)) => match ev {
+            WorkloadResponse::List(res) => {
+                update_widget_item_for_table(window, WORKLOAD_WIDGET_ID, res)
+            }
+            WorkloadResponse::Description(res) => {
+                update_widget_item_for_vec(window, WORKLOAD_DESCRIPTION_WIDGET_ID, res);
+            }
+        },
+
+        Kube::Workload(WorkloadMessage::JobHistoryResponse(res)) => {
+            update_widget_item_for_table(window, WORKLOAD_JOB_HISTORY_DIALOG_ID, res);
+        }
+
+        Kube::Workload(WorkloadMessage::RolloutResponse(ev)) => match ev {
+            RolloutResponse::History(res) => {
+                update_widget_item_for_table(window, WORKLOAD_ROLLOUT_HISTORY_DIALOG_ID, res)
+            }
+            RolloutResponse::Diff(res) => {
+                update_widget_item_for_vec(window, WORKLOAD_ROLLOUT_RESULT_DIALOG_ID, res);
+            }
+            RolloutResponse::Undo(res) => {
+                update_widget_item_for_vec(
+                    window,
+                    WORKLOAD_ROLLOUT_RESULT_DIALOG_ID,
+                    res.map(|message| vec![message]),
+                );
+            }
+        },
+
+        Kube::Helm(HelmMessage::Response(ev)) => match ev {
+            HelmResponse::List(res) => update_widget_item_for_table(window, HELM_WIDGET_ID, res),
+            HelmResponse::Content(res) => {
+                update_widget_item_for_vec(window, HELM_CONTENT_WIDGET_ID, res);
+            }
+        },
+
+        Kube::Exec(ExecMessage::Response(ExecResponse::Containers(res))) => {
+            let widget = window.find_widget_mut(EXEC_CONTAINER_DIALOG_ID);
+
+            match res {
+                Ok(targets) => {
+                    let items = targets
+                        .into_iter()
+                        .map(
+                            |ExecTarget {
+                                 namespace,
+                                 pod,
+                                 container,
+                             }| {
+                                let metadata = BTreeMap::from([
+                                    ("namespace".to_string(), namespace),
+                                    ("pod".to_string(), pod),
+                                    ("container".to_string(), container.clone()),
+                                ]);
+
+                                LiteralItem::new(container, Some(metadata))
+                            },
+                        )
+                        .collect();
+
+                    widget.update_widget_item(Item::Array(items));
+                }
+                Err(e) => {
+                    widget.update_widget_item(Item::Array(error_lines!(e)));
+                }
+            }
+        }
+
+        Kube::PortForward(PortForwardMessage::Response(res)) => match res {
+            PortForwardResponse::Targets {
+                kind,
+                namespace,
+                name,
+                targets,
+            } => {
+                let widget = window.find_widget_mut(PORT_FORWARD_TARGET_DIALOG_ID);
+
+                match targets {
+                    Ok(targets) => {
+                        // Podの場合は、宣言されていないポートも入力して転送できるように転送先のPodを持たせる
+                        let pod_metadata = || match kind {
+                            PortForwardKind::Pod => BTreeMap::from([
+                                ("namespace".into(), namespace.clone()),
+                                ("name".into(), name.clone()),
+                            ]),
+                            PortForwardKind::Service => BTreeMap::new(),
+                        };
+
+                        let mut items: Vec<LiteralItem> = targets
+                            .into_iter()
+                            .map(|target| {
+                                let Ok(json) = serde_json::to_string(&target) else {
+                                    unreachable!()
+                                };
+
+                                let mut metadata = pod_metadata();
+                                metadata.insert("key".into(), json);
+
+                                LiteralItem::new(
+                                    format!("{} -> {}:{}", target, target.pod, target.pod_port),
+                                    Some(metadata),
+                                )
+                            })
+                            .collect();
+
+                        if items.is_empty() && kind == PortForwardKind::Pod {
+                            items.push(LiteralItem::new(
+                                format!(
+                                    "{}/{} has no container ports. Type a port number and press Enter",
+                                    kind, name
+                                ),
+                                Some(pod_metadata()),
+                            ));
+                        }
+
+                        widget.update_widget_item(Item::Array(items));
+                    }
+                    Err(e) => {
+                        widget.update_widget_item(Item::Array(error_lines!(e)));
+                    }
+                }
+            }
+            PortForwardResponse::List(res) => {
+                update_widget_item_for_table(window, PORT_FORWARD_DIALOG_ID, res);
+            }
+        },
+
+        Kube::ResourceAction(ResourceActionMessage::Response(ResourceActionResponse {
+            target,
+            result,
+        })) => {
+            if let Err(e) = result {
+                show_error(
+                    window,
+                    &target,
+                    format!("{:?}", e)
+                        .lines()
+                        .map(ToString::to_string)
+                        .collect(),
+                );
             }
         }
 
         _ => unreachable!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kube::discovery::Scope;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        config::theme::ThemeConfig,
+        features::{
+            api_resources::{kube::ApiResource, view::ApiTab},
+            component_id::RESOURCE_ACTION_DIALOG_ID,
+            resource_action::view::ResourceActionDialogs,
+        },
+        ui::dialog::Dialog,
+    };
+
+    #[test]
+    fn apiタブの行から操作メニューを開く() {
+        let (tx, _rx) = crossbeam::channel::unbounded();
+
+        let theme = ThemeConfig::default();
+
+        let ApiTab { tab, .. } = ApiTab::new("API", &tx, &None, theme.component.clone());
+
+        let dialogs = ResourceActionDialogs::new(&tx, theme);
+
+        let open_menu = dialogs.open_menu();
+
+        let mut window = Window::builder()
+            .tabs([tab])
+            .dialogs([Dialog::new(dialogs.menu_dialog)])
+            .build();
+
+        let tables = vec![ApiTable {
+            resource: ApiResource::Apis {
+                name: "deployments".into(),
+                kind: "Deployment".into(),
+                group: "apps".into(),
+                version: "v1".into(),
+                preferred_version: true,
+                scope: Scope::Namespaced,
+            },
+            title: "deployments.apps (*v1)".into(),
+            header: vec!["NAME".into()],
+            rows: vec![KubeTableRow {
+                namespace: "default".into(),
+                name: "app".into(),
+                row: vec!["app".into()],
+                ..Default::default()
+            }],
+        }];
+
+        update_widget_item_for_api_tables(&mut window, Ok(tables));
+
+        window.find_widget_mut(API_WIDGET_ID).select_last();
+
+        open_menu(&mut window);
+
+        assert!(window.opening_dialog());
+
+        let title = window
+            .find_widget(RESOURCE_ACTION_DIALOG_ID)
+            .widget_base()
+            .append_title()
+            .clone()
+            .map(|title| title.to_string());
+
+        assert_eq!(title, Some(" : deployment/app".to_string()));
+    }
+}
@@ -3,34 +3,35 @@ use std::{cell::RefCell, rc::Rc};
 use crossbeam::channel::Sender;
 use k8s_openapi::{
     api::{
+        apps::v1::{DaemonSet, Deployment, StatefulSet},
+        batch::v1::{CronJob, Job},
         core::v1::{ConfigMap, Pod, Secret, Service},
         networking::v1::{Ingress, NetworkPolicy},
     },
     Resource as _,
 };
-use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
-    layout::Direction,
-    text::Line,
-    widgets::Paragraph,
-};
+use kube::discovery::Scope;
+use ratatui::{layout::Direction, text::Line, widgets::Paragraph};
 
 use crate::{
     clipboard::Clipboard,
-    config::theme::ThemeConfig,
+    config::Config,
     features::{
-        api_resources::view::ApiTab,
+        api_resources::{kube::ApiResource, view::ApiTab},
         component_id::{
             CONFIG_WIDGET_ID, CONTEXT_DIALOG_ID, HELP_DIALOG_ID, MULTIPLE_NAMESPACES_DIALOG_ID,
-            NETWORK_WIDGET_ID, POD_WIDGET_ID, SINGLE_NAMESPACE_DIALOG_ID, YAML_DIALOG_ID,
+            NETWORK_WIDGET_ID, POD_WIDGET_ID, PORT_FORWARD_DIALOG_ID, SINGLE_NAMESPACE_DIALOG_ID,
+            WORKLOAD_WIDGET_ID, YAML_DIALOG_ID,
         },
-        config::view::ConfigTab,
+        config::view::{ConfigTab, RevealDialog},
         context::{message::ContextRequest, view::ContextDialog},
         event::view::EventTab,
+        exec::view::ExecContainerDialog,
         get::{
             message::{GetRequest, GetYamlKind},
             view::YamlDialog,
         },
+        helm::view::{HelmDialogs, HelmTab},
         help::HelpDialog,
         namespace::{
             message::NamespaceRequest,
@@ -38,20 +39,28 @@ use crate::{
         },
         network::{
             message::{GatewayVersion, HTTPRouteVersion},
-            view::NetworkTab,
+            view::{NetworkTab, ReachabilityDialogs},
         },
-        pod::view::PodTab,
+        node::view::NodeTab,
+        pod::view::{LogExportDialogs, PodTab, SavedQueriesDialog},
+        port_forward::view::{PortForwardDialog, PortForwardTargetDialog},
+        resource_action::view::ResourceActionDialogs,
+        workload::view::{JobHistoryDialog, RolloutDialogs, WorkloadTab},
         yaml::view::YamlTab,
     },
     kube::{
-        apis::networking::gateway::v1::{Gateway, HTTPRoute},
+        apis::networking::gateway::{
+            v1::{Gateway, HTTPRoute},
+            v1alpha2::{GRPCRoute, ReferenceGrant, TCPRoute, TLSRoute, UDPRoute},
+        },
         context::{Context, Namespace},
     },
     logger,
-    message::{Message, UserEvent},
+    message::Message,
     ui::{
         dialog::{Dialog, DialogTheme},
         event::{CallbackFn, EventResult},
+        key::KeyBinding,
         widget::{SelectedItem, WidgetTrait},
         Header, HeaderTheme, Tab, TabTheme, Window, WindowAction,
     },
@@ -62,7 +71,7 @@ pub struct WindowInit {
     tx: Sender<Message>,
     context: Rc<RefCell<Context>>,
     namespaces: Rc<RefCell<Namespace>>,
-    theme: ThemeConfig,
+    config: Config,
 }
 
 impl WindowInit {
@@ -71,26 +80,44 @@ impl WindowInit {
         tx: Sender<Message>,
         context: Rc<RefCell<Context>>,
         namespaces: Rc<RefCell<Namespace>>,
-        theme: ThemeConfig,
+        mut config: Config,
+        read_only: bool,
     ) -> Self {
+        // 読み取り専用モードではリソースを変更する操作、execとport-forwardのキーを割り当てない
+        if read_only {
+            let keybindings = &mut config.keybindings;
+
+            keybindings.global.open_resource_action_menu = KeyBinding::new([]);
+            keybindings.pod.exec = KeyBinding::new([]);
+            keybindings.pod.port_forward = KeyBinding::new([]);
+            keybindings.network.port_forward = KeyBinding::new([]);
+        }
+
         Self {
             split_mode,
             tx,
             context,
             namespaces,
-            theme,
+            config,
         }
     }
 
     pub fn build(self) -> Window<'static> {
-        let (tabs, dialogs) = self.tabs_dialogs();
+        let resource_action_dialogs =
+            ResourceActionDialogs::new(&self.tx, self.config.theme.clone());
+
+        let open_resource_action_menu = resource_action_dialogs.open_menu();
+
+        let (tabs, dialogs) = self.tabs_dialogs(resource_action_dialogs);
 
         let builder = Window::builder().tabs(tabs).dialogs(dialogs);
 
         // Configure Action
+        let keys = &self.config.keybindings.global;
+
         let tx = self.tx.clone();
-        let builder = builder.action(
-            UserEvent::Key(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT)),
+        let builder = builder.key_action(
+            &keys.open_multiple_namespaces_dialog,
             move |w: &mut Window| {
                 tx.send(NamespaceRequest::Get.into())
                     .expect("Failed to send NamespaceRequest::Get");
@@ -100,7 +127,7 @@ impl WindowInit {
         );
 
         let tx = self.tx.clone();
-        let builder = builder.action('n', move |w: &mut Window| {
+        let builder = builder.key_action(&keys.open_namespace_dialog, move |w: &mut Window| {
             tx.send(NamespaceRequest::Get.into())
                 .expect("Failed to send NamespaceRequest::Get");
             w.open_dialog(SINGLE_NAMESPACE_DIALOG_ID);
@@ -117,7 +144,7 @@ impl WindowInit {
         };
 
         let tx = self.tx.clone();
-        let builder = builder.action('c', move |w: &mut Window| {
+        let builder = builder.key_action(&keys.select_context, move |w: &mut Window| {
             tx.send(ContextRequest::Get.into())
                 .expect("Failed to send ContextRequest::Get");
             w.open_dialog(CONTEXT_DIALOG_ID);
@@ -131,8 +158,11 @@ impl WindowInit {
 
         let open_yaml = open_yaml(self.tx.clone());
 
-        let builder = builder.action('h', open_help).action('?', open_help);
-        let builder = builder.action('y', open_yaml);
+        let builder = builder.key_action(&keys.open_help, open_help);
+        let builder = builder.key_action(&keys.open_yaml, open_yaml);
+
+        let builder =
+            builder.key_action(&keys.open_resource_action_menu, open_resource_action_menu);
 
         //　分割方向を変更する
         let toggle_split_direction = move |w: &mut Window| {
@@ -143,12 +173,16 @@ impl WindowInit {
             EventResult::Nop
         };
 
-        let builder = builder.action(
-            KeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT),
-            toggle_split_direction,
-        );
+        let builder = builder.key_action(&keys.toggle_split_direction, toggle_split_direction);
+
+        let open_port_forwards = move |w: &mut Window| {
+            w.open_dialog(PORT_FORWARD_DIALOG_ID);
+            EventResult::Nop
+        };
+
+        let builder = builder.key_action(&keys.open_port_forwards, open_port_forwards);
 
-        let builder = builder.action('q', fn_close).action(KeyCode::Esc, fn_close);
+        let builder = builder.key_action(&keys.quit, fn_close);
 
         let context = self.context.clone();
         let namespaces = self.namespaces.clone();
@@ -166,20 +200,23 @@ impl WindowInit {
 
         let builder = builder.header(header);
 
-        let tab_theme = TabTheme::from(self.theme.clone());
+        let tab_theme = TabTheme::from(self.config.theme.clone());
 
         let builder = builder.tab_theme(tab_theme);
 
-        let header_theme = HeaderTheme::from(self.theme.clone());
+        let header_theme = HeaderTheme::from(self.config.theme.clone());
 
         let builder = builder.header_theme(header_theme);
 
-        let builder = builder.base_style(self.theme.base);
+        let builder = builder.base_style(self.config.theme.base);
 
         builder.build()
     }
 
-    fn tabs_dialogs(&self) -> (Vec<Tab<'static>>, Vec<Dialog<'static>>) {
+    fn tabs_dialogs(
+        &self,
+        resource_action_dialogs: ResourceActionDialogs,
+    ) -> (Vec<Tab<'static>>, Vec<Dialog<'static>>) {
         let clipboard = arboard::Clipboard::new()
             .inspect_err(|err| {
                 logger!(error, "Failed to create clipboard. {}", err);
@@ -190,65 +227,189 @@ impl WindowInit {
         let PodTab {
             tab: pod_tab,
             log_query_help_dialog,
+            log_export_dialogs,
+            saved_queries_dialog,
         } = PodTab::new(
             "Pod",
             &self.tx,
             &clipboard,
             self.split_mode,
             self.namespaces.clone(),
-            self.theme.component.clone(),
+            &self.config,
         );
 
-        let ConfigTab { tab: config_tab } = ConfigTab::new(
+        let ConfigTab {
+            tab: config_tab,
+            reveal_dialog: config_reveal_dialog,
+        } = ConfigTab::new(
             "Config",
             &self.tx,
             &clipboard,
             self.split_mode,
-            self.theme.component.clone(),
+            self.config.keybindings.config.clone(),
+            self.config.secret.clone(),
+            self.config.theme.component.clone(),
         );
 
-        let NetworkTab { tab: network_tab } = NetworkTab::new(
+        let NetworkTab {
+            tab: network_tab,
+            reachability_dialogs,
+        } = NetworkTab::new(
             "Network",
             &self.tx,
             &clipboard,
             self.split_mode,
-            self.theme.component.clone(),
+            self.config.keybindings.network.clone(),
+            self.config.theme.component.clone(),
+        );
+
+        let NodeTab { tab: node_tab } = NodeTab::new(
+            "Node",
+            &self.tx,
+            &clipboard,
+            self.split_mode,
+            self.config.keybindings.node.clone(),
+            self.config.theme.component.clone(),
+        );
+
+        let WorkloadTab {
+            tab: workload_tab,
+            rollout_dialogs,
+            job_history_dialog,
+        } = WorkloadTab::new(
+            "Workload",
+            &self.tx,
+            &clipboard,
+            self.split_mode,
+            self.config.keybindings.workload.clone(),
+            self.config.theme.component.clone(),
         );
 
-        let EventTab { tab: event_tab } =
-            EventTab::new("Event", &clipboard, self.theme.component.clone());
+        let HelmTab {
+            tab: helm_tab,
+            dialogs: helm_dialogs,
+        } = HelmTab::new(
+            "Helm",
+            &self.tx,
+            &clipboard,
+            self.split_mode,
+            self.config.keybindings.helm.clone(),
+            self.config.theme.component.clone(),
+        );
+
+        let EventTab { tab: event_tab } = EventTab::new(
+            "Event",
+            &clipboard,
+            self.config.buffer.event,
+            self.config.theme.component.clone(),
+        );
 
         let ApiTab {
             tab: api_tab,
             dialog: api_dialog,
-        } = ApiTab::new("API", &self.tx, &clipboard, self.theme.component.clone());
+        } = ApiTab::new(
+            "API",
+            &self.tx,
+            &clipboard,
+            self.config.theme.component.clone(),
+        );
 
         let YamlTab {
             tab: yaml_tab,
             kind_dialog: yaml_kind_dialog,
             name_dialog: yaml_name_dialog,
             not_found_dialog: yaml_not_found_dialog,
-        } = YamlTab::new("Yaml", &self.tx, &clipboard, self.theme.component.clone());
+        } = YamlTab::new(
+            "Yaml",
+            &self.tx,
+            &clipboard,
+            self.config.theme.component.clone(),
+        );
 
         let ContextDialog {
             widget: context_dialog,
-        } = ContextDialog::new(&self.tx, self.theme.clone());
+        } = ContextDialog::new(&self.tx, self.config.theme.clone());
 
         let SingleNamespaceDialog {
             widget: single_namespace_dialog,
-        } = SingleNamespaceDialog::new(&self.tx, self.theme.clone());
+        } = SingleNamespaceDialog::new(&self.tx, self.config.theme.clone());
 
         let MultipleNamespacesDialog {
             widget: multiple_namespaces_dialog,
-        } = MultipleNamespacesDialog::new(&self.tx, self.theme.clone());
+        } = MultipleNamespacesDialog::new(&self.tx, self.config.theme.clone());
 
         let HelpDialog {
             widget: help_dialog,
-        } = HelpDialog::new(self.theme.clone());
+        } = HelpDialog::new(&self.config.keybindings, self.config.theme.clone());
 
         let YamlDialog {
             widget: yaml_dialog,
-        } = YamlDialog::new(&clipboard, self.theme.clone());
+        } = YamlDialog::new(&clipboard, self.config.theme.clone());
+
+        let ExecContainerDialog {
+            widget: exec_container_dialog,
+        } = ExecContainerDialog::new(self.config.theme.clone());
+
+        let PortForwardTargetDialog {
+            widget: port_forward_target_dialog,
+        } = PortForwardTargetDialog::new(&self.tx, self.config.theme.clone());
+
+        let PortForwardDialog {
+            widget: port_forward_dialog,
+        } = PortForwardDialog::new(
+            &self.tx,
+            self.config.keybindings.port_forward.clone(),
+            self.config.theme.clone(),
+        );
+
+        let LogExportDialogs {
+            format_dialog: log_export_format_dialog,
+            path_dialog: log_export_path_dialog,
+            result_dialog: log_export_result_dialog,
+            ..
+        } = log_export_dialogs;
+
+        let SavedQueriesDialog {
+            widget: saved_queries_dialog,
+        } = saved_queries_dialog;
+
+        let ReachabilityDialogs {
+            input_dialog: reachability_dialog,
+            result_dialog: reachability_result_dialog,
+            ..
+        } = reachability_dialogs;
+
+        let ResourceActionDialogs {
+            menu_dialog: resource_action_dialog,
+            confirm_dialog: resource_action_confirm_dialog,
+            scale_dialog: resource_action_scale_dialog,
+            error_dialog: resource_action_error_dialog,
+            ..
+        } = resource_action_dialogs;
+
+        let RolloutDialogs {
+            history_dialog: rollout_history_dialog,
+            menu_dialog: rollout_menu_dialog,
+            confirm_dialog: rollout_confirm_dialog,
+            result_dialog: rollout_result_dialog,
+            ..
+        } = rollout_dialogs;
+
+        let JobHistoryDialog {
+            widget: job_history_dialog,
+            ..
+        } = job_history_dialog;
+
+        let HelmDialogs {
+            view_dialog: helm_view_dialog,
+            diff_dialog: helm_diff_dialog,
+            ..
+        } = helm_dialogs;
+
+        let RevealDialog {
+            widget: config_reveal_dialog,
+            ..
+        } = config_reveal_dialog;
 
         // Init Window
         let tabs = vec![
@@ -258,9 +419,12 @@ impl WindowInit {
             event_tab,
             api_tab,
             yaml_tab,
+            node_tab,
+            workload_tab,
+            helm_tab,
         ];
 
-        let dialog_theme = DialogTheme::from(self.theme.clone());
+        let dialog_theme = DialogTheme::from(self.config.theme.clone());
 
         let dialog_widgets = vec![
             context_dialog,
@@ -272,7 +436,28 @@ impl WindowInit {
             yaml_not_found_dialog,
             help_dialog,
             log_query_help_dialog,
+            log_export_format_dialog,
+            log_export_path_dialog,
+            log_export_result_dialog,
+            saved_queries_dialog,
             yaml_dialog,
+            exec_container_dialog,
+            port_forward_target_dialog,
+            port_forward_dialog,
+            reachability_dialog,
+            reachability_result_dialog,
+            resource_action_dialog,
+            resource_action_confirm_dialog,
+            resource_action_scale_dialog,
+            resource_action_error_dialog,
+            rollout_history_dialog,
+            rollout_menu_dialog,
+            rollout_confirm_dialog,
+            rollout_result_dialog,
+            job_history_dialog,
+            helm_view_dialog,
+            helm_diff_dialog,
+            config_reveal_dialog,
         ];
 
         let dialogs: Vec<Dialog> = dialog_widgets
@@ -294,7 +479,7 @@ fn open_yaml(tx: Sender<Message>) -> impl CallbackFn {
         let widget = w.active_tab().active_widget();
 
         match widget.id() {
-            POD_WIDGET_ID | CONFIG_WIDGET_ID | NETWORK_WIDGET_ID => {}
+            POD_WIDGET_ID | CONFIG_WIDGET_ID | NETWORK_WIDGET_ID | WORKLOAD_WIDGET_ID => {}
             _ => {
                 return EventResult::Ignore;
             }
@@ -335,6 +520,70 @@ fn open_yaml(tx: Sender<Message>) -> impl CallbackFn {
                 Some("v1beta1") => GetYamlKind::HTTPRoute(HTTPRouteVersion::V1Beta1),
                 _ => unreachable!(),
             },
+            Some(
+                kind @ (GRPCRoute::KIND
+                | TCPRoute::KIND
+                | TLSRoute::KIND
+                | UDPRoute::KIND
+                | ReferenceGrant::KIND),
+            ) => {
+                let Some(version) = version else {
+                    unreachable!();
+                };
+
+                let plural = match kind {
+                    GRPCRoute::KIND => GRPCRoute::URL_PATH_SEGMENT,
+                    TCPRoute::KIND => TCPRoute::URL_PATH_SEGMENT,
+                    TLSRoute::KIND => TLSRoute::URL_PATH_SEGMENT,
+                    UDPRoute::KIND => UDPRoute::URL_PATH_SEGMENT,
+                    _ => ReferenceGrant::URL_PATH_SEGMENT,
+                };
+
+                GetYamlKind::ApiResource(ApiResource::Apis {
+                    name: plural.to_string(),
+                    kind: kind.to_string(),
+                    group: Gateway::GROUP.to_string(),
+                    version: version.to_string(),
+                    preferred_version: false,
+                    scope: Scope::Namespaced,
+                })
+            }
+            Some(
+                kind @ (Deployment::KIND
+                | StatefulSet::KIND
+                | DaemonSet::KIND
+                | Job::KIND
+                | CronJob::KIND),
+            ) => {
+                let (plural, group, version) = match kind {
+                    Deployment::KIND => (
+                        Deployment::URL_PATH_SEGMENT,
+                        Deployment::GROUP,
+                        Deployment::VERSION,
+                    ),
+                    StatefulSet::KIND => (
+                        StatefulSet::URL_PATH_SEGMENT,
+                        StatefulSet::GROUP,
+                        StatefulSet::VERSION,
+                    ),
+                    DaemonSet::KIND => (
+                        DaemonSet::URL_PATH_SEGMENT,
+                        DaemonSet::GROUP,
+                        DaemonSet::VERSION,
+                    ),
+                    Job::KIND => (Job::URL_PATH_SEGMENT, Job::GROUP, Job::VERSION),
+                    _ => (CronJob::URL_PATH_SEGMENT, CronJob::GROUP, CronJob::VERSION),
+                };
+
+                GetYamlKind::ApiResource(ApiResource::Apis {
+                    name: plural.to_string(),
+                    kind: kind.to_string(),
+                    group: group.to_string(),
+                    version: version.to_string(),
+                    preferred_version: false,
+                    scope: Scope::Namespaced,
+                })
+            }
             _ => {
                 unreachable!();
             }
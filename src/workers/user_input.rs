@@ -1,8 +1,12 @@
-use std::time::Duration;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use anyhow::Result;
 use crossbeam::channel::Sender;
 use ratatui::crossterm::event::{poll, read, Event as CEvent, KeyEvent, KeyEventKind};
+use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
     logger,
@@ -10,16 +14,57 @@ use crate::{
     panic_set_hook,
 };
 
+/// ユーザー入力の送信先を一時的に切り替えるためのハンドル
+/// 送信先が設定されている間、端末のイベントはメインスレッドではなく設定された送信先に送られる
+#[derive(Debug, Clone, Default)]
+pub struct InputRedirect {
+    inner: Arc<Mutex<Option<UnboundedSender<CEvent>>>>,
+}
+
+impl InputRedirect {
+    pub fn set(&self, tx: UnboundedSender<CEvent>) {
+        *self.inner.lock().expect("Failed to lock InputRedirect") = Some(tx);
+    }
+
+    pub fn clear(&self) {
+        *self.inner.lock().expect("Failed to lock InputRedirect") = None;
+    }
+
+    /// 送信先が設定されている場合はイベントを送信してtrueを返す
+    fn send(&self, ev: &CEvent) -> bool {
+        let mut inner = self.inner.lock().expect("Failed to lock InputRedirect");
+
+        let Some(tx) = inner.as_ref() else {
+            return false;
+        };
+
+        if tx.send(ev.clone()).is_err() {
+            *inner = None;
+        }
+
+        true
+    }
+}
+
 /// ユーザー入力を受け付けるワーカースレッドを生成する構造体
 /// イベントデータはチャネルを介してメインスレッドに送信される
 pub struct UserInput {
     tx: Sender<Message>,
     tx_shutdown: Sender<Result<()>>,
+    redirect: InputRedirect,
 }
 
 impl UserInput {
-    pub fn new(tx: Sender<Message>, tx_shutdown: Sender<Result<()>>) -> Self {
-        Self { tx, tx_shutdown }
+    pub fn new(
+        tx: Sender<Message>,
+        tx_shutdown: Sender<Result<()>>,
+        redirect: InputRedirect,
+    ) -> Self {
+        Self {
+            tx,
+            tx_shutdown,
+            redirect,
+        }
     }
 
     pub fn start(&self) {
@@ -55,6 +100,19 @@ impl UserInput {
 
                 logger!(debug, "{:?}", ev);
 
+                // キーを離したイベントは、リダイレクト先にも渡さない
+                if let CEvent::Key(KeyEvent {
+                    kind: KeyEventKind::Release,
+                    ..
+                }) = ev
+                {
+                    continue;
+                }
+
+                if self.redirect.send(&ev) {
+                    continue;
+                }
+
                 match ev {
                     CEvent::Key(ev) => {
                         if let KeyEvent {